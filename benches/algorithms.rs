@@ -0,0 +1,96 @@
+//! Benchmarks of the searches, minimum spanning trees and the spanning tree simulation on generated graphs.
+//!
+//! Run with `cargo bench`, reports are written to target/criterion.
+//! The throughput is reported in links, so graphs of different sizes and densities can be compared.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rust_algorithms::frozen::FrozenGraph;
+use rust_algorithms::graph::Graph;
+use rust_algorithms::{bfs, dfs, dijkstra, generate, mst, spanningtree};
+
+/// The number of nodes and the average number of links per node of the generated graphs.
+const SIZES: [(usize, usize); 4] = [(100, 2), (100, 8), (1000, 2), (1000, 8)];
+
+/// Very dense graphs, where Dijkstra's algorithm lowers the cost of queued nodes far more often than it expands nodes.
+const DENSE_SIZES: [(usize, usize); 2] = [(300, 100), (1000, 300)];
+
+/// Generates a connected graph with the given average degree, see generate::connected.
+/// The generator is seeded, so every run benchmarks the same graphs.
+fn generate(nodes: usize, degree: usize) -> Graph {
+    let mut rng = StdRng::seed_from_u64(nodes as u64 * 31 + degree as u64);
+    generate::connected(nodes, degree, 1..10, &mut rng)
+}
+
+fn benchmark(criterion: &mut Criterion) {
+    let graphs: Vec<(String, Graph)> = SIZES
+        .iter()
+        .map(|&(nodes, degree)| (format!("{}x{}", nodes, degree), generate(nodes, degree)))
+        .collect();
+
+    let mut group = criterion.benchmark_group("search");
+    for (name, graph) in &graphs {
+        let target = graph.nodes().len() as isize - 1;
+        group.throughput(Throughput::Elements(graph.links().len() as u64));
+        // bfs and dfs take the graph by value, so the clone is part of what they cost
+        group.bench_with_input(BenchmarkId::new("bfs", name), graph, |bencher, graph| {
+            bencher.iter(|| bfs::bfs_search_node(graph.clone(), 0, target))
+        });
+        group.bench_with_input(BenchmarkId::new("dfs", name), graph, |bencher, graph| {
+            bencher.iter(|| dfs::dfs_search_node(graph.clone(), 0, target))
+        });
+        group.bench_with_input(BenchmarkId::new("dijkstra", name), graph, |bencher, graph| {
+            bencher.iter(|| dijkstra::dijkstra_search_node(graph, 0, target))
+        });
+        let frozen = FrozenGraph::new(graph);
+        group.bench_with_input(BenchmarkId::new("frozen_bfs", name), &frozen, |bencher, frozen| {
+            bencher.iter(|| frozen.bfs_search_node(0, target))
+        });
+        group.bench_with_input(BenchmarkId::new("frozen_dijkstra", name), &frozen, |bencher, frozen| {
+            bencher.iter(|| frozen.dijkstra_search_node(0, target))
+        });
+    }
+    group.finish();
+
+    // compares the priority queues of Dijkstra's algorithm
+    let mut group = criterion.benchmark_group("dijkstra_queue");
+    for &(nodes, degree) in &DENSE_SIZES {
+        let graph = generate(nodes, degree);
+        let name = format!("{}x{}", nodes, degree);
+        let target = nodes as isize - 1;
+        group.throughput(Throughput::Elements(graph.links().len() as u64));
+        for (queue_name, queue) in [("binary", dijkstra::Queue::BinaryHeap), ("pairing", dijkstra::Queue::PairingHeap)] {
+            group.bench_with_input(BenchmarkId::new(queue_name, &name), &graph, |bencher, graph| {
+                bencher.iter(|| dijkstra::dijkstra_search_node_with_queue(graph, 0, target, queue))
+            });
+        }
+    }
+    group.finish();
+
+    let mut group = criterion.benchmark_group("mst");
+    for (name, graph) in &graphs {
+        group.throughput(Throughput::Elements(graph.links().len() as u64));
+        group.bench_with_input(BenchmarkId::new("kruskal", name), graph, |bencher, graph| {
+            bencher.iter(|| mst::minimum_spanning_tree(graph))
+        });
+    }
+    group.finish();
+
+    let mut group = criterion.benchmark_group("spanningtree");
+    group.sample_size(10);
+    for (name, graph) in &graphs {
+        group.throughput(Throughput::Elements(graph.links().len() as u64));
+        group.bench_with_input(BenchmarkId::new("simulate", name), graph, |bencher, graph| {
+            bencher.iter_batched(
+                || spanningtree::Tree::from_graph(graph),
+                |mut tree| tree.simulate(graph.nodes().len(), 0, false),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
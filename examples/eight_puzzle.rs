@@ -0,0 +1,32 @@
+//! Solves an 8-puzzle given as nine digits, row by row with 0 as the blank, and prints every step.
+//!
+//! Run with `cargo run --example eight_puzzle -- 867254301`.
+
+use rust_algorithms::puzzle::Puzzle;
+use std::env;
+use std::process;
+
+fn main() {
+    let digits = env::args().nth(1).unwrap_or_else(|| String::from("867254301"));
+    let tiles: Vec<u8> = digits.chars().filter_map(|digit| digit.to_digit(10)).map(|digit| digit as u8).collect();
+    let puzzle = match Puzzle::new(3, 3, &tiles) {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!("expected the digits 0 to 8, each exactly once, but got {}", digits);
+            process::exit(2);
+        }
+    };
+
+    match puzzle.solve() {
+        Some(solution) => {
+            for (step, puzzle) in solution.iter().enumerate() {
+                println!("step {}:\n{}", step, puzzle);
+            }
+            println!("solved in {} moves", solution.len() - 1);
+        }
+        None => {
+            println!("{}cannot be solved", puzzle);
+            process::exit(1);
+        }
+    }
+}
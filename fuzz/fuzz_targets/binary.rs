@@ -0,0 +1,12 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rust_algorithms::graph::Graph;
+
+// every graph which can be read has to be written and read again without changes
+fuzz_target!(|data: &[u8]| {
+    if let Ok(graph) = Graph::read_binary(data) {
+        let mut written = Vec::new();
+        graph.write_binary(&mut written).unwrap();
+        assert_eq!(Graph::read_binary(&written[..]).unwrap(), graph);
+    }
+});
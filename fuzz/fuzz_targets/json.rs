@@ -0,0 +1,12 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rust_algorithms::graph::Graph;
+
+// every graph which can be read has to be written and read again without changes
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(graph) = Graph::from_json(text) {
+            assert_eq!(Graph::from_json(&graph.to_json()).unwrap(), graph);
+        }
+    }
+});
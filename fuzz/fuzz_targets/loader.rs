@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rust_algorithms::loader::GraphLoader;
+
+// loading may fail on malformed lines, but must never panic
+fuzz_target!(|data: &[u8]| {
+    let _ = GraphLoader::new().on_progress(16, |_| {}).load(data);
+});
@@ -0,0 +1,41 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rust_algorithms::graph::{Graph, Link, Node};
+use rust_algorithms::{bfs, dfs, dijkstra};
+
+const NAMES: [&str; 16] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15",
+];
+
+// The first byte selects the number of nodes, every following three bytes describe a link by its members and cost.
+// Members may be outside of the graph, so searches have to cope with dangling links.
+fuzz_target!(|data: &[u8]| {
+    let (count, links) = match data.split_first() {
+        Some((count, links)) => (usize::from(*count) % NAMES.len() + 1, links),
+        None => return,
+    };
+    let mut graph = Graph::new();
+    for name in &NAMES[..count] {
+        graph.add_node(Node::new(name));
+    }
+    for link in links.chunks_exact(3) {
+        let members = (isize::from(link[0] % 20), isize::from(link[1] % 20));
+        graph.add_link(Link::new(members, usize::from(link[2])));
+    }
+
+    let target = count as isize - 1;
+    let bfs = bfs::bfs_search_node(graph.clone(), 0, target);
+    let dfs = dfs::dfs_search_node(graph.clone(), 0, target);
+    let dijkstra = dijkstra::dijkstra_search_node(&graph, 0, target);
+
+    // all searches have to agree on whether there is a path, and no path can be cheaper than the one of dijkstra
+    assert_eq!(bfs.is_some(), dijkstra.is_some());
+    assert_eq!(dfs.is_some(), dijkstra.is_some());
+    if let (Some(bfs), Some(dfs), Some(dijkstra)) = (bfs, dfs, dijkstra) {
+        assert!(dijkstra.cost <= bfs.cost);
+        assert!(dijkstra.cost <= dfs.cost);
+        for result in [&bfs, &dfs, &dijkstra] {
+            assert_eq!(result.links.iter().map(|link| link.cost).sum::<usize>(), result.cost);
+        }
+    }
+});
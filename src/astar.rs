@@ -0,0 +1,311 @@
+use crate::datastructures::{BinaryHeap, Handle};
+use crate::dijkstra::{index_of, neighbours, path};
+use crate::graph::*;
+
+/// A function to search for the cheapest path to a node using the [A* search algorithm](https://en.wikipedia.org/wiki/A*_search_algorithm).
+///
+/// This function takes two node ids, for the start and target node, and computes the path with the lowest total link cost between them.
+/// The heuristic estimates the cost from a node to the target node. The closer the estimate, the fewer nodes are visited.
+/// If the heuristic never overestimates the cost, the path found is the cheapest one, like with Dijkstra's algorithm.
+/// This path consists of a Vec of Links.
+/// The first link is always from the first element to itself.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::astar::*;
+///
+/// let mut graph = Graph::new();
+///
+/// // the nodes lie on a line, so the distance of the ids is a good estimate
+/// let node0 = graph.add_node(Node::new("Node 0"));
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+///
+/// graph.add_link(Link::new((node0, node2), 5));
+/// graph.add_link(Link::new((node0, node1), 1));
+/// graph.add_link(Link::new((node1, node2), 1));
+///
+/// let distance = |node: &Node| (node2 - node.id) as usize;
+/// let result = astar_search_node(&graph, node0, node2, distance).unwrap();
+///
+/// assert_eq!(result.links[1], Link::new((node0, node1), 1));
+/// assert_eq!(result.links[2], Link::new((node1, node2), 1));
+/// assert_eq!(result.cost, 2);
+/// ```
+pub fn astar_search_node<H>(graph: &Graph, start_node_id: isize, search_node_id: isize, heuristic: H) -> Option<SearchResult>
+where
+    H: Fn(&Node) -> usize,
+{
+    search(graph, start_node_id, search_node_id, heuristic, |_| 0, &mut SearchStats::default())
+}
+
+/// Works like astar_search_node, but additionally reports statistics about the search.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::astar::*;
+///
+/// let mut graph = Graph::new();
+/// let node0 = graph.add_node(Node::new("Node 0"));
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// graph.add_link(Link::new((node0, node1), 1));
+/// graph.add_link(Link::new((node0, node2), 1));
+///
+/// // a perfect estimate leads the search straight to the target
+/// let distance = |node: &Node| if node.id == node2 { 0 } else { 1 };
+/// let report = astar_search_report(&graph, node0, node2, distance);
+/// assert_eq!(report.result.unwrap().cost, 1);
+/// assert_eq!(report.stats.nodes_expanded, 1);
+/// ```
+pub fn astar_search_report<H>(graph: &Graph, start_node_id: isize, search_node_id: isize, heuristic: H) -> SearchReport
+where
+    H: Fn(&Node) -> usize,
+{
+    SearchReport::measure(|stats| search(graph, start_node_id, search_node_id, heuristic, |_| 0, stats))
+}
+
+/// Works like astar_search_node, but additionally charges a cost for every node the path passes through,
+/// like dijkstra::dijkstra_search_node_with_node_costs. The start and the target node are not passed through.
+///
+/// Node costs only add to the cost of the remaining path, so a heuristic which never overestimates the link costs alone
+/// still never overestimates, and the path found is the cheapest one.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::astar::*;
+///
+/// let mut graph = Graph::new();
+/// let node0 = graph.add_node(Node::new("Node 0"));
+/// let slow_switch = graph.add_node(Node::new("Slow Switch"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// graph.add_link(Link::new((node0, slow_switch), 1));
+/// graph.add_link(Link::new((slow_switch, node2), 1));
+/// graph.add_link(Link::new((node0, node2), 5));
+///
+/// let distance = |node: &Node| (node2 - node.id) as usize;
+/// let latency = |node: &Node| if node.name == "Slow Switch" { 10 } else { 1 };
+/// let result = astar_search_node_with_node_costs(&graph, node0, node2, distance, latency).unwrap();
+/// assert_eq!(result.links[1], Link::new((node0, node2), 5));
+/// assert_eq!(result.cost, 5);
+/// ```
+pub fn astar_search_node_with_node_costs<H, F>(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    heuristic: H,
+    node_cost: F,
+) -> Option<SearchResult>
+where
+    H: Fn(&Node) -> usize,
+    F: Fn(&Node) -> usize,
+{
+    search(graph, start_node_id, search_node_id, heuristic, node_cost, &mut SearchStats::default())
+}
+
+/// Works like astar_search_report, but limited and extended by the options.
+///
+/// With a max_depth, the path found is the cheapest one with at most that many links, like with
+/// dijkstra::dijkstra_search_with_options, if the heuristic never overestimates the cost.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::astar::*;
+///
+/// let mut graph = Graph::new();
+/// let node0 = graph.add_node(Node::new("Node 0"));
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// graph.add_link(Link::new((node0, node2), 5));
+/// graph.add_link(Link::new((node0, node1), 1));
+/// graph.add_link(Link::new((node1, node2), 1));
+///
+/// let distance = |node: &Node| (node2 - node.id) as usize;
+/// let options = SearchOptions::new().edge_filter(|link| link.cost < 5).record_trace(true);
+/// let report = astar_search_with_options(&graph, node0, node2, distance, &options);
+/// assert_eq!(report.result.unwrap().cost, 2);
+/// assert_eq!(report.trace, vec![node0, node1]);
+/// ```
+pub fn astar_search_with_options<H>(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    heuristic: H,
+    options: &SearchOptions,
+) -> SearchReport
+where
+    H: Fn(&Node) -> usize,
+{
+    crate::dijkstra::search_with_options(graph, start_node_id, search_node_id, heuristic, options)
+}
+
+fn search<H, F>(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    heuristic: H,
+    node_cost: F,
+    stats: &mut SearchStats,
+) -> Option<SearchResult>
+where
+    H: Fn(&Node) -> usize,
+    F: Fn(&Node) -> usize,
+{
+    // if start node is the node searched for, return a link to itself
+    if start_node_id == search_node_id {
+        return Some(
+            SearchResult::new()
+                .links(vec![Link::new((start_node_id, search_node_id), 0)])
+                .cost(0),
+        );
+    }
+
+    let nodes = graph.nodes();
+    let start = index_of(graph, start_node_id)?;
+    let target = index_of(graph, search_node_id)?;
+    let neighbours = neighbours(graph);
+
+    // the lowest known cost to every node and the link it has been reached through.
+    // the queue is ordered by the estimated total cost of a path through the node and holds every discovered node once,
+    // its handle is kept to lower the estimate when a cheaper way is found.
+    let mut costs: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut previous: Vec<Option<(usize, &Link)>> = vec![None; nodes.len()];
+    let mut handles: Vec<Option<Handle>> = vec![None; nodes.len()];
+    let mut queue = BinaryHeap::with_capacity(nodes.len());
+    costs[start] = Some(0);
+    handles[start] = Some(queue.push((heuristic(&nodes[start]), 0, start)));
+    stats.frontier(queue.len());
+
+    while let Some((_, cost, current)) = queue.pop() {
+        // abort case: node searched for is found. Follow the links back to the start node.
+        if current == target {
+            log_debug!("astar: found {} from {} at cost {}", search_node_id, start_node_id, cost);
+            return Some(SearchResult::new().links(path(&previous, start_node_id, target)).cost(cost));
+        }
+
+        log_trace!("astar: expanding {} at cost {}", nodes[current].id, cost);
+        stats.nodes_expanded += 1;
+        // paths leading on from the current node pass through it, unless it is the start node
+        let passing_cost = if current == start { 0 } else { node_cost(&nodes[current]) };
+        for &(next, link) in &neighbours[current] {
+            stats.links_examined += 1;
+            let next_cost = cost + passing_cost + link.cost;
+            if costs[next].is_none_or(|known| next_cost < known) {
+                costs[next] = Some(next_cost);
+                previous[next] = Some((current, link));
+                let estimate = (next_cost + heuristic(&nodes[next]), next_cost, next);
+                match handles[next] {
+                    Some(handle) if queue.contains(handle) => {
+                        queue.decrease_key(handle, estimate);
+                    }
+                    // nodes which have been expanded before are expanded again, if the heuristic overestimated the cost
+                    _ => handles[next] = Some(queue.push(estimate)),
+                }
+                stats.frontier(queue.len());
+            }
+        }
+    }
+
+    // if the queue is empty and no element was found, return None.
+    log_debug!("astar: no path from {} to {}", start_node_id, search_node_id);
+    None
+}
+
+#[cfg(test)]
+mod discover_test {
+    use super::*;
+
+    fn grid() -> Graph {
+        // a 3x3 grid, the id of a node is 3 * row + column
+        let mut graph = Graph::new();
+        for name in ["00", "01", "02", "10", "11", "12", "20", "21", "22"] {
+            graph.add_node(Node::new(name));
+        }
+        for row in 0..3 {
+            for column in 0..3 {
+                let id = 3 * row + column;
+                if column < 2 {
+                    graph.add_link(Link::new((id, id + 1), 1));
+                }
+                if row < 2 {
+                    graph.add_link(Link::new((id, id + 3), 1));
+                }
+            }
+        }
+        graph
+    }
+
+    fn manhattan(target: isize) -> impl Fn(&Node) -> usize {
+        move |node: &Node| {
+            ((node.id / 3 - target / 3).abs() + (node.id % 3 - target % 3).abs()) as usize
+        }
+    }
+
+    #[test]
+    fn test_discover_no_start_element() {
+        let graph = grid();
+        assert!(astar_search_node(&graph, 9, 0, |_| 0).is_none());
+    }
+
+    #[test]
+    fn test_discover_element_to_self() {
+        let result = astar_search_node(&Graph::new(), 1, 1, |_| 0).unwrap();
+        assert_eq!(result.links[0], Link::new((1, 1), 0));
+        assert_eq!(result.cost, 0);
+    }
+
+    #[test]
+    fn test_discover_grid() {
+        let result = astar_search_node(&grid(), 0, 8, manhattan(8)).unwrap();
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.links.len(), 5);
+    }
+
+    #[test]
+    fn test_discover_with_inconsistent_heuristic() {
+        let mut graph = grid();
+        graph.add_link(Link::new((0, 8), 10));
+        // the heuristic is admissible, but not consistent, so nodes have to be visited again
+        let heuristic = |node: &Node| if node.id == 4 { 2 } else { 0 };
+        let result = astar_search_node(&graph, 0, 8, heuristic).unwrap();
+        assert_eq!(result.cost, 4);
+    }
+
+    #[test]
+    fn test_discover_with_node_costs() {
+        let mut rng = crate::util::SplitMix64::new(52);
+        for _ in 0..20 {
+            let graph = crate::generate::connected(20, 3, 1..5, &mut rng);
+            let node_cost = |node: &Node| (node.id % 4) as usize;
+            for target in [7, 19] {
+                let result = astar_search_node_with_node_costs(&graph, 0, target, |_| 0, node_cost).unwrap();
+                let expected = crate::dijkstra::dijkstra_search_node_with_node_costs(&graph, 0, target, node_cost).unwrap();
+                assert_eq!(result.cost, expected.cost);
+            }
+        }
+        // the manhattan distance only estimates the links, the middle node is avoided at a higher link cost
+        let slow_middle = |node: &Node| if node.id == 4 { 5 } else { 0 };
+        let result = astar_search_node_with_node_costs(&grid(), 3, 5, manhattan(5), slow_middle).unwrap();
+        assert_eq!(result.cost, 4);
+        assert!(!result.node_ids().contains(&4));
+        assert_eq!(astar_search_node_with_node_costs(&grid(), 4, 4, manhattan(4), |_| 9).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn test_discover_with_options() {
+        let graph = grid();
+        let options = SearchOptions::new().record_trace(true);
+        let report = astar_search_with_options(&graph, 0, 8, manhattan(8), &options);
+        assert_eq!(report.result.unwrap().cost, 4);
+        assert_eq!(report.trace.len(), report.stats.nodes_expanded);
+        assert_eq!(report.trace[0], 0);
+        assert!(astar_search_with_options(&graph, 0, 8, manhattan(8), &SearchOptions::new().max_depth(3)).result.is_none());
+        // without the middle and the right node of the middle row, the path has to go around on the left
+        let open = |id: isize| id != 4 && id != 5;
+        let options = SearchOptions::new().edge_filter(|link| open(link.members.0) && open(link.members.1));
+        let result = astar_search_with_options(&graph, 1, 7, manhattan(7), &options).result.unwrap();
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.links.len(), 5);
+    }
+}
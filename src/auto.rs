@@ -0,0 +1,134 @@
+use crate::astar::astar_search_node;
+use crate::bfs::{bfs_search_node, bfs_search_report};
+use crate::dijkstra::{dijkstra_search_node, dijkstra_search_report};
+use crate::graph::*;
+use crate::pathfinder::PathFinder;
+
+/// The search algorithms the automatic selection can choose from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    BreadthFirst,
+    Dijkstra,
+    AStar,
+}
+
+/// The result of an automatically selected search, together with the algorithm which was run.
+#[derive(Debug)]
+pub struct AutoResult {
+    pub algorithm: Algorithm,
+    pub result: SearchResult,
+}
+
+/// Chooses the algorithm which finds the cheapest path in the graph with the least effort.
+///
+/// If all links have the same cost, the path with the fewest links is the cheapest one,
+/// so breadth-first search is chosen. Otherwise Dijkstra's algorithm is used.
+pub fn select(graph: &Graph) -> Algorithm {
+    let mut costs = graph.links().iter().filter(|link| link.members.0 != link.members.1).map(|link| link.cost);
+    match costs.next() {
+        Some(first) if costs.any(|cost| cost != first) => Algorithm::Dijkstra,
+        _ => Algorithm::BreadthFirst,
+    }
+}
+
+/// Searches for the cheapest path between two nodes with the algorithm chosen by select.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::auto::{self, Algorithm};
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node2), 1));
+/// graph.add_link(Link::new((node2, node3), 1));
+///
+/// let found = auto::find_path(&graph, node1, node3).unwrap();
+/// assert_eq!(found.algorithm, Algorithm::BreadthFirst);
+/// assert_eq!(found.result.cost, 2);
+///
+/// graph.add_link(Link::new((node1, node3), 5));
+/// let found = auto::find_path(&graph, node1, node3).unwrap();
+/// assert_eq!(found.algorithm, Algorithm::Dijkstra);
+/// assert_eq!(found.result.cost, 2);
+/// ```
+pub fn find_path(graph: &Graph, start_node_id: isize, search_node_id: isize) -> Option<AutoResult> {
+    let algorithm = select(graph);
+    let result = match algorithm {
+        // the search marks the nodes it discovers, so it works on a copy
+        Algorithm::BreadthFirst => bfs_search_node(graph.topology_clone(), start_node_id, search_node_id),
+        _ => dijkstra_search_node(graph, start_node_id, search_node_id),
+    }?;
+    Some(AutoResult { algorithm, result })
+}
+
+/// Works like find_path, but uses the A* search algorithm with the given heuristic on graphs with different link costs.
+///
+/// The heuristic estimates the cost from a node to the target node and must not overestimate it.
+pub fn find_path_with_heuristic<H>(graph: &Graph, start_node_id: isize, search_node_id: isize, heuristic: H) -> Option<AutoResult>
+where
+    H: Fn(&Node) -> usize,
+{
+    match select(graph) {
+        Algorithm::BreadthFirst => find_path(graph, start_node_id, search_node_id),
+        _ => {
+            let result = astar_search_node(graph, start_node_id, search_node_id, heuristic)?;
+            Some(AutoResult { algorithm: Algorithm::AStar, result })
+        }
+    }
+}
+
+/// Finds the cheapest path using the algorithm chosen by select.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Auto;
+
+impl PathFinder for Auto {
+    fn find_path(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> Option<SearchResult> {
+        find_path(graph, start_node_id, search_node_id).map(|found| found.result)
+    }
+
+    fn find_path_report(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> SearchReport {
+        match select(graph) {
+            Algorithm::BreadthFirst => bfs_search_report(graph.topology_clone(), start_node_id, search_node_id),
+            _ => dijkstra_search_report(graph, start_node_id, search_node_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod auto_test {
+    use super::*;
+
+    #[test]
+    fn select_by_costs() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        assert_eq!(select(&graph), Algorithm::BreadthFirst);
+        graph.add_link(Link::new((0, 1), 3));
+        graph.add_link(Link::new((1, 1), 1));
+        assert_eq!(select(&graph), Algorithm::BreadthFirst);
+        graph.add_link(Link::new((1, 2), 4));
+        assert_eq!(select(&graph), Algorithm::Dijkstra);
+    }
+
+    #[test]
+    fn find_path_with_heuristic() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        let found = super::find_path_with_heuristic(&graph, 0, 2, |_| 0).unwrap();
+        assert_eq!(found.algorithm, Algorithm::BreadthFirst);
+        graph.add_link(Link::new((0, 2), 3));
+        let found = super::find_path_with_heuristic(&graph, 0, 2, |_| 0).unwrap();
+        assert_eq!(found.algorithm, Algorithm::AStar);
+        assert_eq!(found.result.cost, 2);
+        assert!(super::find_path_with_heuristic(&graph, 0, 5, |_| 0).is_none());
+        assert_eq!(Auto.find_path(&graph, 0, 2).unwrap().cost, 2);
+        assert_eq!(Auto.find_path_report(&graph, 0, 2).stats.nodes_expanded, 2);
+    }
+}
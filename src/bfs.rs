@@ -1,3 +1,4 @@
+use crate::dijkstra::{index_of, neighbours, path, to_self};
 use crate::graph::*;
 
 /// A function to search for the path to a node using the [Breadth-first search](https://en.wikipedia.org/wiki/Breadth-first_search) method.
@@ -27,12 +28,148 @@ use crate::graph::*;
 /// assert_eq!(result.links[1], link1);
 /// assert_eq!(result.cost, 5);
 /// ```
-
 pub fn bfs_search_node(
-    mut graph: Graph,
+    graph: Graph,
     start_node_id: isize,
     search_node_id: isize,
 ) -> Option<SearchResult> {
+    search(graph, start_node_id, search_node_id, |_| true, &mut SearchStats::default())
+}
+
+/// Works like bfs_search_node, but additionally reports statistics about the search.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::bfs::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node2), 5));
+/// graph.add_link(Link::new((node1, node3), 5));
+///
+/// let report = bfs_search_report(graph, node1, node3);
+/// assert_eq!(report.result.unwrap().cost, 5);
+/// assert_eq!(report.stats.nodes_expanded, 2);
+/// assert_eq!(report.stats.links_examined, 3);
+/// assert_eq!(report.stats.max_frontier, 2);
+/// ```
+pub fn bfs_search_report(graph: Graph, start_node_id: isize, search_node_id: isize) -> SearchReport {
+    SearchReport::measure(|stats| search(graph, start_node_id, search_node_id, |_| true, stats))
+}
+
+/// Works like bfs_search_node, but only follows the links for which the supplied filter returns true.
+///
+/// This way links can be ignored, like links above a cost threshold or blocked ports, without copying and pruning the graph first.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::bfs::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let result = bfs_search_node_filtered(graph, node1, node3, |link| link.cost < 10).unwrap();
+/// assert_eq!(result.links.len(), 3);
+/// assert_eq!(result.cost, 7);
+/// ```
+pub fn bfs_search_node_filtered<E>(
+    graph: Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    edge_filter: E,
+) -> Option<SearchResult>
+where
+    E: Fn(&Link) -> bool,
+{
+    search(graph, start_node_id, search_node_id, edge_filter, &mut SearchStats::default())
+}
+
+/// Works like bfs_search_report, but limited and extended by the options.
+///
+/// As the search finds the path with the fewest links, a max_depth never hides a path within it.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::bfs::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let report = bfs_search_with_options(&graph, node1, node3, &SearchOptions::new().record_trace(true));
+/// assert_eq!(report.result.unwrap().cost, 7);
+/// assert_eq!(report.trace, vec![node1, node2]);
+/// assert!(bfs_search_with_options(&graph, node1, node3, &SearchOptions::new().max_depth(1)).result.is_none());
+/// ```
+pub fn bfs_search_with_options(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    options: &SearchOptions,
+) -> SearchReport {
+    use std::collections::VecDeque;
+
+    SearchReport::measure_traced(|stats, trace| {
+        if start_node_id == search_node_id {
+            return Some(to_self(start_node_id));
+        }
+        let start = index_of(graph, start_node_id)?;
+        let nodes = graph.nodes();
+        let neighbours = neighbours(graph);
+        let mut previous: Vec<Option<(usize, &Link)>> = vec![None; nodes.len()];
+        let mut depths: Vec<Option<usize>> = vec![None; nodes.len()];
+        let mut queue = VecDeque::new();
+        depths[start] = Some(0);
+        queue.push_back(start);
+        stats.frontier(queue.len());
+        while let Some(current) = queue.pop_front() {
+            if nodes[current].id == search_node_id {
+                let links = path(&previous, start_node_id, current);
+                let cost = links.iter().map(|link| link.cost).sum();
+                return Some(SearchResult::new().links(links).cost(cost));
+            }
+            let depth = depths[current].unwrap_or(0);
+            if !options.extends(depth) {
+                continue;
+            }
+            if !options.expand(nodes[current].id, stats, trace) {
+                return None;
+            }
+            for &(next, link) in &neighbours[current] {
+                stats.links_examined += 1;
+                if depths[next].is_none() && options.follows(link) {
+                    depths[next] = Some(depth + 1);
+                    previous[next] = Some((current, link));
+                    queue.push_back(next);
+                    stats.frontier(queue.len());
+                }
+            }
+        }
+        None
+    })
+}
+
+fn search<E>(
+    mut graph: Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    edge_filter: E,
+    stats: &mut SearchStats,
+) -> Option<SearchResult>
+where
+    E: Fn(&Link) -> bool,
+{
     use std::collections::VecDeque;
 
     if start_node_id == search_node_id {
@@ -47,13 +184,13 @@ pub fn bfs_search_node(
     let mut queue = VecDeque::<(isize, Vec<Link>)>::new();
 
     // the first link leads from the first element to itself with no cost. It is just there to provide any link
-    let mut vector = Vec::new();
-    vector.push(Link::new((start_node_id, start_node_id), 0));
+    let vector = vec![Link::new((start_node_id, start_node_id), 0)];
     queue.push_front((start_node_id, vector));
+    stats.frontier(queue.len());
 
     // retrieve the start node from the graph and mark it as visited.
     // if it does not exist, there cannot be a path, return None.
-    let mut start_node = match graph.get_node(start_node_id) {
+    let start_node = match graph.get_node(start_node_id) {
         Some(node) => node,
         None => {
             return None;
@@ -73,6 +210,7 @@ pub fn bfs_search_node(
             for link in current_queue_element.1.iter() {
                 cost += link.cost;
             }
+            log_debug!("bfs: found {} from {} at cost {}", search_node_id, start_node_id, cost);
             return Some(
                 SearchResult::new()
                     .cost(cost)
@@ -85,9 +223,12 @@ pub fn bfs_search_node(
             // find all links from this node
             let mygraph = graph.clone();
             let links = mygraph.find_links_from_node(current_node);
+            log_trace!("bfs: expanding {} at depth {}", current_node, current_queue_element.1.len() - 1);
+            stats.nodes_expanded += 1;
             for link in links {
-                //ignore circular links (from object to itself)
-                if link.members.0 != link.members.1 {
+                stats.links_examined += 1;
+                //ignore circular links (from object to itself) and links rejected by the filter
+                if link.members.0 != link.members.1 && edge_filter(link) {
                     // get the node_id of the node on the other end of the link
                     let found_node: isize = if link.members.0 == current_node {
                         link.members.1
@@ -106,6 +247,7 @@ pub fn bfs_search_node(
                             queue.push_back((found_node, new_vector));
                             // mark the node as visited, as it will be processed
                             node.is_discovered = true;
+                            stats.frontier(queue.len());
                         }
                     }
                 }
@@ -114,10 +256,137 @@ pub fn bfs_search_node(
     }
 
     // if the queue is empty and no element was found, return None.
+    log_debug!("bfs: no path from {} to {}", start_node_id, search_node_id);
     None
 }
 
 
+/// Runs a breadth-first search from the start node through the whole graph
+/// and returns the links every reachable node has been reached through.
+///
+/// The path to every node then consists of the fewest links possible, like the one bfs_search_node finds.
+/// If the start node is not part of the graph, no node is reached.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::bfs::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node2), 5));
+/// graph.add_link(Link::new((node2, node3), 2));
+///
+/// let predecessors = bfs_predecessors(&graph, node1);
+/// assert_eq!(predecessors.path_to(node2).unwrap().cost, 5);
+/// assert_eq!(predecessors.path_to(node3).unwrap().cost, 7);
+/// ```
+pub fn bfs_predecessors(graph: &Graph, start_node_id: isize) -> Predecessors {
+    use std::collections::VecDeque;
+
+    let mut predecessors = Predecessors::new(start_node_id);
+    let start = match index_of(graph, start_node_id) {
+        Some(start) => start,
+        None => return predecessors,
+    };
+    let nodes = graph.nodes();
+    let neighbours = neighbours(graph);
+    let mut discovered = vec![false; nodes.len()];
+    let mut queue = VecDeque::new();
+    discovered[start] = true;
+    queue.push_back(start);
+    while let Some(current) = queue.pop_front() {
+        for &(next, link) in &neighbours[current] {
+            if !discovered[next] {
+                discovered[next] = true;
+                predecessors.map.insert(nodes[next].id, (nodes[current].id, *link));
+                queue.push_back(next);
+            }
+        }
+    }
+    predecessors
+}
+
+/// Searches for the path with the fewest links from the start node to any node fulfilling the goal.
+///
+/// Other than bfs_search_node, the node searched for does not have to be known in advance.
+/// The search stops at the first node fulfilling the goal, which is the one with the fewest links between it and the start node.
+/// If the start node fulfills the goal, the path only consists of the link from it to itself.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::bfs::*;
+///
+/// let mut graph = Graph::new();
+/// let client = graph.add_node(Node::new("Client"));
+/// let switch = graph.add_node(Node::new("Switch"));
+/// let db = graph.add_node(Node::new("DB 1"));
+/// graph.add_link(Link::new((client, switch), 1));
+/// graph.add_link(Link::new((switch, db), 2));
+///
+/// let result = bfs_search_goal(&graph, client, |node| node.name.starts_with("DB")).unwrap();
+/// assert_eq!(result.links.len(), 3);
+/// assert_eq!(result.cost, 3);
+/// ```
+pub fn bfs_search_goal<G>(graph: &Graph, start_node_id: isize, goal: G) -> Option<SearchResult>
+where
+    G: Fn(&Node) -> bool,
+{
+    use std::collections::VecDeque;
+
+    let start = index_of(graph, start_node_id)?;
+    let nodes = graph.nodes();
+    let neighbours = neighbours(graph);
+    let mut previous: Vec<Option<(usize, &Link)>> = vec![None; nodes.len()];
+    let mut discovered = vec![false; nodes.len()];
+    let mut queue = VecDeque::new();
+    discovered[start] = true;
+    queue.push_back(start);
+    while let Some(current) = queue.pop_front() {
+        if goal(&nodes[current]) {
+            let links = path(&previous, start_node_id, current);
+            let cost = links.iter().map(|link| link.cost).sum();
+            return Some(SearchResult::new().links(links).cost(cost));
+        }
+        for &(next, link) in &neighbours[current] {
+            if !discovered[next] {
+                discovered[next] = true;
+                previous[next] = Some((current, link));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Searches for the path with the fewest links like bfs_search_node, but on any graph implementing GraphLike.
+///
+/// The links of the result point from every node to the next one.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::bfs::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node3, node2), 4));
+///
+/// let result = bfs_search_generic(&graph, node1, node3).unwrap();
+/// assert_eq!(result.links[2], Link::new((node2, node3), 4));
+/// assert_eq!(result.cost, 7);
+/// ```
+pub fn bfs_search_generic<G>(graph: &G, start_node_id: isize, search_node_id: isize) -> Option<SearchResult>
+where
+    G: GraphLike + ?Sized,
+{
+    crate::implicit::bfs_search(start_node_id, |id| id == search_node_id, |id| graph.neighbors(id))
+}
+
 #[cfg(test)]
 mod discover_test {
     use super::*;
@@ -127,7 +396,94 @@ mod discover_test {
         let mut graph = Graph::new();
         let node1 = Node::new("Node 1");
         graph.add_node(node1);
-        assert_eq!(bfs_search_node(graph, 2, 1).is_none(), true);
+        assert!(bfs_search_node(graph, 2, 1).is_none());
+    }
+
+    #[test]
+    fn test_predecessors() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4", "Node 5"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        graph.add_link(Link::new((0, 3), 1));
+        graph.add_link(Link::new((3, 2), 1));
+        let predecessors = bfs_predecessors(&graph, 0);
+        assert_eq!(predecessors.map.len(), 3);
+        assert!(predecessors.contains(0));
+        assert!(!predecessors.contains(4));
+        assert!(predecessors.path_to(4).is_none());
+        assert_eq!(predecessors.path_to(0).unwrap().links, vec![Link::new((0, 0), 0)]);
+        assert_eq!(predecessors.path_to(2).unwrap().links.len(), 3);
+        assert!(bfs_predecessors(&graph, 9).map.is_empty());
+    }
+
+    #[test]
+    fn test_discover_filtered() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 3), 9));
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 3), 1));
+        let result = bfs_search_node_filtered(graph.clone(), 0, 3, |link| link.cost < 5).unwrap();
+        assert_eq!(result.links, vec![Link::new((0, 0), 0), Link::new((0, 1), 1), Link::new((1, 3), 1)]);
+        assert_eq!(bfs_search_node_filtered(graph.clone(), 0, 3, |_| true).unwrap().cost, 9);
+        assert!(bfs_search_node_filtered(graph, 0, 2, |_| true).is_none());
+    }
+
+    #[test]
+    fn test_discover_goal() {
+        let mut graph = Graph::new();
+        for name in ["A", "B1", "C", "B2"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 2), 1));
+        graph.add_link(Link::new((2, 3), 1));
+        graph.add_link(Link::new((0, 1), 5));
+        let is_b = |node: &Node| node.name.starts_with('B');
+        let result = bfs_search_goal(&graph, 0, is_b).unwrap();
+        assert_eq!(result.links, vec![Link::new((0, 0), 0), Link::new((0, 1), 5)]);
+        assert_eq!(result.cost, 5);
+        assert_eq!(bfs_search_goal(&graph, 3, is_b).unwrap().links.len(), 1);
+        assert!(bfs_search_goal(&graph, 0, |node| node.name == "D").is_none());
+        assert!(bfs_search_goal(&graph, 7, is_b).is_none());
+    }
+
+    #[test]
+    fn test_discover_generic() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for name in ["A", "B", "C", "D", "E"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        graph.add_link(Link::new((2, 3), 1));
+        graph.add_link(Link::new((3, 0), 9));
+        graph.add_link(Link::new((3, 7), 1));
+        let result = bfs_search_generic(&graph, 0, 3).unwrap();
+        assert_eq!(result.links, vec![Link::new((0, 0), 0), Link::new((0, 3), 9)]);
+        assert_eq!(result.cost, 9);
+        assert_eq!(bfs_search_generic(&graph, 1, 1).unwrap().links.len(), 1);
+        assert!(bfs_search_generic(&graph, 0, 4).is_none());
+        assert!(bfs_search_generic(&graph, 0, 7).is_none());
+    }
+
+    #[test]
+    fn test_report_unreachable() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new("Node 1"));
+        graph.add_node(Node::new("Node 2"));
+        graph.add_node(Node::new("Node 3"));
+        graph.add_link(Link::new((0, 1), 1));
+        let report = bfs_search_report(graph, 0, 2);
+        assert!(report.result.is_none());
+        assert_eq!(report.stats.nodes_expanded, 2);
+        assert_eq!(report.stats.links_examined, 2);
+        assert_eq!(report.stats.max_frontier, 1);
     }
 
     #[test]
@@ -135,24 +491,25 @@ mod discover_test {
         let mut graph = Graph::new();
         let node1 = Node::new("Node 1");
         graph.add_node(node1);
-        assert_eq!(bfs_search_node(graph, 1, 2).is_none(), true);
+        assert!(bfs_search_node(graph, 1, 2).is_none());
     }
 
     #[test]
     fn test_discover_no_start_and_target_element() {
         let graph = Graph::new();
-        assert_eq!(bfs_search_node(graph, 1, 2).is_none(), true);
+        assert!(bfs_search_node(graph, 1, 2).is_none());
     }
 
     #[test]
     fn test_discover_no_link() {
         let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
         let node1 = Node::new("Node 1");
         let node2 = Node::new("Node 1");
         graph.add_node(node1);
         graph.add_node(node2);
         graph.add_link(Link::new((1, 3), 1));
-        assert_eq!(bfs_search_node(graph, 1, 2).is_none(), true);
+        assert!(bfs_search_node(graph, 1, 2).is_none());
     }
 
     #[test]
@@ -218,6 +575,7 @@ mod discover_test {
     #[test]
     fn discover_elements_with_loose_end_links() {
         let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
         let mut node1 = Node::new("Node 1");
         let mut node2 = Node::new("Node 2");
         node1.id = graph.add_node(node1);
@@ -255,14 +613,14 @@ mod discover_test {
         let mut node3 = Node::new("Node 3");
         let mut node4 = Node::new("Node 4");
         let mut node5 = Node::new("Node 5");
-        let mut node6 = Node::new("Node 6");
+        let node6 = Node::new("Node 6");
         let mut node7 = Node::new("Node 7");
         node1.id = graph.add_node(node1);
         node2.id = graph.add_node(node2);
         node3.id = graph.add_node(node3);
         node4.id = graph.add_node(node4);
         node5.id = graph.add_node(node5);
-        node6.id = graph.add_node(node6);
+        graph.add_node(node6);
         node7.id = graph.add_node(node7);
         let link1 = Link::new((node1.id, node3.id), 1);
         let link2 = Link::new((node1.id, node2.id), 1);
@@ -282,4 +640,35 @@ mod discover_test {
         assert_eq!(result.links[2], Link::new((node2.id, node4.id), 2));
         assert_eq!(result.links[3], Link::new((node4.id, node7.id), 1));
     }
+
+    #[test]
+    fn test_discover_with_options() {
+        use crate::util::SplitMix64;
+        let mut rng = SplitMix64::new(36);
+        for _ in 0..20 {
+            let graph = crate::generate::erdos_renyi(10, 0.3, 1..10, &mut rng);
+            let hops = bfs_search_node(graph.clone(), 0, 9).map(|result| result.links.len() - 1);
+            for max_depth in 0..6 {
+                let report = bfs_search_with_options(&graph, 0, 9, &SearchOptions::new().max_depth(max_depth).record_trace(true));
+                let found = report.result.map(|result| result.links.len() - 1);
+                assert_eq!(found, hops.filter(|hops| *hops <= max_depth));
+                assert_eq!(report.trace.len(), report.stats.nodes_expanded);
+            }
+        }
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 3), 1));
+        graph.add_link(Link::new((0, 2), 1));
+        graph.add_link(Link::new((2, 3), 1));
+        let options = SearchOptions::new().edge_filter(|link| link.members != (1, 3)).record_trace(true);
+        let report = bfs_search_with_options(&graph, 0, 3, &options);
+        assert_eq!(report.result.unwrap().links[2], Link::new((2, 3), 1));
+        assert_eq!(report.trace, vec![0, 1, 2]);
+        assert!(bfs_search_with_options(&graph, 0, 3, &SearchOptions::new().max_nodes(2)).result.is_none());
+        assert!(bfs_search_with_options(&graph, 0, 3, &SearchOptions::new().max_nodes(3)).result.is_some());
+        assert!(bfs_search_with_options(&graph, 7, 3, &SearchOptions::new()).result.is_none());
+    }
 }
@@ -0,0 +1,174 @@
+//! [Community detection](https://en.wikipedia.org/wiki/Community_structure): finding groups of nodes which are densely linked
+//! among each other, but only loosely to the rest of the graph, like the racks or sites of a network.
+//!
+//! Communities are returned as one label per node, in the order of graph.nodes(), numbered from 0 in the order in which
+//! the communities first appear. Links are counted, their cost is ignored.
+
+use crate::dijkstra::neighbours;
+use crate::graph::Graph;
+use crate::util::{self, RandomSource};
+use std::collections::HashMap;
+
+/// The most rounds label_propagation runs before it stops, if the labels keep changing.
+const MAX_ROUNDS: usize = 1000;
+
+/// Detects communities by [label propagation](https://en.wikipedia.org/wiki/Label_propagation_algorithm), in O(links) time per round.
+///
+/// Every node starts with a label of its own. In every round, the nodes are visited in random order and each one takes the
+/// label most of its neighbours have, picking one at random on ties, but keeping its own label if that is among the most common.
+/// Labels spread through densely linked groups quickly, but hardly cross sparse cuts.
+/// The rounds stop once no label changes, so the result depends on the random number generator.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::community;
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::util::SplitMix64;
+///
+/// // two triangles, joined by a single link
+/// let mut graph = Graph::new();
+/// for name in ["A", "B", "C", "D", "E", "F"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// for (a, b) in [(0, 1), (1, 2), (0, 2), (3, 4), (4, 5), (3, 5), (2, 3)] {
+///     graph.add_link(Link::new((a, b), 1));
+/// }
+/// let labels = community::label_propagation(&graph, &mut SplitMix64::new(1));
+/// assert_eq!(labels, vec![0, 0, 0, 1, 1, 1]);
+/// assert!(community::modularity(&graph, &labels) > 0.3);
+/// ```
+pub fn label_propagation<R>(graph: &Graph, rng: &mut R) -> Vec<usize>
+where
+    R: RandomSource + ?Sized,
+{
+    let neighbours = neighbours(graph);
+    let mut labels: Vec<usize> = (0..neighbours.len()).collect();
+    let mut order: Vec<usize> = (0..neighbours.len()).collect();
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for _ in 0..MAX_ROUNDS {
+        let mut changed = false;
+        let len = order.len();
+        for &node in util::choose_multiple(&mut order, len, rng).iter() {
+            if neighbours[node].is_empty() {
+                continue;
+            }
+            counts.clear();
+            for (neighbour, _) in &neighbours[node] {
+                *counts.entry(labels[*neighbour]).or_insert(0) += 1;
+            }
+            let most = counts.values().copied().max().unwrap_or(0);
+            if counts.get(&labels[node]) == Some(&most) {
+                continue;
+            }
+            // sorted, so the random pick does not depend on the order of the map
+            let mut candidates: Vec<usize> = counts.iter().filter(|(_, count)| **count == most).map(|(label, _)| *label).collect();
+            candidates.sort_unstable();
+            labels[node] = candidates[rng.random_index(candidates.len())];
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+    renumber(&labels)
+}
+
+/// Computes the [modularity](https://en.wikipedia.org/wiki/Modularity_(networks)) of the communities given by the labels,
+/// which measures how much denser the communities are linked within than a random graph with the same degrees would be.
+///
+/// It lies between -0.5 and 1, values above 0.3 usually indicate clear communities.
+/// Links from a node to itself and to nodes outside of the graph are ignored, a graph without links has a modularity of 0.
+///
+/// # Panics
+/// Panics if there are fewer labels than nodes.
+pub fn modularity(graph: &Graph, labels: &[usize]) -> f64 {
+    let neighbours = neighbours(graph);
+    assert!(labels.len() >= neighbours.len(), "every node needs a label");
+    let links: usize = neighbours.iter().map(Vec::len).sum::<usize>() / 2;
+    if links == 0 {
+        return 0.0;
+    }
+    // the links within and the total degree of every community
+    let mut inner: HashMap<usize, usize> = HashMap::new();
+    let mut degrees: HashMap<usize, usize> = HashMap::new();
+    for (node, neighbours) in neighbours.iter().enumerate() {
+        *degrees.entry(labels[node]).or_insert(0) += neighbours.len();
+        let same = neighbours.iter().filter(|(neighbour, _)| labels[*neighbour] == labels[node]).count();
+        *inner.entry(labels[node]).or_insert(0) += same;
+    }
+    let links = links as f64;
+    degrees
+        .iter()
+        .map(|(label, degree)| {
+            // every inner link has been counted from both its members
+            let inner = inner[label] as f64 / 2.0;
+            inner / links - (*degree as f64 / (2.0 * links)).powi(2)
+        })
+        .sum()
+}
+
+/// Numbers the labels from 0 in the order they first appear.
+fn renumber(labels: &[usize]) -> Vec<usize> {
+    let mut numbers: HashMap<usize, usize> = HashMap::new();
+    labels
+        .iter()
+        .map(|label| {
+            let next = numbers.len();
+            *numbers.entry(*label).or_insert(next)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod community_test {
+    use super::*;
+    use crate::generate;
+    use crate::graph::{Link, Node};
+    use crate::util::SplitMix64;
+
+    /// Dense clusters of 10 nodes each, every cluster linked to the next one by a single link.
+    fn clusters(count: usize, rng: &mut SplitMix64) -> Graph {
+        let mut graph = Graph::new();
+        for cluster in 0..count {
+            let part = generate::erdos_renyi(10, 0.8, 1..2, rng);
+            let offset = graph.nodes().len() as isize;
+            for index in 0..10 {
+                graph.add_node(Node::new(Box::leak(format!("{}-{}", cluster, index).into_boxed_str())));
+            }
+            for link in part.links() {
+                graph.add_link(Link::new((link.members.0 + offset, link.members.1 + offset), 1));
+            }
+            if cluster > 0 {
+                graph.add_link(Link::new((offset - 1, offset), 1));
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn finds_clusters() {
+        let mut rng = SplitMix64::new(14);
+        let graph = clusters(4, &mut rng);
+        let labels = label_propagation(&graph, &mut rng);
+        assert_eq!(labels.len(), 40);
+        for cluster in labels.chunks(10) {
+            assert!(cluster.iter().all(|label| *label == cluster[0]));
+        }
+        assert_eq!(labels.iter().max(), Some(&3));
+        assert!(modularity(&graph, &labels) > 0.6);
+        // all nodes in one community have a modularity of 0
+        assert!(modularity(&graph, &[0; 40]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn isolated_nodes() {
+        let mut graph = Graph::new();
+        for name in ["A", "B", "C"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((1, 1), 1));
+        assert_eq!(label_propagation(&graph, &mut SplitMix64::new(1)), vec![0, 1, 2]);
+        assert_eq!(modularity(&graph, &[0, 1, 2]), 0.0);
+        assert!(label_propagation(&Graph::new(), &mut SplitMix64::new(1)).is_empty());
+    }
+}
@@ -0,0 +1,283 @@
+//! Approximations for the [vertex cover](https://en.wikipedia.org/wiki/Vertex_cover) and
+//! [independent set](https://en.wikipedia.org/wiki/Independent_set_(graph_theory)) problems, which are NP-hard to solve exactly.
+//!
+//! A vertex cover is a set of nodes touching every link, like the switches to monitor so that every link is watched.
+//! An independent set is a set of nodes of which no two are linked, the nodes not part of a vertex cover.
+//! Node sets are returned as the ids of their nodes in ascending order, links to nodes outside of the graph are ignored.
+//!
+//! A [set cover](https://en.wikipedia.org/wiki/Set_cover_problem) is a choice of sets which together contain every element,
+//! like the monitoring probes to deploy so that every link is watched by at least one of them.
+
+use crate::dijkstra::{index_of, neighbours};
+use crate::graph::Graph;
+
+/// Computes a vertex cover with at most twice as many nodes as the smallest one, in O(nodes + links) time.
+///
+/// Both members of every link not covered yet are added, so the links picked this way form a
+/// [maximal matching](https://en.wikipedia.org/wiki/Maximal_matching). Every cover needs one member of each of these links,
+/// which share no members, so the cover is at most twice as large as needed.
+/// A link from a node to itself is covered by adding that node.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::cover;
+/// use rust_algorithms::graph::*;
+///
+/// // a star: the center alone covers all links
+/// let mut graph = Graph::new();
+/// let center = graph.add_node(Node::new("Center"));
+/// for name in ["A", "B", "C"] {
+///     let leaf = graph.add_node(Node::new(name));
+///     graph.add_link(Link::new((center, leaf), 1));
+/// }
+/// let cover = cover::vertex_cover_2approx(&graph);
+/// assert_eq!(cover, vec![center, 1]);
+/// assert!(cover::is_vertex_cover(&graph, &cover));
+/// ```
+pub fn vertex_cover_2approx(graph: &Graph) -> Vec<isize> {
+    let mut covered = vec![false; graph.nodes().len()];
+    for link in graph.links() {
+        if let (Some(a), Some(b)) = (index_of(graph, link.members.0), index_of(graph, link.members.1)) {
+            if !covered[a] && !covered[b] {
+                covered[a] = true;
+                covered[b] = true;
+            }
+        }
+    }
+    members(graph, &covered)
+}
+
+/// Computes a maximal independent set greedily, in O(n log n + links) time: the nodes are visited from the fewest to the most links,
+/// each one is added if none of its neighbours has been added yet.
+///
+/// The set is maximal, no node can be added to it, but not necessarily the largest one.
+/// Taking the nodes with few links first keeps many of their neighbours available, which usually gives larger sets.
+/// Nodes linked to themselves are never part of the set.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::cover;
+/// use rust_algorithms::graph::*;
+///
+/// // a path of 5 nodes: every second node is independent
+/// let mut graph = Graph::new();
+/// for name in ["A", "B", "C", "D", "E"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// for id in 1..5 {
+///     graph.add_link(Link::new((id - 1, id), 1));
+/// }
+/// let set = cover::maximal_independent_set(&graph);
+/// assert_eq!(set, vec![0, 2, 4]);
+/// assert!(cover::is_independent_set(&graph, &set));
+/// ```
+pub fn maximal_independent_set(graph: &Graph) -> Vec<isize> {
+    let neighbours = neighbours(graph);
+    let mut blocked = vec![false; graph.nodes().len()];
+    for link in graph.links() {
+        if link.members.0 == link.members.1 {
+            if let Some(index) = index_of(graph, link.members.0) {
+                blocked[index] = true;
+            }
+        }
+    }
+    let mut order: Vec<usize> = (0..neighbours.len()).collect();
+    order.sort_by_key(|index| neighbours[*index].len());
+    let mut chosen = vec![false; neighbours.len()];
+    for index in order {
+        if !blocked[index] {
+            chosen[index] = true;
+            for (neighbour, _) in &neighbours[index] {
+                blocked[*neighbour] = true;
+            }
+        }
+    }
+    members(graph, &chosen)
+}
+
+/// Chooses sets which together contain every element of the universe, the elements 0 to universe - 1, at a low total cost.
+/// Every set is given by its elements and its cost. Elements outside of the universe are ignored.
+///
+/// The sets are chosen greedily, always the one with the lowest cost per element it adds, which costs at most
+/// H(s) = 1 + 1/2 + … + 1/s times as much as the cheapest cover, where s is the size of the largest set.
+/// Takes O(chosen sets · sets + elements of all sets) time.
+///
+/// Returns the indices of the chosen sets in the order they were chosen, or None if an element is part of no set.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::cover;
+///
+/// // one set containing everything, or three smaller ones which are cheaper together
+/// let sets = vec![(vec![0, 1, 2, 3, 4, 5], 10), (vec![0, 1], 2), (vec![2, 3], 2), (vec![4, 5], 3)];
+/// assert_eq!(cover::set_cover(6, &sets), Some(vec![1, 2, 3]));
+/// assert_eq!(cover::set_cover(7, &sets), None);
+/// ```
+pub fn set_cover(universe: usize, sets: &[(Vec<usize>, usize)]) -> Option<Vec<usize>> {
+    // the sets every element is part of, and the number of elements every set would add
+    let mut containing: Vec<Vec<usize>> = vec![Vec::new(); universe];
+    for (index, (elements, _)) in sets.iter().enumerate() {
+        for &element in elements.iter().filter(|element| **element < universe) {
+            if containing[element].last() != Some(&index) {
+                containing[element].push(index);
+            }
+        }
+    }
+    let mut adds: Vec<usize> = vec![0; sets.len()];
+    for &index in containing.iter().flatten() {
+        adds[index] += 1;
+    }
+    let mut covered = vec![false; universe];
+    let mut uncovered = universe;
+    let mut chosen = Vec::new();
+    while uncovered > 0 {
+        // the lowest cost per added element, compared without dividing
+        let best = (0..sets.len()).filter(|index| adds[*index] > 0).min_by(|a, b| {
+            (sets[*a].1 as u128 * adds[*b] as u128).cmp(&(sets[*b].1 as u128 * adds[*a] as u128))
+        })?;
+        chosen.push(best);
+        for &element in &sets[best].0 {
+            if element < universe && !covered[element] {
+                covered[element] = true;
+                uncovered -= 1;
+                for &index in &containing[element] {
+                    adds[index] -= 1;
+                }
+            }
+        }
+    }
+    Some(chosen)
+}
+
+/// Returns true if every link of the graph has at least one member among the nodes.
+pub fn is_vertex_cover(graph: &Graph, nodes: &[isize]) -> bool {
+    let set = node_set(graph, nodes);
+    graph.links().iter().all(|link| match (index_of(graph, link.members.0), index_of(graph, link.members.1)) {
+        (Some(a), Some(b)) => set[a] || set[b],
+        _ => true,
+    })
+}
+
+/// Returns true if no two of the nodes are linked, and none is linked to itself.
+pub fn is_independent_set(graph: &Graph, nodes: &[isize]) -> bool {
+    let set = node_set(graph, nodes);
+    graph.links().iter().all(|link| match (index_of(graph, link.members.0), index_of(graph, link.members.1)) {
+        (Some(a), Some(b)) => !(set[a] && set[b]),
+        _ => true,
+    })
+}
+
+/// Marks the nodes of the graph which are part of the given ids.
+fn node_set(graph: &Graph, nodes: &[isize]) -> Vec<bool> {
+    let mut set = vec![false; graph.nodes().len()];
+    for index in nodes.iter().filter_map(|id| index_of(graph, *id)) {
+        set[index] = true;
+    }
+    set
+}
+
+/// Returns the ids of the marked nodes.
+fn members(graph: &Graph, marked: &[bool]) -> Vec<isize> {
+    graph.nodes().iter().zip(marked).filter(|(_, marked)| **marked).map(|(node, _)| node.id).collect()
+}
+
+#[cfg(test)]
+mod cover_test {
+    use super::*;
+    use crate::generate;
+    use crate::graph::{Link, Node};
+    use crate::util::{RandomSource, SplitMix64};
+
+    /// Finds the size of the smallest vertex cover by trying all sets of nodes.
+    fn smallest_cover(graph: &Graph) -> usize {
+        let count = graph.nodes().len();
+        (0..1usize << count)
+            .filter(|set| {
+                let nodes: Vec<isize> = (0..count as isize).filter(|id| set & (1 << id) != 0).collect();
+                is_vertex_cover(graph, &nodes)
+            })
+            .map(|set| set.count_ones() as usize)
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn covers() {
+        let mut rng = SplitMix64::new(10);
+        for _ in 0..30 {
+            let graph = generate::erdos_renyi(10, 0.3, 1..2, &mut rng);
+            let cover = vertex_cover_2approx(&graph);
+            assert!(is_vertex_cover(&graph, &cover));
+            assert!(cover.len() <= 2 * smallest_cover(&graph));
+            assert!(cover.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn independent_sets() {
+        let mut rng = SplitMix64::new(11);
+        for _ in 0..30 {
+            let graph = generate::erdos_renyi(30, 0.1, 1..2, &mut rng);
+            let set = maximal_independent_set(&graph);
+            assert!(is_independent_set(&graph, &set));
+            // maximal: every other node has a neighbour in the set
+            for id in 0..30 {
+                if !set.contains(&id) {
+                    let mut extended = set.clone();
+                    extended.push(id);
+                    assert!(!is_independent_set(&graph, &extended));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn set_covers() {
+        let mut rng = SplitMix64::new(49);
+        for _ in 0..30 {
+            let sets: Vec<(Vec<usize>, usize)> = (0..8)
+                .map(|_| ((0..12).filter(|_| rng.random_f64() < 0.3).collect(), rng.random_range(1..10)))
+                .collect();
+            // the cheapest cover, by trying all choices of sets
+            let cost = |choice: &[usize]| choice.iter().map(|index| sets[*index].1).sum::<usize>();
+            let cheapest = (0..1usize << sets.len())
+                .map(|choice| (0..sets.len()).filter(|index| choice & (1 << index) != 0).collect::<Vec<usize>>())
+                .filter(|choice| (0..12).all(|element| choice.iter().any(|index| sets[*index].0.contains(&element))))
+                .map(|choice| cost(&choice))
+                .min();
+            match set_cover(12, &sets) {
+                Some(chosen) => {
+                    assert!((0..12).all(|element| chosen.iter().any(|index| sets[*index].0.contains(&element))));
+                    // no set is chosen twice, and none without adding anything
+                    let mut distinct = chosen.clone();
+                    distinct.sort_unstable();
+                    distinct.dedup();
+                    assert_eq!(distinct.len(), chosen.len());
+                    let largest = sets.iter().map(|set| set.0.len()).max().unwrap();
+                    let harmonic: f64 = (1..=largest).map(|size| 1.0 / size as f64).sum();
+                    assert!(cost(&chosen) as f64 <= harmonic * cheapest.unwrap() as f64 + 1e-9);
+                }
+                None => assert_eq!(cheapest, None),
+            }
+        }
+        // duplicates and elements outside of the universe, and an empty universe
+        assert_eq!(set_cover(2, &[(vec![1, 1, 5], 1), (vec![0, 9], 0)]), Some(vec![1, 0]));
+        assert_eq!(set_cover(0, &[]), Some(vec![]));
+    }
+
+    #[test]
+    fn self_links() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for name in ["A", "B", "C"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((1, 1), 1));
+        graph.add_link(Link::new((0, 7), 1));
+        assert_eq!(vertex_cover_2approx(&graph), vec![1]);
+        assert_eq!(maximal_independent_set(&graph), vec![0, 2]);
+        assert!(!is_independent_set(&graph, &[1]));
+        assert!(!is_vertex_cover(&graph, &[0, 2]));
+        assert!(vertex_cover_2approx(&Graph::new()).is_empty());
+    }
+}
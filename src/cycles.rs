@@ -0,0 +1,309 @@
+//! Enumeration of the [elementary cycles](https://en.wikipedia.org/wiki/Cycle_(graph_theory)) of a graph,
+//! the closed paths which visit no node twice, like accidental loops in a generated topology.
+//!
+//! Cycles are returned as the ids of their nodes in the order they are visited, starting with the lowest id,
+//! and going on to the lower one of its two neighbours on the cycle, so every cycle is listed exactly once.
+//! A link from a node to itself is a cycle of that node alone, links to nodes outside of the graph are ignored.
+//! The length of a cycle is its number of links, the costs of the links are ignored.
+
+use crate::dijkstra::{index_of, neighbours};
+use crate::graph::{Graph, Link};
+use std::collections::VecDeque;
+
+/// Lists the elementary cycles of the graph using [Johnson's algorithm](https://doi.org/10.1137/0204007), stopping after limit cycles.
+///
+/// The cycles through every node are searched among the nodes with higher ids only. Nodes from which the search cannot get back
+/// to its start stay blocked until that changes, so no time is wasted on dead ends: it takes O((nodes + links) · (cycles + 1)) time.
+/// A graph can have exponentially many cycles, so pick the limit with care.
+/// If exactly limit cycles are returned, there may be more.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::cycles;
+/// use rust_algorithms::graph::*;
+///
+/// // a square with one diagonal
+/// let mut graph = Graph::new();
+/// for name in ["A", "B", "C", "D"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)] {
+///     graph.add_link(Link::new((a, b), 1));
+/// }
+/// assert_eq!(cycles::enumerate(&graph, 10), vec![vec![0, 1, 2, 3], vec![0, 1, 2], vec![0, 2, 3]]);
+/// assert_eq!(cycles::enumerate(&graph, 1).len(), 1);
+/// ```
+pub fn enumerate(graph: &Graph, limit: usize) -> Vec<Vec<isize>> {
+    let neighbours = neighbours(graph);
+    let ids: Vec<isize> = graph.nodes().iter().map(|node| node.id).collect();
+    let mut self_linked = vec![false; ids.len()];
+    for link in graph.links().iter().filter(|link| link.members.0 == link.members.1) {
+        if let Some(index) = index_of(graph, link.members.0) {
+            self_linked[index] = true;
+        }
+    }
+
+    let mut cycles = Vec::new();
+    let mut blocked = vec![false; ids.len()];
+    // the nodes to unblock once a node gets unblocked
+    let mut blocked_by: Vec<Vec<usize>> = vec![Vec::new(); ids.len()];
+    for start in 0..ids.len() {
+        if cycles.len() >= limit {
+            break;
+        }
+        if self_linked[start] {
+            cycles.push(vec![ids[start]]);
+            if cycles.len() >= limit {
+                break;
+            }
+        }
+        blocked.iter_mut().for_each(|blocked| *blocked = false);
+        blocked_by.iter_mut().for_each(Vec::clear);
+
+        // the current path, with the position in the neighbours of every node and whether a cycle has been found from it
+        let mut path = vec![start];
+        let mut frames = vec![(0, false)];
+        blocked[start] = true;
+        while let Some(&node) = path.last() {
+            let frame = frames.last_mut().unwrap();
+            if let Some(&(next, _)) = neighbours[node].get(frame.0) {
+                frame.0 += 1;
+                if next == start {
+                    // back and forth along a single link is not a cycle, and every cycle is found in both directions
+                    frame.1 = true;
+                    if path.len() >= 3 && path[1] < node {
+                        cycles.push(path.iter().map(|index| ids[*index]).collect());
+                        if cycles.len() >= limit {
+                            return cycles;
+                        }
+                    }
+                } else if next > start && !blocked[next] {
+                    blocked[next] = true;
+                    path.push(next);
+                    frames.push((0, false));
+                }
+                continue;
+            }
+
+            let (_, found) = frames.pop().unwrap();
+            path.pop();
+            if found {
+                unblock(node, &mut blocked, &mut blocked_by);
+            } else {
+                for &(next, _) in &neighbours[node] {
+                    if next > start && !blocked_by[next].contains(&node) {
+                        blocked_by[next].push(node);
+                    }
+                }
+            }
+            if let Some(parent) = frames.last_mut() {
+                parent.1 |= found;
+            }
+        }
+    }
+    cycles
+}
+
+/// Finds a cycle through the node with the fewest links, by a breadth-first search from it in O(nodes + links) time.
+///
+/// Returns the ids of the nodes of the cycle starting with the node, going on to the lower one of its two neighbours
+/// on the cycle, or None if it is not part of any cycle or not part of the graph.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::cycles;
+/// use rust_algorithms::generate::{self, TopologySpec};
+///
+/// // a 3 by 3 grid: the shortest cycles are its squares
+/// let graph = generate::topology(&TopologySpec::Grid { rows: 3, columns: 3 }, 1);
+/// assert_eq!(cycles::shortest_cycle_through(&graph, 4), Some(vec![4, 1, 0, 3]));
+/// assert_eq!(cycles::girth(&graph), Some(4));
+/// ```
+pub fn shortest_cycle_through(graph: &Graph, node_id: isize) -> Option<Vec<isize>> {
+    let start = index_of(graph, node_id)?;
+    shortest_cycle(graph, &neighbours(graph), start).map(|cycle| cycle.into_iter().map(|index| graph.nodes()[index].id).collect())
+}
+
+/// Returns the [girth](https://en.wikipedia.org/wiki/Girth_(graph_theory)) of the graph, the number of links of its shortest cycle,
+/// or None if it has no cycles. Runs a breadth-first search from every node, in O(nodes · (nodes + links)) time.
+pub fn girth(graph: &Graph) -> Option<usize> {
+    let neighbours = neighbours(graph);
+    (0..neighbours.len()).filter_map(|start| shortest_cycle(graph, &neighbours, start).map(|cycle| cycle.len())).min()
+}
+
+/// Finds the shortest cycle through the node at the index, as the indices of its nodes.
+///
+/// Every node reached by the search remembers the neighbour of the start it has been reached through.
+/// Two linked nodes reached through different neighbours close a cycle through the start, the shortest such pair the shortest cycle.
+fn shortest_cycle(graph: &Graph, neighbours: &[Vec<(usize, &Link)>], start: usize) -> Option<Vec<usize>> {
+    let self_linked = graph
+        .links()
+        .iter()
+        .any(|link| link.members.0 == link.members.1 && index_of(graph, link.members.0) == Some(start));
+    if self_linked {
+        return Some(vec![start]);
+    }
+
+    // the distance, parent and neighbour of the start every node has been reached through
+    let mut reached: Vec<Option<(usize, usize, usize)>> = vec![None; neighbours.len()];
+    reached[start] = Some((0, start, start));
+    let mut queue = VecDeque::new();
+    for &(next, _) in &neighbours[start] {
+        reached[next] = Some((1, start, next));
+        queue.push_back(next);
+    }
+    let mut best: Option<(usize, usize, usize)> = None;
+    while let Some(node) = queue.pop_front() {
+        let (distance, _, branch) = reached[node].unwrap();
+        // cycles found later are at least as long as 2 · distance + 1
+        if best.is_some_and(|(length, _, _)| length <= 2 * distance + 1) {
+            break;
+        }
+        for &(next, _) in &neighbours[node] {
+            match reached[next] {
+                None => {
+                    reached[next] = Some((distance + 1, node, branch));
+                    queue.push_back(next);
+                }
+                Some((other_distance, _, other_branch)) if next != start && other_branch != branch => {
+                    let length = distance + other_distance + 1;
+                    if best.is_none_or(|(best, _, _)| length < best) {
+                        best = Some((length, node, next));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let (_, a, b) = best?;
+    let to_start = |mut node: usize| {
+        let mut path = Vec::new();
+        while node != start {
+            path.push(node);
+            node = reached[node].unwrap().1;
+        }
+        path
+    };
+    let mut cycle = to_start(a);
+    cycle.push(start);
+    cycle.reverse();
+    let back = to_start(b);
+    cycle.extend(back);
+    if cycle[1] > cycle[cycle.len() - 1] {
+        cycle[1..].reverse();
+    }
+    Some(cycle)
+}
+
+/// Unblocks the node and all nodes waiting for it, transitively.
+fn unblock(node: usize, blocked: &mut [bool], blocked_by: &mut [Vec<usize>]) {
+    let mut pending = vec![node];
+    while let Some(node) = pending.pop() {
+        if blocked[node] {
+            blocked[node] = false;
+            pending.append(&mut blocked_by[node]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod cycles_test {
+    use super::*;
+    use crate::generate;
+    use crate::graph::{Link, Node};
+    use crate::util::SplitMix64;
+    use std::collections::HashSet;
+
+    /// Finds all cycles by trying every path from every start, keeping the ones in the form enumerate returns them.
+    fn all_cycles(graph: &Graph) -> HashSet<Vec<isize>> {
+        fn extend(neighbours: &[Vec<(usize, &Link)>], path: &mut Vec<usize>, cycles: &mut HashSet<Vec<isize>>) {
+            let node = *path.last().unwrap();
+            for &(next, _) in &neighbours[node] {
+                if next == path[0] && path.len() >= 3 && path[1] < node {
+                    cycles.insert(path.iter().map(|index| *index as isize).collect());
+                } else if next > path[0] && !path.contains(&next) {
+                    path.push(next);
+                    extend(neighbours, path, cycles);
+                    path.pop();
+                }
+            }
+        }
+        let neighbours = neighbours(graph);
+        let mut cycles = HashSet::new();
+        for start in 0..neighbours.len() {
+            extend(&neighbours, &mut vec![start], &mut cycles);
+        }
+        cycles
+    }
+
+    #[test]
+    fn random_graphs() {
+        let mut rng = SplitMix64::new(25);
+        for _ in 0..30 {
+            let graph = generate::erdos_renyi(9, 0.4, 1..2, &mut rng);
+            let cycles = enumerate(&graph, usize::MAX);
+            let unique: HashSet<Vec<isize>> = cycles.iter().cloned().collect();
+            assert_eq!(unique.len(), cycles.len());
+            assert_eq!(unique, all_cycles(&graph));
+        }
+    }
+
+    #[test]
+    fn complete_graphs() {
+        // 10 triangles, 15 squares and 12 pentagons
+        let graph = generate::erdos_renyi(5, 1.0, 1..2, &mut SplitMix64::new(1));
+        assert_eq!(enumerate(&graph, usize::MAX).len(), 37);
+        assert_eq!(enumerate(&graph, 20).len(), 20);
+        assert!(enumerate(&graph, 0).is_empty());
+        // nor does a graph without links
+        let empty = generate::erdos_renyi(6, 0.0, 1..2, &mut SplitMix64::new(1));
+        assert!(enumerate(&empty, usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn shortest_cycles() {
+        let mut rng = SplitMix64::new(30);
+        for _ in 0..30 {
+            let graph = generate::erdos_renyi(10, 0.25, 1..2, &mut rng);
+            let all = enumerate(&graph, usize::MAX);
+            for id in 0..10 {
+                let expected = all.iter().filter(|cycle| cycle.contains(&id)).map(Vec::len).min();
+                let cycle = shortest_cycle_through(&graph, id);
+                assert_eq!(cycle.as_ref().map(Vec::len), expected);
+                if let Some(cycle) = cycle {
+                    assert_eq!(cycle[0], id);
+                    assert!(cycle[1] < cycle[cycle.len() - 1]);
+                    for index in 0..cycle.len() {
+                        assert!(graph.contains_link(cycle[index], cycle[(index + 1) % cycle.len()]));
+                        assert!(!cycle[index + 1..].contains(&cycle[index]));
+                    }
+                }
+            }
+            assert_eq!(girth(&graph), all.iter().map(Vec::len).min());
+        }
+        let ring = generate::topology(&generate::TopologySpec::Ring { nodes: 7 }, 1);
+        assert_eq!(girth(&ring), Some(7));
+        assert_eq!(shortest_cycle_through(&ring, 7), None);
+        assert_eq!(girth(&generate::topology(&generate::TopologySpec::Line { nodes: 7 }, 1)), None);
+    }
+
+    #[test]
+    fn self_links() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for name in ["A", "B", "C"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((1, 1), 1));
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((2, 5), 1));
+        assert_eq!(enumerate(&graph, 10), vec![vec![1]]);
+        graph.add_link(Link::new((0, 2), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        assert_eq!(enumerate(&graph, 10), vec![vec![0, 1, 2], vec![1]]);
+        assert_eq!(girth(&graph), Some(1));
+        assert_eq!(shortest_cycle_through(&graph, 1), Some(vec![1]));
+        assert_eq!(shortest_cycle_through(&graph, 0), Some(vec![0, 1, 2]));
+    }
+}
@@ -0,0 +1,360 @@
+//! Algorithms on [directed acyclic graphs](https://en.wikipedia.org/wiki/Directed_acyclic_graph), like the tasks of a project
+//! and the dependencies between them, and on directed graphs with cycles, like the strongly connected components.
+//!
+//! Other than in the rest of the crate, every link is read as directed from its first member to its second.
+//! Since Graph treats both orientations of a link as the same link, there can be at most one link between two nodes.
+//! Links to nodes which are not part of the graph are ignored.
+
+use crate::graph::{Graph, GraphLike, Link, SearchResult};
+use std::collections::HashMap;
+
+/// The result of the critical path method, as returned by critical_path.
+///
+/// The durations are given by the costs of the links, the times by the node ids as indices.
+#[derive(Debug)]
+pub struct CriticalPath {
+    /// The longest path, ending at the node reached the latest. Like the result of a search, its first link is from its start to itself.
+    pub path: SearchResult,
+    /// The earliest time every node can be reached, when starting all nodes without incoming links at 0.
+    pub earliest: Vec<usize>,
+    /// The latest time every node can be reached without delaying the end of the longest path.
+    pub latest: Vec<usize>,
+    /// How much every node can be delayed without delaying the end of the longest path, 0 for the nodes of critical paths.
+    pub slack: Vec<usize>,
+}
+
+impl CriticalPath {
+    /// Returns the ids of the nodes without slack, the ones on any critical path, in ascending order.
+    pub fn critical_nodes(&self) -> Vec<isize> {
+        self.slack.iter().enumerate().filter(|(_, slack)| **slack == 0).map(|(index, _)| index as isize).collect()
+    }
+}
+
+/// Orders the nodes so that every link points from an earlier node to a later one, using Kahn's algorithm in O(nodes + links) time.
+///
+/// Returns the ids of the nodes in that order, or None if the links form a cycle.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::dag;
+/// use rust_algorithms::graph::*;
+///
+/// let mut graph = Graph::new();
+/// let shirt = graph.add_node(Node::new("Shirt"));
+/// let tie = graph.add_node(Node::new("Tie"));
+/// let socks = graph.add_node(Node::new("Socks"));
+/// let jacket = graph.add_node(Node::new("Jacket"));
+/// graph.add_link(Link::new((tie, jacket), 1));
+/// graph.add_link(Link::new((shirt, tie), 1));
+///
+/// assert_eq!(dag::topological_sort(&graph), Some(vec![shirt, socks, tie, jacket]));
+/// graph.add_link(Link::new((jacket, shirt), 1));
+/// assert_eq!(dag::topological_sort(&graph), None);
+/// ```
+pub fn topological_sort(graph: &Graph) -> Option<Vec<isize>> {
+    let (order, _) = graph.topological_order()?;
+    Some(order.into_iter().map(|index| index as isize).collect())
+}
+
+/// Computes the longest path and the slack of every node using the [critical path method](https://en.wikipedia.org/wiki/Critical_path_method),
+/// in O(nodes + links) time.
+///
+/// The nodes are the events of a project, like the start and end of tasks, the links the tasks with their duration as cost.
+/// The longest path is the critical path: it decides how long the whole project takes, and delaying any of its nodes delays the end.
+/// Nodes off the critical path have slack, the time they can be delayed without that.
+/// Returns None if the links form a cycle.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::dag;
+/// use rust_algorithms::graph::*;
+///
+/// let mut graph = Graph::new();
+/// let start = graph.add_node(Node::new("Start"));
+/// let cabling = graph.add_node(Node::new("Cabling done"));
+/// let config = graph.add_node(Node::new("Config done"));
+/// let end = graph.add_node(Node::new("End"));
+/// graph.add_link(Link::new((start, cabling), 5));
+/// graph.add_link(Link::new((start, config), 2));
+/// graph.add_link(Link::new((cabling, end), 3));
+/// graph.add_link(Link::new((config, end), 4));
+///
+/// let result = dag::critical_path(&graph).unwrap();
+/// assert_eq!(result.path.cost, 8);
+/// assert_eq!(result.path.links, vec![Link::new((start, start), 0), Link::new((start, cabling), 5), Link::new((cabling, end), 3)]);
+/// assert_eq!(result.earliest, vec![0, 5, 2, 8]);
+/// assert_eq!(result.slack, vec![0, 0, 2, 0]);
+/// assert_eq!(result.critical_nodes(), vec![start, cabling, end]);
+/// ```
+pub fn critical_path(graph: &Graph) -> Option<CriticalPath> {
+    let (order, successors) = graph.topological_order()?;
+    let links = graph.links();
+    let count = order.len();
+
+    // forward: the longest path to every node and the node and link it is reached through
+    let mut earliest = vec![0; count];
+    let mut previous: Vec<Option<(usize, &Link)>> = vec![None; count];
+    for &node in &order {
+        for &(next, id) in &successors[node] {
+            let time = earliest[node] + links[id.0].cost;
+            if time > earliest[next] || (previous[next].is_none() && time == earliest[next]) {
+                earliest[next] = time;
+                previous[next] = Some((node, &links[id.0]));
+            }
+        }
+    }
+
+    // backward: the latest time every node has to be reached for the end to stay on time
+    let end_time = earliest.iter().copied().max().unwrap_or(0);
+    let mut latest = vec![end_time; count];
+    for &node in order.iter().rev() {
+        for &(next, id) in &successors[node] {
+            latest[node] = latest[node].min(latest[next] - links[id.0].cost);
+        }
+    }
+    let slack = latest.iter().zip(&earliest).map(|(latest, earliest)| latest - earliest).collect();
+
+    let mut path = SearchResult { links: Vec::new(), cost: end_time };
+    // traced back from the first node reached the latest, if there are nodes at all
+    if let Some(end) = (0..count).find(|node| earliest[*node] == end_time) {
+        let mut node = end;
+        while let Some((from, link)) = previous[node] {
+            path.links.push(*link);
+            node = from;
+        }
+        path.links.push(Link::new((node as isize, node as isize), 0));
+        path.links.reverse();
+    }
+    Some(CriticalPath { path, earliest, latest, slack })
+}
+
+/// Returns the ids of the nodes with a directed path to the node, including itself, in ascending order.
+/// This is the reverse of following the links, searched on the transposed view of the graph.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::dag;
+/// use rust_algorithms::graph::*;
+///
+/// let mut graph = Graph::new();
+/// for name in ["Compiler", "Library", "App", "Docs"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// graph.add_link(Link::new((0, 1), 1));
+/// graph.add_link(Link::new((1, 2), 1));
+/// graph.add_link(Link::new((3, 2), 1));
+///
+/// // everything the app depends on
+/// assert_eq!(dag::reaching(&graph, 2), vec![0, 1, 2, 3]);
+/// assert_eq!(dag::reachable_from(&graph, 1), vec![1, 2]);
+/// ```
+pub fn reaching(graph: &Graph, node_id: isize) -> Vec<isize> {
+    search_from(&graph.transposed_view(), node_id, graph.nodes().len())
+}
+
+/// Returns the ids of the nodes the node has a directed path to, including itself, in ascending order.
+pub fn reachable_from(graph: &Graph, node_id: isize) -> Vec<isize> {
+    search_from(&graph.directed_view(), node_id, graph.nodes().len())
+}
+
+/// Labels the [strongly connected components](https://en.wikipedia.org/wiki/Strongly_connected_component) of the graph,
+/// the largest groups of nodes which all have directed paths to each other, like routing loops.
+///
+/// Uses Kosaraju's algorithm: a depth-first search on the directed view orders the nodes by the time they are finished,
+/// then searches on the transposed view, starting from the last finished nodes, collect the components.
+/// Returns the component of every node in the order of graph.nodes(), numbered from 0 in the order the components first appear.
+/// The views look through all links for the neighbours of every node, so this takes O(nodes · links) time, but no extra memory for links.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::dag;
+/// use rust_algorithms::graph::*;
+///
+/// let mut graph = Graph::new();
+/// for name in ["A", "B", "C", "D"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// graph.add_link(Link::new((0, 1), 1));
+/// graph.add_link(Link::new((1, 2), 1));
+/// graph.add_link(Link::new((2, 0), 1));
+/// graph.add_link(Link::new((2, 3), 1));
+///
+/// assert_eq!(dag::strongly_connected_components(&graph), vec![0, 0, 0, 1]);
+/// ```
+pub fn strongly_connected_components(graph: &Graph) -> Vec<usize> {
+    let count = graph.nodes().len();
+    let forward = graph.directed_view();
+
+    // the nodes in the order their depth-first search finishes
+    let mut finished = Vec::with_capacity(count);
+    let mut visited = vec![false; count];
+    for root in 0..count {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut stack = vec![(root, forward.neighbors(root as isize), 0)];
+        while let Some((node, neighbours, position)) = stack.last_mut() {
+            match neighbours.get(*position) {
+                Some(&(next, _)) => {
+                    *position += 1;
+                    if !visited[next as usize] {
+                        visited[next as usize] = true;
+                        stack.push((next as usize, forward.neighbors(next), 0));
+                    }
+                }
+                None => {
+                    finished.push(*node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    let backward = graph.transposed_view();
+    let mut components: Vec<Option<usize>> = vec![None; count];
+    let mut next_component = 0;
+    for &root in finished.iter().rev() {
+        if components[root].is_some() {
+            continue;
+        }
+        components[root] = Some(next_component);
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            for (other, _) in backward.neighbors(node as isize) {
+                if components[other as usize].is_none() {
+                    components[other as usize] = Some(next_component);
+                    stack.push(other as usize);
+                }
+            }
+        }
+        next_component += 1;
+    }
+
+    // numbered in the order of the nodes
+    let mut numbers: HashMap<usize, usize> = HashMap::new();
+    components
+        .into_iter()
+        .map(|component| {
+            let next = numbers.len();
+            *numbers.entry(component.unwrap()).or_insert(next)
+        })
+        .collect()
+}
+
+/// Returns the nodes reachable from the start in the view, including the start, in ascending order.
+fn search_from<G: GraphLike>(view: &G, start: isize, count: usize) -> Vec<isize> {
+    if start < 0 || start as usize >= count {
+        return Vec::new();
+    }
+    let mut reached = vec![false; count];
+    reached[start as usize] = true;
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for (other, _) in view.neighbors(node) {
+            if !reached[other as usize] {
+                reached[other as usize] = true;
+                stack.push(other);
+            }
+        }
+    }
+    (0..count as isize).filter(|id| reached[*id as usize]).collect()
+}
+
+#[cfg(test)]
+mod dag_test {
+    use super::*;
+    use crate::generate;
+    use crate::graph::Node;
+    use crate::util::{RandomSource, SplitMix64};
+
+    /// The length of the longest path ending at every node, by trying all paths recursively.
+    fn longest_to(graph: &Graph, node: isize) -> usize {
+        graph
+            .links()
+            .iter()
+            .filter(|link| link.members.1 == node)
+            .map(|link| longest_to(graph, link.members.0) + link.cost)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn random_dags() {
+        let mut rng = SplitMix64::new(24);
+        for _ in 0..20 {
+            // links only point to higher ids
+            let graph = generate::erdos_renyi(12, 0.3, 1..10, &mut rng);
+            let order = topological_sort(&graph).unwrap();
+            let position = |id: isize| order.iter().position(|other| *other == id).unwrap();
+            assert!(graph.links().iter().all(|link| position(link.members.0) < position(link.members.1)));
+
+            let result = critical_path(&graph).unwrap();
+            let expected: Vec<usize> = (0..12).map(|node| longest_to(&graph, node)).collect();
+            assert_eq!(result.earliest, expected);
+            assert_eq!(result.path.cost, *expected.iter().max().unwrap());
+            assert_eq!(result.path.links.iter().map(|link| link.cost).sum::<usize>(), result.path.cost);
+            assert!(result.path.links.windows(2).all(|pair| pair[0].members.1 == pair[1].members.0));
+            // delaying a node by its slack keeps all links within the latest times
+            for link in graph.links() {
+                let (a, b) = (link.members.0 as usize, link.members.1 as usize);
+                assert!(result.latest[a] + link.cost <= result.latest[b]);
+                assert!(result.earliest[a] + result.slack[a] == result.latest[a]);
+            }
+            for id in result.path.links.iter().map(|link| link.members.1) {
+                assert_eq!(result.slack[id as usize], 0);
+            }
+        }
+    }
+
+    #[test]
+    fn strongly_connected() {
+        let mut rng = SplitMix64::new(29);
+        for _ in 0..20 {
+            // random links in both directions
+            let mut graph = generate::erdos_renyi(25, 0.0, 1..2, &mut rng);
+            for _ in 0..40 {
+                let (a, b) = (rng.random_index(25) as isize, rng.random_index(25) as isize);
+                graph.add_link(Link::new((a, b), 1));
+            }
+            let components = strongly_connected_components(&graph);
+            let forward: Vec<Vec<isize>> = (0..25).map(|id| reachable_from(&graph, id)).collect();
+            for a in 0..25 {
+                // reaching is the reverse of reachable_from
+                let expected: Vec<isize> = (0..25).filter(|b| forward[*b as usize].contains(&a)).collect();
+                assert_eq!(reaching(&graph, a), expected);
+                for b in 0..25 {
+                    let mutual = forward[a as usize].contains(&b) && forward[b as usize].contains(&a);
+                    assert_eq!(components[a as usize] == components[b as usize], mutual);
+                }
+            }
+            assert_eq!(components[0], 0);
+            // a graph without cycles has a component of every node
+            if topological_sort(&graph).is_some() {
+                assert_eq!(components.iter().max(), Some(&24));
+            }
+        }
+        assert!(strongly_connected_components(&Graph::new()).is_empty());
+        assert!(reaching(&Graph::new(), 0).is_empty());
+    }
+
+    #[test]
+    fn edge_cases() {
+        let empty = critical_path(&Graph::new()).unwrap();
+        assert_eq!(empty.path.cost, 0);
+        assert!(empty.path.links.is_empty());
+
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for name in ["A", "B", "C"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 9), 4));
+        let result = critical_path(&graph).unwrap();
+        assert_eq!(result.path.links, vec![Link::new((0, 0), 0)]);
+        assert_eq!(result.critical_nodes(), vec![0, 1, 2]);
+        graph.add_link(Link::new((2, 2), 1));
+        assert!(critical_path(&graph).is_none());
+        assert!(topological_sort(&graph).is_none());
+    }
+}
@@ -0,0 +1,974 @@
+//! Data structures used by the algorithms of the crate, which the standard library does not provide.
+
+use std::ops::{Add, Range, Sub};
+
+/// Identifies an item pushed into a BinaryHeap, to change or remove it later.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Handle(usize);
+
+/// A [binary heap](https://en.wikipedia.org/wiki/Binary_heap) which returns the smallest item first,
+/// and which can change or remove any item by the handle returned when it was pushed.
+///
+/// Other than with the heap of the standard library, searches like Dijkstra's algorithm can lower the priority of a queued node
+/// with decrease_key, instead of pushing it a second time and skipping the outdated entry later.
+/// So the heap never holds more items than there are nodes.
+///
+/// Handles are never reused, not even after their item was removed, so a handle never refers to another item.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::datastructures::BinaryHeap;
+///
+/// let mut heap = BinaryHeap::new();
+/// let a = heap.push((5, "A"));
+/// let b = heap.push((3, "B"));
+/// heap.push((4, "C"));
+/// assert_eq!(heap.peek(), Some(&(3, "B")));
+///
+/// assert!(heap.decrease_key(a, (1, "A")));
+/// assert_eq!(heap.remove(b), Some((3, "B")));
+/// assert_eq!(heap.pop(), Some((1, "A")));
+/// assert_eq!(heap.pop(), Some((4, "C")));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BinaryHeap<T> {
+    /// The items in heap order, each with the number of its handle.
+    items: Vec<(T, usize)>,
+    /// The index in items of the item of every handle, None once it has been removed.
+    positions: Vec<Option<usize>>,
+}
+
+impl<T: Ord> BinaryHeap<T> {
+    /// Creates an empty heap.
+    pub fn new() -> Self {
+        BinaryHeap { items: Vec::new(), positions: Vec::new() }
+    }
+
+    /// Creates an empty heap with room for the given number of items.
+    pub fn with_capacity(capacity: usize) -> Self {
+        BinaryHeap { items: Vec::with_capacity(capacity), positions: Vec::with_capacity(capacity) }
+    }
+
+    /// Returns the number of items in the heap.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if the heap holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the smallest item, without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        self.items.first().map(|(item, _)| item)
+    }
+
+    /// Adds an item in O(log n) time and returns the handle to change or remove it later.
+    pub fn push(&mut self, item: T) -> Handle {
+        let handle = self.positions.len();
+        self.positions.push(Some(self.items.len()));
+        self.items.push((item, handle));
+        self.sift_up(self.items.len() - 1);
+        Handle(handle)
+    }
+
+    /// Removes and returns the smallest item in O(log n) time.
+    pub fn pop(&mut self) -> Option<T> {
+        self.pop_with_handle().map(|(_, item)| item)
+    }
+
+    /// Removes and returns the smallest item together with its handle.
+    pub fn pop_with_handle(&mut self) -> Option<(Handle, T)> {
+        if self.items.is_empty() {
+            return None;
+        }
+        let handle = self.items[0].1;
+        self.remove(Handle(handle)).map(|item| (Handle(handle), item))
+    }
+
+    /// Returns the item of the handle, or None if it is no longer part of the heap.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.position(handle).map(|index| &self.items[index].0)
+    }
+
+    /// Returns true if the item of the handle is still part of the heap.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    /// Replaces the item of the handle by a smaller or equal one, in O(log n) time.
+    ///
+    /// Returns false and leaves the heap unchanged if the item is no longer part of the heap or the new item is greater.
+    pub fn decrease_key(&mut self, handle: Handle, item: T) -> bool {
+        match self.position(handle) {
+            Some(index) if item <= self.items[index].0 => {
+                self.items[index].0 = item;
+                self.sift_up(index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Replaces the item of the handle by any other one, in O(log n) time.
+    ///
+    /// Returns false and leaves the heap unchanged if the item is no longer part of the heap.
+    pub fn update(&mut self, handle: Handle, item: T) -> bool {
+        match self.position(handle) {
+            Some(index) => {
+                self.items[index].0 = item;
+                self.sift_up(index);
+                self.sift_down(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the item of the handle in O(log n) time and returns it, or None if it is no longer part of the heap.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let index = self.position(handle)?;
+        let last = self.items.len() - 1;
+        self.swap(index, last);
+        // the index was valid, so there is a last item
+        let (item, _) = self.items.pop().unwrap();
+        self.positions[handle.0] = None;
+        if index < last {
+            self.sift_up(index);
+            self.sift_down(index);
+        }
+        Some(item)
+    }
+
+    /// Removes all items. The handles of the removed items stay invalid.
+    pub fn clear(&mut self) {
+        for (_, handle) in self.items.drain(..) {
+            self.positions[handle] = None;
+        }
+    }
+
+    fn position(&self, handle: Handle) -> Option<usize> {
+        self.positions.get(handle.0).copied().flatten()
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.items.swap(a, b);
+        self.positions[self.items[a].1] = Some(a);
+        self.positions[self.items[b].1] = Some(b);
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.items[index].0 >= self.items[parent].0 {
+                return;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let mut smallest = index;
+            for child in [2 * index + 1, 2 * index + 2] {
+                if child < self.items.len() && self.items[child].0 < self.items[smallest].0 {
+                    smallest = child;
+                }
+            }
+            if smallest == index {
+                return;
+            }
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+}
+
+impl<T: Ord> Default for BinaryHeap<T> {
+    fn default() -> Self {
+        BinaryHeap::new()
+    }
+}
+
+/// The operations searches need from a priority queue with decrease-key, so they can run on BinaryHeap as well as on PairingHeap.
+pub trait PriorityQueue<T: Ord> {
+    /// Adds an item and returns the handle to change it later.
+    fn push(&mut self, item: T) -> Handle;
+    /// Removes and returns the smallest item.
+    fn pop(&mut self) -> Option<T>;
+    /// Replaces the item of the handle by a smaller or equal one.
+    /// Returns false and leaves the queue unchanged if the item is no longer part of the queue or the new item is greater.
+    fn decrease_key(&mut self, handle: Handle, item: T) -> bool;
+    /// Returns true if the item of the handle is still part of the queue.
+    fn contains(&self, handle: Handle) -> bool;
+    /// Returns the number of items in the queue.
+    fn len(&self) -> usize;
+    /// Returns true if the queue holds no items.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for BinaryHeap<T> {
+    fn push(&mut self, item: T) -> Handle {
+        BinaryHeap::push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinaryHeap::pop(self)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, item: T) -> bool {
+        BinaryHeap::decrease_key(self, handle, item)
+    }
+
+    fn contains(&self, handle: Handle) -> bool {
+        BinaryHeap::contains(self, handle)
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+}
+
+/// A node of a PairingHeap, stored at the index of its handle.
+#[derive(Clone, Debug)]
+struct PairingNode<T> {
+    /// None once the item has been removed.
+    item: Option<T>,
+    child: Option<usize>,
+    sibling: Option<usize>,
+    /// The parent if this is the first child, otherwise the previous sibling.
+    previous: Option<usize>,
+}
+
+/// A [pairing heap](https://en.wikipedia.org/wiki/Pairing_heap) which returns the smallest item first,
+/// with the same handles as BinaryHeap.
+///
+/// Pushing and decreasing an item take constant time, only removing items costs O(log n) amortized time.
+/// So it pays off over BinaryHeap when there are many more decrease_key calls than pops,
+/// like for Dijkstra's algorithm on very dense graphs.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::datastructures::PairingHeap;
+///
+/// let mut heap = PairingHeap::new();
+/// let a = heap.push(5);
+/// heap.push(3);
+/// heap.push(4);
+/// assert!(heap.decrease_key(a, 1));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), Some(3));
+/// assert_eq!(heap.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PairingHeap<T> {
+    nodes: Vec<PairingNode<T>>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<T: Ord> PairingHeap<T> {
+    /// Creates an empty heap.
+    pub fn new() -> Self {
+        PairingHeap { nodes: Vec::new(), root: None, len: 0 }
+    }
+
+    /// Creates an empty heap with room for the given number of items.
+    pub fn with_capacity(capacity: usize) -> Self {
+        PairingHeap { nodes: Vec::with_capacity(capacity), root: None, len: 0 }
+    }
+
+    /// Returns the number of items in the heap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the heap holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the smallest item, without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        self.root.and_then(|root| self.nodes[root].item.as_ref())
+    }
+
+    /// Adds an item in constant time and returns the handle to change or remove it later.
+    pub fn push(&mut self, item: T) -> Handle {
+        let index = self.nodes.len();
+        self.nodes.push(PairingNode { item: Some(item), child: None, sibling: None, previous: None });
+        self.root = Some(self.meld(self.root, index));
+        self.len += 1;
+        Handle(index)
+    }
+
+    /// Removes and returns the smallest item in O(log n) amortized time.
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        self.remove(Handle(root))
+    }
+
+    /// Returns the item of the handle, or None if it is no longer part of the heap.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes.get(handle.0).and_then(|node| node.item.as_ref())
+    }
+
+    /// Returns true if the item of the handle is still part of the heap.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    /// Replaces the item of the handle by a smaller or equal one, in constant amortized time.
+    ///
+    /// Returns false and leaves the heap unchanged if the item is no longer part of the heap or the new item is greater.
+    pub fn decrease_key(&mut self, handle: Handle, item: T) -> bool {
+        match self.get(handle) {
+            Some(current) if item <= *current => {}
+            _ => return false,
+        }
+        self.nodes[handle.0].item = Some(item);
+        if self.root != Some(handle.0) {
+            self.cut(handle.0);
+            self.root = Some(self.meld(self.root, handle.0));
+        }
+        true
+    }
+
+    /// Removes the item of the handle in O(log n) amortized time and returns it, or None if it is no longer part of the heap.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let index = handle.0;
+        let item = self.nodes.get_mut(index)?.item.take()?;
+        self.len -= 1;
+        let first_child = self.nodes[index].child.take();
+        let children = self.merge_pairs(first_child);
+        if self.root == Some(index) {
+            self.root = children;
+        } else {
+            self.cut(index);
+            if let Some(children) = children {
+                self.root = Some(self.meld(self.root, children));
+            }
+        }
+        Some(item)
+    }
+
+    /// Removes the node from the list of children it is part of. The node must not be the root.
+    fn cut(&mut self, index: usize) {
+        let sibling = self.nodes[index].sibling.take();
+        // every node other than the root has a previous node
+        let previous = self.nodes[index].previous.take().unwrap();
+        if self.nodes[previous].child == Some(index) {
+            self.nodes[previous].child = sibling;
+        } else {
+            self.nodes[previous].sibling = sibling;
+        }
+        if let Some(sibling) = sibling {
+            self.nodes[sibling].previous = Some(previous);
+        }
+    }
+
+    /// Links the tree of index with the tree of root and returns the root of the combined tree.
+    fn meld(&mut self, root: Option<usize>, index: usize) -> usize {
+        let root = match root {
+            Some(root) => root,
+            None => return index,
+        };
+        let (parent, child) = if self.nodes[index].item < self.nodes[root].item { (index, root) } else { (root, index) };
+        let first = self.nodes[parent].child;
+        self.nodes[child].sibling = first;
+        self.nodes[child].previous = Some(parent);
+        if let Some(first) = first {
+            self.nodes[first].previous = Some(child);
+        }
+        self.nodes[parent].child = Some(child);
+        parent
+    }
+
+    /// Combines the list of siblings starting at first into a single tree, melding pairs from the front, then the results from the back.
+    fn merge_pairs(&mut self, first: Option<usize>) -> Option<usize> {
+        let mut trees = Vec::new();
+        let mut next = first;
+        while let Some(index) = next {
+            next = self.nodes[index].sibling.take();
+            self.nodes[index].previous = None;
+            trees.push(index);
+        }
+        let mut pairs: Vec<usize> = trees.chunks(2).map(|pair| match *pair {
+            [a, b] => self.meld(Some(a), b),
+            [a] => a,
+            _ => unreachable!("chunks are never empty"),
+        }).collect();
+        let mut root = pairs.pop()?;
+        while let Some(tree) = pairs.pop() {
+            root = self.meld(Some(root), tree);
+        }
+        Some(root)
+    }
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        PairingHeap::new()
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for PairingHeap<T> {
+    fn push(&mut self, item: T) -> Handle {
+        PairingHeap::push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        PairingHeap::pop(self)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, item: T) -> bool {
+        PairingHeap::decrease_key(self, handle, item)
+    }
+
+    fn contains(&self, handle: Handle) -> bool {
+        PairingHeap::contains(self, handle)
+    }
+
+    fn len(&self) -> usize {
+        PairingHeap::len(self)
+    }
+}
+
+/// A [Fenwick tree](https://en.wikipedia.org/wiki/Fenwick_tree) of numbers, which sums up any range and changes any number in O(log n) time.
+///
+/// It needs less memory than a SegmentTree, but only works for sums, since ranges are summed up by subtracting prefix sums.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::datastructures::FenwickTree;
+///
+/// let mut tree = FenwickTree::from_slice(&[3, 1, 4, 1, 5]);
+/// assert_eq!(tree.sum(1..4), 6);
+/// tree.add(2, 10);
+/// assert_eq!(tree.sum(1..4), 16);
+/// assert_eq!(tree.prefix_sum(5), 24);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FenwickTree<T> {
+    /// The tree is one based, the item at index i holds the sum of the lowbit(i) numbers ending at i.
+    tree: Vec<T>,
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates a tree of the given number of zeros, the default of T.
+    pub fn new(len: usize) -> Self {
+        FenwickTree { tree: vec![T::default(); len + 1] }
+    }
+
+    /// Creates a tree of the numbers, in O(n) time.
+    pub fn from_slice(numbers: &[T]) -> Self {
+        let mut tree = vec![T::default(); numbers.len() + 1];
+        tree[1..].copy_from_slice(numbers);
+        for index in 1..tree.len() {
+            let parent = index + lowbit(index);
+            if parent < tree.len() {
+                tree[parent] = tree[parent] + tree[index];
+            }
+        }
+        FenwickTree { tree }
+    }
+
+    /// Returns the number of numbers in the tree.
+    pub fn len(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    /// Returns true if the tree holds no numbers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds the delta to the number at the index.
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn add(&mut self, index: usize, delta: T) {
+        assert!(index < self.len(), "index {} out of bounds for a tree of {} numbers", index, self.len());
+        let mut index = index + 1;
+        while index < self.tree.len() {
+            self.tree[index] = self.tree[index] + delta;
+            index += lowbit(index);
+        }
+    }
+
+    /// Replaces the number at the index.
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set(&mut self, index: usize, number: T) {
+        let current = self.sum(index..index + 1);
+        self.add(index, number - current);
+    }
+
+    /// Returns the sum of the first end numbers.
+    ///
+    /// Panics if end is greater than the number of numbers.
+    pub fn prefix_sum(&self, end: usize) -> T {
+        assert!(end <= self.len(), "end {} out of bounds for a tree of {} numbers", end, self.len());
+        let mut sum = T::default();
+        let mut index = end;
+        while index > 0 {
+            sum = sum + self.tree[index];
+            index -= lowbit(index);
+        }
+        sum
+    }
+
+    /// Returns the sum of the numbers in the range, zero for an empty range.
+    ///
+    /// Panics if the range ends after the last number.
+    pub fn sum(&self, range: Range<usize>) -> T {
+        if range.start >= range.end {
+            return T::default();
+        }
+        self.prefix_sum(range.end) - self.prefix_sum(range.start)
+    }
+}
+
+/// The lowest set bit of the index.
+fn lowbit(index: usize) -> usize {
+    index & index.wrapping_neg()
+}
+
+/// A [segment tree](https://en.wikipedia.org/wiki/Segment_tree), which combines the values of any range and changes any value in O(log n) time.
+///
+/// The values are combined by an associative function, like the sum, the minimum or the maximum, with the identity being
+/// the result for empty ranges, like 0 for sums or the greatest value for the minimum.
+/// The function does not need to be commutative, values are always combined from left to right.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::datastructures::SegmentTree;
+///
+/// let costs = [7, 2, 9, 4, 8];
+/// let mut minimum = SegmentTree::new(&costs, usize::MAX, |a, b| *a.min(b));
+/// assert_eq!(minimum.query(2..5), 4);
+/// minimum.set(4, 1);
+/// assert_eq!(minimum.query(2..5), 1);
+/// assert_eq!(minimum.query(3..3), usize::MAX);
+///
+/// let sum = SegmentTree::new(&costs, 0, |a, b| a + b);
+/// assert_eq!(sum.query(0..5), 30);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SegmentTree<T> {
+    /// The values are the leaves at len..2 * len, every other index i holds the combination of its children 2 * i and 2 * i + 1.
+    tree: Vec<T>,
+    identity: T,
+    combine: fn(&T, &T) -> T,
+}
+
+impl<T: Clone> SegmentTree<T> {
+    /// Creates a tree of the values, in O(n) time.
+    pub fn new(values: &[T], identity: T, combine: fn(&T, &T) -> T) -> Self {
+        let len = values.len();
+        let mut tree = vec![identity.clone(); len];
+        tree.extend_from_slice(values);
+        for index in (1..len).rev() {
+            tree[index] = combine(&tree[2 * index], &tree[2 * index + 1]);
+        }
+        SegmentTree { tree, identity, combine }
+    }
+
+    /// Returns the number of values in the tree.
+    pub fn len(&self) -> usize {
+        self.tree.len() / 2
+    }
+
+    /// Returns true if the tree holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Returns the value at the index, or None if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            Some(&self.tree[self.len() + index])
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the value at the index.
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set(&mut self, index: usize, value: T) {
+        assert!(index < self.len(), "index {} out of bounds for a tree of {} values", index, self.len());
+        let mut index = self.len() + index;
+        self.tree[index] = value;
+        while index > 1 {
+            index /= 2;
+            self.tree[index] = (self.combine)(&self.tree[2 * index], &self.tree[2 * index + 1]);
+        }
+    }
+
+    /// Returns the combination of the values in the range, the identity for an empty range.
+    ///
+    /// Panics if the range ends after the last value.
+    pub fn query(&self, range: Range<usize>) -> T {
+        assert!(range.end <= self.len(), "range end {} out of bounds for a tree of {} values", range.end, self.len());
+        // the combination of the values left of low and right of high, which both move up the tree
+        let mut left = self.identity.clone();
+        let mut right = self.identity.clone();
+        let (mut low, mut high) = (range.start + self.len(), range.end + self.len());
+        while low < high {
+            if low % 2 == 1 {
+                left = (self.combine)(&left, &self.tree[low]);
+                low += 1;
+            }
+            if high % 2 == 1 {
+                high -= 1;
+                right = (self.combine)(&self.tree[high], &right);
+            }
+            low /= 2;
+            high /= 2;
+        }
+        (self.combine)(&left, &right)
+    }
+}
+
+/// A [disjoint-set forest](https://en.wikipedia.org/wiki/Disjoint-set_data_structure), also called union-find,
+/// of the numbers from 0 to len - 1, which joins their sets and tells the set of a number in nearly constant amortized time.
+///
+/// Every number points towards the representative of its set. Paths are halved whenever they are followed and the smaller
+/// set is joined into the larger one, so they stay short. The algorithms of the crate use it for the connected parts of a graph,
+/// with the index of every node as its number.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::datastructures::DisjointSet;
+///
+/// let mut sets = DisjointSet::new(5);
+/// assert!(sets.union(0, 1));
+/// assert!(sets.union(3, 1));
+/// assert!(!sets.union(0, 3));
+/// assert_eq!(sets.find(0), sets.find(3));
+/// assert_ne!(sets.find(0), sets.find(2));
+/// assert_eq!(sets.size(1), 3);
+/// assert_eq!(sets.sets(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    /// The number of members of every set, only kept up to date for the representatives.
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl DisjointSet {
+    /// Creates a set of its own for every number from 0 to len - 1.
+    pub fn new(len: usize) -> Self {
+        DisjointSet { parents: (0..len).collect(), sizes: vec![1; len], sets: len }
+    }
+
+    /// Returns the count of numbers.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns true if there are no numbers.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns the representative of the set of the number, which is the same for all of its members.
+    /// Panics if the number is not less than len.
+    pub fn find(&mut self, mut number: usize) -> usize {
+        while self.parents[number] != number {
+            self.parents[number] = self.parents[self.parents[number]];
+            number = self.parents[number];
+        }
+        number
+    }
+
+    /// Joins the sets of both numbers. Returns false if they are in the same set already.
+    /// Panics if a number is not less than len.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (small, large) = if self.sizes[a] < self.sizes[b] { (a, b) } else { (b, a) };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.sets -= 1;
+        true
+    }
+
+    /// Returns the number of members of the set of the number.
+    /// Panics if the number is not less than len.
+    pub fn size(&mut self, number: usize) -> usize {
+        let representative = self.find(number);
+        self.sizes[representative]
+    }
+
+    /// Returns the number of sets.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+}
+
+#[cfg(test)]
+mod binary_heap_test {
+    use super::*;
+    use crate::util::{RandomSource, SplitMix64};
+
+    /// Checks the heap property and that every position points back at its handle.
+    fn check<T: Ord>(heap: &BinaryHeap<T>) {
+        for index in 1..heap.items.len() {
+            assert!(heap.items[(index - 1) / 2].0 <= heap.items[index].0);
+        }
+        for (index, (_, handle)) in heap.items.iter().enumerate() {
+            assert_eq!(heap.positions[*handle], Some(index));
+        }
+        assert_eq!(heap.positions.iter().filter(|position| position.is_some()).count(), heap.len());
+    }
+
+    #[test]
+    fn sorts() {
+        let mut rng = SplitMix64::new(8);
+        let mut heap = BinaryHeap::with_capacity(200);
+        let mut expected: Vec<usize> = (0..200).map(|_| rng.random_index(50)).collect();
+        for number in &expected {
+            heap.push(*number);
+        }
+        check(&heap);
+        expected.sort_unstable();
+        let mut popped = Vec::new();
+        while let Some(number) = heap.pop() {
+            popped.push(number);
+        }
+        assert_eq!(popped, expected);
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+    }
+
+    #[test]
+    fn handles() {
+        let mut heap = BinaryHeap::default();
+        let handles: Vec<Handle> = (0..10).map(|number| heap.push(number * 10)).collect();
+        assert!(heap.decrease_key(handles[7], 5));
+        assert!(!heap.decrease_key(handles[2], 25));
+        assert_eq!(heap.get(handles[2]), Some(&20));
+        assert!(heap.update(handles[0], 95));
+        assert!(heap.update(handles[9], 1));
+        check(&heap);
+        assert_eq!(heap.remove(handles[4]), Some(40));
+        assert_eq!(heap.remove(handles[4]), None);
+        assert!(!heap.contains(handles[4]));
+        assert!(!heap.update(handles[4], 0));
+        check(&heap);
+
+        assert_eq!(heap.pop_with_handle(), Some((handles[9], 1)));
+        assert_eq!(heap.pop(), Some(5));
+        assert_eq!(heap.len(), 7);
+        let new = heap.push(3);
+        assert_ne!(new, handles[9]);
+        assert_eq!(heap.peek(), Some(&3));
+        heap.clear();
+        assert!(heap.is_empty() && !heap.contains(new) && !heap.contains(handles[1]));
+    }
+
+    #[test]
+    fn random_operations() {
+        let mut rng = SplitMix64::new(21);
+        let mut heap = BinaryHeap::new();
+        let mut handles = Vec::new();
+        for _ in 0..2000 {
+            match rng.random_index(4) {
+                0 | 1 => handles.push(heap.push(rng.random_index(1000))),
+                2 if !handles.is_empty() => {
+                    let handle = handles[rng.random_index(handles.len())];
+                    let item = rng.random_index(1000);
+                    let current = heap.get(handle).copied();
+                    assert_eq!(heap.decrease_key(handle, item), current.is_some_and(|current| item <= current));
+                }
+                _ if !handles.is_empty() => {
+                    let handle = handles.swap_remove(rng.random_index(handles.len()));
+                    heap.remove(handle);
+                }
+                _ => {}
+            }
+            check(&heap);
+        }
+    }
+}
+
+#[cfg(test)]
+mod pairing_heap_test {
+    use super::*;
+    use crate::util::{RandomSource, SplitMix64};
+
+    #[test]
+    fn sorts() {
+        let mut rng = SplitMix64::new(2);
+        let mut heap = PairingHeap::with_capacity(300);
+        let mut expected: Vec<usize> = (0..300).map(|_| rng.random_index(100)).collect();
+        for number in &expected {
+            heap.push(*number);
+        }
+        expected.sort_unstable();
+        let mut popped = Vec::new();
+        while let Some(number) = heap.pop() {
+            popped.push(number);
+        }
+        assert_eq!(popped, expected);
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+    }
+
+    #[test]
+    fn handles() {
+        let mut heap = PairingHeap::default();
+        let handles: Vec<Handle> = (0..10).map(|number| heap.push(number * 10)).collect();
+        assert!(heap.decrease_key(handles[7], 5));
+        assert!(!heap.decrease_key(handles[2], 25));
+        assert_eq!(heap.get(handles[2]), Some(&20));
+        assert_eq!(heap.pop(), Some(0));
+        assert!(!heap.decrease_key(handles[0], 0));
+        assert_eq!(heap.peek(), Some(&5));
+        assert_eq!(heap.remove(handles[5]), Some(50));
+        assert_eq!(heap.remove(handles[5]), None);
+        assert!(!heap.contains(handles[5]));
+        assert_eq!(heap.remove(handles[7]), Some(5));
+        assert_eq!(heap.len(), 7);
+        let mut popped = Vec::new();
+        while let Some(number) = heap.pop() {
+            popped.push(number);
+        }
+        assert_eq!(popped, vec![10, 20, 30, 40, 60, 80, 90]);
+    }
+
+    /// Runs the same random operations on both heaps, which need to agree on every result.
+    #[test]
+    fn matches_binary_heap() {
+        let mut rng = SplitMix64::new(13);
+        let mut pairing = PairingHeap::new();
+        let mut binary = BinaryHeap::new();
+        let mut handles = Vec::new();
+        for _ in 0..3000 {
+            match rng.random_index(5) {
+                0 | 1 => {
+                    // the index makes all items different, so both heaps pop the same ones
+                    let item = (rng.random_index(1000), handles.len());
+                    handles.push((pairing.push(item), binary.push(item)));
+                }
+                2 => assert_eq!(pairing.pop(), binary.pop()),
+                3 if !handles.is_empty() => {
+                    let (a, b) = handles[rng.random_index(handles.len())];
+                    if let Some(&(cost, index)) = binary.get(b) {
+                        let item = (cost.saturating_sub(rng.random_index(500)), index);
+                        assert!(pairing.decrease_key(a, item));
+                        assert!(binary.decrease_key(b, item));
+                    }
+                }
+                _ if !handles.is_empty() => {
+                    let (a, b) = handles[rng.random_index(handles.len())];
+                    assert_eq!(pairing.remove(a), binary.remove(b));
+                }
+                _ => {}
+            }
+            assert_eq!(pairing.len(), binary.len());
+            assert_eq!(pairing.peek(), binary.peek());
+        }
+    }
+}
+
+#[cfg(test)]
+mod range_tree_test {
+    use super::*;
+    use crate::util::{RandomSource, SplitMix64};
+
+    #[test]
+    fn fenwick_tree() {
+        let mut rng = SplitMix64::new(21);
+        let mut numbers: Vec<i64> = (0..37).map(|_| rng.random_index(100) as i64 - 50).collect();
+        let mut tree = FenwickTree::from_slice(&numbers);
+        assert_eq!(tree.len(), 37);
+        for _ in 0..200 {
+            let index = rng.random_index(numbers.len());
+            let number = rng.random_index(100) as i64 - 50;
+            if rng.random_index(2) == 0 {
+                tree.add(index, number);
+                numbers[index] += number;
+            } else {
+                tree.set(index, number);
+                numbers[index] = number;
+            }
+            let (a, b) = (rng.random_index(38), rng.random_index(38));
+            let range = a.min(b)..a.max(b);
+            assert_eq!(tree.sum(range.clone()), numbers[range].iter().sum::<i64>());
+        }
+        assert_eq!(tree.sum(5..5), 0);
+        let empty: FenwickTree<u32> = FenwickTree::new(0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.prefix_sum(0), 0);
+    }
+
+    #[test]
+    fn segment_tree() {
+        let mut rng = SplitMix64::new(4);
+        let mut values: Vec<usize> = (0..29).map(|_| rng.random_index(1000)).collect();
+        let mut minimum = SegmentTree::new(&values, usize::MAX, |a, b| *a.min(b));
+        for _ in 0..200 {
+            let index = rng.random_index(values.len());
+            values[index] = rng.random_index(1000);
+            minimum.set(index, values[index]);
+            let (a, b) = (rng.random_index(30), rng.random_index(30));
+            let range = a.min(b)..a.max(b);
+            assert_eq!(minimum.query(range.clone()), values[range].iter().copied().min().unwrap_or(usize::MAX));
+        }
+        assert_eq!(minimum.get(3), Some(&values[3]));
+        assert_eq!(minimum.get(29), None);
+
+        // concatenation is not commutative, so this checks the order in which values are combined
+        let letters: Vec<String> = "segment".chars().map(String::from).collect();
+        let mut words = SegmentTree::new(&letters, String::new(), |a, b| format!("{}{}", a, b));
+        assert_eq!(words.query(0..7), "segment");
+        assert_eq!(words.query(2..6), "gmen");
+        words.set(0, "f".to_string());
+        assert_eq!(words.query(0..3), "feg");
+        assert!(SegmentTree::new(&[], 0, |a: &u8, b: &u8| a + b).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod disjoint_set_test {
+    use super::*;
+    use crate::util::{RandomSource, SplitMix64};
+
+    #[test]
+    fn joins() {
+        let mut rng = SplitMix64::new(54);
+        let mut sets = DisjointSet::new(40);
+        // the label of every number, relabeling a whole set on every join
+        let mut labels: Vec<usize> = (0..40).collect();
+        for _ in 0..60 {
+            let (a, b) = (rng.random_index(40), rng.random_index(40));
+            let (from, to) = (labels[a], labels[b]);
+            assert_eq!(sets.union(a, b), from != to);
+            labels.iter_mut().filter(|label| **label == from).for_each(|label| *label = to);
+            for number in 0..40 {
+                assert_eq!(sets.find(number) == sets.find(a), labels[number] == labels[a]);
+                assert_eq!(sets.size(number), labels.iter().filter(|label| **label == labels[number]).count());
+            }
+            let mut distinct = labels.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            assert_eq!(sets.sets(), distinct.len());
+        }
+        assert_eq!(sets.len(), 40);
+        assert!(DisjointSet::new(0).is_empty() && DisjointSet::new(0).sets() == 0);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::dijkstra::{index_of, neighbours, to_self};
 use crate::graph::*;
 
 /// A function to search for the path to a node using the [Depth-first search](https://en.wikipedia.org/wiki/Depth-first_search) method.
@@ -28,9 +29,181 @@ use crate::graph::*;
 /// assert_eq!(result.cost, 5);
 /// ```
 pub fn dfs_search_node(
+    graph: Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+) -> Option<SearchResult> {
+    search(graph, start_node_id, search_node_id, &|_| true, &mut SearchStats::default())
+}
+
+/// Works like dfs_search_node, but additionally reports statistics about the search.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dfs::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node2), 5));
+/// graph.add_link(Link::new((node2, node3), 5));
+///
+/// let report = dfs_search_report(graph, node1, node3);
+/// assert_eq!(report.result.unwrap().cost, 10);
+/// assert_eq!(report.stats.nodes_expanded, 2);
+/// ```
+pub fn dfs_search_report(graph: Graph, start_node_id: isize, search_node_id: isize) -> SearchReport {
+    SearchReport::measure(|stats| search(graph, start_node_id, search_node_id, &|_| true, stats))
+}
+
+/// Works like dfs_search_node, but only follows the links for which the supplied filter returns true.
+///
+/// This way links can be ignored, like links above a cost threshold or blocked ports, without copying and pruning the graph first.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dfs::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let result = dfs_search_node_filtered(graph, node1, node3, |link| link.cost < 10).unwrap();
+/// assert_eq!(result.links.len(), 3);
+/// assert_eq!(result.cost, 7);
+/// ```
+pub fn dfs_search_node_filtered<E>(
+    graph: Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    edge_filter: E,
+) -> Option<SearchResult>
+where
+    E: Fn(&Link) -> bool,
+{
+    search(graph, start_node_id, search_node_id, &edge_filter, &mut SearchStats::default())
+}
+
+/// Searches for any path like dfs_search_node, but on any graph implementing GraphLike.
+///
+/// The search does not recurse, so it also works on graphs with very long paths.
+/// The last neighbour of every node is visited first, so the path found may differ from the one of dfs_search_node.
+/// The links of the result point from every node to the next one.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dfs::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node3, node2), 4));
+///
+/// let result = dfs_search_generic(&graph, node1, node3).unwrap();
+/// assert_eq!(result.links.len(), 3);
+/// assert_eq!(result.cost, 7);
+/// ```
+pub fn dfs_search_generic<G>(graph: &G, start_node_id: isize, search_node_id: isize) -> Option<SearchResult>
+where
+    G: GraphLike + ?Sized,
+{
+    crate::implicit::dfs_search(start_node_id, |id| id == search_node_id, |id| graph.neighbors(id))
+}
+
+/// Works like dfs_search_report, but limited and extended by the options.
+///
+/// The search does not recurse and follows the links of every node in the order they were added, so the path found may differ
+/// from the one of dfs_search_node. With a max_depth, a node is visited again if it is reached by fewer links than before,
+/// so no path within the depth is missed.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dfs::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+/// graph.add_link(Link::new((node1, node3), 10));
+///
+/// let report = dfs_search_with_options(&graph, node1, node3, &SearchOptions::new().record_trace(true));
+/// assert_eq!(report.result.unwrap().cost, 7);
+/// assert_eq!(report.trace, vec![node1, node2]);
+/// let report = dfs_search_with_options(&graph, node1, node3, &SearchOptions::new().max_depth(1));
+/// assert_eq!(report.result.unwrap().cost, 10);
+/// ```
+pub fn dfs_search_with_options(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    options: &SearchOptions,
+) -> SearchReport {
+    SearchReport::measure_traced(|stats, trace| {
+        if start_node_id == search_node_id {
+            return Some(to_self(start_node_id));
+        }
+        let start = index_of(graph, start_node_id)?;
+        let nodes = graph.nodes();
+        let neighbours = neighbours(graph);
+        // the fewest links every node has been reached by, and the nodes of the current path,
+        // each with the position of the next of its neighbours to try
+        let mut depths: Vec<Option<usize>> = vec![None; nodes.len()];
+        let mut stack: Vec<(usize, usize)> = vec![(start, 0)];
+        let mut links: Vec<Link> = vec![Link::new((start_node_id, start_node_id), 0)];
+        depths[start] = Some(0);
+        stats.frontier(stack.len());
+        if !options.expand(start_node_id, stats, trace) {
+            return None;
+        }
+        while let Some(&(current, position)) = stack.last() {
+            let depth = stack.len() - 1;
+            let neighbour = neighbours[current].get(position).filter(|_| options.extends(depth));
+            let &(next, link) = match neighbour {
+                Some(neighbour) => neighbour,
+                None => {
+                    stack.pop();
+                    links.pop();
+                    continue;
+                }
+            };
+            stack.last_mut().unwrap().1 += 1;
+            stats.links_examined += 1;
+            if !options.follows(link) || depths[next].is_some_and(|known| known <= depth + 1) {
+                continue;
+            }
+            depths[next] = Some(depth + 1);
+            links.push(*link);
+            if nodes[next].id == search_node_id {
+                let cost = links.iter().map(|link| link.cost).sum();
+                return Some(SearchResult::new().links(links).cost(cost));
+            }
+            if !options.expand(nodes[next].id, stats, trace) {
+                return None;
+            }
+            stack.push((next, 0));
+            stats.frontier(stack.len());
+        }
+        None
+    })
+}
+
+fn search(
     mut graph: Graph,
     start_node_id: isize,
     search_node_id: isize,
+    edge_filter: &dyn Fn(&Link) -> bool,
+    stats: &mut SearchStats,
 ) -> Option<SearchResult> {
 
     // if start node is the node searched for, return a link to itself
@@ -42,8 +215,14 @@ pub fn dfs_search_node(
         );
     }
 
+    // mark the start node as discovered, so the search does not return to it
+    if let Some(node) = graph.get_node(start_node_id) {
+        node.is_discovered = true;
+    }
+
     // call the recursive function with the link from the start element to itself as first link
-    let result = search_node_recursive(&mut graph, start_node_id, search_node_id, vec!(Link::new((0,0),0)));
+    let mut frontier = 0;
+    let result = search_node_recursive(&mut graph, start_node_id, search_node_id, vec!(Link::new((start_node_id, start_node_id), 0)), edge_filter, stats, &mut frontier);
     
     // compute the total link cost and return the result
     match result {
@@ -53,19 +232,25 @@ pub fn dfs_search_node(
                 cost += link.cost;
             }
             res.cost = cost;
+            log_debug!("dfs: found {} from {} at cost {}", search_node_id, start_node_id, cost);
             Some(res)
         },
         None => {
+            log_debug!("dfs: no path from {} to {}", start_node_id, search_node_id);
             None
         }
     }
 }
 
+/// The frontier counts the nodes which are on the stack of any level of the recursion.
 fn search_node_recursive(
     graph: &mut Graph,
     start_node_id: isize,
     search_node_id: isize,
     link_chain: Vec<Link>,
+    edge_filter: &dyn Fn(&Link) -> bool,
+    stats: &mut SearchStats,
+    frontier: &mut usize,
 ) -> Option<SearchResult> {
 
     // Abort condition: check if the current node is the one searched for.
@@ -78,7 +263,14 @@ fn search_node_recursive(
     let mygraph = graph.clone();
 
     // find all links going out from the current start_node
+    log_trace!("dfs: expanding {} at depth {}", start_node_id, link_chain.len() - 1);
+    stats.nodes_expanded += 1;
     for link in mygraph.find_links_from_node(start_node_id) {
+        stats.links_examined += 1;
+        // links rejected by the filter are ignored
+        if !edge_filter(link) {
+            continue;
+        }
         let other_node = if link.members.0 == start_node_id {
             link.members.1
         } else {
@@ -89,22 +281,22 @@ fn search_node_recursive(
             if !node.is_discovered {
                 stack.push((link, other_node));
                 node.is_discovered = true;
+                *frontier += 1;
+                stats.frontier(*frontier);
             }
         }
     }
 
     // visit every element on the stack
-    while !stack.is_empty() {
-        // we can safely unwrap(), as we checked for is_empty() in the while loop
-        let stack_element = stack.pop().unwrap();
-        
+    while let Some(stack_element) = stack.pop() {
+        *frontier -= 1;
         // clone the link chain and add the link to the new element to it
         let mut new_vector = link_chain.clone();
         new_vector.push(*stack_element.0);
 
         // recursively call the function for the new element
         if let Some(result) =
-            search_node_recursive(graph, stack_element.1, search_node_id, new_vector)
+            search_node_recursive(graph, stack_element.1, search_node_id, new_vector, edge_filter, stats, frontier)
         {
             return Some(result);
         }
@@ -123,7 +315,56 @@ mod discover_test {
         let mut graph = Graph::new();
         let node1 = Node::new("Node 1");
         graph.add_node(node1);
-        assert_eq!(dfs_search_node(graph, 2, 1).is_none(), true);
+        assert!(dfs_search_node(graph, 2, 1).is_none());
+    }
+
+    #[test]
+    fn test_report_unreachable() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new("Node 1"));
+        graph.add_node(Node::new("Node 2"));
+        graph.add_node(Node::new("Node 3"));
+        graph.add_node(Node::new("Node 4"));
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((0, 2), 1));
+        let report = dfs_search_report(graph, 0, 3);
+        assert!(report.result.is_none());
+        assert_eq!(report.stats.nodes_expanded, 3);
+        assert_eq!(report.stats.links_examined, 4);
+        assert_eq!(report.stats.max_frontier, 2);
+    }
+
+    #[test]
+    fn test_discover_filtered() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 3), 1));
+        graph.add_link(Link::new((0, 2), 2));
+        graph.add_link(Link::new((2, 3), 2));
+        let result = dfs_search_node_filtered(graph.clone(), 0, 3, |link| link.members.0 != 1).unwrap();
+        assert_eq!(result.links, vec![Link::new((0, 0), 0), Link::new((0, 2), 2), Link::new((2, 3), 2)]);
+        assert!(dfs_search_node_filtered(graph, 0, 3, |link| link.cost > 2).is_none());
+    }
+
+    #[test]
+    fn test_discover_generic() {
+        /// A path through 100000 nodes, which would overflow the stack of a recursive search.
+        struct Path;
+
+        impl GraphLike for Path {
+            fn neighbors(&self, node_id: isize) -> Vec<(isize, usize)> {
+                [node_id - 1, node_id + 1].iter().filter(|id| (0..100_000).contains(*id)).map(|id| (*id, 1)).collect()
+            }
+        }
+
+        let result = dfs_search_generic(&Path, 0, 99_999).unwrap();
+        assert_eq!(result.links.len(), 100_000);
+        assert_eq!(result.cost, 99_999);
+        assert!(dfs_search_generic(&Path, 0, 100_000).is_none());
+        assert_eq!(dfs_search_generic(&Path, 5, 5).unwrap().links, vec![Link::new((5, 5), 0)]);
     }
 
     #[test]
@@ -131,24 +372,25 @@ mod discover_test {
         let mut graph = Graph::new();
         let node1 = Node::new("Node 1");
         graph.add_node(node1);
-        assert_eq!(dfs_search_node(graph, 1, 2).is_none(), true);
+        assert!(dfs_search_node(graph, 1, 2).is_none());
     }
 
     #[test]
     fn test_discover_no_start_and_target_element() {
         let graph = Graph::new();
-        assert_eq!(dfs_search_node(graph, 1, 2).is_none(), true);
+        assert!(dfs_search_node(graph, 1, 2).is_none());
     }
 
     #[test]
     fn test_discover_no_link() {
         let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
         let node1 = Node::new("Node 1");
         let node2 = Node::new("Node 1");
         graph.add_node(node1);
         graph.add_node(node2);
         graph.add_link(Link::new((1, 3), 1));
-        assert_eq!(dfs_search_node(graph, 1, 2).is_none(), true);
+        assert!(dfs_search_node(graph, 1, 2).is_none());
     }
 
     #[test]
@@ -214,6 +456,7 @@ mod discover_test {
     #[test]
     fn discover_elements_with_loose_end_links() {
         let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
         let mut node1 = Node::new("Node 1");
         let mut node2 = Node::new("Node 2");
         node1.id = graph.add_node(node1);
@@ -251,14 +494,14 @@ mod discover_test {
         let mut node3 = Node::new("Node 3");
         let mut node4 = Node::new("Node 4");
         let mut node5 = Node::new("Node 5");
-        let mut node6 = Node::new("Node 6");
+        let node6 = Node::new("Node 6");
         let mut node7 = Node::new("Node 7");
         node1.id = graph.add_node(node1);
         node2.id = graph.add_node(node2);
         node3.id = graph.add_node(node3);
         node4.id = graph.add_node(node4);
         node5.id = graph.add_node(node5);
-        node6.id = graph.add_node(node6);
+        graph.add_node(node6);
         node7.id = graph.add_node(node7);
         let link1 = Link::new((node1.id, node3.id), 1);
         let link2 = Link::new((node1.id, node2.id), 1);
@@ -278,4 +521,59 @@ mod discover_test {
         assert_eq!(result.links[2], Link::new((node2.id, node4.id), 2));
         assert_eq!(result.links[3], Link::new((node4.id, node7.id), 1));
     }
+
+    #[test]
+    fn start_link() {
+        let mut graph = Graph::new();
+        let node1 = graph.add_node(Node::new("Node 1"));
+        let node2 = graph.add_node(Node::new("Node 2"));
+        graph.add_link(Link::new((node1, node2), 3));
+        // the path begins with the link from the start node to itself, also if that is not the first node
+        let result = dfs_search_node(graph, node2, node1).unwrap();
+        assert_eq!(result.links[0], Link::new((node2, node2), 0));
+        assert_eq!(result.cost, 3);
+    }
+
+    #[test]
+    fn display_result() {
+        let mut graph = Graph::new();
+        let start = graph.add_node(Node::new("Node 1"));
+        let middle = graph.add_node(Node::new("Node 2"));
+        let target = graph.add_node(Node::new("Node 3"));
+        graph.add_link(Link::new((middle, start), 5));
+        graph.add_link(Link::new((middle, target), 2));
+        let result = dfs_search_node(graph, target, start).unwrap();
+        assert_eq!(result.to_string(), "2 -(2)-> 1 -(5)-> 0, total 7");
+    }
+
+    #[test]
+    fn test_discover_with_options() {
+        use crate::util::SplitMix64;
+        let mut rng = SplitMix64::new(37);
+        for _ in 0..20 {
+            let graph = crate::generate::erdos_renyi(10, 0.3, 1..10, &mut rng);
+            let hops = crate::bfs::bfs_search_node(graph.clone(), 0, 9).map(|result| result.links.len() - 1);
+            for max_depth in 0..6 {
+                let report = dfs_search_with_options(&graph, 0, 9, &SearchOptions::new().max_depth(max_depth).record_trace(true));
+                assert_eq!(report.result.is_some(), hops.is_some_and(|hops| hops <= max_depth));
+                assert_eq!(report.trace.len(), report.stats.nodes_expanded);
+                if let Some(result) = report.result {
+                    assert!(result.links.len() <= max_depth + 1);
+                    assert_eq!(result.cost, result.links.iter().map(|link| link.cost).sum::<usize>());
+                    let ids = result.node_ids();
+                    assert_eq!((ids[0], ids[ids.len() - 1]), (0, 9));
+                }
+            }
+        }
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        let options = SearchOptions::new().edge_filter(|link| link.members != (1, 2));
+        assert!(dfs_search_with_options(&graph, 0, 2, &options).result.is_none());
+        assert!(dfs_search_with_options(&graph, 0, 2, &SearchOptions::new().max_nodes(1)).result.is_none());
+        assert_eq!(dfs_search_with_options(&graph, 0, 2, &SearchOptions::new().max_nodes(2)).result.unwrap().cost, 2);
+    }
 }
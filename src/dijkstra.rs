@@ -0,0 +1,1090 @@
+use crate::datastructures::{self, Handle, PairingHeap, PriorityQueue};
+use crate::graph::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use crate::util::node_index;
+
+/// A function to search for the cheapest path to a node using [Dijkstra's algorithm](https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm).
+///
+/// This function takes two node ids, for the start and target node, and computes the path with the lowest total link cost between them.
+/// This path consists of a Vec of Links.
+/// The first link is always from the first element to itself.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+///
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+///
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let result = dijkstra_search_node(&graph, node1, node3).unwrap();
+///
+/// assert_eq!(result.links[0], Link::new((node1, node1), 0));
+/// assert_eq!(result.links[1], Link::new((node1, node2), 3));
+/// assert_eq!(result.links[2], Link::new((node2, node3), 4));
+/// assert_eq!(result.cost, 7);
+/// ```
+pub fn dijkstra_search_node(graph: &Graph, start_node_id: isize, search_node_id: isize) -> Option<SearchResult> {
+    dijkstra_search_node_with_node_costs(graph, start_node_id, search_node_id, |_| 0)
+}
+
+/// Works like dijkstra_search_node, but additionally reports statistics about the search.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let report = dijkstra_search_report(&graph, node1, node3);
+/// assert_eq!(report.result.unwrap().cost, 7);
+/// assert_eq!(report.stats.nodes_expanded, 2);
+/// assert_eq!(report.stats.links_examined, 4);
+/// ```
+pub fn dijkstra_search_report(graph: &Graph, start_node_id: isize, search_node_id: isize) -> SearchReport {
+    SearchReport::measure(|stats| {
+        if start_node_id == search_node_id {
+            return Some(to_self(start_node_id));
+        }
+        search(graph, start_node_id, |node| node.id == search_node_id, |_| 0, |_| true, stats)
+    })
+}
+
+/// Works like dijkstra_search_node, but additionally charges a cost for every node the path passes through,
+/// like the forwarding latency of a switch.
+///
+/// The cost of a node is given by the supplied function and is added whenever the path leads through the node.
+/// The start and the target node are not passed through, so their cost is not added.
+/// The cost of the result is the total of all link and node costs.
+/// astar::astar_search_node_with_node_costs charges node costs the same way.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+///
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let slow_switch = graph.add_node(Node::new("Slow Switch"));
+/// let fast_switch = graph.add_node(Node::new("Fast Switch"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+///
+/// graph.add_link(Link::new((node1, slow_switch), 1));
+/// graph.add_link(Link::new((slow_switch, node2), 1));
+/// graph.add_link(Link::new((node1, fast_switch), 2));
+/// graph.add_link(Link::new((fast_switch, node2), 2));
+///
+/// let latency = |node: &Node| if node.name == "Slow Switch" { 10 } else { 1 };
+/// let result = dijkstra_search_node_with_node_costs(&graph, node1, node2, latency).unwrap();
+///
+/// assert_eq!(result.links[1], Link::new((node1, fast_switch), 2));
+/// assert_eq!(result.cost, 5);
+/// ```
+pub fn dijkstra_search_node_with_node_costs<F>(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    node_cost: F,
+) -> Option<SearchResult>
+where
+    F: Fn(&Node) -> usize,
+{
+    // if start node is the node searched for, return a link to itself
+    if start_node_id == search_node_id {
+        return Some(to_self(start_node_id));
+    }
+    let goal = |node: &Node| node.id == search_node_id;
+    search(graph, start_node_id, goal, node_cost, |_| true, &mut SearchStats::default())
+}
+
+/// Works like dijkstra_search_node, but only follows the links for which the supplied filter returns true.
+///
+/// This way links can be ignored, like links above a cost threshold or blocked ports, without copying and pruning the graph first.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+///
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+///
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let result = dijkstra_search_node_filtered(&graph, node1, node3, |link| link.cost > 3).unwrap();
+/// assert_eq!(result.links[1], Link::new((node1, node3), 10));
+/// assert_eq!(result.cost, 10);
+///
+/// assert!(dijkstra_search_node_filtered(&graph, node1, node3, |link| link.cost < 4).is_none());
+/// ```
+pub fn dijkstra_search_node_filtered<E>(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    edge_filter: E,
+) -> Option<SearchResult>
+where
+    E: Fn(&Link) -> bool,
+{
+    // if start node is the node searched for, return a link to itself
+    if start_node_id == search_node_id {
+        return Some(to_self(start_node_id));
+    }
+    let goal = |node: &Node| node.id == search_node_id;
+    search(graph, start_node_id, goal, |_| 0, edge_filter, &mut SearchStats::default())
+}
+
+/// Searches for the cheapest path from the start node to any node fulfilling the goal.
+///
+/// Other than dijkstra_search_node, the node searched for does not have to be known in advance.
+/// The search stops at the first node fulfilling the goal, which is the one that can be reached the cheapest.
+/// If the start node fulfills the goal, the path only consists of the link from it to itself.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+/// let client = graph.add_node(Node::new("Client"));
+/// let db1 = graph.add_node(Node::new("DB 1"));
+/// let switch = graph.add_node(Node::new("Switch"));
+/// let db2 = graph.add_node(Node::new("DB 2"));
+///
+/// graph.add_link(Link::new((client, db1), 10));
+/// graph.add_link(Link::new((client, switch), 1));
+/// graph.add_link(Link::new((switch, db2), 1));
+///
+/// let result = dijkstra_search_goal(&graph, client, |node| node.name.starts_with("DB")).unwrap();
+/// assert_eq!(result.links.last().unwrap().members, (switch, db2));
+/// assert_eq!(result.cost, 2);
+/// ```
+pub fn dijkstra_search_goal<G>(graph: &Graph, start_node_id: isize, goal: G) -> Option<SearchResult>
+where
+    G: Fn(&Node) -> bool,
+{
+    search(graph, start_node_id, goal, |_| 0, |_| true, &mut SearchStats::default())
+}
+
+/// Works like dijkstra_search_node, but returns the links of the path by their ids instead of copying them.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let path = dijkstra_search_ids(&graph, node1, node3).unwrap();
+/// assert_eq!(path.link_ids, vec![LinkId(1), LinkId(2)]);
+/// assert_eq!(path.cost, 7);
+/// assert_eq!(path.resolve(&graph).links, dijkstra_search_node(&graph, node1, node3).unwrap().links);
+/// ```
+pub fn dijkstra_search_ids(graph: &Graph, start_node_id: isize, search_node_id: isize) -> Option<LinkPath> {
+    if start_node_id == search_node_id {
+        return Some(LinkPath { start: start_node_id, link_ids: Vec::new(), cost: 0 });
+    }
+    let goal = |node: &Node| node.id == search_node_id;
+    search_ids(graph, start_node_id, goal, |_| 0, |_| true, &mut SearchStats::default(), binary_heap(graph))
+}
+
+/// The priority queue Dijkstra's algorithm keeps the discovered nodes in.
+///
+/// Both give the same results, they only differ in speed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Queue {
+    /// The binary heap of datastructures, where every operation takes O(log n) time. Fastest for most graphs.
+    #[default]
+    BinaryHeap,
+    /// The pairing heap of datastructures, where lowering the cost of a queued node takes constant amortized time.
+    /// Can be faster on very dense graphs, where cheaper paths to queued nodes are found far more often than nodes are expanded.
+    PairingHeap,
+}
+
+/// Works like dijkstra_search_node, but keeps the discovered nodes in the given kind of priority queue.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let result = dijkstra_search_node_with_queue(&graph, node1, node3, Queue::PairingHeap).unwrap();
+/// assert_eq!(result.cost, 7);
+/// assert_eq!(result.links, dijkstra_search_node_with_queue(&graph, node1, node3, Queue::BinaryHeap).unwrap().links);
+/// ```
+pub fn dijkstra_search_node_with_queue(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    queue: Queue,
+) -> Option<SearchResult> {
+    if start_node_id == search_node_id {
+        return Some(to_self(start_node_id));
+    }
+    let goal = |node: &Node| node.id == search_node_id;
+    let stats = &mut SearchStats::default();
+    let path = match queue {
+        Queue::BinaryHeap => search_ids(graph, start_node_id, goal, |_| 0, |_| true, stats, binary_heap(graph)),
+        Queue::PairingHeap => {
+            let queue = PairingHeap::with_capacity(graph.nodes().len());
+            search_ids(graph, start_node_id, goal, |_| 0, |_| true, stats, queue)
+        }
+    };
+    path.map(|path| path.resolve(graph))
+}
+
+/// Works like dijkstra_search_report, but limited and extended by the options.
+///
+/// With a max_depth, the path found is the cheapest one with at most that many links, found by a search through up to
+/// max_depth + 1 copies of every node, one for every number of links it can be reached by.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// let node4 = graph.add_node(Node::new("Node 4"));
+/// graph.add_link(Link::new((node1, node2), 1));
+/// graph.add_link(Link::new((node2, node3), 1));
+/// graph.add_link(Link::new((node3, node4), 1));
+/// graph.add_link(Link::new((node1, node3), 5));
+///
+/// assert_eq!(dijkstra_search_with_options(&graph, node1, node4, &SearchOptions::new()).result.unwrap().cost, 3);
+/// let report = dijkstra_search_with_options(&graph, node1, node4, &SearchOptions::new().max_depth(2));
+/// assert_eq!(report.result.unwrap().cost, 6);
+/// ```
+pub fn dijkstra_search_with_options(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    options: &SearchOptions,
+) -> SearchReport {
+    search_with_options(graph, start_node_id, search_node_id, |_| 0, options)
+}
+
+/// Runs Dijkstra's algorithm, or A* with a heuristic other than 0, limited and extended by the options.
+///
+/// Every node is searched once for every number of links up to max_depth, a link leads to the copy of its other member
+/// for one more link. Without a max_depth, or one which does not limit simple paths, there is a single copy.
+pub(crate) fn search_with_options<H>(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    heuristic: H,
+    options: &SearchOptions,
+) -> SearchReport
+where
+    H: Fn(&Node) -> usize,
+{
+    SearchReport::measure_traced(|stats, trace| {
+        if start_node_id == search_node_id {
+            return Some(to_self(start_node_id));
+        }
+        let nodes = graph.nodes();
+        let start = index_of(graph, start_node_id)?;
+        let neighbours = neighbours(graph);
+        let count = nodes.len();
+        let max_depth = options.max_depth.filter(|max_depth| max_depth + 1 < count);
+        let states = count * max_depth.map_or(1, |max_depth| max_depth + 1);
+
+        let mut costs: Vec<Option<usize>> = vec![None; states];
+        let mut previous: Vec<Option<(usize, &Link)>> = vec![None; states];
+        let mut handles: Vec<Option<Handle>> = vec![None; states];
+        let mut queue = datastructures::BinaryHeap::with_capacity(count);
+        costs[start] = Some(0);
+        handles[start] = Some(queue.push((heuristic(&nodes[start]), 0, start)));
+        stats.frontier(queue.len());
+
+        while let Some((_, cost, state)) = queue.pop() {
+            let (current, depth) = (state % count, state / count);
+            if nodes[current].id == search_node_id {
+                return Some(SearchResult::new().links(path(&previous, start_node_id, state)).cost(cost));
+            }
+            if max_depth == Some(depth) {
+                continue;
+            }
+            if !options.expand(nodes[current].id, stats, trace) {
+                return None;
+            }
+            for &(next, link) in &neighbours[current] {
+                stats.links_examined += 1;
+                if !options.follows(link) {
+                    continue;
+                }
+                let next_state = if max_depth.is_some() { (depth + 1) * count + next } else { next };
+                let next_cost = cost + link.cost;
+                if costs[next_state].is_none_or(|known| next_cost < known) {
+                    costs[next_state] = Some(next_cost);
+                    previous[next_state] = Some((state, link));
+                    let estimate = (next_cost + heuristic(&nodes[next]), next_cost, next_state);
+                    match handles[next_state] {
+                        Some(handle) if queue.contains(handle) => {
+                            queue.decrease_key(handle, estimate);
+                        }
+                        _ => handles[next_state] = Some(queue.push(estimate)),
+                    }
+                    stats.frontier(queue.len());
+                }
+            }
+        }
+        None
+    })
+}
+
+/// Searches for the cheapest path like dijkstra_search_node, but on any graph implementing GraphLike.
+///
+/// The links of the result point from every node to the next one.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node2, node1), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let result = dijkstra_search_generic(&graph, node1, node3).unwrap();
+/// assert_eq!(result.links[1], Link::new((node1, node2), 3));
+/// assert_eq!(result.cost, 7);
+/// ```
+pub fn dijkstra_search_generic<G>(graph: &G, start_node_id: isize, search_node_id: isize) -> Option<SearchResult>
+where
+    G: GraphLike + ?Sized,
+{
+    crate::implicit::dijkstra_search(start_node_id, |id| id == search_node_id, |id| graph.neighbors(id))
+}
+
+fn search<G, F, E>(
+    graph: &Graph,
+    start_node_id: isize,
+    goal: G,
+    node_cost: F,
+    edge_filter: E,
+    stats: &mut SearchStats,
+) -> Option<SearchResult>
+where
+    G: Fn(&Node) -> bool,
+    F: Fn(&Node) -> usize,
+    E: Fn(&Link) -> bool,
+{
+    search_ids(graph, start_node_id, goal, node_cost, edge_filter, stats, binary_heap(graph)).map(|path| path.resolve(graph))
+}
+
+/// An empty binary heap with room for all nodes of the graph.
+fn binary_heap(graph: &Graph) -> datastructures::BinaryHeap<(usize, usize)> {
+    datastructures::BinaryHeap::with_capacity(graph.nodes().len())
+}
+
+/// Runs Dijkstra's algorithm using the given empty queue.
+fn search_ids<G, F, E, Q>(
+    graph: &Graph,
+    start_node_id: isize,
+    goal: G,
+    node_cost: F,
+    edge_filter: E,
+    stats: &mut SearchStats,
+    mut queue: Q,
+) -> Option<LinkPath>
+where
+    G: Fn(&Node) -> bool,
+    F: Fn(&Node) -> usize,
+    E: Fn(&Link) -> bool,
+    Q: PriorityQueue<(usize, usize)>,
+{
+    let nodes = graph.nodes();
+    let links = graph.links();
+    let start = index_of(graph, start_node_id)?;
+    let neighbours = indexed_neighbours(graph);
+
+    // the lowest known cost to every node and the link it has been reached through
+    // the queue holds every discovered node once, its handle is kept to lower its cost when a cheaper way is found
+    let mut costs: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut previous: Vec<Option<(usize, LinkId)>> = vec![None; nodes.len()];
+    let mut handles: Vec<Option<Handle>> = vec![None; nodes.len()];
+    costs[start] = Some(0);
+    handles[start] = Some(queue.push((0, start)));
+    stats.frontier(queue.len());
+
+    while let Some((cost, current)) = queue.pop() {
+        // abort case: node searched for is found. Follow the links back to the start node.
+        if goal(&nodes[current]) {
+            let mut link_ids = Vec::new();
+            let mut node = current;
+            while let Some((from, id)) = previous[node] {
+                link_ids.push(id);
+                node = from;
+            }
+            link_ids.reverse();
+            log_debug!("dijkstra: found {} from {} at cost {}", nodes[current].id, start_node_id, cost);
+            return Some(LinkPath { start: start_node_id, link_ids, cost });
+        }
+
+        let passing_cost = if current == start { 0 } else { node_cost(&nodes[current]) };
+        log_trace!("dijkstra: expanding {} at cost {}", nodes[current].id, cost);
+        stats.nodes_expanded += 1;
+        for &(next, id) in &neighbours[current] {
+            let link = &links[id.0];
+            stats.links_examined += 1;
+            if !edge_filter(link) {
+                continue;
+            }
+            let next_cost = cost + passing_cost + link.cost;
+            if costs[next].is_none_or(|known| next_cost < known) {
+                costs[next] = Some(next_cost);
+                previous[next] = Some((current, id));
+                match handles[next] {
+                    Some(handle) if queue.contains(handle) => {
+                        queue.decrease_key(handle, (next_cost, next));
+                    }
+                    _ => handles[next] = Some(queue.push((next_cost, next))),
+                }
+                stats.frontier(queue.len());
+            }
+        }
+    }
+
+    // if the queue is empty and no element was found, return None.
+    log_debug!("dijkstra: no path from {}", start_node_id);
+    None
+}
+
+/// Searches for the path which arrives at the target node the earliest, when the cost of links depends on the time.
+///
+/// The search departs from the start node at the time `departure`.
+/// The supplied function returns how long it takes to traverse a link when entering it at the given time,
+/// or None if the link cannot be used at that time, like during a scheduled outage.
+/// Instead of entering a link right away, the path may wait at a node until a later time,
+/// if that leads to an earlier arrival, like when a link is down or congested.
+/// All times up to `horizon` are considered, paths which would have to enter a link after it are not found.
+/// The entry times of a link are tried in order and no longer once they are as late as the earliest arrival found,
+/// so a large horizon only costs time for links which stay unusable. Arrivals after usize::MAX are skipped.
+///
+/// The cost of the result is the total travel time, including the time spent waiting.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+///
+/// let home = graph.add_node(Node::new("Home"));
+/// let bridge = graph.add_node(Node::new("Bridge"));
+/// let work = graph.add_node(Node::new("Work"));
+///
+/// graph.add_link(Link::new((home, work), 10));
+/// graph.add_link(Link::new((home, bridge), 2));
+/// graph.add_link(Link::new((bridge, work), 2));
+///
+/// // the bridge is closed until time 5 and the direct road is congested during the rush hour
+/// let travel_time = |link: &Link, time: usize| {
+///     if link.members == (bridge, work) && time < 5 {
+///         None
+///     } else if link.members == (home, work) && time < 8 {
+///         Some(link.cost * 2)
+///     } else {
+///         Some(link.cost)
+///     }
+/// };
+///
+/// let result = dijkstra_search_node_time_dependent(&graph, home, work, 0, 100, travel_time).unwrap();
+/// assert_eq!(result.links[1], Link::new((home, bridge), 2));
+/// assert_eq!(result.cost, 7);
+///
+/// let result = dijkstra_search_node_time_dependent(&graph, home, work, 8, 100, travel_time).unwrap();
+/// assert_eq!(result.links[1], Link::new((home, bridge), 2));
+/// assert_eq!(result.cost, 4);
+/// ```
+pub fn dijkstra_search_node_time_dependent<F>(
+    graph: &Graph,
+    start_node_id: isize,
+    search_node_id: isize,
+    departure: usize,
+    horizon: usize,
+    cost_fn: F,
+) -> Option<SearchResult>
+where
+    F: Fn(&Link, usize) -> Option<usize>,
+{
+    if start_node_id == search_node_id {
+        return Some(to_self(start_node_id));
+    }
+
+    let nodes = graph.nodes();
+    let start = index_of(graph, start_node_id)?;
+    let target = index_of(graph, search_node_id)?;
+    let neighbours = neighbours(graph);
+
+    // the earliest known arrival time at every node and the link it has been reached through.
+    // as waiting is allowed, arriving earlier at a node is never worse than arriving later.
+    let mut arrivals: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut previous: Vec<Option<(usize, &Link)>> = vec![None; nodes.len()];
+    let mut queue = BinaryHeap::new();
+    arrivals[start] = Some(departure);
+    queue.push(Reverse((departure, start)));
+
+    while let Some(Reverse((time, current))) = queue.pop() {
+        if arrivals[current] != Some(time) {
+            continue;
+        }
+        if current == target {
+            return Some(SearchResult::new().links(path(&previous, start_node_id, target)).cost(time - departure));
+        }
+
+        for &(next, link) in &neighbours[current] {
+            // every time the link can be entered at is a copy of it in the time-expanded graph.
+            // entering at or after the earliest arrival known cannot arrive any earlier, so later times are left out
+            let mut arrival: Option<usize> = None;
+            for entry in time..=horizon {
+                if arrival.or(arrivals[next]).is_some_and(|earliest| entry >= earliest) {
+                    break;
+                }
+                if let Some(candidate) = cost_fn(link, entry).and_then(|cost| entry.checked_add(cost)) {
+                    arrival = Some(arrival.map_or(candidate, |arrival| arrival.min(candidate)));
+                }
+            }
+            if let Some(arrival) = arrival.filter(|arrival| arrivals[next].is_none_or(|known| *arrival < known)) {
+                arrivals[next] = Some(arrival);
+                previous[next] = Some((current, link));
+                queue.push(Reverse((arrival, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Runs Dijkstra's algorithm from the start node through the whole graph
+/// and returns the links every reachable node has been reached through.
+///
+/// The path to every node then is the cheapest one, like the one dijkstra_search_node finds.
+/// If the start node is not part of the graph, no node is reached.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let predecessors = dijkstra_predecessors(&graph, node1);
+/// assert_eq!(predecessors.path_to(node2).unwrap().cost, 3);
+/// assert_eq!(predecessors.path_to(node3).unwrap().cost, 7);
+/// ```
+pub fn dijkstra_predecessors(graph: &Graph, start_node_id: isize) -> Predecessors {
+    let mut predecessors = Predecessors::new(start_node_id);
+    let start = match index_of(graph, start_node_id) {
+        Some(start) => start,
+        None => return predecessors,
+    };
+    let nodes = graph.nodes();
+    let neighbours = neighbours(graph);
+    let mut costs: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut queue = BinaryHeap::new();
+    costs[start] = Some(0);
+    queue.push(Reverse((0, start)));
+    while let Some(Reverse((cost, current))) = queue.pop() {
+        if costs[current] != Some(cost) {
+            continue;
+        }
+        for &(next, link) in &neighbours[current] {
+            let next_cost = cost + link.cost;
+            if costs[next].is_none_or(|known| next_cost < known) {
+                costs[next] = Some(next_cost);
+                predecessors.map.insert(nodes[next].id, (nodes[current].id, *link));
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    predecessors
+}
+
+/// Returns every cheapest path from the start node to the target node, like the equal-cost routes a router with
+/// [ECMP](https://en.wikipedia.org/wiki/Equal-cost_multi-path_routing) splits traffic across.
+///
+/// Dijkstra's algorithm computes the lowest cost to every node, then the paths are followed back from the target node along
+/// the links on which the costs add up. The paths are sorted by the ids of their nodes.
+/// There can be exponentially many cheapest paths, like in a grid of links of the same cost.
+/// Links of cost 0 cannot make a node appear twice on a path.
+/// If there is no path, or the start or target node is not part of the graph, the result is empty.
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// let node4 = graph.add_node(Node::new("Node 4"));
+/// graph.add_link(Link::new((node1, node2), 1));
+/// graph.add_link(Link::new((node1, node3), 2));
+/// graph.add_link(Link::new((node2, node4), 2));
+/// graph.add_link(Link::new((node3, node4), 1));
+/// graph.add_link(Link::new((node1, node4), 4));
+///
+/// let paths = all_shortest_paths(&graph, node1, node4);
+/// assert_eq!(paths.len(), 2);
+/// assert_eq!(paths[0].node_ids(), vec![node1, node2, node4]);
+/// assert_eq!(paths[1].node_ids(), vec![node1, node3, node4]);
+/// assert!(paths.iter().all(|path| path.cost == 3));
+/// ```
+pub fn all_shortest_paths(graph: &Graph, start_node_id: isize, search_node_id: isize) -> Vec<SearchResult> {
+    if start_node_id == search_node_id {
+        return vec![to_self(start_node_id)];
+    }
+    let (start, target) = match (index_of(graph, start_node_id), index_of(graph, search_node_id)) {
+        (Some(start), Some(target)) => (start, target),
+        _ => return Vec::new(),
+    };
+    let neighbours = neighbours(graph);
+    let mut costs: Vec<Option<usize>> = vec![None; neighbours.len()];
+    let mut queue = BinaryHeap::new();
+    costs[start] = Some(0);
+    queue.push(Reverse((0, start)));
+    while let Some(Reverse((cost, current))) = queue.pop() {
+        // the nodes costing more than the target cannot be part of a cheapest path to it
+        if costs[target].is_some_and(|target_cost| cost > target_cost) {
+            break;
+        }
+        if costs[current] != Some(cost) {
+            continue;
+        }
+        for &(next, link) in &neighbours[current] {
+            let next_cost = cost + link.cost;
+            if costs[next].is_none_or(|known| next_cost < known) {
+                costs[next] = Some(next_cost);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    let target_cost = match costs[target] {
+        Some(cost) => cost,
+        None => return Vec::new(),
+    };
+
+    // the nodes of the current path back from the target, each with the position of the next of its neighbours to try
+    let mut paths = Vec::new();
+    let mut stack: Vec<(usize, usize)> = vec![(target, 0)];
+    let mut links: Vec<&Link> = Vec::new();
+    let mut on_path = vec![false; neighbours.len()];
+    on_path[target] = true;
+    while let Some(&(current, position)) = stack.last() {
+        let previous = neighbours[current].get(position).filter(|_| current != start);
+        match previous {
+            Some(&(previous, link)) => {
+                stack.last_mut().unwrap().1 += 1;
+                let cheapest = costs[previous].is_some_and(|cost| Some(cost + link.cost) == costs[current]);
+                if cheapest && !on_path[previous] {
+                    on_path[previous] = true;
+                    stack.push((previous, 0));
+                    links.push(link);
+                }
+            }
+            None => {
+                if current == start {
+                    let mut path = vec![Link::new((start_node_id, start_node_id), 0)];
+                    path.extend(links.iter().rev().map(|link| **link));
+                    paths.push(SearchResult::new().links(path).cost(target_cost));
+                }
+                on_path[current] = false;
+                stack.pop();
+                links.pop();
+            }
+        }
+    }
+    paths.sort_by_cached_key(SearchResult::node_ids);
+    paths
+}
+
+/// Returns the result of a search for the start node itself, consisting of the link from the start node to itself.
+pub(crate) fn to_self(start_node_id: isize) -> SearchResult {
+    SearchResult::new().links(vec![Link::new((start_node_id, start_node_id), 0)]).cost(0)
+}
+
+/// Returns the index of the node with the given id, if it is part of the graph.
+pub(crate) fn index_of(graph: &Graph, id: isize) -> Option<usize> {
+    node_index(id, graph.nodes().len())
+}
+
+/// Returns the neighbours of every node, together with the links leading to them.
+/// Links to nodes outside of the graph and links from a node to itself are ignored.
+pub(crate) fn neighbours(graph: &Graph) -> Vec<Vec<(usize, &Link)>> {
+    let mut neighbours: Vec<Vec<(usize, &Link)>> = vec![Vec::new(); graph.nodes().len()];
+    for link in graph.links() {
+        if let (Some(a), Some(b)) = (index_of(graph, link.members.0), index_of(graph, link.members.1)) {
+            if a != b {
+                neighbours[a].push((b, link));
+                neighbours[b].push((a, link));
+            }
+        }
+    }
+    neighbours
+}
+
+/// Like neighbours, but refers to the links by their ids.
+pub(crate) fn indexed_neighbours(graph: &Graph) -> Vec<Vec<(usize, LinkId)>> {
+    let mut neighbours: Vec<Vec<(usize, LinkId)>> = vec![Vec::new(); graph.nodes().len()];
+    for (index, link) in graph.links().iter().enumerate() {
+        if let (Some(a), Some(b)) = (index_of(graph, link.members.0), index_of(graph, link.members.1)) {
+            if a != b {
+                neighbours[a].push((b, LinkId(index)));
+                neighbours[b].push((a, LinkId(index)));
+            }
+        }
+    }
+    neighbours
+}
+
+/// Follows the links back from the target to the start node and returns them in order,
+/// starting with the link from the start element to itself.
+pub(crate) fn path(previous: &[Option<(usize, &Link)>], start_node_id: isize, target: usize) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut node = target;
+    while let Some((from, link)) = previous[node] {
+        links.push(*link);
+        node = from;
+    }
+    links.push(Link::new((start_node_id, start_node_id), 0));
+    links.reverse();
+    links
+}
+
+#[cfg(test)]
+mod discover_test {
+    use super::*;
+
+    #[test]
+    fn test_discover_no_start_element() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new("Node 1"));
+        assert!(dijkstra_search_node(&graph, 2, 0).is_none());
+        assert!(dijkstra_search_node(&graph, -1, 0).is_none());
+    }
+
+    #[test]
+    fn test_discover_no_link() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_node(Node::new("Node 1"));
+        graph.add_node(Node::new("Node 2"));
+        graph.add_link(Link::new((1, 3), 1));
+        assert!(dijkstra_search_node(&graph, 0, 1).is_none());
+    }
+
+    #[test]
+    fn test_discover_element_to_self() {
+        let graph = Graph::new();
+        let result = dijkstra_search_node(&graph, 1, 1).unwrap();
+        assert_eq!(result.links[0], Link::new((1, 1), 0));
+        assert_eq!(result.cost, 0);
+    }
+
+    #[test]
+    fn test_discover_cheapest_path() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4", "Node 5"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 7));
+        graph.add_link(Link::new((0, 2), 2));
+        graph.add_link(Link::new((2, 1), 3));
+        graph.add_link(Link::new((1, 3), 1));
+        graph.add_link(Link::new((2, 3), 8));
+        graph.add_link(Link::new((3, 4), 1));
+        graph.add_link(Link::new((3, 3), 0));
+        let result = dijkstra_search_node(&graph, 0, 4).unwrap();
+        assert_eq!(result.cost, 7);
+        assert_eq!(result.to_string(), "0 -(2)-> 2 -(3)-> 1 -(1)-> 3 -(1)-> 4, total 7");
+    }
+
+    #[test]
+    fn test_discover_filtered() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 3), 1));
+        graph.add_link(Link::new((0, 2), 2));
+        graph.add_link(Link::new((2, 3), 2));
+        let blocked = Link::new((1, 3), 1);
+        let result = dijkstra_search_node_filtered(&graph, 0, 3, |link| *link != blocked).unwrap();
+        assert_eq!(result.links, vec![Link::new((0, 0), 0), Link::new((0, 2), 2), Link::new((2, 3), 2)]);
+        assert_eq!(result.cost, 4);
+        assert!(dijkstra_search_node_filtered(&graph, 0, 3, |_| false).is_none());
+        assert_eq!(dijkstra_search_node_filtered(&graph, 0, 0, |_| false).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn test_discover_ids() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((2, 1), 1));
+        graph.add_link(Link::new((0, 2), 5));
+        let path = dijkstra_search_ids(&graph, 0, 2).unwrap();
+        assert_eq!(path.link_ids, vec![LinkId(0), LinkId(1)]);
+        assert_eq!(path.links(&graph).collect::<Vec<_>>(), vec![&Link::new((0, 1), 1), &Link::new((2, 1), 1)]);
+        assert_eq!(path.cost, 2);
+        assert!(dijkstra_search_ids(&graph, 0, 0).unwrap().link_ids.is_empty());
+        assert_eq!(dijkstra_search_ids(&graph, 0, 0).unwrap().resolve(&graph).links, vec![Link::new((0, 0), 0)]);
+        assert!(dijkstra_search_ids(&graph, 0, 3).is_none());
+    }
+
+    #[test]
+    fn test_discover_with_queue() {
+        // a dense graph, where costs of queued nodes are lowered often
+        let graph = crate::generate::connected(60, 30, 1..20, &mut crate::util::SplitMix64::new(8));
+        for target in 0..60 {
+            let expected = dijkstra_search_node(&graph, 0, target).unwrap();
+            assert_eq!(dijkstra_search_node_with_queue(&graph, 0, target, Queue::BinaryHeap).unwrap().links, expected.links);
+            let result = dijkstra_search_node_with_queue(&graph, 0, target, Queue::PairingHeap).unwrap();
+            assert_eq!(result.cost, expected.cost);
+        }
+        assert!(dijkstra_search_node_with_queue(&graph, 0, 60, Queue::PairingHeap).is_none());
+        assert_eq!(Queue::default(), Queue::BinaryHeap);
+    }
+
+    #[test]
+    fn test_discover_goal() {
+        let mut graph = Graph::new();
+        for name in ["A", "B1", "C", "B2"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 5));
+        graph.add_link(Link::new((0, 2), 1));
+        graph.add_link(Link::new((2, 3), 1));
+        let is_b = |node: &Node| node.name.starts_with('B');
+        assert_eq!(dijkstra_search_goal(&graph, 0, is_b).unwrap().cost, 2);
+        assert_eq!(dijkstra_search_goal(&graph, 1, is_b).unwrap().links, vec![Link::new((1, 1), 0)]);
+        assert!(dijkstra_search_goal(&graph, 0, |node| node.name == "D").is_none());
+        assert!(dijkstra_search_goal(&graph, 7, is_b).is_none());
+    }
+
+    #[test]
+    fn test_discover_generic() {
+        /// A grid of 5x5 nodes, with the id being 10 * row + column and links getting more expensive to the right.
+        struct Grid;
+
+        impl GraphLike for Grid {
+            fn neighbors(&self, node_id: isize) -> Vec<(isize, usize)> {
+                let (row, column) = (node_id / 10, node_id % 10);
+                let mut neighbors = Vec::new();
+                if row > 0 { neighbors.push((node_id - 10, 1)); }
+                if row < 4 { neighbors.push((node_id + 10, 1)); }
+                if column > 0 { neighbors.push((node_id - 1, column as usize)); }
+                if column < 4 { neighbors.push((node_id + 1, column as usize + 1)); }
+                neighbors
+            }
+        }
+
+        let result = dijkstra_search_generic(&Grid, 0, 44).unwrap();
+        assert_eq!(result.cost, 14);
+        assert_eq!(result.links.len(), 9);
+        assert_eq!(result.links[0], Link::new((0, 0), 0));
+        assert!(result.links.windows(2).all(|pair| pair[0].members.1 == pair[1].members.0));
+        assert_eq!(dijkstra_search_generic(&Grid, 12, 12).unwrap().cost, 0);
+        assert!(dijkstra_search_generic(&Grid, 0, 45).is_none());
+        assert_eq!(Grid.link_cost(2, 3), Some(3));
+
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for name in ["A", "B", "C", "D"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 7));
+        graph.add_link(Link::new((1, 2), 1));
+        graph.add_link(Link::new((0, 2), 9));
+        graph.add_link(Link::new((2, 2), 0));
+        graph.add_link(Link::new((2, 6), 0));
+        assert_eq!(graph.neighbors(2), vec![(1, 1), (0, 9)]);
+        assert!(graph.neighbors(6).is_empty());
+        assert_eq!(graph.link_cost(2, 0), Some(9));
+        assert_eq!(graph.link_cost(2, 3), None);
+        for target in 0..5 {
+            let expected = dijkstra_search_node(&graph, 0, target).map(|result| result.cost);
+            assert_eq!(dijkstra_search_generic(&graph, 0, target).map(|result| result.cost), expected);
+        }
+    }
+
+    #[test]
+    fn test_predecessors() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 5));
+        graph.add_link(Link::new((0, 2), 1));
+        graph.add_link(Link::new((2, 1), 1));
+        let predecessors = dijkstra_predecessors(&graph, 0);
+        for target in 0..4 {
+            let single = dijkstra_search_node(&graph, 0, target).map(|result| result.cost);
+            assert_eq!(predecessors.path_to(target).map(|result| result.cost), single);
+        }
+        assert_eq!(predecessors.map[&1], (2, Link::new((2, 1), 1)));
+    }
+
+    #[test]
+    fn test_discover_with_node_costs() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        graph.add_link(Link::new((2, 3), 1));
+        graph.add_link(Link::new((0, 3), 4));
+        let result = dijkstra_search_node_with_node_costs(&graph, 0, 3, |_| 1).unwrap();
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.links.len(), 2);
+        let result = dijkstra_search_node_with_node_costs(&graph, 0, 2, |_| 1).unwrap();
+        assert_eq!(result.cost, 3);
+    }
+
+    #[test]
+    fn test_discover_time_dependent() {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        // the second link is only up at odd times
+        let cost = |link: &Link, time: usize| {
+            if link.members == (1, 2) && time.is_multiple_of(2) {
+                None
+            } else {
+                Some(link.cost)
+            }
+        };
+        let result = dijkstra_search_node_time_dependent(&graph, 0, 2, 0, 10, cost).unwrap();
+        assert_eq!(result.cost, 2);
+        // arriving at the second node at time 2 requires waiting for the link to come up
+        let result = dijkstra_search_node_time_dependent(&graph, 0, 2, 1, 10, cost).unwrap();
+        assert_eq!(result.cost, 3);
+        assert!(dijkstra_search_node_time_dependent(&graph, 0, 2, 0, 0, cost).is_none());
+        assert!(dijkstra_search_node_time_dependent(&graph, 0, 2, 0, 10, |_, _| None).is_none());
+        // the entry times stop at the earliest arrival, so an unlimited horizon still ends
+        let result = dijkstra_search_node_time_dependent(&graph, 0, 2, 1, usize::MAX, cost).unwrap();
+        assert_eq!(result.cost, 3);
+        // arrivals which would overflow are skipped, waiting for a usable time instead
+        let crowded = |_: &Link, time: usize| if time < 5 { Some(usize::MAX) } else { Some(1) };
+        let result = dijkstra_search_node_time_dependent(&graph, 0, 2, 3, usize::MAX, crowded).unwrap();
+        assert_eq!(result.cost, 4);
+    }
+
+    #[test]
+    fn test_discover_with_options() {
+        use crate::util::SplitMix64;
+        let mut rng = SplitMix64::new(35);
+        for _ in 0..20 {
+            let graph = crate::generate::erdos_renyi(8, 0.4, 1..10, &mut rng);
+            let unlimited = dijkstra_search_with_options(&graph, 0, 7, &SearchOptions::new());
+            assert_eq!(unlimited.result.map(|result| result.cost), dijkstra_search_node(&graph, 0, 7).map(|result| result.cost));
+            // the cheapest of the paths within the depth
+            for max_depth in 0..8 {
+                let cheapest = crate::paths::all_simple_paths(&graph, 0, 7, max_depth).map(|path| path.cost).min();
+                let options = SearchOptions::new().max_depth(max_depth).record_trace(true);
+                let report = dijkstra_search_with_options(&graph, 0, 7, &options);
+                let result = report.result.as_ref();
+                assert_eq!(result.map(|result| result.cost), cheapest);
+                assert!(result.is_none_or(|result| result.links.len() <= max_depth + 1));
+                assert_eq!(report.trace.len(), report.stats.nodes_expanded);
+                // giving up just before the search is done
+                if report.stats.nodes_expanded > 0 && cheapest.is_some() {
+                    let options = options.max_nodes(report.stats.nodes_expanded - 1);
+                    assert!(dijkstra_search_with_options(&graph, 0, 7, &options).result.is_none());
+                }
+            }
+            let cheap = SearchOptions::new().edge_filter(|link| link.cost < 5);
+            let result = dijkstra_search_with_options(&graph, 0, 7, &cheap).result;
+            let filtered = dijkstra_search_node_filtered(&graph, 0, 7, |link| link.cost < 5);
+            assert_eq!(result.map(|result| result.cost), filtered.map(|result| result.cost));
+        }
+        let graph = crate::generate::erdos_renyi(3, 1.0, 1..2, &mut rng);
+        assert!(dijkstra_search_with_options(&graph, 5, 1, &SearchOptions::new()).result.is_none());
+        assert_eq!(dijkstra_search_with_options(&graph, 1, 1, &SearchOptions::new().max_nodes(0)).result.unwrap().links.len(), 1);
+        assert_eq!(dijkstra_search_with_options(&graph, 0, 2, &SearchOptions::new().max_depth(1)).result.unwrap().links.len(), 2);
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        use crate::util::SplitMix64;
+        use std::collections::HashSet;
+        let mut rng = SplitMix64::new(38);
+        for _ in 0..30 {
+            // few different costs, so there are many paths of the same cost
+            let mut graph = crate::generate::erdos_renyi(8, 0.4, 1..3, &mut rng);
+            graph.add_link(Link::new((2, 5), 0));
+            let paths = all_shortest_paths(&graph, 0, 7);
+            let all: Vec<SearchResult> = crate::paths::all_simple_paths(&graph, 0, 7, usize::MAX).collect();
+            let cheapest = all.iter().map(|path| path.cost).min();
+            assert_eq!(paths.first().map(|path| path.cost), cheapest);
+            assert_eq!(dijkstra_search_node(&graph, 0, 7).map(|result| result.cost), cheapest);
+            let expected: HashSet<Vec<isize>> =
+                all.iter().filter(|path| Some(path.cost) == cheapest).map(SearchResult::node_ids).collect();
+            let found: Vec<Vec<isize>> = paths.iter().map(SearchResult::node_ids).collect();
+            assert_eq!(found.len(), expected.len());
+            assert_eq!(found.iter().cloned().collect::<HashSet<_>>(), expected);
+            assert!(found.windows(2).all(|pair| pair[0] < pair[1]));
+            for path in &paths {
+                assert_eq!(path.cost, path.links.iter().map(|link| link.cost).sum::<usize>());
+            }
+        }
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        assert!(all_shortest_paths(&graph, 0, 2).is_empty());
+        assert!(all_shortest_paths(&graph, 0, 5).is_empty());
+        assert_eq!(all_shortest_paths(&graph, 2, 2)[0].links, vec![Link::new((2, 2), 0)]);
+        assert_eq!(all_shortest_paths(&graph, 1, 0)[0].links, vec![Link::new((1, 1), 0), Link::new((0, 1), 1)]);
+    }
+}
@@ -0,0 +1,331 @@
+use crate::graph::Graph;
+use crate::simulation::Engine;
+use std::collections::BTreeMap;
+
+/// The cost RIP uses to mark a destination as unreachable.
+/// All costs are capped at this value, so counting to infinity eventually ends.
+pub const DEFAULT_INFINITY: usize = 16;
+
+/// The rules a router follows when it advertises its distance vector to a neighbour.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Every route is advertised to every neighbour.
+    Basic,
+    /// Routes are not advertised to the neighbour they were learned from.
+    SplitHorizon,
+    /// Routes are advertised to the neighbour they were learned from with a cost of infinity.
+    PoisonReverse,
+}
+
+/// A route a router knows to a destination.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    pub cost: usize,
+    /// The neighbour packets to the destination are sent to, or None for the route of a router to itself.
+    pub next_hop: Option<isize>,
+}
+
+/// A router, which knows the routes to all destinations it has heard of.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Router {
+    pub id: isize,
+    routes: BTreeMap<isize, Route>,
+}
+
+/// A simulation of [distance-vector routing](https://en.wikipedia.org/wiki/Distance-vector_routing_protocol),
+/// the distributed variant of the Bellman-Ford algorithm.
+///
+/// Every node of a graph acts as a router. When a router is activated, it sends its distance vector,
+/// which is the cost of all its routes, to its neighbours. A neighbour accepts a route if it is cheaper than the one it knows,
+/// or if it was learned from the sender anyway, as the sender knows best where its own route leads.
+/// The network can be driven by the functions of the simulation module.
+#[derive(Clone, Debug)]
+pub struct Network {
+    routers: Vec<Router>,
+    links: Vec<(isize, isize, usize)>,
+    mode: Mode,
+    infinity: usize,
+    messages: usize,
+}
+
+impl Router {
+    /// Returns all routes the router knows, by their destination.
+    /// Unreachable destinations have a route with a cost of infinity.
+    pub fn routes(&self) -> &BTreeMap<isize, Route> {
+        &self.routes
+    }
+}
+
+impl Network {
+    /// Creates a network with a router for every node of the graph, connected by the links of the graph.
+    /// Every router only knows the route to itself at the beginning.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    /// use rust_algorithms::distvec::*;
+    /// use rust_algorithms::simulation;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// let c = graph.add_node(Node::new("C"));
+    /// graph.add_link(Link::new((a, b), 1));
+    /// graph.add_link(Link::new((b, c), 2));
+    ///
+    /// let mut network = Network::from_graph(&graph, Mode::SplitHorizon);
+    /// simulation::run_rounds(&mut network, 10);
+    /// assert_eq!(network.route(a, c), Some(Route { cost: 3, next_hop: Some(b) }));
+    /// ```
+    pub fn from_graph(graph: &Graph, mode: Mode) -> Self {
+        let routers: Vec<Router> = graph
+            .nodes()
+            .iter()
+            .map(|node| {
+                let mut routes = BTreeMap::new();
+                routes.insert(node.id, Route { cost: 0, next_hop: None });
+                Router { id: node.id, routes }
+            })
+            .collect();
+        let contains = |id: isize| routers.iter().any(|router| router.id == id);
+        let links = graph
+            .links()
+            .iter()
+            .filter(|link| link.members.0 != link.members.1)
+            .filter(|link| contains(link.members.0) && contains(link.members.1))
+            .map(|link| (link.members.0, link.members.1, link.cost))
+            .collect();
+        Network {
+            routers,
+            links,
+            mode,
+            infinity: DEFAULT_INFINITY,
+            messages: 0,
+        }
+    }
+
+    /// Sets the cost which marks a destination as unreachable.
+    ///
+    /// For more information see DEFAULT_INFINITY.
+    pub fn infinity(mut self, infinity: usize) -> Self {
+        self.infinity = infinity;
+        self
+    }
+
+    /// Returns the router with the given id.
+    pub fn router(&self, id: isize) -> Option<&Router> {
+        self.routers.iter().find(|router| router.id == id)
+    }
+
+    /// Returns the route the router from currently knows to the router to,
+    /// or None if one of them does not exist or the destination is unreachable.
+    pub fn route(&self, from: isize, to: isize) -> Option<Route> {
+        self.router(from)
+            .and_then(|router| router.routes.get(&to).copied())
+            .filter(|route| route.cost < self.infinity)
+    }
+
+    /// Returns the number of distance vectors which have been sent so far.
+    pub fn messages(&self) -> usize {
+        self.messages
+    }
+
+    /// Removes the link between two routers, like a failing cable.
+    ///
+    /// Both routers notice the failure immediately and mark all routes over the link as unreachable.
+    /// The other routers only learn about the failure from the distance vectors they receive,
+    /// which leads to counting to infinity if no countermeasure like split horizon is used.
+    ///
+    /// Returns false if there was no link between the two routers.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    /// use rust_algorithms::distvec::*;
+    /// use rust_algorithms::simulation;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// let c = graph.add_node(Node::new("C"));
+    /// graph.add_link(Link::new((a, b), 1));
+    /// graph.add_link(Link::new((b, c), 1));
+    ///
+    /// let mut network = Network::from_graph(&graph, Mode::Basic);
+    /// simulation::run_rounds(&mut network, 10);
+    /// assert_eq!(network.remove_link(b, c), true);
+    /// simulation::run_rounds(&mut network, 100);
+    /// assert_eq!(network.route(a, c), None);
+    /// ```
+    pub fn remove_link(&mut self, a: isize, b: isize) -> bool {
+        let count = self.links.len();
+        self.links.retain(|&(x, y, _)| !(x == a && y == b || x == b && y == a));
+        if self.links.len() == count {
+            return false;
+        }
+        let infinity = self.infinity;
+        for (router_id, neighbour) in [(a, b), (b, a)] {
+            if let Some(router) = self.routers.iter_mut().find(|router| router.id == router_id) {
+                for route in router.routes.values_mut() {
+                    if route.next_hop == Some(neighbour) {
+                        route.cost = infinity;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Computes the routes a router would change after receiving the distance vector of a neighbour over a link with the given cost.
+    fn updates(&self, from: usize, to: usize, link_cost: usize) -> Vec<(isize, Route)> {
+        let sender = &self.routers[from];
+        let receiver = &self.routers[to];
+        let mut updates = Vec::new();
+        for (&destination, route) in &sender.routes {
+            if destination == receiver.id {
+                continue;
+            }
+            let learned_from_receiver = route.next_hop == Some(receiver.id);
+            let advertised = match self.mode {
+                Mode::SplitHorizon if learned_from_receiver => continue,
+                Mode::PoisonReverse if learned_from_receiver => self.infinity,
+                _ => route.cost,
+            };
+            let cost = advertised.saturating_add(link_cost).min(self.infinity);
+            let offered = Route { cost, next_hop: Some(sender.id) };
+            match receiver.routes.get(&destination) {
+                Some(current) if current.next_hop == Some(sender.id) => {
+                    if current.cost != cost {
+                        updates.push((destination, offered));
+                    }
+                }
+                Some(current) if current.cost <= cost => {}
+                None if cost >= self.infinity => {}
+                _ => updates.push((destination, offered)),
+            }
+        }
+        updates
+    }
+
+    /// Returns the indices of the neighbours of a router together with the cost of the link to them.
+    fn neighbours(&self, id: isize) -> Vec<(usize, usize)> {
+        let index_of = |id: isize| self.routers.iter().position(|router| router.id == id);
+        self.links
+            .iter()
+            .filter_map(|&(a, b, cost)| {
+                if a == id {
+                    index_of(b).map(|index| (index, cost))
+                } else if b == id {
+                    index_of(a).map(|index| (index, cost))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl Engine for Network {
+    fn node_ids(&self) -> Vec<isize> {
+        self.routers.iter().map(|router| router.id).collect()
+    }
+
+    /// Sends the distance vector of the router to all its neighbours.
+    fn activate(&mut self, node_id: isize) {
+        let from = match self.routers.iter().position(|router| router.id == node_id) {
+            Some(index) => index,
+            None => return,
+        };
+        for (to, cost) in self.neighbours(node_id) {
+            self.messages += 1;
+            for (destination, route) in self.updates(from, to, cost) {
+                self.routers[to].routes.insert(destination, route);
+            }
+        }
+    }
+
+    /// The network has converged once no distance vector would change a route anymore.
+    fn converged(&self) -> bool {
+        self.routers.iter().enumerate().all(|(from, router)| {
+            self.neighbours(router.id)
+                .into_iter()
+                .all(|(to, cost)| self.updates(from, to, cost).is_empty())
+        })
+    }
+}
+
+#[cfg(test)]
+mod distvec_test {
+    use super::*;
+    use crate::graph::{Link, Node};
+    use crate::simulation;
+
+    fn line(mode: Mode) -> Network {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_node(Node::new("A"));
+        graph.add_node(Node::new("B"));
+        graph.add_node(Node::new("C"));
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        graph.add_link(Link::new((2, 7), 1));
+        Network::from_graph(&graph, mode)
+    }
+
+    #[test]
+    fn converges_to_shortest_paths() {
+        let mut graph = Graph::new();
+        for name in ["A", "B", "C", "D"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        graph.add_link(Link::new((0, 2), 5));
+        graph.add_link(Link::new((2, 3), 1));
+        let mut network = Network::from_graph(&graph, Mode::Basic);
+        assert!(!simulation::Engine::converged(&network));
+        let rounds = simulation::run_rounds(&mut network, 10);
+        assert!(rounds < 10);
+        assert_eq!(network.route(0, 2), Some(Route { cost: 2, next_hop: Some(1) }));
+        assert_eq!(network.route(0, 3), Some(Route { cost: 3, next_hop: Some(1) }));
+        assert_eq!(network.route(3, 0), Some(Route { cost: 3, next_hop: Some(2) }));
+        assert_eq!(network.route(2, 2), Some(Route { cost: 0, next_hop: None }));
+        assert_eq!(network.route(9, 2), None);
+        assert_eq!(network.messages(), rounds * 8);
+    }
+
+    #[test]
+    fn count_to_infinity() {
+        let mut network = line(Mode::Basic);
+        simulation::run_rounds(&mut network, 10);
+        assert_eq!(network.route(0, 2).unwrap().cost, 2);
+        assert!(network.remove_link(1, 2));
+        assert!(!network.remove_link(1, 2));
+        let rounds = simulation::run_rounds(&mut network, 100);
+        assert!(rounds > 5);
+        assert_eq!(network.route(0, 2), None);
+        assert_eq!(network.route(1, 2), None);
+    }
+
+    #[test]
+    fn split_horizon_prevents_counting() {
+        for mode in [Mode::SplitHorizon, Mode::PoisonReverse] {
+            let mut network = line(mode);
+            simulation::run_rounds(&mut network, 10);
+            network.remove_link(1, 2);
+            let rounds = simulation::run_rounds(&mut network, 100);
+            assert!(rounds <= 2);
+            assert_eq!(network.route(0, 2), None);
+        }
+    }
+
+    #[test]
+    fn custom_infinity() {
+        let mut network = line(Mode::Basic).infinity(4);
+        simulation::run_rounds(&mut network, 10);
+        network.remove_link(1, 2);
+        simulation::run_rounds(&mut network, 100);
+        assert_eq!(network.route(0, 2), None);
+        assert_eq!(network.router(0).unwrap().routes()[&2].cost, 4);
+    }
+}
@@ -0,0 +1,17 @@
+//! Helpers for writing the [DOT language](https://graphviz.org/doc/info/lang.html) of Graphviz, used by the export functions of the crate.
+
+/// Escapes a string so it can be used inside of a quoted DOT string.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod dot_test {
+    use super::*;
+
+    #[test]
+    fn escape_special_characters() {
+        assert_eq!(escape("a \"b\"\\c\nd"), "a \\\"b\\\"\\\\c\\nd");
+        assert_eq!(escape("plain"), "plain");
+    }
+}
@@ -0,0 +1,255 @@
+//! Classic [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming) problems.
+//!
+//! Every function returns the optimal value together with a solution reaching it, which is reconstructed from the table
+//! of the subproblems. Like the searches returning the path along with its cost, the value alone is rarely enough.
+
+/// The result of the 0/1 knapsack problem: the greatest total value and the indices of the items packed to reach it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Knapsack {
+    pub value: usize,
+    pub weight: usize,
+    /// The indices of the packed items, in ascending order.
+    pub items: Vec<usize>,
+}
+
+/// Solves the [0/1 knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem): picks items, each at most once,
+/// with the greatest total value while their total weight stays within the capacity.
+///
+/// Items are given as (weight, value) pairs. Takes O(items * capacity) time and memory, so the capacity should be small,
+/// like the bandwidth of a link in Mbit/s.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::dp::*;
+///
+/// let items = [(10, 60), (20, 100), (30, 120)];
+/// let result = knapsack(&items, 50);
+/// assert_eq!(result.value, 220);
+/// assert_eq!(result.weight, 50);
+/// assert_eq!(result.items, vec![1, 2]);
+/// ```
+pub fn knapsack(items: &[(usize, usize)], capacity: usize) -> Knapsack {
+    // best[i][c] is the greatest value of the first i items within the capacity c
+    let mut best = vec![vec![0; capacity + 1]; items.len() + 1];
+    for (index, &(weight, value)) in items.iter().enumerate() {
+        for space in 0..=capacity {
+            best[index + 1][space] = best[index][space];
+            if weight <= space {
+                best[index + 1][space] = best[index + 1][space].max(best[index][space - weight] + value);
+            }
+        }
+    }
+
+    // an item is packed if leaving it out would lose value
+    let mut packed = Vec::new();
+    let mut space = capacity;
+    for index in (0..items.len()).rev() {
+        if best[index + 1][space] != best[index][space] {
+            packed.push(index);
+            space -= items[index].0;
+        }
+    }
+    packed.reverse();
+    Knapsack { value: best[items.len()][capacity], weight: capacity - space, items: packed }
+}
+
+/// Finds one of the [longest common subsequences](https://en.wikipedia.org/wiki/Longest_common_subsequence) of a and b,
+/// the longest sequence of elements appearing in both in the same order, but not necessarily next to each other.
+///
+/// Takes O(a * b) time and memory.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::dp::*;
+///
+/// let a: Vec<char> = "ABCBDAB".chars().collect();
+/// let b: Vec<char> = "BDCABA".chars().collect();
+/// let subsequence = longest_common_subsequence(&a, &b);
+/// assert_eq!(subsequence.len(), 4);
+/// assert_eq!(subsequence.iter().collect::<String>(), "BDAB");
+/// ```
+pub fn longest_common_subsequence<T: PartialEq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    // lengths[i][j] is the length of the longest common subsequence of a[i..] and b[j..]
+    let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut subsequence = Vec::with_capacity(lengths[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            subsequence.push(a[i].clone());
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    subsequence
+}
+
+/// A step turning one sequence into another, see edit_distance.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Edit {
+    /// The element at the index of the source is kept.
+    Keep(usize),
+    /// The element at the index of the target is inserted.
+    Insert(usize),
+    /// The element at the index of the source is deleted.
+    Delete(usize),
+    /// The element at the first index of the source is replaced by the element at the second index of the target.
+    Replace(usize, usize),
+}
+
+/// Computes the [Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance) from source to target,
+/// the fewest insertions, deletions and replacements of single elements turning the source into the target,
+/// together with the edits doing so, from the first element to the last.
+///
+/// Takes O(source * target) time and memory.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::dp::*;
+///
+/// let (distance, edits) = edit_distance(b"kitten", b"sitting");
+/// assert_eq!(distance, 3);
+/// assert_eq!(edits[0], Edit::Replace(0, 0));
+/// assert_eq!(edits.last(), Some(&Edit::Insert(6)));
+/// assert_eq!(edits.iter().filter(|edit| !matches!(edit, Edit::Keep(_))).count(), 3);
+/// ```
+pub fn edit_distance<T: PartialEq>(source: &[T], target: &[T]) -> (usize, Vec<Edit>) {
+    // distances[i][j] is the distance from source[i..] to target[j..]
+    let mut distances = vec![vec![0; target.len() + 1]; source.len() + 1];
+    for i in (0..=source.len()).rev() {
+        for j in (0..=target.len()).rev() {
+            distances[i][j] = if i == source.len() {
+                target.len() - j
+            } else if j == target.len() {
+                source.len() - i
+            } else if source[i] == target[j] {
+                distances[i + 1][j + 1]
+            } else {
+                1 + distances[i + 1][j + 1].min(distances[i + 1][j]).min(distances[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < source.len() || j < target.len() {
+        let distance = distances[i][j];
+        if i < source.len() && j < target.len() && source[i] == target[j] && distance == distances[i + 1][j + 1] {
+            edits.push(Edit::Keep(i));
+            i += 1;
+            j += 1;
+        } else if i < source.len() && j < target.len() && distance == distances[i + 1][j + 1] + 1 {
+            edits.push(Edit::Replace(i, j));
+            i += 1;
+            j += 1;
+        } else if i < source.len() && distance == distances[i + 1][j] + 1 {
+            edits.push(Edit::Delete(i));
+            i += 1;
+        } else {
+            edits.push(Edit::Insert(j));
+            j += 1;
+        }
+    }
+    (distances[0][0], edits)
+}
+
+#[cfg(test)]
+mod dp_test {
+    use super::*;
+    use crate::util::{RandomSource, SplitMix64};
+
+    /// Tries all subsets of the items.
+    fn brute_force_knapsack(items: &[(usize, usize)], capacity: usize) -> usize {
+        (0..1usize << items.len())
+            .filter_map(|subset| {
+                let picked = items.iter().enumerate().filter(|(index, _)| subset & (1 << index) != 0);
+                let (weight, value) = picked.fold((0, 0), |(weight, value), (_, item)| (weight + item.0, value + item.1));
+                if weight <= capacity {
+                    Some(value)
+                } else {
+                    None
+                }
+            })
+            .max()
+            .unwrap()
+    }
+
+    fn is_subsequence(subsequence: &[u8], sequence: &[u8]) -> bool {
+        let mut elements = sequence.iter();
+        subsequence.iter().all(|element| elements.any(|other| other == element))
+    }
+
+    #[test]
+    fn knapsacks() {
+        let mut rng = SplitMix64::new(6);
+        for _ in 0..50 {
+            let items: Vec<(usize, usize)> = (0..rng.random_index(10)).map(|_| (rng.random_index(15), rng.random_index(30))).collect();
+            let capacity = rng.random_index(40);
+            let result = knapsack(&items, capacity);
+            assert_eq!(result.value, brute_force_knapsack(&items, capacity));
+            assert_eq!(result.value, result.items.iter().map(|index| items[*index].1).sum::<usize>());
+            assert_eq!(result.weight, result.items.iter().map(|index| items[*index].0).sum::<usize>());
+            assert!(result.weight <= capacity);
+        }
+        assert_eq!(knapsack(&[], 10), Knapsack { value: 0, weight: 0, items: vec![] });
+        assert_eq!(knapsack(&[(5, 10)], 4).items, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn subsequences() {
+        let mut rng = SplitMix64::new(11);
+        for _ in 0..50 {
+            let a: Vec<u8> = (0..rng.random_index(12)).map(|_| b"abc"[rng.random_index(3)]).collect();
+            let b: Vec<u8> = (0..rng.random_index(12)).map(|_| b"abc"[rng.random_index(3)]).collect();
+            let subsequence = longest_common_subsequence(&a, &b);
+            assert!(is_subsequence(&subsequence, &a) && is_subsequence(&subsequence, &b));
+            // no common subsequence is longer, checked by brute force over the subsequences of a
+            let longest = (0..1usize << a.len())
+                .map(|subset| a.iter().enumerate().filter(|(index, _)| subset & (1 << index) != 0).map(|(_, element)| *element).collect::<Vec<u8>>())
+                .filter(|candidate| is_subsequence(candidate, &b))
+                .map(|candidate| candidate.len())
+                .max()
+                .unwrap();
+            assert_eq!(subsequence.len(), longest);
+        }
+        assert!(longest_common_subsequence::<u8>(b"abc", b"").is_empty());
+    }
+
+    #[test]
+    fn edits() {
+        let mut rng = SplitMix64::new(12);
+        for _ in 0..100 {
+            let source: Vec<u8> = (0..rng.random_index(8)).map(|_| b"ab"[rng.random_index(2)]).collect();
+            let target: Vec<u8> = (0..rng.random_index(8)).map(|_| b"ab"[rng.random_index(2)]).collect();
+            let (distance, edits) = edit_distance(&source, &target);
+            // applying the edits to the source gives the target
+            let mut result = Vec::new();
+            for edit in &edits {
+                match *edit {
+                    Edit::Keep(i) => result.push(source[i]),
+                    Edit::Insert(j) | Edit::Replace(_, j) => result.push(target[j]),
+                    Edit::Delete(_) => {}
+                }
+            }
+            assert_eq!(result, target);
+            assert_eq!(edits.iter().filter(|edit| !matches!(edit, Edit::Keep(_))).count(), distance);
+            assert_eq!(edit_distance(&target, &source).0, distance);
+        }
+        assert_eq!(edit_distance(b"", b"abc"), (3, vec![Edit::Insert(0), Edit::Insert(1), Edit::Insert(2)]));
+        assert_eq!(edit_distance(b"same", b"same").0, 0);
+        assert_eq!(edit_distance(b"flaw", b"lawn").0, 2);
+    }
+}
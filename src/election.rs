@@ -0,0 +1,277 @@
+use crate::graph::Graph;
+use crate::simulation::Engine;
+
+/// The result of a leader election.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ElectionResult {
+    /// The id of the elected leader, or None if no leader could be elected.
+    pub leader: Option<isize>,
+    /// The number of messages which have been sent to elect the leader.
+    /// Announcing the leader afterwards is not counted.
+    pub messages: usize,
+}
+
+/// Elects the node with the highest id in a unidirectional ring using the
+/// [Chang and Roberts algorithm](https://en.wikipedia.org/wiki/Chang_and_Roberts_algorithm).
+///
+/// The ring is given as the ids of its nodes in the order messages travel, the last node sends to the first one.
+/// Every node sends its id to the next node. A node forwards ids higher than its own and swallows lower ones.
+/// The node whose id travels around the whole ring is the leader.
+/// This needs O(n log n) messages on average and O(n²) in the worst case.
+/// The ids have to be unique.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::election;
+///
+/// let result = election::chang_roberts(&[1, 2, 3]);
+/// assert_eq!(result.leader, Some(3));
+/// assert_eq!(result.messages, 5);
+///
+/// let result = election::chang_roberts(&[3, 2, 1]);
+/// assert_eq!(result.leader, Some(3));
+/// assert_eq!(result.messages, 6);
+/// ```
+pub fn chang_roberts(ring: &[isize]) -> ElectionResult {
+    let mut result = ElectionResult::default();
+    let len = ring.len();
+    for (start, &candidate) in ring.iter().enumerate() {
+        // follow the message of the candidate until it is swallowed or returns
+        for hop in 1..=len {
+            result.messages += 1;
+            let receiver = ring[(start + hop) % len];
+            if receiver == candidate {
+                result.leader = Some(candidate);
+                break;
+            }
+            if receiver > candidate {
+                break;
+            }
+        }
+    }
+    result
+}
+
+/// Elects the node with the highest id in a bidirectional ring using the
+/// [Hirschberg and Sinclair algorithm](https://en.wikipedia.org/wiki/Leader_election#Hirschberg-Sinclair_algorithm).
+///
+/// The ring is given as the ids of its nodes in order, the last node is the neighbour of the first one.
+/// In phase k, every remaining candidate sends its id 2^k hops in both directions.
+/// Nodes with a higher id swallow the message, otherwise it is forwarded and finally returned to the candidate.
+/// Only candidates whose messages returned from both directions take part in the next phase.
+/// The candidate whose message travels around the whole ring is the leader.
+/// This needs O(n log n) messages in the worst case.
+/// The ids have to be unique.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::election;
+///
+/// let result = election::hirschberg_sinclair(&[4, 1, 3, 2]);
+/// assert_eq!(result.leader, Some(4));
+/// ```
+pub fn hirschberg_sinclair(ring: &[isize]) -> ElectionResult {
+    let mut result = ElectionResult::default();
+    let len = ring.len();
+    let mut candidates: Vec<usize> = (0..len).collect();
+    let mut distance = 1;
+    while result.leader.is_none() && !candidates.is_empty() {
+        let mut next_candidates = Vec::new();
+        for &start in &candidates {
+            let candidate = ring[start];
+            let mut returned = 0;
+            for &forward in &[true, false] {
+                for hop in 1..=distance {
+                    result.messages += 1;
+                    let offset = hop % len;
+                    let position = if forward { (start + offset) % len } else { (start + len - offset) % len };
+                    let receiver = ring[position];
+                    if receiver == candidate {
+                        result.leader = Some(candidate);
+                        return result;
+                    }
+                    if receiver > candidate {
+                        break;
+                    }
+                    if hop == distance {
+                        // the reply travels back the same way
+                        result.messages += distance;
+                        returned += 1;
+                    }
+                }
+            }
+            if returned == 2 {
+                next_candidates.push(start);
+            }
+        }
+        candidates = next_candidates;
+        distance *= 2;
+    }
+    result
+}
+
+/// A leader election on an arbitrary connected graph, where every node floods the highest id it knows of.
+///
+/// Every node starts knowing only its own id. When a node is activated, it sends the highest id it knows
+/// to all its neighbours, which remember it if it is higher than theirs.
+/// Once the election has converged, all nodes of a connected graph know the highest id, which is the leader.
+/// The election can be driven by the functions of the simulation module.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::election::*;
+/// use rust_algorithms::simulation;
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node(Node::new("A"));
+/// let b = graph.add_node(Node::new("B"));
+/// let c = graph.add_node(Node::new("C"));
+/// graph.add_link(Link::new((a, b), 1));
+/// graph.add_link(Link::new((b, c), 1));
+///
+/// let mut election = FloodingElection::from_graph(&graph);
+/// simulation::run_rounds(&mut election, 10);
+/// assert_eq!(election.result(), ElectionResult { leader: Some(c), messages: 8 });
+/// ```
+#[derive(Clone, Debug)]
+pub struct FloodingElection {
+    /// The id of every node together with the highest id it knows of.
+    known: Vec<(isize, isize)>,
+    links: Vec<(usize, usize)>,
+    messages: usize,
+}
+
+impl FloodingElection {
+    /// Creates an election between all nodes of the graph.
+    pub fn from_graph(graph: &Graph) -> Self {
+        let known: Vec<(isize, isize)> = graph.nodes().iter().map(|node| (node.id, node.id)).collect();
+        let index_of = |id: isize| known.iter().position(|&(node_id, _)| node_id == id);
+        let links = graph
+            .links()
+            .iter()
+            .filter_map(|link| match (index_of(link.members.0), index_of(link.members.1)) {
+                (Some(a), Some(b)) if a != b => Some((a, b)),
+                _ => None,
+            })
+            .collect();
+        FloodingElection { known, links, messages: 0 }
+    }
+
+    /// Returns the highest id the node knows of, or None if the node is not part of the election.
+    pub fn leader_of(&self, node_id: isize) -> Option<isize> {
+        self.known.iter().find(|&&(id, _)| id == node_id).map(|&(_, leader)| leader)
+    }
+
+    /// Returns the current result of the election.
+    /// There is only a leader if all nodes agree on it, which is not the case before the election has converged,
+    /// or if the graph is not connected.
+    pub fn result(&self) -> ElectionResult {
+        let mut leaders = self.known.iter().map(|&(_, leader)| leader);
+        let leader = leaders.next().filter(|first| leaders.all(|leader| leader == *first));
+        ElectionResult { leader, messages: self.messages }
+    }
+}
+
+impl Engine for FloodingElection {
+    fn node_ids(&self) -> Vec<isize> {
+        self.known.iter().map(|&(id, _)| id).collect()
+    }
+
+    fn activate(&mut self, node_id: isize) {
+        let sender = match self.known.iter().position(|&(id, _)| id == node_id) {
+            Some(index) => index,
+            None => return,
+        };
+        let highest = self.known[sender].1;
+        for &(a, b) in &self.links {
+            let receiver = if a == sender {
+                b
+            } else if b == sender {
+                a
+            } else {
+                continue;
+            };
+            self.messages += 1;
+            if highest > self.known[receiver].1 {
+                self.known[receiver].1 = highest;
+            }
+        }
+    }
+
+    fn converged(&self) -> bool {
+        self.links.iter().all(|&(a, b)| self.known[a].1 == self.known[b].1)
+    }
+}
+
+#[cfg(test)]
+mod election_test {
+    use super::*;
+    use crate::graph::{Link, Node};
+    use crate::simulation;
+
+    #[test]
+    fn chang_roberts_message_counts() {
+        let ascending: Vec<isize> = (1..=8).collect();
+        let descending: Vec<isize> = (1..=8).rev().collect();
+        assert_eq!(chang_roberts(&ascending), ElectionResult { leader: Some(8), messages: 15 });
+        assert_eq!(chang_roberts(&descending), ElectionResult { leader: Some(8), messages: 36 });
+        assert_eq!(chang_roberts(&[5, 9, 2, 7]).leader, Some(9));
+        assert_eq!(chang_roberts(&[4]), ElectionResult { leader: Some(4), messages: 1 });
+        assert_eq!(chang_roberts(&[]), ElectionResult::default());
+    }
+
+    #[test]
+    fn hirschberg_sinclair_elects_highest() {
+        assert_eq!(hirschberg_sinclair(&[5, 9, 2, 7]).leader, Some(9));
+        assert_eq!(hirschberg_sinclair(&[4]), ElectionResult { leader: Some(4), messages: 1 });
+        assert_eq!(hirschberg_sinclair(&[]), ElectionResult::default());
+        // phase 0: 1 is swallowed in both directions (2 messages), 2 gets both replies (4 messages).
+        // phase 1: the message of 2 reaches itself after 2 hops.
+        assert_eq!(hirschberg_sinclair(&[1, 2]), ElectionResult { leader: Some(2), messages: 8 });
+    }
+
+    #[test]
+    fn hirschberg_sinclair_beats_chang_roberts_in_worst_case() {
+        let descending: Vec<isize> = (1..=64).rev().collect();
+        let chang_roberts = chang_roberts(&descending);
+        let hirschberg_sinclair = hirschberg_sinclair(&descending);
+        assert_eq!(hirschberg_sinclair.leader, Some(64));
+        assert!(hirschberg_sinclair.messages < chang_roberts.messages);
+    }
+
+    #[test]
+    fn flooding_election() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for name in ["A", "B", "C", "D", "E"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        graph.add_link(Link::new((2, 3), 1));
+        graph.add_link(Link::new((3, 0), 1));
+        graph.add_link(Link::new((4, 1), 1));
+        graph.add_link(Link::new((4, 9), 1));
+        let mut election = FloodingElection::from_graph(&graph);
+        assert_eq!(election.result().leader, None);
+        simulation::run_random(&mut election, 5, None, &mut crate::util::SplitMix64::new(5));
+        assert_eq!(election.result().leader, Some(4));
+        assert_eq!(election.leader_of(2), Some(4));
+        assert_eq!(election.leader_of(9), None);
+    }
+
+    #[test]
+    fn flooding_election_on_disconnected_graph() {
+        let mut graph = Graph::new();
+        for name in ["A", "B", "C"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        let mut election = FloodingElection::from_graph(&graph);
+        simulation::run_rounds(&mut election, 10);
+        assert_eq!(election.result().leader, None);
+        assert_eq!(election.leader_of(0), Some(1));
+        assert_eq!(election.leader_of(2), Some(2));
+    }
+}
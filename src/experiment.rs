@@ -0,0 +1,370 @@
+//! A harness for reproducible experiments: a scenario declares as plain data how the network is generated, how it changes
+//! from round to round, which algorithm runs on it and what is measured, and run plays it through and reports the results.
+//!
+//! Every random choice is drawn from the random number generator given to run, so the same scenario and seed always give
+//! the same report, and scenarios can be compared by changing one part at a time.
+
+use crate::community;
+use crate::dijkstra::dijkstra_search_node;
+use crate::generate::{self, TopologySpec};
+use crate::graph::{Graph, Link, Node};
+use crate::mst::minimum_spanning_tree;
+use crate::util::RandomSource;
+use std::convert::TryFrom;
+use std::ops::Range;
+
+/// How the network of a scenario is generated, with the functions of the generate module of the same name.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Generator {
+    Topology { spec: TopologySpec, cost: usize },
+    ErdosRenyi { nodes: usize, probability: f64, costs: Range<usize> },
+    Connected { nodes: usize, degree: usize, costs: Range<usize> },
+    FatTree { k: usize },
+}
+
+/// A change made to the network at the start of every round after the first one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    /// Links between the given number of random pairs of nodes which are not linked yet, or all of them if there are fewer.
+    AddLinks { count: usize, costs: Range<usize> },
+    /// Removes the given number of random links, or all of them if there are fewer.
+    FailLinks { count: usize },
+    /// The given number of new nodes, each linked to the given number of random nodes which were already there.
+    AddNodes { count: usize, degree: usize, costs: Range<usize> },
+}
+
+/// The algorithm run on the network in every round.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Dijkstra's algorithm between the given number of random pairs of nodes.
+    ShortestPaths { pairs: usize },
+    /// The minimum spanning tree, a forest if the network is not connected.
+    SpanningTree,
+    /// Communities found by label propagation.
+    Communities,
+}
+
+/// A value measured in every round.
+///
+/// The values of the network are always measured, the others only if the algorithm of the scenario produces them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Metric {
+    Nodes,
+    Links,
+    /// The number of connected parts of the network.
+    Components,
+    MeanDegree,
+    /// The share of the pairs of a ShortestPaths run which have a path.
+    Routed,
+    /// The average cost of the paths found by a ShortestPaths run, None if there are none.
+    MeanPathCost,
+    /// The total cost of the links of the SpanningTree.
+    TreeCost,
+    /// The number of Communities.
+    Communities,
+    /// The modularity of the Communities.
+    Modularity,
+}
+
+/// An experiment, declared as data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scenario {
+    pub name: String,
+    pub generator: Generator,
+    /// The number of rounds after the first one, each starting with all changes in the given order.
+    pub rounds: usize,
+    pub changes: Vec<Change>,
+    pub algorithm: Algorithm,
+    pub metrics: Vec<Metric>,
+}
+
+/// The values measured in one round, in the order of the metrics of the scenario.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Round {
+    /// The number of the round, 0 for the network as generated.
+    pub round: usize,
+    /// The value of every metric, None if it is not produced by the algorithm or not defined in this round.
+    pub values: Vec<Option<f64>>,
+}
+
+/// The results of run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExperimentReport {
+    pub name: String,
+    pub metrics: Vec<Metric>,
+    /// Every round, starting with the network as generated.
+    pub rounds: Vec<Round>,
+}
+
+impl Scenario {
+    /// Creates a scenario which runs the algorithm once on the generated network, without any metrics.
+    pub fn new(name: &str, generator: Generator, algorithm: Algorithm) -> Self {
+        Scenario {
+            name: name.to_string(),
+            generator,
+            rounds: 0,
+            changes: Vec::new(),
+            algorithm,
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Sets the number of rounds after the first one.
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Adds a change made at the start of every round after the first one.
+    pub fn change(mut self, change: Change) -> Self {
+        self.changes.push(change);
+        self
+    }
+
+    /// Adds a metric to measure in every round.
+    pub fn metric(mut self, metric: Metric) -> Self {
+        self.metrics.push(metric);
+        self
+    }
+}
+
+impl ExperimentReport {
+    /// Returns the values of the metric over all rounds, or None if the scenario did not measure it.
+    pub fn series(&self, metric: Metric) -> Option<Vec<Option<f64>>> {
+        let index = self.metrics.iter().position(|measured| *measured == metric)?;
+        Some(self.rounds.iter().map(|round| round.values[index]).collect())
+    }
+}
+
+/// Plays the scenario through: generates the network and runs the algorithm on it, then makes the changes and runs
+/// the algorithm again for every further round, measuring the metrics each time.
+///
+/// # Panics
+/// Panics if a range of costs the scenario draws from is empty.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::experiment::*;
+/// use rust_algorithms::generate::TopologySpec;
+/// use rust_algorithms::util::SplitMix64;
+///
+/// // a ring losing a link in every round
+/// let scenario = Scenario::new("ring failures", Generator::Topology { spec: TopologySpec::Ring { nodes: 8 }, cost: 1 },
+///     Algorithm::SpanningTree)
+///     .rounds(3)
+///     .change(Change::FailLinks { count: 1 })
+///     .metric(Metric::Links)
+///     .metric(Metric::Components)
+///     .metric(Metric::TreeCost)
+///     .metric(Metric::Routed);
+///
+/// let report = run(&scenario, &mut SplitMix64::new(1));
+/// assert_eq!(report.rounds.len(), 4);
+/// assert_eq!(report.series(Metric::Links).unwrap(), vec![Some(8.0), Some(7.0), Some(6.0), Some(5.0)]);
+/// assert_eq!(report.series(Metric::Components).unwrap(), vec![Some(1.0), Some(1.0), Some(2.0), Some(3.0)]);
+/// assert_eq!(report.series(Metric::TreeCost).unwrap(), vec![Some(7.0), Some(7.0), Some(6.0), Some(5.0)]);
+/// // no shortest paths are searched
+/// assert_eq!(report.rounds[0].values[3], None);
+/// assert_eq!(report, run(&scenario, &mut SplitMix64::new(1)));
+/// ```
+pub fn run<R>(scenario: &Scenario, rng: &mut R) -> ExperimentReport
+where
+    R: RandomSource + ?Sized,
+{
+    let mut graph = match &scenario.generator {
+        Generator::Topology { spec, cost } => generate::topology(spec, *cost),
+        Generator::ErdosRenyi { nodes, probability, costs } => generate::erdos_renyi(*nodes, *probability, costs.clone(), rng),
+        Generator::Connected { nodes, degree, costs } => generate::connected(*nodes, *degree, costs.clone(), rng),
+        Generator::FatTree { k } => generate::fat_tree(*k),
+    };
+    let mut rounds = Vec::with_capacity(scenario.rounds + 1);
+    for round in 0..=scenario.rounds {
+        if round > 0 {
+            for change in &scenario.changes {
+                graph = apply(graph, change, rng);
+            }
+        }
+        let values = measure(&graph, scenario.algorithm, &scenario.metrics, rng);
+        rounds.push(Round { round, values });
+    }
+    ExperimentReport {
+        name: scenario.name.clone(),
+        metrics: scenario.metrics.clone(),
+        rounds,
+    }
+}
+
+/// Makes the change to the graph, keeping the tags of the nodes and of the links which are left.
+fn apply<R>(mut graph: Graph, change: &Change, rng: &mut R) -> Graph
+where
+    R: RandomSource + ?Sized,
+{
+    match change {
+        Change::AddLinks { count, costs } => {
+            let nodes = graph.nodes().len() as isize;
+            let mut missing: Vec<(isize, isize)> = (0..nodes)
+                .flat_map(|a| (a + 1..nodes).map(move |b| (a, b)))
+                .filter(|(a, b)| !graph.contains_link(*a, *b))
+                .collect();
+            for _ in 0..(*count).min(missing.len()) {
+                let members = missing.swap_remove(rng.random_index(missing.len()));
+                graph.add_link(Link::new(members, rng.random_range(costs.clone())));
+            }
+            graph
+        }
+        Change::FailLinks { count } => {
+            let mut links = graph.links().to_vec();
+            for _ in 0..(*count).min(links.len()) {
+                links.swap_remove(rng.random_index(links.len()));
+            }
+            let mut failed = Graph::from_parts(graph.nodes().to_vec(), Vec::new());
+            for link in links {
+                failed.add_link(link);
+                for tag in graph.link_tags(link.members.0, link.members.1) {
+                    failed.tag_link(link.members.0, link.members.1, tag);
+                }
+            }
+            for node in graph.nodes() {
+                for tag in graph.node_tags(node.id) {
+                    failed.tag_node(node.id, tag);
+                }
+            }
+            failed
+        }
+        Change::AddNodes { count, degree, costs } => {
+            for _ in 0..*count {
+                let existing = graph.nodes().len();
+                // names of their own, as nodes of the same name are only added once
+                let name = format!("added-{}", existing);
+                let id = graph.add_node(Node::new(Box::leak(name.into_boxed_str())));
+                let mut candidates: Vec<usize> = (0..existing).collect();
+                for _ in 0..(*degree).min(existing) {
+                    let other = candidates.swap_remove(rng.random_index(candidates.len()));
+                    graph.add_link(Link::new((isize::try_from(other).unwrap(), id), rng.random_range(costs.clone())));
+                }
+            }
+            graph
+        }
+    }
+}
+
+/// Runs the algorithm on the graph and returns the value of every metric.
+fn measure<R>(graph: &Graph, algorithm: Algorithm, metrics: &[Metric], rng: &mut R) -> Vec<Option<f64>>
+where
+    R: RandomSource + ?Sized,
+{
+    let summary = graph.summary();
+    let mut routed = None;
+    let mut mean_path_cost = None;
+    let mut tree_cost = None;
+    let mut communities = None;
+    let mut modularity = None;
+    match algorithm {
+        Algorithm::ShortestPaths { pairs } => {
+            let nodes = graph.nodes().len();
+            if nodes > 0 && pairs > 0 {
+                let costs: Vec<usize> = (0..pairs)
+                    .filter_map(|_| {
+                        let (a, b) = (rng.random_index(nodes), rng.random_index(nodes));
+                        dijkstra_search_node(graph, a as isize, b as isize).map(|result| result.cost)
+                    })
+                    .collect();
+                routed = Some(costs.len() as f64 / pairs as f64);
+                if !costs.is_empty() {
+                    mean_path_cost = Some(costs.iter().sum::<usize>() as f64 / costs.len() as f64);
+                }
+            }
+        }
+        Algorithm::SpanningTree => {
+            tree_cost = Some(minimum_spanning_tree(graph).iter().map(|link| link.cost).sum::<usize>() as f64);
+        }
+        Algorithm::Communities => {
+            let labels = community::label_propagation(graph, rng);
+            let mut distinct = labels.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            communities = Some(distinct.len() as f64);
+            modularity = Some(community::modularity(graph, &labels));
+        }
+    }
+    metrics
+        .iter()
+        .map(|metric| match metric {
+            Metric::Nodes => Some(summary.nodes as f64),
+            Metric::Links => Some(summary.links as f64),
+            Metric::Components => Some(summary.components as f64),
+            Metric::MeanDegree => Some(summary.avg_degree),
+            Metric::Routed => routed,
+            Metric::MeanPathCost => mean_path_cost,
+            Metric::TreeCost => tree_cost,
+            Metric::Communities => communities,
+            Metric::Modularity => modularity,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod experiment_test {
+    use super::*;
+    use crate::util::SplitMix64;
+
+    #[test]
+    fn growing_network() {
+        let scenario = Scenario::new("growth", Generator::Connected { nodes: 20, degree: 2, costs: 1..5 },
+            Algorithm::ShortestPaths { pairs: 30 })
+            .rounds(5)
+            .change(Change::AddNodes { count: 2, degree: 3, costs: 1..5 })
+            .change(Change::AddLinks { count: 4, costs: 1..2 })
+            .metric(Metric::Nodes)
+            .metric(Metric::Links)
+            .metric(Metric::Components)
+            .metric(Metric::Routed)
+            .metric(Metric::MeanPathCost)
+            .metric(Metric::Modularity);
+        let report = run(&scenario, &mut SplitMix64::new(5));
+        assert_eq!(report.name, "growth");
+        assert_eq!(report.rounds.iter().map(|round| round.round).collect::<Vec<usize>>(), (0..6).collect::<Vec<usize>>());
+        let nodes: Vec<Option<f64>> = (0..6).map(|round| Some(20.0 + 2.0 * round as f64)).collect();
+        assert_eq!(report.series(Metric::Nodes).unwrap(), nodes);
+        let links: Vec<Option<f64>> = (0..6).map(|round| Some(20.0 + 10.0 * round as f64)).collect();
+        assert_eq!(report.series(Metric::Links).unwrap(), links);
+        // the network stays connected, so every pair is routed
+        assert!(report.series(Metric::Components).unwrap().iter().all(|components| *components == Some(1.0)));
+        assert!(report.series(Metric::Routed).unwrap().iter().all(|routed| *routed == Some(1.0)));
+        assert!(report.series(Metric::MeanPathCost).unwrap().iter().all(|cost| cost.is_some()));
+        assert!(report.series(Metric::Modularity).unwrap().iter().all(|modularity| modularity.is_none()));
+        assert_eq!(report.series(Metric::TreeCost), None);
+    }
+
+    #[test]
+    fn changes() {
+        let mut rng = SplitMix64::new(8);
+        let mut graph = generate::fat_tree(4);
+        graph.tag_link(0, 4, "uplink");
+        let links = graph.links().len();
+        let failed = apply(graph.clone(), &Change::FailLinks { count: 3 }, &mut rng);
+        assert_eq!(failed.links().len(), links - 3);
+        assert_eq!(failed.nodes_with_tag("core"), graph.nodes_with_tag("core"));
+        assert!(failed.links_with_tag("uplink").len() <= 1);
+        assert!(apply(graph.clone(), &Change::FailLinks { count: 1000 }, &mut rng).links().is_empty());
+
+        let line = generate::topology(&TopologySpec::Line { nodes: 4 }, 1);
+        let complete = apply(line.clone(), &Change::AddLinks { count: 100, costs: 2..3 }, &mut rng);
+        assert_eq!(complete.links().len(), 6);
+        assert!(complete.links()[3..].iter().all(|link| link.cost == 2));
+        let grown = apply(line, &Change::AddNodes { count: 2, degree: 10, costs: 1..2 }, &mut rng);
+        assert_eq!(grown.nodes()[5].name, "added-5");
+        assert_eq!(grown.links().len(), 3 + 4 + 5);
+
+        let empty = Scenario::new("empty", Generator::Topology { spec: TopologySpec::Line { nodes: 0 }, cost: 1 },
+            Algorithm::ShortestPaths { pairs: 5 })
+            .metric(Metric::Routed)
+            .metric(Metric::MeanDegree);
+        assert_eq!(run(&empty, &mut rng).rounds, vec![Round { round: 0, values: vec![None, Some(0.0)] }]);
+        let communities = Scenario::new("communities", Generator::FatTree { k: 4 }, Algorithm::Communities)
+            .metric(Metric::Communities)
+            .metric(Metric::Modularity);
+        let values = &run(&communities, &mut rng).rounds[0].values;
+        assert!(values[0].unwrap() >= 1.0 && values[1].is_some());
+    }
+}
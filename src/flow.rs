@@ -0,0 +1,611 @@
+//! [Network flows](https://en.wikipedia.org/wiki/Flow_network): how much can be sent from one node to another through links
+//! of limited capacity, how to send it as cheaply as possible, and the disjoint paths between two nodes, which keep them
+//! connected if links or nodes fail.
+//!
+//! Links are undirected, so a link can carry its capacity in either direction, but not in both at once.
+//! The capacity of a link is given by a function, so the cost of the links stays free for other purposes, like the price of
+//! sending a unit through them for min_cost_flow.
+//! Links from a node to itself and to nodes outside of the graph are ignored.
+
+use crate::datastructures::{BinaryHeap, Handle};
+use crate::dijkstra::index_of;
+use crate::graph::{Graph, Link, SearchResult};
+use std::collections::VecDeque;
+
+/// A flow through a graph, as returned by max_flow.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Flow {
+    /// The amount sent from the source to the sink.
+    pub value: usize,
+    /// The amount every link carries, in the order of graph.links(): positive from its first member to its second, negative the other way.
+    pub link_flows: Vec<isize>,
+}
+
+/// A flow through a graph together with its cost, as returned by min_cost_flow.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MinCostFlow {
+    pub flow: Flow,
+    /// The total cost of the flow, the sum of the amount every link carries times its cost.
+    pub cost: usize,
+}
+
+/// Which elements disjoint paths may not share.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Disjoint {
+    /// No two paths use the same link, but they may pass through the same nodes.
+    /// Such paths survive the failure of any single link.
+    #[default]
+    Links,
+    /// No two paths pass through the same node, except for both ends, so they share no links either.
+    /// Such paths survive the failure of any single node between both ends.
+    Nodes,
+}
+
+/// Computes the maximum flow from the source to the sink, using the
+/// [Edmonds–Karp algorithm](https://en.wikipedia.org/wiki/Edmonds%E2%80%93Karp_algorithm) in O(nodes · links²) time.
+///
+/// By the max-flow min-cut theorem, the value is also the lowest total capacity of links which separate the sink from the source.
+/// If source and sink are the same or not both part of the graph, nothing flows.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::flow;
+/// use rust_algorithms::graph::*;
+///
+/// let mut graph = Graph::new();
+/// for name in ["Source", "A", "B", "Sink"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// // the costs are the capacities
+/// graph.add_link(Link::new((0, 1), 3));
+/// graph.add_link(Link::new((0, 2), 2));
+/// graph.add_link(Link::new((1, 2), 5));
+/// graph.add_link(Link::new((1, 3), 2));
+/// graph.add_link(Link::new((3, 2), 4));
+///
+/// let flow = flow::max_flow(&graph, 0, 3, |link| link.cost);
+/// assert_eq!(flow.value, 5);
+/// assert_eq!(flow.link_flows, vec![3, 2, 1, 2, -3]);
+/// ```
+pub fn max_flow<F>(graph: &Graph, source: isize, sink: isize, capacity: F) -> Flow
+where
+    F: Fn(&Link) -> usize,
+{
+    let mut network = Network::new(graph.nodes().len());
+    let arcs = network.add_links(graph, false, capacity);
+    let mut value = 0;
+    if let (Some(source), Some(sink)) = (index_of(graph, source), index_of(graph, sink)) {
+        if source != sink {
+            loop {
+                let pushed = network.augment(source, sink, usize::MAX);
+                if pushed == 0 {
+                    break;
+                }
+                value += pushed;
+            }
+        }
+    }
+    let link_flows = arcs.iter().map(|arc| arc.map_or(0, |arc| network.flow(arc))).collect();
+    Flow { value, link_flows }
+}
+
+/// Sends up to amount from the source to the sink as cheaply as possible, where sending a unit through a link costs the
+/// result of the cost function, using [successive shortest paths](https://en.wikipedia.org/wiki/Minimum-cost_flow_problem).
+///
+/// The flow is pushed along the cheapest path with capacity left, one path at a time, found by Dijkstra's algorithm on the
+/// residual network with the costs adjusted by node potentials, in O(amount · links · log nodes) time at most.
+/// Less than amount is sent if the maximum flow is lower, so usize::MAX asks for the cheapest of the maximum flows,
+/// e.g. to spread traffic over links of different latency without overloading any of them.
+/// If source and sink are the same or not both part of the graph, nothing flows.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::flow;
+/// use rust_algorithms::graph::*;
+///
+/// let mut graph = Graph::new();
+/// for name in ["Source", "A", "B", "Sink"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// // the costs are the prices of sending a unit, every link carries at most 1
+/// graph.add_link(Link::new((0, 1), 1));
+/// graph.add_link(Link::new((1, 3), 1));
+/// graph.add_link(Link::new((0, 2), 2));
+/// graph.add_link(Link::new((2, 3), 2));
+/// graph.add_link(Link::new((1, 2), 1));
+///
+/// let cheapest = flow::min_cost_flow(&graph, 0, 3, 1, |_| 1, |link| link.cost);
+/// assert_eq!((cheapest.flow.value, cheapest.cost), (1, 2));
+/// let all = flow::min_cost_flow(&graph, 0, 3, usize::MAX, |_| 1, |link| link.cost);
+/// assert_eq!((all.flow.value, all.cost), (2, 6));
+/// assert_eq!(all.flow.link_flows, vec![1, 1, 1, 1, 0]);
+/// ```
+pub fn min_cost_flow<C, K>(graph: &Graph, source: isize, sink: isize, amount: usize, capacity: C, cost: K) -> MinCostFlow
+where
+    C: Fn(&Link) -> usize,
+    K: Fn(&Link) -> usize,
+{
+    let count = graph.nodes().len();
+    let mut network = Network::new(count);
+    // links carry their flow in one of two arcs, so that both directions cost the same
+    let arcs: Vec<Option<(usize, usize)>> = graph
+        .links()
+        .iter()
+        .enumerate()
+        .map(|(id, link)| match (index_of(graph, link.members.0), index_of(graph, link.members.1)) {
+            (Some(a), Some(b)) if a != b => {
+                let (capacity, cost) = (capacity(link), cost(link) as isize);
+                let forward = network.add_arc(a, b, capacity, 0, Some(id));
+                let backward = network.add_arc(b, a, capacity, 0, Some(id));
+                network.set_cost(forward, cost);
+                network.set_cost(backward, cost);
+                Some((forward, backward))
+            }
+            _ => None,
+        })
+        .collect();
+    let (mut value, mut total) = (0, 0);
+    if let (Some(source), Some(sink)) = (index_of(graph, source), index_of(graph, sink)) {
+        if source != sink {
+            let mut potentials = vec![0; count];
+            while value < amount {
+                let (pushed, path_cost) = network.augment_cheapest(source, sink, amount - value, &mut potentials);
+                if pushed == 0 {
+                    break;
+                }
+                value += pushed;
+                total += pushed as isize * path_cost;
+            }
+        }
+    }
+    let link_flows = arcs
+        .iter()
+        .map(|arcs| arcs.map_or(0, |(forward, backward)| network.flow(forward) - network.flow(backward)))
+        .collect();
+    MinCostFlow { flow: Flow { value, link_flows }, cost: total as usize }
+}
+
+/// Finds up to k paths between a and b which share no links, or no nodes besides a and b, picking as many as possible.
+///
+/// The paths are the augmenting paths of a maximum flow with a capacity of 1 on every link, or on every node as well,
+/// found by breadth-first searches in O(k · (nodes + links)) time. By Menger's theorem, as many paths are found as links
+/// or nodes between a and b have to fail to separate them, if that is at most k.
+/// Every path is a simple path, and they are sorted by their cost. A path from a node to itself consists of that node alone.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::flow::{self, Disjoint};
+/// use rust_algorithms::graph::*;
+///
+/// // two routes between A and D, both passing through B
+/// let mut graph = Graph::new();
+/// for name in ["A", "B", "C", "D", "E"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// for (a, b) in [(0, 1), (1, 3), (0, 2), (2, 1), (1, 4), (4, 3)] {
+///     graph.add_link(Link::new((a, b), 1));
+/// }
+///
+/// let paths = flow::disjoint_paths(&graph, 0, 3, 5, Disjoint::Links);
+/// assert_eq!(paths.len(), 2);
+/// assert_eq!(paths[0].node_ids(), vec![0, 1, 3]);
+/// assert_eq!(paths[1].node_ids(), vec![0, 2, 1, 4, 3]);
+/// assert_eq!(flow::disjoint_paths(&graph, 0, 3, 5, Disjoint::Nodes).len(), 1);
+/// ```
+pub fn disjoint_paths(graph: &Graph, a: isize, b: isize, k: usize, disjoint: Disjoint) -> Vec<SearchResult> {
+    let (source, sink) = match (index_of(graph, a), index_of(graph, b)) {
+        (Some(source), Some(sink)) => (source, sink),
+        _ => return Vec::new(),
+    };
+    if source == sink {
+        return if k > 0 { vec![SearchResult::new().cost(0).links(vec![Link::new((a, a), 0)])] } else { Vec::new() };
+    }
+
+    let count = graph.nodes().len();
+    let (mut network, source, sink) = match disjoint {
+        Disjoint::Links => {
+            let mut network = Network::new(count);
+            network.add_links(graph, false, |_| 1);
+            (network, source, sink)
+        }
+        Disjoint::Nodes => {
+            // every node is split into an entry and an exit, linked with a capacity of 1
+            let mut network = Network::new(2 * count);
+            for index in 0..count {
+                network.add_arc(2 * index, 2 * index + 1, 1, 0, None);
+            }
+            network.add_links(graph, true, |_| 1);
+            (network, 2 * source + 1, 2 * sink)
+        }
+    };
+    let mut found = 0;
+    while found < k && network.augment(source, sink, 1) == 1 {
+        found += 1;
+    }
+
+    let mut paths: Vec<SearchResult> = (0..found).map(|_| network.take_path(graph, source, sink, a)).collect();
+    paths.sort_by_key(|path| path.cost);
+    paths
+}
+
+/// A residual network of arcs stored in pairs, every arc at an even index followed by its reverse.
+struct Network {
+    /// The arcs leaving every node.
+    arcs_of_nodes: Vec<Vec<usize>>,
+    target: Vec<usize>,
+    capacity: Vec<usize>,
+    residual: Vec<usize>,
+    /// The cost of sending a unit along every arc, the negated cost of its pair for a reverse arc.
+    cost: Vec<isize>,
+    /// The index of the link of the graph every arc belongs to.
+    link: Vec<Option<usize>>,
+}
+
+impl Network {
+    fn new(nodes: usize) -> Self {
+        Network {
+            arcs_of_nodes: vec![Vec::new(); nodes],
+            target: Vec::new(),
+            capacity: Vec::new(),
+            residual: Vec::new(),
+            cost: Vec::new(),
+            link: Vec::new(),
+        }
+    }
+
+    /// Adds an arc with the given capacity, and its reverse with the reverse capacity. Returns the index of the arc.
+    fn add_arc(&mut self, from: usize, to: usize, capacity: usize, reverse_capacity: usize, link: Option<usize>) -> usize {
+        let arc = self.target.len();
+        for &(from, to, capacity) in &[(from, to, capacity), (to, from, reverse_capacity)] {
+            self.arcs_of_nodes[from].push(self.target.len());
+            self.target.push(to);
+            self.capacity.push(capacity);
+            self.residual.push(capacity);
+            self.cost.push(0);
+            self.link.push(link);
+        }
+        arc
+    }
+
+    /// Sets the cost of the arc, and the negated cost of its reverse.
+    fn set_cost(&mut self, arc: usize, cost: isize) {
+        self.cost[arc] = cost;
+        self.cost[arc ^ 1] = -cost;
+    }
+
+    /// Adds the arcs of every link of the graph and returns the arc of every link from its first member to its second,
+    /// None for the links which are ignored.
+    ///
+    /// If split is false, the network has a node for every node of the graph, and the link is a pair of arcs which can carry
+    /// its capacity either way. Otherwise every node of the graph has an entry at 2 · index and an exit at 2 · index + 1,
+    /// and the link is an arc from the exit of each member to the entry of the other one.
+    fn add_links<F>(&mut self, graph: &Graph, split: bool, capacity: F) -> Vec<Option<usize>>
+    where
+        F: Fn(&Link) -> usize,
+    {
+        graph
+            .links()
+            .iter()
+            .enumerate()
+            .map(|(id, link)| match (index_of(graph, link.members.0), index_of(graph, link.members.1)) {
+                (Some(a), Some(b)) if a != b => {
+                    let capacity = capacity(link);
+                    if split {
+                        self.add_arc(2 * b + 1, 2 * a, capacity, 0, Some(id));
+                        Some(self.add_arc(2 * a + 1, 2 * b, capacity, 0, Some(id)))
+                    } else {
+                        Some(self.add_arc(a, b, capacity, capacity, Some(id)))
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The flow on the arc, negative if it flows the other way.
+    fn flow(&self, arc: usize) -> isize {
+        self.capacity[arc] as isize - self.residual[arc] as isize
+    }
+
+    /// Pushes as much as possible, but at most limit, along a shortest path with residual capacity. Returns the amount pushed.
+    fn augment(&mut self, source: usize, sink: usize, limit: usize) -> usize {
+        let mut reached_by: Vec<Option<usize>> = vec![None; self.arcs_of_nodes.len()];
+        let mut queue = VecDeque::new();
+        queue.push_back(source);
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                break;
+            }
+            for &arc in &self.arcs_of_nodes[node] {
+                let next = self.target[arc];
+                if self.residual[arc] > 0 && next != source && reached_by[next].is_none() {
+                    reached_by[next] = Some(arc);
+                    queue.push_back(next);
+                }
+            }
+        }
+        self.push_along(&reached_by, sink, limit)
+    }
+
+    /// Pushes as much as possible, but at most limit, along the cheapest path with residual capacity.
+    /// Returns the amount pushed and the cost of a unit along the path.
+    ///
+    /// The costs are reduced by the potentials of the nodes, which keeps them from being negative for Dijkstra's algorithm
+    /// as long as the potentials are the distances of an earlier search. Nodes unreachable from the source stay that way.
+    fn augment_cheapest(&mut self, source: usize, sink: usize, limit: usize, potentials: &mut [isize]) -> (usize, isize) {
+        let count = self.arcs_of_nodes.len();
+        let mut distances: Vec<Option<isize>> = vec![None; count];
+        let mut reached_by: Vec<Option<usize>> = vec![None; count];
+        let mut handles: Vec<Option<Handle>> = vec![None; count];
+        let mut done = vec![false; count];
+        let mut queue = BinaryHeap::with_capacity(count);
+        distances[source] = Some(0);
+        handles[source] = Some(queue.push((0, source)));
+        while let Some((distance, node)) = queue.pop() {
+            done[node] = true;
+            for &arc in &self.arcs_of_nodes[node] {
+                let next = self.target[arc];
+                if self.residual[arc] == 0 || done[next] {
+                    continue;
+                }
+                let reduced = distance + self.cost[arc] + potentials[node] - potentials[next];
+                if distances[next].is_none_or(|known| reduced < known) {
+                    distances[next] = Some(reduced);
+                    reached_by[next] = Some(arc);
+                    match handles[next] {
+                        Some(handle) => {
+                            queue.decrease_key(handle, (reduced, next));
+                        }
+                        None => handles[next] = Some(queue.push((reduced, next))),
+                    }
+                }
+            }
+        }
+        if distances[sink].is_none() {
+            return (0, 0);
+        }
+        for (potential, distance) in potentials.iter_mut().zip(&distances) {
+            *potential += distance.unwrap_or(0);
+        }
+        let mut cost = 0;
+        let mut node = sink;
+        while let Some(arc) = reached_by[node] {
+            cost += self.cost[arc];
+            node = self.target[arc ^ 1];
+        }
+        (self.push_along(&reached_by, sink, limit), cost)
+    }
+
+    /// Pushes as much as possible, but at most limit, along the arcs by which the nodes were reached, back from the sink.
+    /// Returns the amount pushed.
+    fn push_along(&mut self, reached_by: &[Option<usize>], sink: usize, limit: usize) -> usize {
+        if reached_by[sink].is_none() {
+            return 0;
+        }
+        let mut amount = limit;
+        let mut node = sink;
+        while let Some(arc) = reached_by[node] {
+            amount = amount.min(self.residual[arc]);
+            node = self.target[arc ^ 1];
+        }
+        node = sink;
+        while let Some(arc) = reached_by[node] {
+            self.residual[arc] -= amount;
+            self.residual[arc ^ 1] += amount;
+            node = self.target[arc ^ 1];
+        }
+        amount
+    }
+
+    /// Removes one unit of flow along a path from source to sink and returns it as a path of the graph starting at start.
+    fn take_path(&mut self, graph: &Graph, source: usize, sink: usize, start: isize) -> SearchResult {
+        let mut nodes = vec![source];
+        let mut arcs: Vec<usize> = Vec::new();
+        while let Some(&node) = nodes.last() {
+            if node == sink {
+                break;
+            }
+            let arc = *self.arcs_of_nodes[node].iter().find(|arc| self.flow(**arc) > 0).expect("the flow reaches the sink");
+            self.residual[arc] += 1;
+            self.residual[arc ^ 1] -= 1;
+            let next = self.target[arc];
+            match nodes.iter().position(|node| *node == next) {
+                // a cycle of the flow, which is not part of the path
+                Some(position) => {
+                    nodes.truncate(position + 1);
+                    arcs.truncate(position);
+                }
+                None => {
+                    nodes.push(next);
+                    arcs.push(arc);
+                }
+            }
+        }
+        let mut links = vec![Link::new((start, start), 0)];
+        links.extend(arcs.iter().filter_map(|arc| self.link[*arc]).map(|id| graph.links()[id]));
+        let cost = links.iter().map(|link| link.cost).sum();
+        SearchResult::new().cost(cost).links(links)
+    }
+}
+
+#[cfg(test)]
+mod flow_test {
+    use super::*;
+    use crate::dijkstra;
+    use crate::generate;
+    use crate::graph::GraphLike;
+    use crate::util::SplitMix64;
+
+    /// Whether b can be reached from a in the view.
+    fn connected<G: GraphLike>(view: &G, a: isize, b: isize) -> bool {
+        crate::bfs::bfs_search_generic(view, a, b).is_some()
+    }
+
+    /// The lowest total capacity of links separating a from b, by trying every set of nodes on the side of a.
+    fn min_cut(graph: &Graph, a: isize, b: isize) -> usize {
+        let count = graph.nodes().len();
+        (0..1usize << count)
+            .filter(|side| side & (1 << a) != 0 && side & (1 << b) == 0)
+            .map(|side| {
+                let inside = |id: isize| side & (1 << id) != 0;
+                graph.links().iter().filter(|link| inside(link.members.0) != inside(link.members.1)).map(|link| link.cost).sum()
+            })
+            .min()
+            .unwrap()
+    }
+
+    /// The fewest nodes besides a and b whose failure separates them, plus one if they are linked directly.
+    fn min_node_cut(graph: &Graph, a: isize, b: isize) -> usize {
+        let count = graph.nodes().len();
+        let direct = graph.contains_link(a, b) as usize;
+        let cut = (0..1usize << count)
+            .filter(|failed| failed & (1 << a) == 0 && failed & (1 << b) == 0)
+            .filter(|failed| {
+                let view = graph.view(|node| failed & (1 << node.id) == 0, |link| link.members != (a, b) && link.members != (b, a));
+                !connected(&view, a, b)
+            })
+            .map(|failed| failed.count_ones() as usize)
+            .min()
+            .unwrap();
+        cut + direct
+    }
+
+    #[test]
+    fn max_flows() {
+        let mut rng = SplitMix64::new(32);
+        for _ in 0..20 {
+            let graph = generate::erdos_renyi(9, 0.4, 1..10, &mut rng);
+            let flow = max_flow(&graph, 0, 8, |link| link.cost);
+            assert_eq!(flow.value, min_cut(&graph, 0, 8));
+            // within the capacities, and conserved at every node but source and sink
+            let mut balance = [0isize; 9];
+            for (link, amount) in graph.links().iter().zip(&flow.link_flows) {
+                assert!(amount.unsigned_abs() <= link.cost);
+                balance[link.members.0 as usize] -= amount;
+                balance[link.members.1 as usize] += amount;
+            }
+            assert_eq!(balance[0], -(flow.value as isize));
+            assert_eq!(balance[8], flow.value as isize);
+            assert!(balance[1..8].iter().all(|balance| *balance == 0));
+        }
+        let graph = generate::topology(&generate::TopologySpec::Line { nodes: 3 }, 4);
+        assert_eq!(max_flow(&graph, 1, 1, |_| 1).value, 0);
+        assert_eq!(max_flow(&graph, 0, 7, |_| 1), Flow { value: 0, link_flows: vec![0, 0] });
+    }
+
+    /// Whether the residual network of the flow has a cycle of negative cost, which would make the flow cheaper.
+    fn has_negative_cycle(graph: &Graph, flows: &[isize], capacity: impl Fn(&Link) -> usize) -> bool {
+        let mut arcs: Vec<(usize, usize, isize)> = Vec::new();
+        for (link, flow) in graph.links().iter().zip(flows) {
+            let (a, b) = (link.members.0 as usize, link.members.1 as usize);
+            let (from, to, flow) = if *flow >= 0 { (a, b, *flow as usize) } else { (b, a, flow.unsigned_abs()) };
+            let cost = link.cost as isize;
+            if flow < capacity(link) {
+                arcs.push((from, to, cost));
+            }
+            if flow > 0 {
+                arcs.push((to, from, -cost));
+            } else if capacity(link) > 0 {
+                arcs.push((to, from, cost));
+            }
+        }
+        // Bellman-Ford from a virtual node linked to all others
+        let mut distances = vec![0isize; graph.nodes().len()];
+        for _ in 0..graph.nodes().len() {
+            for &(from, to, cost) in &arcs {
+                distances[to] = distances[to].min(distances[from] + cost);
+            }
+        }
+        arcs.iter().any(|&(from, to, cost)| distances[from] + cost < distances[to])
+    }
+
+    #[test]
+    fn min_cost_flows() {
+        let mut rng = SplitMix64::new(34);
+        let capacity = |link: &Link| (link.members.0 + link.members.1) as usize % 3 + 1;
+        for _ in 0..20 {
+            let graph = generate::erdos_renyi(9, 0.4, 1..10, &mut rng);
+            let maximum = max_flow(&graph, 0, 8, capacity).value;
+            for amount in [1, 2, 3, usize::MAX] {
+                let result = min_cost_flow(&graph, 0, 8, amount, capacity, |link| link.cost);
+                assert_eq!(result.flow.value, maximum.min(amount));
+                let mut balance = [0isize; 9];
+                for (link, flow) in graph.links().iter().zip(&result.flow.link_flows) {
+                    assert!(flow.unsigned_abs() <= capacity(link));
+                    balance[link.members.0 as usize] -= flow;
+                    balance[link.members.1 as usize] += flow;
+                }
+                assert_eq!(balance[8], result.flow.value as isize);
+                assert!(balance[1..8].iter().all(|balance| *balance == 0));
+                let cost = graph.links().iter().zip(&result.flow.link_flows).map(|(link, flow)| flow.unsigned_abs() * link.cost);
+                assert_eq!(result.cost, cost.sum::<usize>());
+                assert!(!has_negative_cycle(&graph, &result.flow.link_flows, capacity));
+            }
+            // a single unit takes the cheapest path
+            let single = min_cost_flow(&graph, 0, 8, 1, |_| 1, |link| link.cost);
+            assert_eq!(single.cost, dijkstra::dijkstra_search_node(&graph, 0, 8).map_or(0, |path| path.cost));
+        }
+        let graph = generate::topology(&generate::TopologySpec::Line { nodes: 3 }, 4);
+        let nothing = MinCostFlow { flow: Flow { value: 0, link_flows: vec![0, 0] }, cost: 0 };
+        assert_eq!(min_cost_flow(&graph, 1, 1, 5, |_| 1, |link| link.cost), nothing);
+        assert_eq!(min_cost_flow(&graph, 0, 2, 0, |_| 1, |link| link.cost).flow.value, 0);
+        let result = min_cost_flow(&graph, 2, 0, 5, |_| 3, |link| link.cost);
+        assert_eq!((result.flow.value, result.cost, result.flow.link_flows), (3, 24, vec![-3, -3]));
+    }
+
+    #[test]
+    fn disjoint() {
+        let mut rng = SplitMix64::new(33);
+        for _ in 0..20 {
+            let graph = generate::erdos_renyi(9, 0.4, 1..10, &mut rng);
+            let unit = graph.normalize_costs(crate::graph::Normalization::Clamp { min: 1, max: 1 });
+            for disjoint in [Disjoint::Links, Disjoint::Nodes] {
+                let expected = match disjoint {
+                    Disjoint::Links => min_cut(&unit, 0, 8),
+                    Disjoint::Nodes => min_node_cut(&graph, 0, 8),
+                };
+                let paths = disjoint_paths(&graph, 0, 8, 10, disjoint);
+                assert_eq!(paths.len(), expected);
+                assert_eq!(disjoint_paths(&graph, 0, 8, 1, disjoint).len(), expected.min(1));
+                let mut used_links = Vec::new();
+                let mut used_nodes = Vec::new();
+                for path in &paths {
+                    let ids = path.node_ids();
+                    assert_eq!((ids[0], ids[ids.len() - 1]), (0, 8));
+                    assert_eq!(path.cost, path.links.iter().map(|link| link.cost).sum::<usize>());
+                    for pair in ids.windows(2) {
+                        assert!(graph.contains_link(pair[0], pair[1]));
+                        used_links.push((pair[0].min(pair[1]), pair[0].max(pair[1])));
+                    }
+                    used_nodes.extend_from_slice(&ids[1..ids.len() - 1]);
+                }
+                assert!(paths.windows(2).all(|pair| pair[0].cost <= pair[1].cost));
+                let unique = |items: &mut Vec<(isize, isize)>| {
+                    let len = items.len();
+                    items.sort_unstable();
+                    items.dedup();
+                    items.len() == len
+                };
+                assert!(unique(&mut used_links));
+                if disjoint == Disjoint::Nodes {
+                    let mut nodes: Vec<(isize, isize)> = used_nodes.iter().map(|id| (*id, 0)).collect();
+                    assert!(unique(&mut nodes));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ends() {
+        let graph = generate::topology(&generate::TopologySpec::Line { nodes: 3 }, 4);
+        let paths = disjoint_paths(&graph, 1, 1, 3, Disjoint::Nodes);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].links, vec![Link::new((1, 1), 0)]);
+        assert!(disjoint_paths(&graph, 1, 1, 0, Disjoint::Links).is_empty());
+        assert!(disjoint_paths(&graph, 0, 5, 3, Disjoint::Links).is_empty());
+        let paths = disjoint_paths(&graph, 2, 0, 3, Disjoint::Links);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].node_ids(), vec![2, 1, 0]);
+        assert_eq!(paths[0].cost, 8);
+    }
+}
@@ -0,0 +1,300 @@
+use crate::dijkstra::{path, to_self};
+use crate::graph::{Graph, GraphLike, Link, Node, SearchResult};
+use crate::util::node_index;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// An immutable copy of a graph, laid out for answering many queries on a topology which does not change.
+///
+/// The neighbours of all nodes are stored in a single array, in the [compressed sparse row](https://en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_row_(CSR,_CRS_or_Yale_format)) layout,
+/// and the neighbours of every node are sorted by id. Other than the searches on Graph,
+/// the searches of a frozen graph neither copy the graph nor mark its nodes, so they only need a shared reference
+/// and can run from several threads at once.
+///
+/// Like the searches on Graph, links from a node to itself and links to nodes which are not part of the graph are ignored.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::frozen::FrozenGraph;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node2, node1), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let frozen = FrozenGraph::new(&graph);
+/// assert_eq!(frozen.neighbours(node1).map(|(id, _)| id).collect::<Vec<_>>(), vec![node2, node3]);
+/// assert_eq!(frozen.bfs_search_node(node1, node3).unwrap().cost, 10);
+/// assert_eq!(frozen.dijkstra_search_node(node1, node3).unwrap().cost, 7);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrozenGraph {
+    nodes: Vec<Node>,
+    links: Vec<Link>,
+    /// The neighbours of the node with index i are stored at offsets[i]..offsets[i + 1] of adjacency.
+    offsets: Vec<usize>,
+    /// The index of every neighbour together with the index of the link leading to it.
+    adjacency: Vec<(usize, usize)>,
+}
+
+impl FrozenGraph {
+    /// Creates a frozen copy of the graph.
+    pub fn new(graph: &Graph) -> Self {
+        let nodes = graph.nodes().to_vec();
+        let links = graph.links().to_vec();
+        let index_of = |id: isize| node_index(id, nodes.len());
+
+        let mut pairs: Vec<(usize, usize, usize)> = Vec::with_capacity(links.len() * 2);
+        for (index, link) in links.iter().enumerate() {
+            if let (Some(a), Some(b)) = (index_of(link.members.0), index_of(link.members.1)) {
+                if a != b {
+                    pairs.push((a, b, index));
+                    pairs.push((b, a, index));
+                }
+            }
+        }
+        pairs.sort_unstable();
+
+        let mut offsets = vec![0; nodes.len() + 1];
+        for &(a, _, _) in &pairs {
+            offsets[a + 1] += 1;
+        }
+        for index in 0..nodes.len() {
+            offsets[index + 1] += offsets[index];
+        }
+        let adjacency = pairs.into_iter().map(|(_, b, link)| (b, link)).collect();
+        FrozenGraph { nodes, links, offsets, adjacency }
+    }
+
+    /// Returns all nodes of the graph.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns all links of the graph, including the ones which are ignored by the searches.
+    pub fn links(&self) -> &[Link] {
+        &self.links
+    }
+
+    /// Returns the ids of the neighbours of the node, sorted by id, together with the links leading to them.
+    /// Nodes which are not part of the graph have no neighbours.
+    pub fn neighbours(&self, node_id: isize) -> impl Iterator<Item = (isize, &Link)> + '_ {
+        let range = match self.index_of(node_id) {
+            Some(index) => self.offsets[index]..self.offsets[index + 1],
+            None => 0..0,
+        };
+        self.adjacency[range]
+            .iter()
+            .map(move |&(next, link)| (self.nodes[next].id, &self.links[link]))
+    }
+
+    /// Returns the number of neighbours of the node.
+    pub fn degree(&self, node_id: isize) -> usize {
+        self.index_of(node_id)
+            .map_or(0, |index| self.offsets[index + 1] - self.offsets[index])
+    }
+
+    /// Finds the link between two nodes, in either orientation, using a binary search over the neighbours of the first one.
+    pub fn find_link(&self, a: isize, b: isize) -> Option<&Link> {
+        let a = self.index_of(a)?;
+        let b = self.index_of(b)?;
+        let neighbours = &self.adjacency[self.offsets[a]..self.offsets[a + 1]];
+        let position = neighbours.binary_search_by_key(&b, |&(next, _)| next).ok()?;
+        Some(&self.links[neighbours[position].1])
+    }
+
+    /// Searches for the path with the fewest links, like bfs_search_node.
+    pub fn bfs_search_node(&self, start_node_id: isize, search_node_id: isize) -> Option<SearchResult> {
+        if start_node_id == search_node_id {
+            return Some(to_self(start_node_id));
+        }
+        let start = self.index_of(start_node_id)?;
+        let target = self.index_of(search_node_id)?;
+
+        let mut previous: Vec<Option<(usize, &Link)>> = vec![None; self.nodes.len()];
+        let mut discovered = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        discovered[start] = true;
+        queue.push_back(start);
+        while let Some(current) = queue.pop_front() {
+            if current == target {
+                return Some(self.result(path(&previous, start_node_id, target)));
+            }
+            for &(next, link) in self.adjacent(current) {
+                if !discovered[next] {
+                    discovered[next] = true;
+                    previous[next] = Some((current, &self.links[link]));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Searches for any path, like dfs_search_node.
+    /// The neighbours with the highest ids are visited first, so the path found may differ from the one of dfs_search_node.
+    pub fn dfs_search_node(&self, start_node_id: isize, search_node_id: isize) -> Option<SearchResult> {
+        if start_node_id == search_node_id {
+            return Some(to_self(start_node_id));
+        }
+        let start = self.index_of(start_node_id)?;
+        let target = self.index_of(search_node_id)?;
+
+        let mut previous: Vec<Option<(usize, &Link)>> = vec![None; self.nodes.len()];
+        let mut discovered = vec![false; self.nodes.len()];
+        let mut stack = vec![start];
+        discovered[start] = true;
+        while let Some(current) = stack.pop() {
+            if current == target {
+                return Some(self.result(path(&previous, start_node_id, target)));
+            }
+            for &(next, link) in self.adjacent(current) {
+                if !discovered[next] {
+                    discovered[next] = true;
+                    previous[next] = Some((current, &self.links[link]));
+                    stack.push(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Searches for the cheapest path, like dijkstra_search_node.
+    pub fn dijkstra_search_node(&self, start_node_id: isize, search_node_id: isize) -> Option<SearchResult> {
+        if start_node_id == search_node_id {
+            return Some(to_self(start_node_id));
+        }
+        let start = self.index_of(start_node_id)?;
+        let target = self.index_of(search_node_id)?;
+
+        let mut costs: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut previous: Vec<Option<(usize, &Link)>> = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+        costs[start] = Some(0);
+        queue.push(Reverse((0, start)));
+        while let Some(Reverse((cost, current))) = queue.pop() {
+            if costs[current] != Some(cost) {
+                continue;
+            }
+            if current == target {
+                return Some(SearchResult::new().links(path(&previous, start_node_id, target)).cost(cost));
+            }
+            for &(next, link) in self.adjacent(current) {
+                let link = &self.links[link];
+                let next_cost = cost + link.cost;
+                if costs[next].is_none_or(|known| next_cost < known) {
+                    costs[next] = Some(next_cost);
+                    previous[next] = Some((current, link));
+                    queue.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+        None
+    }
+
+    fn index_of(&self, id: isize) -> Option<usize> {
+        node_index(id, self.nodes.len())
+    }
+
+    fn adjacent(&self, index: usize) -> &[(usize, usize)] {
+        &self.adjacency[self.offsets[index]..self.offsets[index + 1]]
+    }
+
+    fn result(&self, links: Vec<Link>) -> SearchResult {
+        let cost = links.iter().map(|link| link.cost).sum();
+        SearchResult::new().links(links).cost(cost)
+    }
+}
+
+impl From<&Graph> for FrozenGraph {
+    fn from(graph: &Graph) -> Self {
+        FrozenGraph::new(graph)
+    }
+}
+
+impl GraphLike for FrozenGraph {
+    fn neighbors(&self, node_id: isize) -> Vec<(isize, usize)> {
+        self.neighbours(node_id).map(|(id, link)| (id, link.cost)).collect()
+    }
+
+    fn link_cost(&self, a: isize, b: isize) -> Option<usize> {
+        self.find_link(a, b).map(|link| link.cost)
+    }
+}
+
+#[cfg(test)]
+mod frozen_test {
+    use super::*;
+    use crate::{bfs, dijkstra};
+
+    fn graph() -> Graph {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for name in ["A", "B", "C", "D", "E", "F"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 7));
+        graph.add_link(Link::new((0, 2), 9));
+        graph.add_link(Link::new((5, 0), 14));
+        graph.add_link(Link::new((1, 2), 10));
+        graph.add_link(Link::new((3, 1), 15));
+        graph.add_link(Link::new((2, 3), 11));
+        graph.add_link(Link::new((2, 5), 2));
+        graph.add_link(Link::new((3, 4), 6));
+        graph.add_link(Link::new((4, 5), 9));
+        graph.add_link(Link::new((4, 4), 1));
+        graph.add_link(Link::new((4, 8), 1));
+        graph
+    }
+
+    #[test]
+    fn layout() {
+        let frozen = FrozenGraph::new(&graph());
+        assert_eq!(frozen.links().len(), 11);
+        assert_eq!(frozen.degree(0), 3);
+        assert_eq!(frozen.degree(4), 2);
+        assert_eq!(frozen.degree(9), 0);
+        assert_eq!(frozen.neighbours(2).map(|(id, _)| id).collect::<Vec<_>>(), vec![0, 1, 3, 5]);
+        assert_eq!(frozen.neighbours(-1).count(), 0);
+        assert_eq!(frozen.find_link(1, 3), Some(&Link::new((3, 1), 15)));
+        assert_eq!(frozen.find_link(4, 4), None);
+        assert_eq!(frozen.find_link(0, 4), None);
+        assert_eq!(FrozenGraph::new(&Graph::new()).nodes().len(), 0);
+    }
+
+    #[test]
+    fn searches() {
+        let graph = graph();
+        let frozen = FrozenGraph::from(&graph);
+        for start in 0..7 {
+            for target in 0..7 {
+                let cheapest = dijkstra::dijkstra_search_node(&graph, start, target).map(|result| result.cost);
+                assert_eq!(frozen.dijkstra_search_node(start, target).map(|result| result.cost), cheapest);
+                let fewest = bfs::bfs_search_node(graph.clone(), start, target).map(|result| result.links.len());
+                assert_eq!(frozen.bfs_search_node(start, target).map(|result| result.links.len()), fewest);
+                assert_eq!(frozen.dfs_search_node(start, target).is_some(), cheapest.is_some());
+            }
+        }
+        let result = frozen.dfs_search_node(0, 4).unwrap();
+        assert_eq!(result.cost, result.links.iter().map(|link| link.cost).sum::<usize>());
+        assert_eq!(frozen.dijkstra_search_node(0, 4).unwrap().cost, 20);
+    }
+
+    #[test]
+    fn graph_like() {
+        let graph = graph();
+        let frozen = FrozenGraph::new(&graph);
+        for id in -1..8 {
+            let mut neighbors = graph.neighbors(id);
+            neighbors.sort_unstable();
+            assert_eq!(frozen.neighbors(id), neighbors);
+        }
+        assert_eq!(frozen.link_cost(3, 2), Some(11));
+        assert_eq!(frozen.link_cost(3, 5), None);
+        assert_eq!(dijkstra::dijkstra_search_generic(&frozen, 0, 4).unwrap().cost, 20);
+    }
+}
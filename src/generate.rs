@@ -0,0 +1,485 @@
+//! Generators of random graphs, e.g. to test or benchmark the searches and simulations on many different topologies.
+//!
+//! Every generator takes the random number generator as an argument, so passing a seeded one,
+//! like `StdRng::seed_from_u64` or util::SplitMix64, always generates the same graph.
+//! The nodes are named by their index, the costs of the links are picked uniformly from the given range.
+//!
+//! The geometric generators turn points of the plane, like the locations of sites, into a graph instead,
+//! linking nodes by their distance. They are not random, the same points always give the same graph.
+//!
+//! Finally, topology and fat_tree expand templates of common network shapes, like rings or data center fabrics,
+//! so they need not be built by hand.
+
+use crate::geometry::Point;
+use crate::graph::{leak_name, Graph, Link, Node};
+use crate::spanningtree::LinkSpeed;
+use crate::util::{RandomSource, WeightedSampler};
+use std::ops::Range;
+
+/// Generates a random graph in the [Erdős–Rényi model](https://en.wikipedia.org/wiki/Erd%C5%91s%E2%80%93R%C3%A9nyi_model) G(n, p):
+/// every possible link between two different nodes is part of the graph with the given probability.
+///
+/// # Panics
+/// Panics if the range of the costs is empty.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate;
+/// use rust_algorithms::util::SplitMix64;
+///
+/// let graph = generate::erdos_renyi(10, 1.0, 1..5, &mut SplitMix64::new(1));
+/// assert_eq!(graph.links().len(), 45);
+/// assert!(graph.links().iter().all(|link| (1..5).contains(&link.cost)));
+/// ```
+pub fn erdos_renyi<R>(nodes: usize, probability: f64, costs: Range<usize>, rng: &mut R) -> Graph
+where
+    R: RandomSource + ?Sized,
+{
+    let mut graph = with_nodes(nodes);
+    for a in 0..nodes as isize {
+        for b in a + 1..nodes as isize {
+            if rng.random_f64() < probability {
+                graph.add_link(Link::new((a, b), rng.random_range(costs.clone())));
+            }
+        }
+    }
+    graph
+}
+
+/// Generates a random connected graph: a path through all nodes in the order of their ids,
+/// plus random links until the nodes have the given average degree, or as close to it as a graph without duplicate links can get.
+///
+/// # Panics
+/// Panics if the range of the costs is empty.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::{dijkstra, generate};
+/// use rust_algorithms::util::SplitMix64;
+///
+/// let graph = generate::connected(100, 4, 1..10, &mut SplitMix64::new(1));
+/// assert_eq!(graph.links().len(), 200);
+/// assert!(dijkstra::dijkstra_search_node(&graph, 0, 99).is_some());
+/// assert_eq!(graph, generate::connected(100, 4, 1..10, &mut SplitMix64::new(1)));
+/// ```
+pub fn connected<R>(nodes: usize, degree: usize, costs: Range<usize>, rng: &mut R) -> Graph
+where
+    R: RandomSource + ?Sized,
+{
+    let mut graph = with_nodes(nodes);
+    for index in 1..nodes as isize {
+        graph.add_link(Link::new((index - 1, index), rng.random_range(costs.clone())));
+    }
+    let links = (nodes * degree / 2).min(nodes * nodes.saturating_sub(1) / 2);
+    while graph.links().len() < links {
+        let a = rng.random_index(nodes) as isize;
+        let b = rng.random_index(nodes) as isize;
+        if a != b && !graph.contains_link(a, b) {
+            graph.add_link(Link::new((a, b), rng.random_range(costs.clone())));
+        }
+    }
+    graph
+}
+
+/// Generates a random graph in the [Chung–Lu model](https://en.wikipedia.org/wiki/Chung%E2%80%93Lu_model):
+/// both members of every link are picked with a probability proportional to their weight,
+/// so the expected degrees of the nodes are proportional to the weights, e.g. to get a few hubs and many leaves.
+///
+/// Generates the given number of links, unless there are not enough pairs of nodes with weights above 0
+/// and avoiding duplicate links fails too often, in which case the graph has fewer links.
+///
+/// # Panics
+/// Panics if the weights are not valid weights of util::WeightedSampler, or if the range of the costs is empty.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate;
+/// use rust_algorithms::util::SplitMix64;
+///
+/// let mut weights = vec![1.0; 50];
+/// weights[0] = 50.0;
+/// let graph = generate::weighted(&weights, 60, 1..2, &mut SplitMix64::new(1));
+/// assert_eq!(graph.links().len(), 60);
+/// assert!(graph.find_links_from_node(0).len() > graph.find_links_from_node(1).len());
+/// ```
+pub fn weighted<R>(weights: &[f64], links: usize, costs: Range<usize>, rng: &mut R) -> Graph
+where
+    R: RandomSource + ?Sized,
+{
+    let sampler = WeightedSampler::new(weights).expect("the weights need to be valid and at least one above 0");
+    let mut graph = with_nodes(weights.len());
+    let mut attempts = 0;
+    while graph.links().len() < links && attempts < 100 * links {
+        attempts += 1;
+        let a = sampler.sample(rng) as isize;
+        let b = sampler.sample(rng) as isize;
+        if a != b && !graph.contains_link(a, b) {
+            graph.add_link(Link::new((a, b), rng.random_range(costs.clone())));
+        }
+    }
+    graph
+}
+
+/// Generates the [k-nearest neighbour graph](https://en.wikipedia.org/wiki/Nearest_neighbor_graph) of the points:
+/// every point is linked to the k points closest to it, with the id of its node being its index.
+///
+/// A link exists if either member is one of the k nearest of the other, so nodes can have more than k links.
+/// Of points at the same distance, the one with the lower index is nearer. The cost of every link is computed from
+/// the distance of its members by the supplied function, like rounding it to whole kilometres.
+/// Takes O(n² log n) time.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::{dijkstra, generate};
+/// use rust_algorithms::geometry::Point;
+///
+/// let sites = [Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(2.0, 0.1), Point::new(10.0, 0.0)];
+/// let graph = generate::k_nearest(&sites, 1, |distance| (distance * 10.0).round() as usize);
+/// assert_eq!(graph.links().len(), 3);
+/// assert!(graph.contains_link(2, 3));
+/// assert_eq!(dijkstra::dijkstra_search_node(&graph, 0, 3).unwrap().cost, 10 + 10 + 80);
+/// ```
+pub fn k_nearest<F>(points: &[Point], k: usize, cost: F) -> Graph
+where
+    F: Fn(f64) -> usize,
+{
+    let mut graph = with_nodes(points.len());
+    for (index, point) in points.iter().enumerate() {
+        let mut others: Vec<(f64, usize)> = points
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .map(|(other, position)| (point.distance(position), other))
+            .collect();
+        others.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        for &(distance, other) in others.iter().take(k) {
+            let members = (index.min(other) as isize, index.max(other) as isize);
+            graph.add_link(Link::new(members, cost(distance)));
+        }
+    }
+    graph
+}
+
+/// Generates the [unit disk graph](https://en.wikipedia.org/wiki/Unit_disk_graph) of the points:
+/// every pair of points no further than the radius apart is linked, like radios within range of each other.
+///
+/// The id of the node of every point is its index. The cost of every link is computed from the distance of its members
+/// by the supplied function. Takes O(n²) time.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate;
+/// use rust_algorithms::geometry::Point;
+///
+/// let radios = [Point::new(0.0, 0.0), Point::new(3.0, 4.0), Point::new(6.0, 8.0)];
+/// let graph = generate::within_distance(&radios, 5.0, |distance| distance as usize);
+/// assert_eq!(graph.links().len(), 2);
+/// assert!(!graph.contains_link(0, 2));
+/// assert_eq!(graph.links()[0].cost, 5);
+/// ```
+pub fn within_distance<F>(points: &[Point], radius: f64, cost: F) -> Graph
+where
+    F: Fn(f64) -> usize,
+{
+    let mut graph = with_nodes(points.len());
+    for (a, first) in points.iter().enumerate() {
+        for (b, second) in points.iter().enumerate().skip(a + 1) {
+            let distance = first.distance(second);
+            if distance <= radius {
+                graph.add_link(Link::new((a as isize, b as isize), cost(distance)));
+            }
+        }
+    }
+    graph
+}
+
+/// A template of a common network topology, expanded into a graph by topology.
+///
+/// The ids of the nodes are their index, in the order given for every template.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TopologySpec {
+    /// A path through the nodes, in the order of their ids.
+    Line { nodes: usize },
+    /// A path through the nodes in the order of their ids, with the last node linked back to the first one.
+    Ring { nodes: usize },
+    /// A hub, named "hub", linked to each of the leaves, named "leaf-0" and so on.
+    Star { leaves: usize },
+    /// Every pair of nodes linked.
+    FullMesh { nodes: usize },
+    /// Nodes in rows, every node linked to the nodes left, right, above and below of it. The nodes are numbered row by row.
+    Grid { rows: usize, columns: usize },
+    /// The spines, named "spine-0" and so on, then the leaves, named "leaf-0" and so on, with every leaf linked to every spine.
+    SpineLeaf { spines: usize, leaves: usize },
+}
+
+/// Expands the template into a graph, with every link having the given cost.
+///
+/// Nodes are named by their index, unless the template names them by their role.
+/// Templates too small for their shape, like a ring of two nodes, get every link that fits without duplicates or links
+/// from a node to itself.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+///
+/// let fabric = generate::topology(&TopologySpec::SpineLeaf { spines: 2, leaves: 4 }, 10);
+/// assert_eq!(fabric.nodes().len(), 6);
+/// assert_eq!(fabric.links().len(), 8);
+/// assert_eq!(fabric.nodes()[5].name, "leaf-3");
+/// assert_eq!(fabric.find_links_from_node(0).len(), 4);
+///
+/// let ring = generate::topology(&TopologySpec::Ring { nodes: 5 }, 1);
+/// assert!(ring.contains_link(4, 0));
+/// ```
+pub fn topology(spec: &TopologySpec, cost: usize) -> Graph {
+    let mut links: Vec<(usize, usize)> = Vec::new();
+    let mut graph = match *spec {
+        TopologySpec::Line { nodes } | TopologySpec::Ring { nodes } => {
+            links.extend((1..nodes).map(|index| (index - 1, index)));
+            if let TopologySpec::Ring { .. } = spec {
+                if nodes > 2 {
+                    links.push((nodes - 1, 0));
+                }
+            }
+            with_nodes(nodes)
+        }
+        TopologySpec::Star { leaves } => {
+            links.extend((1..=leaves).map(|leaf| (0, leaf)));
+            let names = (0..leaves).map(|leaf| format!("leaf-{}", leaf));
+            with_names(std::iter::once("hub".to_string()).chain(names))
+        }
+        TopologySpec::FullMesh { nodes } => {
+            links.extend((0..nodes).flat_map(|a| (a + 1..nodes).map(move |b| (a, b))));
+            with_nodes(nodes)
+        }
+        TopologySpec::Grid { rows, columns } => {
+            for index in 0..rows * columns {
+                if index % columns + 1 < columns {
+                    links.push((index, index + 1));
+                }
+                if index + columns < rows * columns {
+                    links.push((index, index + columns));
+                }
+            }
+            with_nodes(rows * columns)
+        }
+        TopologySpec::SpineLeaf { spines, leaves } => {
+            links.extend((0..spines).flat_map(|spine| (spines..spines + leaves).map(move |leaf| (spine, leaf))));
+            let names = (0..spines).map(|spine| format!("spine-{}", spine));
+            with_names(names.chain((0..leaves).map(|leaf| format!("leaf-{}", leaf))))
+        }
+    };
+    for (a, b) in links {
+        graph.add_link(Link::new((a as isize, b as isize), cost));
+    }
+    graph
+}
+
+/// Builds the k-ary [fat-tree](https://en.wikipedia.org/wiki/Fat_tree), the Clos topology of many data centers.
+///
+/// There are k pods, each with k/2 edge switches and k/2 aggregation switches linked to all edge switches of their pod.
+/// Every edge switch connects k/2 hosts. Each of the (k/2)² core switches is linked to one aggregation switch of every pod:
+/// the first k/2 core switches to the first aggregation switch of every pod, the next k/2 to the second one, and so on.
+/// That makes k³/4 hosts, each with the same bandwidth to all others.
+///
+/// The nodes are the core switches, named "core-0" and so on, then the aggregation switches, named "agg-<pod>-<index>",
+/// then the edge switches, named "edge-<pod>-<index>", then the hosts, named "host-<pod>-<edge>-<index>".
+/// Each node is tagged with its layer: "core", "aggregation", "edge" or "host".
+/// Links between switches have the STP cost of 10 Gbit/s, links to hosts the one of 1 Gbit/s, see LinkSpeed::to_stp_cost.
+///
+/// # Panics
+/// Panics if k is 0 or odd.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::{dijkstra, generate};
+///
+/// let graph = generate::fat_tree(4);
+/// assert_eq!(graph.nodes().len(), 4 + 8 + 8 + 16);
+/// assert_eq!(graph.links().len(), 48);
+/// assert_eq!(graph.nodes()[20].name, "host-0-0-0");
+/// // hosts of different pods are 6 hops apart: edge, aggregation, core, aggregation, edge
+/// let last = graph.nodes().len() as isize - 1;
+/// assert_eq!(dijkstra::dijkstra_search_node(&graph, 20, last).unwrap().cost, 4 + 2 * 4 + 4);
+/// ```
+pub fn fat_tree(k: usize) -> Graph {
+    assert!(k > 0 && k.is_multiple_of(2), "a fat-tree needs an even number of ports per switch");
+    let half = k / 2;
+    let cores = half * half;
+    // the index of every aggregation switch, edge switch and host by its position in the tree
+    let agg = |pod: usize, index: usize| cores + pod * half + index;
+    let edge = |pod: usize, index: usize| cores + k * half + pod * half + index;
+    let host = |pod: usize, edge: usize, index: usize| cores + 2 * k * half + (pod * half + edge) * half + index;
+
+    let mut names: Vec<String> = (0..cores).map(|core| format!("core-{}", core)).collect();
+    for layer in ["agg", "edge"] {
+        names.extend((0..k).flat_map(|pod| (0..half).map(move |index| format!("{}-{}-{}", layer, pod, index))));
+    }
+    for pod in 0..k {
+        names.extend((0..half).flat_map(|edge| (0..half).map(move |index| format!("host-{}-{}-{}", pod, edge, index))));
+    }
+    let mut graph = with_names(names);
+    let mut id = 0;
+    for (layer, count) in [("core", cores), ("aggregation", k * half), ("edge", k * half), ("host", k * half * half)] {
+        for _ in 0..count {
+            graph.tag_node(id, layer);
+            id += 1;
+        }
+    }
+
+    let fabric = LinkSpeed::TenGigabit.to_stp_cost();
+    let access = LinkSpeed::Gigabit.to_stp_cost();
+    let mut add = |a: usize, b: usize, cost: usize| graph.add_link(Link::new((a as isize, b as isize), cost));
+    for pod in 0..k {
+        for index in 0..half {
+            for core in 0..half {
+                add(index * half + core, agg(pod, index), fabric);
+            }
+            for other in 0..half {
+                add(agg(pod, index), edge(pod, other), fabric);
+                add(edge(pod, index), host(pod, index, other), access);
+            }
+        }
+    }
+    graph
+}
+
+/// Creates a graph without links, with the ids of the nodes being their index.
+fn with_nodes(nodes: usize) -> Graph {
+    with_names((0..nodes).map(|index| index.to_string()))
+}
+
+/// Creates a graph without links with a node of every name, the ids of the nodes being their index.
+/// The names are leaked, see Node::name.
+fn with_names<I: IntoIterator<Item = String>>(names: I) -> Graph {
+    let names = names.into_iter();
+    let mut graph = Graph::with_capacity(names.size_hint().0, names.size_hint().0);
+    for name in names {
+        graph.add_node(Node::new(leak_name(name)));
+    }
+    graph
+}
+
+#[cfg(test)]
+mod generate_test {
+    use super::*;
+    use crate::util::SplitMix64;
+
+    fn grid(size: usize) -> Vec<Point> {
+        (0..size * size).map(|index| Point::new((index % size) as f64, (index / size) as f64)).collect()
+    }
+
+    #[test]
+    fn geometric() {
+        // on a grid, the 2 nearest neighbours of every point are next to it, preferring the ones above and to the left,
+        // so every point is linked to all points next to it
+        let points = grid(5);
+        let graph = k_nearest(&points, 2, |distance| (distance * 100.0) as usize);
+        assert_eq!(graph.links().len(), 40);
+        assert!(graph.links().iter().all(|link| link.cost == 100));
+        assert_eq!(graph.summary().components, 1);
+        assert_eq!(graph, within_distance(&points, 1.0, |distance| (distance * 100.0) as usize));
+        // within the diagonal, nodes inside the grid have 8 neighbours
+        assert_eq!(within_distance(&points, 1.5, |_| 1).find_links_from_node(12).len(), 8);
+
+        assert!(k_nearest(&points, 0, |_| 1).links().is_empty());
+        assert_eq!(k_nearest(&points[..3], 10, |_| 1).links().len(), 3);
+        assert_eq!(k_nearest(&[], 3, |_| 1).nodes().len(), 0);
+    }
+
+    #[test]
+    fn topologies() {
+        let degrees = |graph: &Graph| -> Vec<usize> {
+            (0..graph.nodes().len() as isize).map(|id| graph.find_links_from_node(id).len()).collect()
+        };
+        let line = topology(&TopologySpec::Line { nodes: 4 }, 3);
+        assert_eq!(degrees(&line), vec![1, 2, 2, 1]);
+        assert!(line.links().iter().all(|link| link.cost == 3));
+        assert_eq!(degrees(&topology(&TopologySpec::Ring { nodes: 6 }, 1)), vec![2; 6]);
+        assert_eq!(degrees(&topology(&TopologySpec::Star { leaves: 3 }, 1)), vec![3, 1, 1, 1]);
+        assert_eq!(degrees(&topology(&TopologySpec::FullMesh { nodes: 5 }, 1)), vec![4; 5]);
+        let grid = topology(&TopologySpec::Grid { rows: 3, columns: 4 }, 1);
+        assert_eq!(grid.links().len(), 17);
+        assert_eq!(degrees(&grid), vec![2, 3, 3, 2, 3, 4, 4, 3, 2, 3, 3, 2]);
+        let fabric = topology(&TopologySpec::SpineLeaf { spines: 3, leaves: 5 }, 1);
+        assert_eq!(degrees(&fabric), vec![5, 5, 5, 3, 3, 3, 3, 3]);
+        assert_eq!(fabric.nodes()[2].name, "spine-2");
+        assert_eq!(fabric.nodes()[3].name, "leaf-0");
+
+        // too small for their shape
+        assert_eq!(topology(&TopologySpec::Ring { nodes: 2 }, 1).links().len(), 1);
+        assert!(topology(&TopologySpec::Ring { nodes: 1 }, 1).links().is_empty());
+        assert_eq!(topology(&TopologySpec::Grid { rows: 1, columns: 3 }, 1).links().len(), 2);
+        assert!(topology(&TopologySpec::SpineLeaf { spines: 0, leaves: 4 }, 1).links().is_empty());
+        assert_eq!(topology(&TopologySpec::Star { leaves: 0 }, 1).nodes().len(), 1);
+    }
+
+    #[test]
+    fn fat_trees() {
+        for k in [2, 4, 6, 8] {
+            let graph = fat_tree(k);
+            let (switches, hosts) = (5 * k * k / 4, k * k * k / 4);
+            assert_eq!(graph.nodes().len(), switches + hosts);
+            assert_eq!(graph.links().len(), 3 * hosts);
+            assert_eq!(graph.summary().components, 1);
+            // every switch has k ports, every host one
+            for id in 0..(switches + hosts) as isize {
+                let expected = if (id as usize) < switches { k } else { 1 };
+                assert_eq!(graph.find_links_from_node(id).len(), expected, "{}", graph.nodes()[id as usize].name);
+            }
+        }
+        let graph = fat_tree(4);
+        assert_eq!(graph.nodes()[4].name, "agg-0-0");
+        assert_eq!(graph.nodes()[12].name, "edge-0-0");
+        assert!(graph.contains_link(1, 4) && graph.contains_link(2, 5) && graph.contains_link(3, 11));
+        assert!(graph.contains_link(12, 20) && graph.contains_link(19, 35));
+        assert!(graph.links().iter().all(|link| (link.cost == 4) == (link.members.1 >= 20)));
+    }
+
+    #[test]
+    #[should_panic(expected = "even number of ports")]
+    fn odd_fat_tree() {
+        fat_tree(3);
+    }
+
+    #[test]
+    fn erdos_renyi_density() {
+        let mut rng = SplitMix64::new(3);
+        assert!(erdos_renyi(20, 0.0, 1..2, &mut rng).links().is_empty());
+        let graph = erdos_renyi(100, 0.1, 1..2, &mut rng);
+        assert_eq!(graph.nodes().len(), 100);
+        // 495 links are expected
+        assert!((400..600).contains(&graph.links().len()));
+        assert!(graph.links().iter().all(|link| link.members.0 < link.members.1));
+    }
+
+    #[test]
+    fn connected_limits() {
+        let mut rng = SplitMix64::new(4);
+        assert_eq!(connected(5, 100, 1..2, &mut rng).links().len(), 10);
+        assert_eq!(connected(1, 4, 1..2, &mut rng).links().len(), 0);
+        assert_eq!(connected(0, 4, 1..2, &mut rng).nodes().len(), 0);
+        let graph = connected(30, 1, 2..3, &mut rng);
+        assert_eq!(graph.links().len(), 29);
+        assert_eq!(graph.summary().components, 1);
+    }
+
+    #[test]
+    fn weighted_degrees() {
+        let mut rng = SplitMix64::new(5);
+        let graph = weighted(&[0.0, 1.0, 1.0, 0.0], 5, 1..2, &mut rng);
+        assert_eq!(graph.links(), &[Link::new((1, 2), 1)]);
+
+        let weights: Vec<f64> = (1..=40).map(f64::from).collect();
+        let graph = weighted(&weights, 200, 3..4, &mut rng);
+        assert_eq!(graph.links().len(), 200);
+        let degree = |id| graph.find_links_from_node(id).len();
+        assert!((30..40).map(degree).sum::<usize>() > 3 * (0..10).map(degree).sum::<usize>());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one above 0")]
+    fn weighted_without_weights() {
+        weighted(&[0.0], 1, 1..2, &mut SplitMix64::new(1));
+    }
+}
@@ -0,0 +1,224 @@
+//! Basic [computational geometry](https://en.wikipedia.org/wiki/Computational_geometry) on points of the plane:
+//! the convex hull of a point set and the closest pair of points.
+//!
+//! Coordinates are floating point numbers. Points with coordinates which are not finite, like NaN, give unspecified results.
+
+/// A point of the plane, like the location of a node on a map.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Point { x, y }
+    }
+
+    /// Returns the euclidean distance to the other point.
+    pub fn distance(&self, other: &Point) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+/// Returns the cross product of the vectors from origin to a and from origin to b.
+/// It is positive if the turn from a to b is counter-clockwise, negative if it is clockwise and zero if the points are collinear.
+pub fn cross(origin: &Point, a: &Point, b: &Point) -> f64 {
+    (a.x - origin.x) * (b.y - origin.y) - (a.y - origin.y) * (b.x - origin.x)
+}
+
+/// Computes the [convex hull](https://en.wikipedia.org/wiki/Convex_hull) of the points using Andrew's monotone chain algorithm,
+/// a variant of the [Graham scan](https://en.wikipedia.org/wiki/Graham_scan), in O(n log n) time.
+///
+/// Returns the corners of the hull in counter-clockwise order, starting with the one with the lowest x, then lowest y coordinate.
+/// Points on the edges of the hull and duplicates are left out. If all points are collinear, the hull consists of the two outermost ones.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::geometry::*;
+///
+/// let points: Vec<Point> = [(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 2.0), (1.0, 2.0)]
+///     .iter()
+///     .map(|&(x, y)| Point::new(x, y))
+///     .collect();
+/// let hull = convex_hull(&points);
+/// assert_eq!(hull, vec![Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 2.0), Point::new(0.0, 2.0)]);
+/// assert_eq!(area(&hull), 4.0);
+/// ```
+pub fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // the lower hull from left to right, then the upper hull from right to left, each ending with the start of the other one
+    let mut hull = half_hull(sorted.iter());
+    let mut upper = half_hull(sorted.iter().rev());
+    hull.pop();
+    upper.pop();
+    hull.append(&mut upper);
+    hull
+}
+
+/// Walks along the points, dropping the last corner as long as it does not make a counter-clockwise turn.
+fn half_hull<'a, I: Iterator<Item = &'a Point>>(points: I) -> Vec<Point> {
+    let mut chain: Vec<Point> = Vec::new();
+    for point in points {
+        while chain.len() >= 2 && cross(&chain[chain.len() - 2], &chain[chain.len() - 1], point) <= 0.0 {
+            chain.pop();
+        }
+        chain.push(*point);
+    }
+    chain
+}
+
+/// Returns the area of the polygon with the given corners using the [shoelace formula](https://en.wikipedia.org/wiki/Shoelace_formula).
+///
+/// The area is positive if the corners are in counter-clockwise order, negative if they are clockwise.
+pub fn area(polygon: &[Point]) -> f64 {
+    let twice: f64 = (0..polygon.len())
+        .map(|index| {
+            let (a, b) = (&polygon[index], &polygon[(index + 1) % polygon.len()]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum();
+    twice / 2.0
+}
+
+/// Finds the [closest pair of points](https://en.wikipedia.org/wiki/Closest_pair_of_points_problem) by divide and conquer,
+/// in O(n log n) time.
+///
+/// Returns the indices of both points, the lower one first, and their distance, or None if there are less than two points.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::geometry::*;
+///
+/// let points = [Point::new(0.0, 0.0), Point::new(5.0, 4.0), Point::new(3.0, 1.0), Point::new(5.0, 5.0)];
+/// assert_eq!(closest_pair(&points), Some((1, 3, 1.0)));
+/// assert_eq!(closest_pair(&points[..1]), None);
+/// ```
+pub fn closest_pair(points: &[Point]) -> Option<(usize, usize, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&a, &b| points[a].x.total_cmp(&points[b].x));
+    let mut buffer = Vec::with_capacity(points.len());
+    let (distance, a, b) = closest(points, &mut order, &mut buffer);
+    Some((a.min(b), a.max(b), distance))
+}
+
+/// Returns the closest pair of the points at the indices of order, which need to be sorted by x, and sorts them by y.
+fn closest(points: &[Point], order: &mut [usize], buffer: &mut Vec<usize>) -> (f64, usize, usize) {
+    let by_y = |a: &usize, b: &usize| points[*a].y.total_cmp(&points[*b].y);
+    if order.len() <= 3 {
+        let mut best = (f64::INFINITY, order[0], order[0]);
+        for (index, &a) in order.iter().enumerate() {
+            for &b in &order[index + 1..] {
+                let distance = points[a].distance(&points[b]);
+                if distance < best.0 {
+                    best = (distance, a, b);
+                }
+            }
+        }
+        order.sort_by(by_y);
+        return best;
+    }
+
+    let middle = order.len() / 2;
+    let split = points[order[middle]].x;
+    let (left, right) = order.split_at_mut(middle);
+    let mut best = closest(points, left, buffer);
+    let other = closest(points, right, buffer);
+    if other.0 < best.0 {
+        best = other;
+    }
+
+    // merge both halves by y
+    buffer.clear();
+    let (mut a, mut b) = (0, 0);
+    while a < left.len() || b < right.len() {
+        if b == right.len() || (a < left.len() && by_y(&left[a], &right[b]).is_le()) {
+            buffer.push(left[a]);
+            a += 1;
+        } else {
+            buffer.push(right[b]);
+            b += 1;
+        }
+    }
+    order.copy_from_slice(buffer);
+
+    // a closer pair can only consist of points near the split, and only points less than best apart by y need to be compared
+    buffer.clear();
+    buffer.extend(order.iter().copied().filter(|index| (points[*index].x - split).abs() < best.0));
+    for (index, &a) in buffer.iter().enumerate() {
+        for &b in &buffer[index + 1..] {
+            if points[b].y - points[a].y >= best.0 {
+                break;
+            }
+            let distance = points[a].distance(&points[b]);
+            if distance < best.0 {
+                best = (distance, a, b);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod geometry_test {
+    use super::*;
+    use crate::util::{RandomSource, SplitMix64};
+
+    fn random_points(rng: &mut SplitMix64, count: usize) -> Vec<Point> {
+        // integer coordinates, which makes for many duplicates and collinear points
+        (0..count).map(|_| Point::new(rng.random_index(20) as f64, rng.random_index(20) as f64)).collect()
+    }
+
+    #[test]
+    fn hulls() {
+        let mut rng = SplitMix64::new(7);
+        for count in 0..60 {
+            let points = random_points(&mut rng, count);
+            let hull = convex_hull(&points);
+            assert!(hull.iter().all(|corner| points.contains(corner)));
+            if hull.len() >= 3 {
+                // every point lies within the hull, every corner makes a strict counter-clockwise turn
+                for index in 0..hull.len() {
+                    let (a, b) = (&hull[index], &hull[(index + 1) % hull.len()]);
+                    assert!(points.iter().all(|point| cross(a, b, point) >= 0.0));
+                    assert!(cross(a, b, &hull[(index + 2) % hull.len()]) > 0.0);
+                }
+                assert!(area(&hull) > 0.0);
+            }
+        }
+        assert!(convex_hull(&[]).is_empty());
+        let line = [Point::new(2.0, 2.0), Point::new(0.0, 0.0), Point::new(1.0, 1.0), Point::new(0.0, 0.0)];
+        assert_eq!(convex_hull(&line), vec![Point::new(0.0, 0.0), Point::new(2.0, 2.0)]);
+        assert_eq!(convex_hull(&line[1..2]), vec![Point::new(0.0, 0.0)]);
+    }
+
+    #[test]
+    fn closest_pairs() {
+        let mut rng = SplitMix64::new(8);
+        for count in 2..80 {
+            let points: Vec<Point> = (0..count).map(|_| Point::new(rng.random_f64() * 100.0, rng.random_f64() * 100.0)).collect();
+            let mut expected = f64::INFINITY;
+            for a in 0..count {
+                for b in a + 1..count {
+                    expected = expected.min(points[a].distance(&points[b]));
+                }
+            }
+            let (a, b, distance) = closest_pair(&points).unwrap();
+            assert!(a < b);
+            assert_eq!(distance, expected);
+            assert_eq!(points[a].distance(&points[b]), distance);
+        }
+        let duplicates = random_points(&mut rng, 50);
+        assert_eq!(closest_pair(&duplicates).unwrap().2, 0.0);
+        assert_eq!(closest_pair(&[]), None);
+    }
+}
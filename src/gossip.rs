@@ -0,0 +1,159 @@
+use crate::graph::Graph;
+use crate::util::{self, RandomSource};
+use std::collections::BTreeSet;
+
+/// The result of a gossip simulation, as returned by simulate().
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GossipReport {
+    /// The ids of all infected nodes, in ascending order, before the first round and after every round.
+    pub infected: Vec<Vec<isize>>,
+    /// The number of messages which have been sent.
+    pub messages: usize,
+    /// The round after which all nodes of the graph were infected, or None if that did not happen.
+    pub complete_round: Option<usize>,
+    /// The number of nodes of the graph.
+    pub node_count: usize,
+}
+
+impl GossipReport {
+    /// Returns the share of the nodes infected at the end of the simulation, between 0 and 1.
+    pub fn coverage(&self) -> f64 {
+        match self.infected.last() {
+            Some(infected) if self.node_count > 0 => infected.len() as f64 / self.node_count as f64,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Simulates the [epidemic dissemination](https://en.wikipedia.org/wiki/Gossip_protocol) of a message through a graph.
+///
+/// The seed nodes know the message at the beginning. In every round, every infected node sends the message
+/// to fanout neighbours, chosen at random by the given random number generator.
+/// Nodes infected during a round send the message themselves starting with the next round.
+/// The simulation stops after the given number of rounds, or as soon as all nodes are infected.
+/// Seed ids which are not part of the graph are ignored.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::gossip;
+/// use rust_algorithms::util::SplitMix64;
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node(Node::new("A"));
+/// let b = graph.add_node(Node::new("B"));
+/// let c = graph.add_node(Node::new("C"));
+/// graph.add_link(Link::new((a, b), 1));
+/// graph.add_link(Link::new((b, c), 1));
+///
+/// let report = gossip::simulate(&graph, &[a], 2, 10, &mut SplitMix64::new(1));
+/// assert_eq!(report.infected[0], vec![a]);
+/// assert_eq!(report.infected[1], vec![a, b]);
+/// assert_eq!(report.complete_round, Some(2));
+/// assert_eq!(report.coverage(), 1.0);
+/// ```
+pub fn simulate<R>(graph: &Graph, seed_nodes: &[isize], fanout: usize, rounds: usize, rng: &mut R) -> GossipReport
+where
+    R: RandomSource + ?Sized,
+{
+    let node_count = graph.nodes().len();
+    let mut infected: BTreeSet<isize> = seed_nodes
+        .iter()
+        .copied()
+        .filter(|id| graph.nodes().iter().any(|node| node.id == *id))
+        .collect();
+    let mut report = GossipReport {
+        infected: vec![infected.iter().copied().collect()],
+        node_count,
+        ..GossipReport::default()
+    };
+    if node_count > 0 && infected.len() == node_count {
+        report.complete_round = Some(0);
+        return report;
+    }
+
+    for round in 1..=rounds {
+        let mut newly_infected = Vec::new();
+        for &node_id in &infected {
+            let mut neighbours: Vec<isize> = graph
+                .find_links_from_node(node_id)
+                .iter()
+                .map(|link| if link.members.0 == node_id { link.members.1 } else { link.members.0 })
+                .filter(|&other| other != node_id && graph.nodes().iter().any(|node| node.id == other))
+                .collect();
+            for &neighbour in util::choose_multiple(&mut neighbours, fanout, rng) {
+                report.messages += 1;
+                if !infected.contains(&neighbour) {
+                    newly_infected.push(neighbour);
+                }
+            }
+        }
+        infected.extend(newly_infected);
+        report.infected.push(infected.iter().copied().collect());
+        if infected.len() == node_count {
+            report.complete_round = Some(round);
+            break;
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod gossip_test {
+    use super::*;
+    use crate::graph::{Link, Node};
+
+    fn star(leaves: usize) -> Graph {
+        let mut graph = Graph::new();
+        let center = graph.add_node(Node::new("Center"));
+        let names = ["L1", "L2", "L3", "L4", "L5", "L6", "L7", "L8"];
+        for name in &names[..leaves] {
+            let leaf = graph.add_node(Node::new(name));
+            graph.add_link(Link::new((center, leaf), 1));
+        }
+        graph
+    }
+
+    #[test]
+    fn full_fanout_floods() {
+        let graph = star(8);
+        let report = simulate(&graph, &[0], 8, 5, &mut util::SplitMix64::new(9));
+        assert_eq!(report.complete_round, Some(1));
+        assert_eq!(report.infected.len(), 2);
+        assert_eq!(report.messages, 8);
+    }
+
+    #[test]
+    fn fanout_limits_messages() {
+        let graph = star(8);
+        let report = simulate(&graph, &[0], 1, 3, &mut util::SplitMix64::new(9));
+        assert_eq!(report.infected[1].len(), 2);
+        assert!(report.infected.windows(2).all(|pair| pair[0].len() <= pair[1].len()));
+        assert!(report.messages <= 1 + 2 + 4);
+        assert!(report.coverage() < 1.0);
+        assert_eq!(report.complete_round, None);
+    }
+
+    #[test]
+    fn seeds() {
+        let graph = star(2);
+        let report = simulate(&graph, &[1, 2, 0, 42], 1, 5, &mut util::SplitMix64::new(9));
+        assert_eq!(report.infected, vec![vec![0, 1, 2]]);
+        assert_eq!(report.complete_round, Some(0));
+
+        let report = simulate(&graph, &[42], 1, 5, &mut util::SplitMix64::new(9));
+        assert_eq!(report.infected.len(), 6);
+        assert!(report.infected.iter().all(|infected| infected.is_empty()));
+        assert_eq!(report.coverage(), 0.0);
+    }
+
+    #[test]
+    fn disconnected_nodes_stay_healthy() {
+        let mut graph = star(2);
+        graph.add_node(Node::new("Island"));
+        let report = simulate(&graph, &[1], 2, 10, &mut util::SplitMix64::new(9));
+        assert_eq!(report.infected.last().unwrap(), &vec![0, 1, 2]);
+        assert_eq!(report.complete_round, None);
+        assert!((report.coverage() - 0.75).abs() < 1e-9);
+    }
+}
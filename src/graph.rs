@@ -1,27 +1,107 @@
-use std::convert::TryInto;
+use crate::datastructures::DisjointSet;
+use crate::dot;
+use crate::json;
+use crate::util::node_index;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
 
 /// A node which can be part of a graph.
 /// Use a graph to work with nodes.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Node {
     pub id: isize,
+    /// The name is static, so that nodes can be copied freely.
+    /// Names only known at runtime, like those read from a file, are leaked for this and stay allocated for the rest of
+    /// the program, since a graph cannot tell whether a name is still held by a copy of its node.
     pub name: &'static str,
     pub is_discovered: bool,
 }
 
 /// This structure represents a link between two nodes.
 /// It also contains the path cost of that link.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Link {
     pub members: (isize, isize),
     pub cost: usize,
 }
 
 /// A graph, consisting of nodes and links between them.
+///
+/// Two graphs are equal if they have the same topology: nodes with the same ids and names,
+/// and links with the same costs between the same nodes.
+/// The order and direction of links, the discovery state of nodes, tags, subgraphs, the history and whether dangling links
+/// are allowed are ignored.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use std::collections::HashSet;
+///
+/// let mut graph1 = Graph::new();
+/// graph1.allow_dangling_links(true);
+/// graph1.add_link(Link::new((1, 2), 5));
+/// graph1.add_link(Link::new((2, 3), 1));
+/// let mut graph2 = Graph::new();
+/// graph2.allow_dangling_links(true);
+/// graph2.add_link(Link::new((3, 2), 1));
+/// graph2.add_link(Link::new((1, 2), 5));
+/// assert_eq!(graph1, graph2);
+///
+/// let mut seen = HashSet::new();
+/// seen.insert(graph1);
+/// assert!(seen.contains(&graph2));
+/// ```
 #[derive(Clone, Debug, Default)]
 pub struct Graph {
     node_list: Vec<Node>,
     link_list: Vec<Link>,
+    /// The members of every link, ordered by id, so links can be looked up in constant time.
+    link_set: HashSet<(isize, isize)>,
+    /// The tags of the nodes which have any, by node id.
+    node_tags: HashMap<isize, BTreeSet<String>>,
+    /// The tags of the links which have any, by their members ordered by id.
+    link_tags: HashMap<(isize, isize), BTreeSet<String>>,
+    /// The graphs the nodes which have any expand into, by node id.
+    subgraphs: HashMap<isize, Graph>,
+    /// The changes made since the history has been recorded, if it is.
+    history: Option<History>,
+    /// Whether links may end at ids which are no nodes of the graph, see allow_dangling_links.
+    dangling_links_allowed: bool,
+}
+
+/// A change made to a graph, as recorded in its history.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mutation {
+    AddNode(Node),
+    AddLink(Link),
+    TagNode { node_id: isize, tag: String },
+    UntagNode { node_id: isize, tag: String },
+    TagLink { a: isize, b: isize, tag: String },
+    UntagLink { a: isize, b: isize, tag: String },
+    SetSubgraph { node_id: isize, subgraph: Box<Graph> },
+    RemoveSubgraph { node_id: isize },
+    /// The other graph merged into the graph by Graph::merge, or by Graph::merge_prefixed with the names already prefixed.
+    Merge(Box<Graph>),
+}
+
+/// A change in the history of a graph, together with the time it was made.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    /// The time since the history has been recorded.
+    pub at: Duration,
+    pub mutation: Mutation,
+}
+
+/// The changes made to a graph, and the graph they were made to.
+#[derive(Clone, Debug)]
+struct History {
+    started: Instant,
+    base: Box<Graph>,
+    entries: Vec<HistoryEntry>,
 }
 
 /// A result of a search algorithm for a path between two nodes,
@@ -32,6 +112,244 @@ pub struct SearchResult {
     pub cost: usize,
 }
 
+/// The links a search has reached every node through, starting from one node.
+///
+/// A single search from the start node answers path queries to all nodes it has reached.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+///
+/// let mut predecessors = Predecessors::new(1);
+/// predecessors.map.insert(2, (1, Link::new((1, 2), 5)));
+/// predecessors.map.insert(3, (2, Link::new((3, 2), 1)));
+///
+/// let result = predecessors.path_to(3).unwrap();
+/// assert_eq!(result.links, vec![Link::new((1, 1), 0), Link::new((1, 2), 5), Link::new((3, 2), 1)]);
+/// assert_eq!(result.cost, 6);
+/// assert!(predecessors.path_to(4).is_none());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Predecessors {
+    /// The node the search started at.
+    pub start: isize,
+    /// Maps the id of every reached node, except the start node, to the node and the link it has been reached through.
+    pub map: HashMap<isize, (isize, Link)>,
+}
+
+/// The position of a link in Graph::links(), which stays valid as long as no link is removed from the graph.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LinkId(pub usize);
+
+/// A path which refers to the links of its graph by their ids instead of copying them,
+/// saving allocations when many or very long paths are computed.
+///
+/// Other than a SearchResult, it does not contain the link from the start node to itself,
+/// so a path from a node to itself has no links.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkPath {
+    /// The node the path starts at.
+    pub start: isize,
+    /// The ids of the links of the path, in order from the start node on.
+    pub link_ids: Vec<LinkId>,
+    pub cost: usize,
+}
+
+/// Statistics about the work a search algorithm has done.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SearchStats {
+    /// The number of nodes whose links have been followed.
+    pub nodes_expanded: usize,
+    /// The number of links which have been looked at while expanding nodes.
+    pub links_examined: usize,
+    /// The highest number of nodes which were discovered, but not yet expanded, at the same time.
+    pub max_frontier: usize,
+    /// The time the search took.
+    pub elapsed: Duration,
+}
+
+/// The result of a search together with statistics about it.
+#[derive(Debug)]
+pub struct SearchReport {
+    pub result: Option<SearchResult>,
+    pub stats: SearchStats,
+    /// The ids of the nodes in the order they were expanded, if the search was asked to record them with SearchOptions,
+    /// otherwise empty.
+    pub trace: Vec<isize>,
+}
+
+/// A boxed link filter, like the ones the filtered searches take.
+pub type EdgeFilter<'a> = Box<dyn Fn(&Link) -> bool + 'a>;
+
+/// The limits and extras of the searches taking options, like bfs::bfs_search_with_options, so new ones do not need
+/// another variant of every search function.
+///
+/// The options are built from the defaults, which put no limits on the search, by the methods named like the fields.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::dijkstra_search_with_options;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let options = SearchOptions::new().max_depth(1).record_trace(true);
+/// let report = dijkstra_search_with_options(&graph, node1, node3, &options);
+/// assert_eq!(report.result.unwrap().cost, 10);
+/// assert_eq!(report.trace, vec![node1]);
+///
+/// let options = SearchOptions::new().edge_filter(|link| link.cost < 10).max_nodes(1);
+/// assert!(dijkstra_search_with_options(&graph, node1, node3, &options).result.is_none());
+/// ```
+#[derive(Default)]
+pub struct SearchOptions<'a> {
+    /// The most links a path may have, not counting the link from the start node to itself. None for no limit.
+    pub max_depth: Option<usize>,
+    /// The most nodes the search may expand, it gives up without a result once it would expand more. None for no limit.
+    pub max_nodes: Option<usize>,
+    /// Only the links for which it returns true are followed, all links if it is None.
+    pub edge_filter: Option<EdgeFilter<'a>>,
+    /// Whether the expanded nodes are recorded in the trace of the report.
+    pub record_trace: bool,
+}
+
+/// How Graph::normalize_costs maps the costs of the links, e.g. to bring datasets with different cost scales onto the same one.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Normalization {
+    /// Maps the costs linearly onto the range from min to max, rounded: the lowest cost becomes min, the highest one max.
+    /// If all costs are the same, they become min.
+    MinMax { min: usize, max: usize },
+    /// Maps the costs linearly so they have the given mean and standard deviation, rounded and raised to 0 if they would be negative.
+    /// If all costs are the same, they become the mean.
+    ZScore { mean: f64, deviation: f64 },
+    /// Raises costs below min to min and lowers costs above max to max, leaving the others unchanged.
+    Clamp { min: usize, max: usize },
+}
+
+/// An overview of the size and shape of a graph, as returned by Graph::summary().
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GraphSummary {
+    pub nodes: usize,
+    pub links: usize,
+    /// The lowest degree of any node, 0 for an empty graph.
+    pub min_degree: usize,
+    /// The average degree of the nodes, 0 for an empty graph.
+    pub avg_degree: f64,
+    /// The highest degree of any node, 0 for an empty graph.
+    pub max_degree: usize,
+    /// The number of connected parts of the graph, counting nodes without links as parts of their own.
+    pub components: usize,
+    /// The lowest and highest link cost, None if there are no links.
+    pub cost_range: Option<(usize, usize)>,
+}
+
+/// Which nodes of a graph can reach each other, as returned by Graph::reachability().
+///
+/// Every row is a bitset of the nodes of a connected part of the graph, so a query only tests a single bit.
+/// Links are undirected, so nodes reach each other exactly if they are in the same part, and all nodes of a part share its row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reachability {
+    /// The row of every node.
+    rows_of_nodes: Vec<usize>,
+    rows: Vec<Vec<u64>>,
+}
+
+impl Reachability {
+    /// Returns true if there is a path between both nodes, in O(1) time. Every node of the graph reaches itself.
+    pub fn reachable(&self, a: isize, b: isize) -> bool {
+        match (self.index_of(a), self.index_of(b)) {
+            (Some(a), Some(b)) => self.rows[self.rows_of_nodes[a]][b / 64] & (1 << (b % 64)) != 0,
+            _ => false,
+        }
+    }
+
+    /// Returns the ids of all nodes reachable from the node, including itself, in ascending order.
+    /// Nodes which are not part of the graph reach nothing.
+    pub fn reachable_from(&self, node_id: isize) -> Vec<isize> {
+        let row = match self.index_of(node_id) {
+            Some(index) => &self.rows[self.rows_of_nodes[index]],
+            None => return Vec::new(),
+        };
+        let mut ids = Vec::new();
+        for (block, bits) in row.iter().enumerate() {
+            let mut bits = *bits;
+            while bits != 0 {
+                ids.push((block * 64 + bits.trailing_zeros() as usize) as isize);
+                bits &= bits - 1;
+            }
+        }
+        ids
+    }
+
+    fn index_of(&self, id: isize) -> Option<usize> {
+        node_index(id, self.rows_of_nodes.len())
+    }
+}
+
+/// Graphs stored in any way, so the generic searches like bfs::bfs_search_generic can run on them without converting them into a Graph,
+/// e.g. graphs stored in a database or the entities of a game.
+///
+/// Nodes are identified by their id only, links are undirected like the ones of Graph.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::GraphLike;
+/// use rust_algorithms::dijkstra;
+///
+/// // a ring of ten nodes, with links getting more expensive along the ring
+/// struct Ring;
+///
+/// impl GraphLike for Ring {
+///     fn neighbors(&self, node_id: isize) -> Vec<(isize, usize)> {
+///         let next = (node_id + 1) % 10;
+///         let previous = (node_id + 9) % 10;
+///         vec![(next, node_id as usize + 1), (previous, previous as usize + 1)]
+///     }
+/// }
+///
+/// let result = dijkstra::dijkstra_search_generic(&Ring, 0, 8).unwrap();
+/// assert_eq!(result.cost, 19);
+/// assert_eq!(Ring.link_cost(3, 4), Some(4));
+/// ```
+pub trait GraphLike {
+    /// Returns the ids of the neighbours of the node, each together with the cost of the link leading to it.
+    /// Nodes which are not part of the graph have no neighbours.
+    fn neighbors(&self, node_id: isize) -> Vec<(isize, usize)>;
+
+    /// Returns the cost of the link between two nodes, or None if they are not linked.
+    ///
+    /// Looks through the neighbours of the first node, implementations may provide a faster lookup.
+    fn link_cost(&self, a: isize, b: isize) -> Option<usize> {
+        self.neighbors(a).into_iter().find(|(other, _)| *other == b).map(|(_, cost)| cost)
+    }
+}
+
+/// A graph restricted to the nodes and links accepted by two predicates, as returned by Graph::view().
+///
+/// The predicates are evaluated whenever the neighbours of a node are looked up, so nothing is copied
+/// and the view always reflects the current state of whatever the predicates look at.
+pub struct GraphView<'a, N, L> {
+    graph: &'a Graph,
+    node_filter: N,
+    link_filter: L,
+}
+
+/// The links of a graph read as directed from their first member to their second, or the other way around,
+/// as returned by Graph::directed_view() and Graph::transposed_view().
+///
+/// Its neighbours are the nodes the links of a node lead to, so the generic searches follow the links in one direction only.
+#[derive(Copy, Clone, Debug)]
+pub struct DirectedView<'a> {
+    graph: &'a Graph,
+    transposed: bool,
+}
+
 impl Node {
     /// Create a new node object, with some sensible default values.
     ///
@@ -53,6 +371,11 @@ impl Node {
     }
 }
 
+/// Leaks the name to make it static, as Node::name explains.
+pub(crate) fn leak_name(name: String) -> &'static str {
+    Box::leak(name.into_boxed_str())
+}
+
 impl Link {
     /// Creates a link between two nodes.
     /// # Example
@@ -81,9 +404,45 @@ impl Graph {
         Graph {
             node_list: Vec::new(),
             link_list: Vec::new(),
+            link_set: HashSet::new(),
+            node_tags: HashMap::new(),
+            link_tags: HashMap::new(),
+            subgraphs: HashMap::new(),
+            history: None,
+            dangling_links_allowed: false,
+        }
+    }
+
+    /// Creates a new graph with room for the given number of nodes and links,
+    /// so building a large graph does not repeatedly reallocate its lists.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::Graph;
+    ///
+    /// let graph = Graph::with_capacity(100, 400);
+    /// assert!(graph.nodes().is_empty());
+    /// ```
+    pub fn with_capacity(nodes: usize, links: usize) -> Self {
+        Graph {
+            node_list: Vec::with_capacity(nodes),
+            link_list: Vec::with_capacity(links),
+            link_set: HashSet::with_capacity(links),
+            node_tags: HashMap::new(),
+            link_tags: HashMap::new(),
+            subgraphs: HashMap::new(),
+            history: None,
+            dangling_links_allowed: false,
         }
     }
 
+    /// Reserves room for at least the given number of additional nodes and links.
+    pub fn reserve(&mut self, nodes: usize, links: usize) {
+        self.node_list.reserve(nodes);
+        self.link_list.reserve(links);
+        self.link_set.reserve(links);
+    }
+
     /// Checks for existence of and finds a link between two specific nodes.
     ///
     /// # Example
@@ -92,6 +451,7 @@ impl Graph {
     /// use rust_algorithms::graph::Graph;
     ///
     /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
     ///
     /// graph.add_link(Link::new((1, 2), 5));
     /// graph.add_link(Link::new((2, 5), 8));
@@ -103,6 +463,9 @@ impl Graph {
     /// assert_eq!(graph.find_link(7, 9).is_none(), true);
     /// ```
     pub fn find_link(&mut self, a: isize, b: isize) -> Option<&Link> {
+        if !self.contains_link(a, b) {
+            return None;
+        }
         let mut found_link: Option<&Link> = Option::default();
         for link in &self.link_list {
             if link.members.0 == a && link.members.1 == b
@@ -115,6 +478,26 @@ impl Graph {
         found_link
     }
 
+    /// Checks whether a link between two specific nodes is part of the graph, in either orientation.
+    ///
+    /// Other than find_link, this takes constant time.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::Link;
+    /// use rust_algorithms::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
+    /// graph.add_link(Link::new((1, 2), 5));
+    ///
+    /// assert!(graph.contains_link(2, 1));
+    /// assert!(!graph.contains_link(1, 3));
+    /// ```
+    pub fn contains_link(&self, a: isize, b: isize) -> bool {
+        self.link_set.contains(&link_key(a, b))
+    }
+
     /// Finds all links in which the given node is part of.
     /// It does not check for/change the orientation of the link.
     ///
@@ -125,6 +508,7 @@ impl Graph {
     /// use rust_algorithms::graph::Node;
     ///
     /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
     ///
     /// let node1 = Node::new("Node 1");
     /// let node2 = Node::new("Node 2");
@@ -158,15 +542,19 @@ impl Graph {
     /// use rust_algorithms::graph::Link;
     ///
     /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
     /// let link = Link::new((1, 2), 5);
     /// graph.add_link(link);
     ///
     /// assert_eq!(graph.find_link(link.members.0, link.members.1).unwrap(), &link);
     /// ```
     pub fn add_link(&mut self, link: Link) {
-        if self.find_link(link.members.0, link.members.1).is_none() {
+        if self.link_set.insert(link_key(link.members.0, link.members.1)) {
             self.link_list.push(link);
+            self.record(|| Mutation::AddLink(link));
         }
+        #[cfg(feature = "debug-validate")]
+        self.validate();
     }
 
     /// Adds a node to the graph, if it is not a part of the graph yet.
@@ -197,9 +585,57 @@ impl Graph {
         let len = self.node_list.len();
         node.id = len.try_into().unwrap();
         self.node_list.push(node);
+        self.record(|| Mutation::AddNode(node));
+        #[cfg(feature = "debug-validate")]
+        self.validate();
         node.id
     }
 
+    /// Creates a graph from nodes and links which are already known to be consistent,
+    /// without the linear time check of add_node. Links ending at ids which are no nodes are allowed then, like in from_json.
+    pub(crate) fn from_parts(node_list: Vec<Node>, link_list: Vec<Link>) -> Self {
+        let link_set = link_list.iter().map(|link| link_key(link.members.0, link.members.1)).collect();
+        let mut graph = Graph { node_list, link_list, link_set, ..Graph::default() };
+        graph.dangling_links_allowed = graph.has_dangling_links();
+        #[cfg(feature = "debug-validate")]
+        graph.validate();
+        graph
+    }
+
+    /// Copies the nodes and links of the graph, but not the state algorithms keep in it,
+    /// so every node of the copy is undiscovered.
+    ///
+    /// Searches like bfs_search_node take the graph they work on and mark its nodes,
+    /// so a copy like this can be handed to them while the original is kept for later searches.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    /// use rust_algorithms::bfs::bfs_search_node;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// graph.add_link(Link::new((a, b), 5));
+    /// graph.get_node(b).unwrap().is_discovered = true;
+    ///
+    /// // the stale state would hide B from the search
+    /// assert!(bfs_search_node(graph.clone(), a, b).is_none());
+    /// assert_eq!(bfs_search_node(graph.topology_clone(), a, b).unwrap().cost, 5);
+    /// ```
+    pub fn topology_clone(&self) -> Self {
+        let mut graph = self.clone();
+        graph.reset_state();
+        graph
+    }
+
+    /// Resets the state algorithms keep in the graph, marking every node as undiscovered.
+    pub fn reset_state(&mut self) {
+        for node in &mut self.node_list {
+            node.is_discovered = false;
+        }
+    }
+
     /// Retrieves a node from the graph by its id.
     /// Returns None, if no node with that id is present inside the graph instead of panicing.
     ///
@@ -222,150 +658,2739 @@ impl Graph {
         let usizeindex: usize = node_id.try_into().unwrap();
         self.node_list.get_mut(usizeindex)
     }
-}
 
-impl SearchResult {
-    /// Creates a new search result to return from a function.
+    /// Returns the link with the given id, if the graph has that many links.
     ///
-    /// The result contains a list of links, which summarize the path from start to finish.
-    /// Those links are ordered, but their individual orientation might be scrambled.
-    /// An Example would be a link from node 1 to node 3, consisting of (1, 2) , (3, 2) instead of (1, 2) , (2 , 3)
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
+    /// graph.add_link(Link::new((1, 2), 5));
+    ///
+    /// assert_eq!(graph.link(LinkId(0)), Some(&Link::new((1, 2), 5)));
+    /// assert_eq!(graph.link(LinkId(1)), None);
+    /// ```
+    pub fn link(&self, id: LinkId) -> Option<&Link> {
+        self.link_list.get(id.0)
+    }
+
+    /// Returns all nodes of the graph, ordered by their id.
     ///
-    /// 
-    /// This struct has a builder pattern, so it may be initialized as follows:
     /// # Example
     /// ```
-    /// use rust_algorithms::graph::SearchResult;
+    /// use rust_algorithms::graph::Graph;
+    /// use rust_algorithms::graph::Node;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_node(Node::new("Node1"));
+    /// graph.add_node(Node::new("Node2"));
+    ///
+    /// assert_eq!(graph.nodes().len(), 2);
+    /// assert_eq!(graph.nodes()[1].name, "Node2");
+    /// ```
+    pub fn nodes(&self) -> &[Node] {
+        &self.node_list
+    }
+
+    /// Returns all links of the graph, in the order they were added.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::Graph;
     /// use rust_algorithms::graph::Link;
-    /// 
-    /// let result = SearchResult::new();
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
+    /// graph.add_link(Link::new((1, 2), 5));
+    /// graph.add_link(Link::new((2, 1), 5));
+    ///
+    /// assert_eq!(graph.links(), &[Link::new((1, 2), 5)]);
+    /// ```
+    pub fn links(&self) -> &[Link] {
+        &self.link_list
+    }
 
-    /// let testlink = Link::new((1, 1), 2);
-    /// let buildresult = SearchResult::new().cost(2).links(vec![testlink]);
+    /// Tags the node, like marking it as part of a layer of the network.
+    /// Returns true if the node is part of the graph and did not have the tag yet.
     ///
-    /// assert_eq!(buildresult.cost, 2);
-    /// assert_eq!(buildresult.links, vec!(testlink));
+    /// # Example
     /// ```
-    pub fn new() -> Self {
-        SearchResult {
-            links: Vec::<Link>::new(),
-            cost: 0,
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// let core = graph.add_node(Node::new("Core"));
+    /// let edge = graph.add_node(Node::new("Edge"));
+    /// assert!(graph.tag_node(core, "core"));
+    /// assert!(!graph.tag_node(core, "core"));
+    /// assert!(!graph.tag_node(7, "core"));
+    /// graph.tag_node(edge, "edge");
+    /// graph.tag_node(core, "monitored");
+    ///
+    /// assert_eq!(graph.nodes_with_tag("core"), vec![core]);
+    /// assert_eq!(graph.node_tags(core), vec!["core", "monitored"]);
+    /// assert!(graph.has_node_tag(edge, "edge"));
+    /// ```
+    pub fn tag_node(&mut self, node_id: isize, tag: &str) -> bool {
+        if usize::try_from(node_id).map_or(true, |index| index >= self.node_list.len()) {
+            return false;
         }
+        let added = self.node_tags.entry(node_id).or_default().insert(tag.to_string());
+        if added {
+            self.record(|| Mutation::TagNode { node_id, tag: tag.to_string() });
+        }
+        added
     }
 
-    /// Part of the builder pattern for a SearchResult.
-    /// 
-    /// For more information see the SearchResult::new() documentation.
-    pub fn links(mut self, links: Vec<Link>) -> Self {
-        self.links = links;
-        self
-    }
-    
-    /// Part of the builder pattern for a SearchResult.
-    /// 
-    /// For more information see the SearchResult::new() documentation.
-    pub fn cost(mut self, cost: usize) -> Self {
-        self.cost = cost;
-        self
+    /// Removes the tag from the node. Returns true if the node had it.
+    pub fn untag_node(&mut self, node_id: isize, tag: &str) -> bool {
+        let removed = self.node_tags.get_mut(&node_id).is_some_and(|tags| tags.remove(tag));
+        self.node_tags.retain(|_, tags| !tags.is_empty());
+        if removed {
+            self.record(|| Mutation::UntagNode { node_id, tag: tag.to_string() });
+        }
+        removed
     }
-}
 
-#[cfg(test)]
-mod graph_tests {
-    use super::*;
+    /// Returns true if the node has the tag.
+    pub fn has_node_tag(&self, node_id: isize, tag: &str) -> bool {
+        self.node_tags.get(&node_id).is_some_and(|tags| tags.contains(tag))
+    }
 
-    #[test]
-    fn new() {
-        let graph = Graph::new();
-        assert_eq!(graph.link_list.len(), 0);
-        assert_eq!(graph.node_list.len(), 0);
+    /// Returns the tags of the node, sorted.
+    pub fn node_tags(&self, node_id: isize) -> Vec<&str> {
+        self.node_tags.get(&node_id).map_or_else(Vec::new, |tags| tags.iter().map(String::as_str).collect())
     }
 
-    #[test]
-    fn add_link() {
-        let mut graph = Graph::new();
-        graph.add_link(Link::new((1, 2), 5));
-        graph.add_link(Link::new((2, 5), 8));
-        assert_eq!(graph.link_list.len(), 2);
-        assert_eq!(graph.link_list[0].members.1, 2);
+    /// Returns the ids of the nodes with the tag, in ascending order.
+    pub fn nodes_with_tag(&self, tag: &str) -> Vec<isize> {
+        let mut ids: Vec<isize> = self.node_tags.iter().filter(|(_, tags)| tags.contains(tag)).map(|(id, _)| *id).collect();
+        ids.sort_unstable();
+        ids
     }
 
-    #[test]
-    fn find_link() {
-        let mut graph = Graph::new();
-        graph.add_link(Link::new((1, 2), 5));
-        graph.add_link(Link::new((2, 5), 8));
-        let link = graph.find_link(2, 1);
-        assert_eq!(link.is_some(), true);
-        let unwrapped_link = link.unwrap();
-        assert_eq!(unwrapped_link.cost, 5);
-        assert_eq!(graph.find_link(7, 9).is_none(), true);
+    /// Tags the link between both nodes, in either direction.
+    /// Returns true if the graph has that link and it did not have the tag yet.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
+    /// graph.add_link(Link::new((1, 2), 5));
+    /// graph.add_link(Link::new((2, 3), 1));
+    /// assert!(graph.tag_link(2, 1, "wan"));
+    /// assert!(!graph.tag_link(1, 3, "wan"));
+    ///
+    /// assert_eq!(graph.links_with_tag("wan"), vec![&Link::new((1, 2), 5)]);
+    /// assert!(graph.has_link_tag(1, 2, "wan"));
+    /// ```
+    pub fn tag_link(&mut self, a: isize, b: isize, tag: &str) -> bool {
+        if !self.contains_link(a, b) {
+            return false;
+        }
+        let added = self.link_tags.entry(link_key(a, b)).or_default().insert(tag.to_string());
+        if added {
+            self.record(|| Mutation::TagLink { a, b, tag: tag.to_string() });
+        }
+        added
     }
 
-    #[test]
-    fn find_links_from_node() {
-        let mut graph = Graph::new();
-        let node1 = Node::new("Node 1");
-        let node2 = Node::new("Node 2");
-        graph.add_node(node1);
-        graph.add_node(node2);
-        let link1 = Link::new((1, 1), 1);
-        let link2 = Link::new((1, 2), 1);
-        let link3 = Link::new((1, 3), 1);
-        let link4 = Link::new((2, 2), 1);
-        graph.add_link(link1);
-        graph.add_link(link2);
-        graph.add_link(link3);
-        graph.add_link(link4);
-        let links = graph.find_links_from_node(1);
-        assert_eq!(links.len(), 3);
-        assert!(links.contains(&&link1));
-        assert!(links.contains(&&link2));
-        assert!(links.contains(&&link3));
+    /// Removes the tag from the link between both nodes. Returns true if the link had it.
+    pub fn untag_link(&mut self, a: isize, b: isize, tag: &str) -> bool {
+        let removed = self.link_tags.get_mut(&link_key(a, b)).is_some_and(|tags| tags.remove(tag));
+        self.link_tags.retain(|_, tags| !tags.is_empty());
+        if removed {
+            self.record(|| Mutation::UntagLink { a, b, tag: tag.to_string() });
+        }
+        removed
     }
 
-    #[test]
-    fn add_node() {
-        let mut graph = Graph::new();
-        let node = Node::new("Node1");
-        graph.add_node(node);
-        assert_eq!(graph.node_list[0].id, 0);
-        assert_eq!(graph.node_list[0].name, node.name);
+    /// Returns true if the link between both nodes has the tag.
+    pub fn has_link_tag(&self, a: isize, b: isize, tag: &str) -> bool {
+        self.link_tags.get(&link_key(a, b)).is_some_and(|tags| tags.contains(tag))
     }
 
-    #[test]
-    fn add_already_existing_node() {
-        let mut graph = Graph::new();
-        let node = Node::new("Node1");
-        let id1 = graph.add_node(node);
-        let id2 = graph.add_node(node);
-        assert_eq!(id1, id2);
+    /// Returns the tags of the link between both nodes, sorted.
+    pub fn link_tags(&self, a: isize, b: isize) -> Vec<&str> {
+        self.link_tags.get(&link_key(a, b)).map_or_else(Vec::new, |tags| tags.iter().map(String::as_str).collect())
     }
 
-    #[test]
-    fn get_node() {
-        let mut graph = Graph::new();
-        let node = Node::new("Node1");
-        graph.add_node(node);
-        let node_retrieved = graph.get_node(0).unwrap();
-        assert_eq!(node_retrieved.name, node.name);
-        let node_retrieved2 = graph.get_node(2);
-        assert!(node_retrieved2.is_none());
+    /// Returns the links with the tag, in the order they were added.
+    pub fn links_with_tag(&self, tag: &str) -> Vec<&Link> {
+        self.link_list.iter().filter(|link| self.has_link_tag(link.members.0, link.members.1, tag)).collect()
     }
-}
 
-#[cfg(test)]
-mod link_tests {
-    use super::Link;
-    #[test]
-    pub fn new() {
-        let link = Link::new((1, 2), 3);
-        assert_eq!(link.members, (1, 2));
-        assert_eq!(link.cost, 3);
+    /// Returns a link filter for the filtered searches, like bfs_search_node_filtered, which only follows links
+    /// between nodes with the tag, restricting the search to one layer of the network.
+    ///
+    /// The filter copies the tagged nodes, so it stays valid while the graph is handed to the search.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    /// use rust_algorithms::bfs::bfs_search_node_filtered;
+    ///
+    /// let mut graph = Graph::new();
+    /// for name in ["Core 1", "Core 2", "Edge", "Core 3"] {
+    ///     graph.add_node(Node::new(name));
+    /// }
+    /// for id in [0, 1, 3] {
+    ///     graph.tag_node(id, "core");
+    /// }
+    /// graph.add_link(Link::new((0, 2), 1));
+    /// graph.add_link(Link::new((2, 3), 1));
+    /// graph.add_link(Link::new((0, 1), 5));
+    /// graph.add_link(Link::new((1, 3), 5));
+    ///
+    /// let filter = graph.tag_filter("core");
+    /// let result = bfs_search_node_filtered(graph, 0, 3, filter).unwrap();
+    /// assert_eq!(result.cost, 10);
+    /// ```
+    pub fn tag_filter(&self, tag: &str) -> impl Fn(&Link) -> bool {
+        let tagged: HashSet<isize> = self.nodes_with_tag(tag).into_iter().collect();
+        move |link: &Link| tagged.contains(&link.members.0) && tagged.contains(&link.members.1)
     }
-}
 
-#[cfg(test)]
+    /// Returns a view of the graph which only contains the nodes and links the predicates accept, without copying the graph.
+    /// The view implements GraphLike, so the generic searches like dijkstra::dijkstra_search_generic can run on it.
+    ///
+    /// A link is only part of the view if both its members are.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    /// use rust_algorithms::dijkstra::dijkstra_search_generic;
+    ///
+    /// let mut graph = Graph::new();
+    /// for name in ["A", "B", "C", "Blocked"] {
+    ///     graph.add_node(Node::new(name));
+    /// }
+    /// graph.add_link(Link::new((0, 1), 1));
+    /// graph.add_link(Link::new((1, 2), 1));
+    /// graph.add_link(Link::new((0, 3), 1));
+    /// graph.add_link(Link::new((3, 2), 1));
+    /// graph.add_link(Link::new((0, 2), 10));
+    ///
+    /// let view = graph.view(|node| node.name != "Blocked", |link| link.cost < 10);
+    /// assert_eq!(dijkstra_search_generic(&view, 0, 2).unwrap().cost, 2);
+    /// assert_eq!(view.neighbors(0), vec![(1, 1)]);
+    /// assert_eq!(view.link_cost(0, 2), None);
+    /// ```
+    pub fn view<N, L>(&self, node_filter: N, link_filter: L) -> GraphView<'_, N, L>
+    where
+        N: Fn(&Node) -> bool,
+        L: Fn(&Link) -> bool,
+    {
+        GraphView { graph: self, node_filter, link_filter }
+    }
+
+    /// Returns a view of the graph in which every link leads from its first member to its second only, like the dag module reads them.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    /// use rust_algorithms::bfs::bfs_search_generic;
+    ///
+    /// let mut graph = Graph::new();
+    /// for name in ["A", "B", "C"] {
+    ///     graph.add_node(Node::new(name));
+    /// }
+    /// graph.add_link(Link::new((0, 1), 1));
+    /// graph.add_link(Link::new((1, 2), 1));
+    ///
+    /// assert!(bfs_search_generic(&graph.directed_view(), 0, 2).is_some());
+    /// assert!(bfs_search_generic(&graph.directed_view(), 2, 0).is_none());
+    /// assert!(bfs_search_generic(&graph.transposed_view(), 2, 0).is_some());
+    /// assert_eq!(graph.transposed_view().neighbors(1), vec![(0, 1)]);
+    /// ```
+    pub fn directed_view(&self) -> DirectedView<'_> {
+        DirectedView { graph: self, transposed: false }
+    }
+
+    /// Returns a view of the graph in which every link leads from its second member to its first only,
+    /// the [transpose](https://en.wikipedia.org/wiki/Transpose_graph) of directed_view, without copying the links.
+    pub fn transposed_view(&self) -> DirectedView<'_> {
+        DirectedView { graph: self, transposed: true }
+    }
+
+    /// Returns a link filter for the filtered searches which only follows links with the tag, see tag_filter.
+    pub fn link_tag_filter(&self, tag: &str) -> impl Fn(&Link) -> bool {
+        let tagged: HashSet<(isize, isize)> =
+            self.link_tags.iter().filter(|(_, tags)| tags.contains(tag)).map(|(key, _)| *key).collect();
+        move |link: &Link| tagged.contains(&link_key(link.members.0, link.members.1))
+    }
+
+    /// Returns a copy of the graph with the costs of all links normalized by the strategy.
+    ///
+    /// The nodes, the order of the links and the tags stay the same.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
+    /// graph.add_link(Link::new((0, 1), 100));
+    /// graph.add_link(Link::new((1, 2), 400));
+    /// graph.add_link(Link::new((2, 3), 1000));
+    ///
+    /// let costs = |graph: &Graph| graph.links().iter().map(|link| link.cost).collect::<Vec<usize>>();
+    /// assert_eq!(costs(&graph.normalize_costs(Normalization::MinMax { min: 1, max: 10 })), vec![1, 4, 10]);
+    /// assert_eq!(costs(&graph.normalize_costs(Normalization::Clamp { min: 200, max: 500 })), vec![200, 400, 500]);
+    /// assert_eq!(costs(&graph.normalize_costs(Normalization::ZScore { mean: 10.0, deviation: 2.0 })), vec![8, 9, 13]);
+    /// ```
+    pub fn normalize_costs(&self, strategy: Normalization) -> Graph {
+        let costs: Vec<f64> = self.link_list.iter().map(|link| link.cost as f64).collect();
+        let lowest = costs.iter().copied().fold(f64::INFINITY, f64::min);
+        let highest = costs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mean = costs.iter().sum::<f64>() / costs.len().max(1) as f64;
+        let deviation = (costs.iter().map(|cost| (cost - mean).powi(2)).sum::<f64>() / costs.len().max(1) as f64).sqrt();
+        let normalize = |cost: usize| -> usize {
+            match strategy {
+                Normalization::MinMax { min, max } if highest > lowest => {
+                    let share = (cost as f64 - lowest) / (highest - lowest);
+                    (min as f64 + share * (max as f64 - min as f64)).round() as usize
+                }
+                Normalization::MinMax { min, .. } => min,
+                Normalization::ZScore { mean: target, deviation: target_deviation } if deviation > 0.0 => {
+                    // casting saturates, so negative costs become 0
+                    (target + (cost as f64 - mean) / deviation * target_deviation).round() as usize
+                }
+                Normalization::ZScore { mean: target, .. } => target.round() as usize,
+                Normalization::Clamp { min, max } => cost.max(min).min(max),
+            }
+        };
+        let mut graph = self.clone();
+        for link in &mut graph.link_list {
+            link.cost = normalize(link.cost);
+        }
+        graph
+    }
+
+    /// Adds the nodes and links of the other graph, stitching both graphs together at the nodes with the same names.
+    ///
+    /// Nodes of the other graph get the id of the node with the same name, or a new id after the nodes of this graph
+    /// if there is none. Links are added between the translated ids, unless this graph links those nodes already,
+    /// and the tags of nodes and links are copied as well, just like subgraphs, unless the node has one already. Links to nodes outside of the other graph are left out,
+    /// as there is no id to translate them to.
+    ///
+    /// Returns the new id of every node of the other graph, indexed by its old id.
+    /// Takes O(nodes + links) time of both graphs.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut site = Graph::new();
+    /// let gateway = site.add_node(Node::new("Gateway"));
+    /// let switch = site.add_node(Node::new("Switch"));
+    /// site.add_link(Link::new((gateway, switch), 1));
+    ///
+    /// let mut wan = Graph::new();
+    /// let router = wan.add_node(Node::new("Router"));
+    /// let remote = wan.add_node(Node::new("Gateway"));
+    /// wan.add_link(Link::new((router, remote), 20));
+    ///
+    /// let ids = site.merge(&wan);
+    /// assert_eq!(ids, vec![2, gateway]);
+    /// assert_eq!(site.nodes().len(), 3);
+    /// assert!(site.contains_link(2, gateway));
+    /// ```
+    pub fn merge(&mut self, other: &Graph) -> Vec<isize> {
+        // the merge is recorded as a whole, not as the changes it is made of
+        let history = self.history.take();
+        let mut ids: HashMap<&'static str, isize> = self.node_list.iter().map(|node| (node.name, node.id)).collect();
+        let translation: Vec<isize> = other
+            .node_list
+            .iter()
+            .map(|node| {
+                *ids.entry(node.name).or_insert_with(|| {
+                    let id = self.node_list.len().try_into().unwrap();
+                    self.node_list.push(Node { id, ..*node });
+                    id
+                })
+            })
+            .collect();
+        self.merge_links(other, &translation);
+        for (id, subgraph) in &other.subgraphs {
+            self.subgraphs.entry(translation[*id as usize]).or_insert_with(|| subgraph.clone());
+        }
+        self.history = history;
+        self.record(|| Mutation::Merge(Box::new(other.without_history())));
+        translation
+    }
+
+    /// Works like merge, but prefixes the names of the nodes of the other graph, so none of them is shared
+    /// unless its prefixed name already is part of this graph, like two sites which use the same names for their switches.
+    /// The prefixed names are leaked, see Node::name.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::generate::{self, TopologySpec};
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut network = Graph::new();
+    /// let berlin = network.merge_prefixed(&generate::topology(&TopologySpec::Ring { nodes: 4 }, 1), "berlin/");
+    /// let paris = network.merge_prefixed(&generate::topology(&TopologySpec::Ring { nodes: 4 }, 1), "paris/");
+    /// network.add_link(Link::new((berlin[0], paris[0]), 10));
+    ///
+    /// assert_eq!(paris, vec![4, 5, 6, 7]);
+    /// assert_eq!(network.nodes()[4].name, "paris/0");
+    /// assert_eq!(network.summary().components, 1);
+    /// ```
+    pub fn merge_prefixed(&mut self, other: &Graph, prefix: &str) -> Vec<isize> {
+        let mut renamed = other.clone();
+        for node in &mut renamed.node_list {
+            node.name = leak_name(format!("{}{}", prefix, node.name));
+        }
+        self.merge(&renamed)
+    }
+
+    /// Lets the node expand into the subgraph, like a site containing its own switch fabric, replacing its previous subgraph.
+    ///
+    /// The node with id 0 of the subgraph is its gateway: when the graph is flattened, it takes the place of the node itself.
+    /// Returns false, dropping the subgraph, if the node is not part of the graph.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::generate::{self, TopologySpec};
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// let office = graph.add_node(Node::new("Office"));
+    /// let site = graph.add_node(Node::new("Site"));
+    /// graph.add_link(Link::new((office, site), 20));
+    /// // the hub of the star is the gateway
+    /// graph.set_subgraph(site, generate::topology(&TopologySpec::Star { leaves: 3 }, 1));
+    ///
+    /// let flat = graph.flatten();
+    /// assert_eq!(flat.nodes().len(), 5);
+    /// assert_eq!(flat.nodes()[1].name, "Site/hub");
+    /// assert_eq!(flat.nodes()[4].name, "Site/leaf-2");
+    /// assert!(flat.contains_link(office, 1));
+    /// assert!(graph.to_dot_hierarchical().contains("subgraph \"cluster_1\" {"));
+    /// ```
+    pub fn set_subgraph(&mut self, node_id: isize, subgraph: Graph) -> bool {
+        if usize::try_from(node_id).map_or(true, |index| index >= self.node_list.len()) {
+            return false;
+        }
+        self.record(|| Mutation::SetSubgraph { node_id, subgraph: Box::new(subgraph.without_history()) });
+        self.subgraphs.insert(node_id, subgraph);
+        true
+    }
+
+    /// Returns the subgraph the node expands into, if it has one.
+    pub fn subgraph(&self, node_id: isize) -> Option<&Graph> {
+        self.subgraphs.get(&node_id)
+    }
+
+    /// Returns the subgraph the node expands into for changing it, if it has one.
+    pub fn subgraph_mut(&mut self, node_id: isize) -> Option<&mut Graph> {
+        self.subgraphs.get_mut(&node_id)
+    }
+
+    /// Removes the subgraph of the node and returns it, if it had one.
+    pub fn remove_subgraph(&mut self, node_id: isize) -> Option<Graph> {
+        let removed = self.subgraphs.remove(&node_id);
+        if removed.is_some() {
+            self.record(|| Mutation::RemoveSubgraph { node_id });
+        }
+        removed
+    }
+
+    /// Returns the ids of the nodes which expand into a subgraph, in ascending order.
+    pub fn nodes_with_subgraph(&self) -> Vec<isize> {
+        let mut ids: Vec<isize> = self.subgraphs.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Starts or stops recording the history of the graph: every change made to it from now on, with the time it was made,
+    /// so a long simulation can be audited and its earlier states looked at again with replay_to.
+    ///
+    /// Starting while recording keeps the history, stopping drops it. The history only grows and is kept in memory.
+    /// Only the changes made through the methods of the graph are recorded, changes to nodes through get_node and
+    /// to subgraphs through subgraph_mut are not. A merge is recorded as a whole.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// graph.record_history(true);
+    /// let b = graph.add_node(Node::new("B"));
+    /// graph.add_link(Link::new((a, b), 5));
+    /// graph.add_link(Link::new((b, a), 7));
+    /// graph.tag_link(a, b, "wan");
+    ///
+    /// assert_eq!(graph.history().len(), 3);
+    /// assert_eq!(graph.history()[1].mutation, Mutation::AddLink(Link::new((a, b), 5)));
+    /// assert_eq!(graph.replay_to(0).unwrap().nodes().len(), 1);
+    /// assert!(!graph.replay_to(2).unwrap().has_link_tag(a, b, "wan"));
+    /// assert!(graph.replay_to(3).unwrap().has_link_tag(a, b, "wan"));
+    /// assert!(graph.replay_to(4).is_none());
+    /// ```
+    pub fn record_history(&mut self, enabled: bool) {
+        if !enabled {
+            self.history = None;
+        } else if self.history.is_none() {
+            self.history = Some(History { started: Instant::now(), base: Box::new(self.without_history()), entries: Vec::new() });
+        }
+    }
+
+    /// Returns true if the history of the graph is recorded.
+    pub fn is_recording_history(&self) -> bool {
+        self.history.is_some()
+    }
+
+    /// Sets whether links may end at ids which are no nodes of the graph, like links added before their nodes.
+    ///
+    /// The algorithms ignore such links, but with the `debug-validate` feature the graph reports them as a violated
+    /// invariant after every mutation, unless they have been allowed here. They are not allowed by default,
+    /// and copies of the graph keep the setting. Without the feature, this setting has no effect.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
+    /// graph.add_link(Link::new((0, 1), 1));
+    /// graph.add_node(Node::new("A"));
+    /// graph.add_node(Node::new("B"));
+    /// assert!(graph.allows_dangling_links());
+    /// ```
+    pub fn allow_dangling_links(&mut self, allowed: bool) {
+        self.dangling_links_allowed = allowed;
+        #[cfg(feature = "debug-validate")]
+        self.validate();
+    }
+
+    /// Returns true if links may end at ids which are no nodes of the graph, see allow_dangling_links.
+    pub fn allows_dangling_links(&self) -> bool {
+        self.dangling_links_allowed
+    }
+
+    /// Returns true if any link ends at an id which is no node of the graph.
+    fn has_dangling_links(&self) -> bool {
+        let count = self.node_list.len();
+        self.link_list
+            .iter()
+            .any(|link| node_index(link.members.0, count).is_none() || node_index(link.members.1, count).is_none())
+    }
+
+    /// Returns the changes recorded in the history, in the order they were made, empty if it is not recorded.
+    pub fn history(&self) -> &[HistoryEntry] {
+        self.history.as_ref().map_or(&[], |history| &history.entries)
+    }
+
+    /// Reconstructs the graph as it was after the given number of recorded changes, from the state it was in
+    /// when the recording started. The reconstructed graph does not record its history.
+    ///
+    /// Returns None if the history is not recorded or has fewer changes.
+    pub fn replay_to(&self, step: usize) -> Option<Graph> {
+        let history = self.history.as_ref()?;
+        let mut graph = (*history.base).clone();
+        for entry in history.entries.get(..step)? {
+            graph.apply(&entry.mutation);
+        }
+        Some(graph)
+    }
+
+    /// Makes the change to the graph.
+    fn apply(&mut self, mutation: &Mutation) {
+        match mutation {
+            Mutation::AddNode(node) => {
+                self.add_node(*node);
+            }
+            Mutation::AddLink(link) => self.add_link(*link),
+            Mutation::TagNode { node_id, tag } => {
+                self.tag_node(*node_id, tag);
+            }
+            Mutation::UntagNode { node_id, tag } => {
+                self.untag_node(*node_id, tag);
+            }
+            Mutation::TagLink { a, b, tag } => {
+                self.tag_link(*a, *b, tag);
+            }
+            Mutation::UntagLink { a, b, tag } => {
+                self.untag_link(*a, *b, tag);
+            }
+            Mutation::SetSubgraph { node_id, subgraph } => {
+                self.set_subgraph(*node_id, (**subgraph).clone());
+            }
+            Mutation::RemoveSubgraph { node_id } => {
+                self.remove_subgraph(*node_id);
+            }
+            Mutation::Merge(other) => {
+                self.merge(other);
+            }
+        }
+    }
+
+    /// Adds the change to the history, if it is recorded.
+    fn record<F>(&mut self, mutation: F)
+    where
+        F: FnOnce() -> Mutation,
+    {
+        if let Some(history) = &mut self.history {
+            history.entries.push(HistoryEntry { at: history.started.elapsed(), mutation: mutation() });
+        }
+    }
+
+    /// Returns a copy of the graph which does not record its history.
+    fn without_history(&self) -> Graph {
+        let history = None;
+        Graph { history, ..self.clone() }
+    }
+
+    /// Expands every node with a subgraph into the nodes of that subgraph, recursively, so algorithms see the whole network.
+    ///
+    /// The nodes of a subgraph are named by the name of the expanded node, a slash, and their own name, like "Site/Switch",
+    /// and take the place of the expanded node in the order of the nodes. Their gateway, the node with id 0, takes over the
+    /// links and tags of the expanded node. Nodes with an empty subgraph stay as they are.
+    /// Nodes whose names collide, like a node named "Site/Switch" next to the expanded node "Site", are stitched together as by merge.
+    /// The new names are leaked, see Node::name.
+    pub fn flatten(&self) -> Graph {
+        let mut flat = Graph::with_capacity(self.node_list.len(), self.link_list.len());
+        let mut names: HashMap<&'static str, isize> = HashMap::new();
+        let mut translation = Vec::with_capacity(self.node_list.len());
+        for node in &self.node_list {
+            let id = match self.subgraphs.get(&node.id).filter(|subgraph| !subgraph.node_list.is_empty()) {
+                Some(subgraph) => {
+                    let known = flat.node_list.len();
+                    let ids = flat.merge_prefixed(&subgraph.flatten(), &format!("{}/", node.name));
+                    names.extend(flat.node_list[known..].iter().map(|node| (node.name, node.id)));
+                    ids[0]
+                }
+                None => *names.entry(node.name).or_insert_with(|| {
+                    let id = flat.node_list.len().try_into().unwrap();
+                    flat.node_list.push(Node { id, ..*node });
+                    id
+                }),
+            };
+            translation.push(id);
+        }
+        flat.merge_links(self, &translation);
+        flat
+    }
+
+    /// Renders the graph as DOT like to_dot, with every subgraph drawn as a cluster labeled with the name of its node.
+    ///
+    /// The nodes of a subgraph are identified by the id of the expanded node, a slash and their own id, like "1/0".
+    /// Links of the expanded node are drawn to the gateway of its subgraph, the node with id 0.
+    pub fn to_dot_hierarchical(&self) -> String {
+        let mut dot = String::from("graph {\n");
+        self.dot_cluster("", 1, &mut dot);
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes the nodes, clusters and links of the graph as DOT statements, with the node ids prefixed.
+    fn dot_cluster(&self, prefix: &str, depth: usize, dot: &mut String) {
+        let indent = "    ".repeat(depth);
+        for node in &self.node_list {
+            match self.subgraphs.get(&node.id).filter(|subgraph| !subgraph.node_list.is_empty()) {
+                Some(subgraph) => {
+                    dot.push_str(&format!("{}subgraph \"cluster_{}{}\" {{\n", indent, prefix, node.id));
+                    dot.push_str(&format!("{}    label=\"{}\";\n", indent, dot::escape(node.name)));
+                    subgraph.dot_cluster(&format!("{}{}/", prefix, node.id), depth + 1, dot);
+                    dot.push_str(&format!("{}}}\n", indent));
+                }
+                None => dot.push_str(&format!("{}\"{}{}\" [label=\"{}\"];\n", indent, prefix, node.id, dot::escape(node.name))),
+            }
+        }
+        for link in &self.link_list {
+            let (a, b) = (self.dot_id(prefix, link.members.0), self.dot_id(prefix, link.members.1));
+            dot.push_str(&format!("{}\"{}\" -- \"{}\" [label=\"{}\"];\n", indent, a, b, link.cost));
+        }
+    }
+
+    /// Returns the DOT id of the node, or of the gateway of its subgraph.
+    fn dot_id(&self, prefix: &str, node_id: isize) -> String {
+        match self.subgraphs.get(&node_id).filter(|subgraph| !subgraph.node_list.is_empty()) {
+            Some(subgraph) => subgraph.dot_id(&format!("{}{}/", prefix, node_id), 0),
+            None => format!("{}{}", prefix, node_id),
+        }
+    }
+
+    /// Adds the links and tags of the other graph, with the ids of its nodes translated.
+    fn merge_links(&mut self, other: &Graph, translation: &[isize]) {
+        let translate = |id: isize| usize::try_from(id).ok().and_then(|index| translation.get(index).copied());
+        for link in &other.link_list {
+            if let (Some(a), Some(b)) = (translate(link.members.0), translate(link.members.1)) {
+                self.add_link(Link::new((a, b), link.cost));
+                for tag in other.link_tags(link.members.0, link.members.1) {
+                    self.tag_link(a, b, tag);
+                }
+            }
+        }
+        for (id, tags) in &other.node_tags {
+            if let Some(new_id) = translate(*id) {
+                for tag in tags {
+                    self.tag_node(new_id, tag);
+                }
+            }
+        }
+        #[cfg(feature = "debug-validate")]
+        self.validate();
+    }
+
+    /// Computes a fingerprint of the nodes and links of the graph.
+    ///
+    /// The fingerprint does not depend on the order in which links have been added, on the direction of links
+    /// or on the discovery state of the nodes, so it changes exactly when the topology changes (except for
+    /// unlikely hash collisions). The value is deterministic and stays the same across runs and platforms.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph1 = Graph::new();
+    /// graph1.add_node(Node::new("A"));
+    /// graph1.add_node(Node::new("B"));
+    /// graph1.add_node(Node::new("C"));
+    /// let mut graph2 = graph1.clone();
+    ///
+    /// graph1.add_link(Link::new((0, 1), 1));
+    /// graph1.add_link(Link::new((1, 2), 1));
+    /// graph2.add_link(Link::new((2, 1), 1));
+    /// graph2.add_link(Link::new((1, 0), 1));
+    /// assert_eq!(graph1.fingerprint(), graph2.fingerprint());
+    ///
+    /// graph2.get_node(0).unwrap().is_discovered = true;
+    /// assert_eq!(graph1.fingerprint(), graph2.fingerprint());
+    ///
+    /// graph2.add_link(Link::new((0, 2), 1));
+    /// assert_ne!(graph1.fingerprint(), graph2.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        // the hashes of the single elements are summed up, so their order does not matter
+        let nodes = self.node_list.iter().fold(0u64, |sum, node| {
+            let mut hash = fnv1a(FNV_OFFSET, &node.id.to_le_bytes());
+            hash = fnv1a(hash, node.name.as_bytes());
+            sum.wrapping_add(hash)
+        });
+        let links = self.link_list.iter().fold(0u64, |sum, link| {
+            let (a, b) = link.members;
+            let mut hash = fnv1a(FNV_OFFSET, &a.min(b).to_le_bytes());
+            hash = fnv1a(hash, &a.max(b).to_le_bytes());
+            hash = fnv1a(hash, &(link.cost as u64).to_le_bytes());
+            sum.wrapping_add(hash)
+        });
+        fnv1a(fnv1a(FNV_OFFSET, &nodes.to_le_bytes()), &links.to_le_bytes())
+    }
+
+    /// Exports the graph as JSON, following version `JSON_VERSION` of the schema:
+    ///
+    /// ```json
+    /// {
+    ///   "version": 1,
+    ///   "nodes": [{"id": 0, "name": "A"}, {"id": 1, "name": "B"}],
+    ///   "links": [{"source": 0, "target": 1, "cost": 5}]
+    /// }
+    /// ```
+    ///
+    /// Nodes are written in the order of their ids and links in the order they were added.
+    /// The discovery state of nodes is not exported.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_node(Node::new("A"));
+    /// graph.add_node(Node::new("B"));
+    /// graph.add_link(Link::new((0, 1), 5));
+    ///
+    /// let json = graph.to_json();
+    /// assert_eq!(
+    ///     json,
+    ///     r#"{"version":1,"nodes":[{"id":0,"name":"A"},{"id":1,"name":"B"}],"links":[{"source":0,"target":1,"cost":5}]}"#
+    /// );
+    /// assert_eq!(Graph::from_json(&json).unwrap(), graph);
+    /// ```
+    pub fn to_json(&self) -> String {
+        let nodes: Vec<String> = self
+            .node_list
+            .iter()
+            .map(|node| format!("{{\"id\":{},\"name\":\"{}\"}}", node.id, json::escape(node.name)))
+            .collect();
+        let links: Vec<String> = self
+            .link_list
+            .iter()
+            .map(|link| {
+                format!(
+                    "{{\"source\":{},\"target\":{},\"cost\":{}}}",
+                    link.members.0, link.members.1, link.cost
+                )
+            })
+            .collect();
+        format!(
+            "{{\"version\":{},\"nodes\":[{}],\"links\":[{}]}}",
+            JSON_VERSION,
+            nodes.join(","),
+            links.join(",")
+        )
+    }
+
+    /// Reads a graph from JSON written by `to_json`.
+    ///
+    /// Fields which are not part of the schema are ignored, so files written by later versions of the crate
+    /// which only add fields can still be read. Files with a version higher than `JSON_VERSION` are rejected.
+    /// The node ids have to be 0 up to the number of nodes, in any order, and the node names have to be unique.
+    /// Links ending at ids which are no nodes are kept, and the graph read allows them then, see allow_dangling_links.
+    ///
+    /// The names read are leaked, see Node::name.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let json = r#"{
+    ///     "version": 1,
+    ///     "comment": "unknown fields are ignored",
+    ///     "nodes": [{"id": 1, "name": "B"}, {"id": 0, "name": "A", "color": "red"}],
+    ///     "links": [{"source": 1, "target": 0, "cost": 3}]
+    /// }"#;
+    /// let graph = Graph::from_json(json).unwrap();
+    /// assert_eq!(graph.nodes()[0].name, "A");
+    /// assert_eq!(graph.links(), &[Link::new((1, 0), 3)]);
+    ///
+    /// assert_eq!(Graph::from_json(r#"{"version": 2}"#), Err(JsonError::UnsupportedVersion(2)));
+    /// ```
+    pub fn from_json(text: &str) -> Result<Graph, JsonError> {
+        let document = json::parse(text).map_err(JsonError::Syntax)?;
+        let schema = |message: &str| JsonError::Schema(String::from(message));
+        let version = document
+            .get("version")
+            .and_then(|version| version.as_u64())
+            .ok_or_else(|| schema("missing version"))?;
+        if version > JSON_VERSION {
+            return Err(JsonError::UnsupportedVersion(version));
+        }
+        let integer = |value: &json::Value, key: &str| {
+            value
+                .get(key)
+                .and_then(|field| field.as_i64())
+                .and_then(|field| field.try_into().ok())
+                .ok_or_else(|| JsonError::Schema(format!("missing or invalid {}", key)))
+        };
+        let list = |key: &str| {
+            document
+                .get(key)
+                .and_then(|field| field.as_array())
+                .ok_or_else(|| JsonError::Schema(format!("missing or invalid {}", key)))
+        };
+
+        let mut nodes = Vec::new();
+        for node in list("nodes")? {
+            let id = integer(node, "id")?;
+            let name = node
+                .get("name")
+                .and_then(|name| name.as_str())
+                .ok_or_else(|| schema("missing or invalid name"))?;
+            nodes.push((id, name));
+        }
+        nodes.sort_unstable();
+        let mut graph = Graph::new();
+        for (index, (id, name)) in nodes.into_iter().enumerate() {
+            let name = leak_name(name.to_string());
+            let added = graph.add_node(Node::new(name));
+            if added != id || index.try_into() != Ok(added) {
+                return Err(schema("node ids have to be consecutive and node names unique"));
+            }
+        }
+        graph.dangling_links_allowed = true;
+        for link in list("links")? {
+            let cost = link
+                .get("cost")
+                .and_then(|cost| cost.as_u64())
+                .and_then(|cost| cost.try_into().ok())
+                .ok_or_else(|| schema("missing or invalid cost"))?;
+            graph.add_link(Link::new((integer(link, "source")?, integer(link, "target")?), cost));
+        }
+        graph.dangling_links_allowed = graph.has_dangling_links();
+        Ok(graph)
+    }
+
+    /// Writes the graph in a compact binary format, which is much smaller and faster to read than JSON.
+    ///
+    /// All numbers are little-endian. The format starts with the magic bytes `RAGB` and a version byte,
+    /// followed by the number of nodes as u64 and every node name in the order of the node ids,
+    /// each as its length in bytes as u32 followed by its UTF-8 bytes.
+    /// Then the number of links follows as u64 and every link as its members as two i64 and its cost as u64.
+    ///
+    /// Writing many small pieces is slow on unbuffered writers, so files should be wrapped in a `BufWriter`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_node(Node::new("A"));
+    /// graph.add_node(Node::new("B"));
+    /// graph.add_link(Link::new((0, 1), 5));
+    ///
+    /// let mut bytes = Vec::new();
+    /// graph.write_binary(&mut bytes).unwrap();
+    /// assert_eq!(bytes.len(), 5 + 8 + 2 * 5 + 8 + 24);
+    /// assert_eq!(Graph::read_binary(&bytes[..]).unwrap(), graph);
+    /// ```
+    pub fn write_binary<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(BINARY_MAGIC)?;
+        writer.write_all(&[BINARY_VERSION])?;
+        writer.write_all(&(self.node_list.len() as u64).to_le_bytes())?;
+        for node in &self.node_list {
+            let length: u32 = node
+                .name
+                .len()
+                .try_into()
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "node name too long"))?;
+            writer.write_all(&length.to_le_bytes())?;
+            writer.write_all(node.name.as_bytes())?;
+        }
+        writer.write_all(&(self.link_list.len() as u64).to_le_bytes())?;
+        for link in &self.link_list {
+            writer.write_all(&(link.members.0 as i64).to_le_bytes())?;
+            writer.write_all(&(link.members.1 as i64).to_le_bytes())?;
+            writer.write_all(&(link.cost as u64).to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Reads a graph written by `write_binary`.
+    ///
+    /// Unlike adding the nodes and links one by one, this takes linear time, even for millions of links.
+    /// Input which is not in the binary format, has an unknown version or contains duplicate node names or links
+    /// fails with `ErrorKind::InvalidData`.
+    /// Links ending at ids which are no nodes are kept, and the graph read allows them then, see allow_dangling_links.
+    /// The names read are leaked, see Node::name.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::Graph;
+    /// use std::io::ErrorKind;
+    ///
+    /// let error = Graph::read_binary(&b"{\"version\": 1}"[..]).unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::InvalidData);
+    /// ```
+    pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Graph> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+        let mut magic = [0; 5];
+        reader.read_exact(&mut magic)?;
+        if &magic[..4] != BINARY_MAGIC {
+            return Err(invalid("not a binary graph"));
+        }
+        if magic[4] != BINARY_VERSION {
+            return Err(invalid("unsupported binary graph version"));
+        }
+        let read_u64 = |reader: &mut R| -> io::Result<u64> {
+            let mut bytes = [0; 8];
+            reader.read_exact(&mut bytes)?;
+            Ok(u64::from_le_bytes(bytes))
+        };
+
+        let mut graph = Graph::new();
+        let mut names = HashSet::new();
+        let node_count = read_u64(&mut reader)?;
+        for id in 0..node_count {
+            let mut length = [0; 4];
+            reader.read_exact(&mut length)?;
+            let mut name = Vec::new();
+            (&mut reader).take(u32::from_le_bytes(length).into()).read_to_end(&mut name)?;
+            if name.len() != u32::from_le_bytes(length) as usize {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+            let name = String::from_utf8(name).map_err(|_| invalid("node name is not valid UTF-8"))?;
+            let name = leak_name(name);
+            if !names.insert(name) {
+                return Err(invalid("duplicate node name"));
+            }
+            let mut node = Node::new(name);
+            node.id = id.try_into().map_err(|_| invalid("too many nodes"))?;
+            graph.node_list.push(node);
+        }
+
+        let link_count = read_u64(&mut reader)?;
+        for _ in 0..link_count {
+            let a = read_u64(&mut reader)? as i64;
+            let b = read_u64(&mut reader)? as i64;
+            let cost = read_u64(&mut reader)?;
+            let members: (isize, isize) = match (a.try_into(), b.try_into()) {
+                (Ok(a), Ok(b)) => (a, b),
+                _ => return Err(invalid("node id out of range")),
+            };
+            if !graph.link_set.insert(link_key(members.0, members.1)) {
+                return Err(invalid("duplicate link"));
+            }
+            let cost = cost.try_into().map_err(|_| invalid("link cost out of range"))?;
+            graph.link_list.push(Link::new(members, cost));
+        }
+        graph.dangling_links_allowed = graph.has_dangling_links();
+        #[cfg(feature = "debug-validate")]
+        graph.validate();
+        Ok(graph)
+    }
+
+    /// Draws the graph as text, to make it readable in test failures and examples.
+    ///
+    /// Every node gets a line listing its neighbours with the cost of the link to them in brackets.
+    /// Neighbours which are not part of the graph are shown as `?`.
+    /// Graphs with up to `ASCII_MATRIX_LIMIT` nodes additionally get an adjacency matrix with the link costs,
+    /// where `.` means that there is no link.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_node(Node::new("A"));
+    /// graph.add_node(Node::new("B"));
+    /// graph.add_node(Node::new("C"));
+    /// graph.add_link(Link::new((0, 1), 5));
+    /// graph.add_link(Link::new((0, 2), 12));
+    ///
+    /// let expected = "\
+    /// 0 A: 1 B [5], 2 C [12]
+    /// 1 B: 0 A [5]
+    /// 2 C: 0 A [12]
+    ///
+    ///      0  1  2
+    ///   0  .  5 12
+    ///   1  5  .  .
+    ///   2 12  .  .
+    /// ";
+    /// assert_eq!(graph.to_ascii(), expected);
+    /// ```
+    pub fn to_ascii(&self) -> String {
+        let name_of = |id: isize| match self.node_list.iter().find(|node| node.id == id) {
+            Some(node) => format!("{} {}", id, node.name),
+            None => format!("{} ?", id),
+        };
+        let mut ascii = String::new();
+        for node in &self.node_list {
+            let neighbours: Vec<String> = self
+                .find_links_from_node(node.id)
+                .iter()
+                .map(|link| {
+                    let other = if link.members.0 == node.id { link.members.1 } else { link.members.0 };
+                    format!("{} [{}]", name_of(other), link.cost)
+                })
+                .collect();
+            ascii.push_str(&format!("{} {}: {}\n", node.id, node.name, neighbours.join(", ")));
+        }
+
+        let count = self.node_list.len();
+        if count == 0 || count > ASCII_MATRIX_LIMIT {
+            return ascii;
+        }
+        let cell = |a: isize, b: isize| {
+            self.link_list
+                .iter()
+                .find(|link| link.members == (a, b) || link.members == (b, a))
+                .map_or_else(|| String::from("."), |link| link.cost.to_string())
+        };
+        let width = self
+            .node_list
+            .iter()
+            .flat_map(|a| self.node_list.iter().map(move |b| (a.id, b.id)))
+            .map(|(a, b)| cell(a, b).len())
+            .chain(self.node_list.iter().map(|node| node.id.to_string().len()))
+            .max()
+            .unwrap_or(1)
+            + 1;
+        ascii.push('\n');
+        ascii.push_str(&" ".repeat(width));
+        for node in &self.node_list {
+            ascii.push_str(&format!("{:>width$}", node.id, width = width));
+        }
+        ascii.push('\n');
+        for a in &self.node_list {
+            ascii.push_str(&format!("{:>width$}", a.id, width = width));
+            for b in &self.node_list {
+                ascii.push_str(&format!("{:>width$}", cell(a.id, b.id), width = width));
+            }
+            ascii.push('\n');
+        }
+        ascii
+    }
+
+    /// Renders the graph in the [DOT language](https://graphviz.org/doc/info/lang.html) of Graphviz.
+    ///
+    /// Every node is labeled with its name, every link with its cost.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// graph.add_link(Link::new((a, b), 5));
+    ///
+    /// let dot = graph.to_dot();
+    /// assert!(dot.contains("\"0\" [label=\"A\"];"));
+    /// assert!(dot.contains("\"0\" -- \"1\" [label=\"5\"];"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.dot(&HashMap::new(), &HashMap::new())
+    }
+
+    /// Renders the graph as DOT, highlighting the nodes and links with a hop number in the given maps.
+    fn dot(&self, node_hops: &HashMap<isize, usize>, link_hops: &HashMap<(isize, isize), usize>) -> String {
+        let mut dot = String::from("graph {\n");
+        for node in &self.node_list {
+            let label = dot::escape(node.name);
+            match node_hops.get(&node.id) {
+                Some(hop) => dot.push_str(&format!(
+                    "    \"{}\" [label=\"{}\\nhop {}\", color=red, fontcolor=red];\n",
+                    node.id, label, hop
+                )),
+                None => dot.push_str(&format!("    \"{}\" [label=\"{}\"];\n", node.id, label)),
+            }
+        }
+        for link in &self.link_list {
+            let (a, b) = link.members;
+            match link_hops.get(&link_key(a, b)) {
+                Some(hop) => dot.push_str(&format!(
+                    "    \"{}\" -- \"{}\" [label=\"{} (hop {})\", color=red, fontcolor=red, penwidth=2];\n",
+                    a, b, link.cost, hop
+                )),
+                None => dot.push_str(&format!("    \"{}\" -- \"{}\" [label=\"{}\"];\n", a, b, link.cost)),
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Computes an overview of the graph, like the number of nodes and links, their degrees and the range of link costs.
+    ///
+    /// The degree of a node is the number of links it is a member of, a link from a node to itself counts twice.
+    /// Links to nodes which are not part of the graph count towards the links and the cost range,
+    /// but not towards any degree or component.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// let c = graph.add_node(Node::new("C"));
+    /// graph.add_node(Node::new("D"));
+    /// graph.add_link(Link::new((a, b), 2));
+    /// graph.add_link(Link::new((a, c), 7));
+    ///
+    /// let summary = graph.summary();
+    /// assert_eq!(summary.max_degree, 2);
+    /// assert_eq!(summary.components, 2);
+    /// assert_eq!(summary.to_string(), "4 nodes, 2 links, degree 0/1.00/2, 2 components, cost 2..7");
+    /// ```
+    pub fn summary(&self) -> GraphSummary {
+        let count = self.node_list.len();
+        let degrees = self.degrees();
+        let mut components = DisjointSet::new(count);
+        for link in &self.link_list {
+            if let (Some(a), Some(b)) = (node_index(link.members.0, count), node_index(link.members.1, count)) {
+                components.union(a, b);
+            }
+        }
+
+        let costs = self.link_list.iter().map(|link| link.cost);
+        GraphSummary {
+            nodes: count,
+            links: self.link_list.len(),
+            min_degree: degrees.iter().copied().min().unwrap_or(0),
+            avg_degree: if count == 0 { 0.0 } else { degrees.iter().sum::<usize>() as f64 / count as f64 },
+            max_degree: degrees.iter().copied().max().unwrap_or(0),
+            components: components.sets(),
+            cost_range: costs.clone().min().zip(costs.max()),
+        }
+    }
+
+    /// Returns the highest degree of any node, 0 for an empty graph, e.g. to check a topology against the number of ports
+    /// of its switches. Degrees are counted like in summary.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::generate::{self, TopologySpec};
+    ///
+    /// let fabric = generate::topology(&TopologySpec::SpineLeaf { spines: 2, leaves: 6 }, 1);
+    /// assert_eq!(fabric.max_degree(), 6);
+    /// assert!(!fabric.is_regular());
+    /// assert!(generate::topology(&TopologySpec::Ring { nodes: 5 }, 1).is_regular());
+    /// ```
+    pub fn max_degree(&self) -> usize {
+        self.degrees().into_iter().max().unwrap_or(0)
+    }
+
+    /// Returns true if all nodes have the same degree, which an empty graph trivially fulfills.
+    /// Degrees are counted like in summary.
+    pub fn is_regular(&self) -> bool {
+        let degrees = self.degrees();
+        degrees.windows(2).all(|pair| pair[0] == pair[1])
+    }
+
+    /// Returns the degree of every node, in the order of the nodes.
+    /// A link from a node to itself counts twice, links to nodes which are not part of the graph are ignored.
+    pub(crate) fn degrees(&self) -> Vec<usize> {
+        let count = self.node_list.len();
+        let mut degrees = vec![0; count];
+        for link in &self.link_list {
+            if let (Some(a), Some(b)) = (node_index(link.members.0, count), node_index(link.members.1, count)) {
+                degrees[a] += 1;
+                degrees[b] += 1;
+            }
+        }
+        degrees
+    }
+
+    /// Computes which nodes can reach each other, so that reachability queries take O(1) time afterwards.
+    ///
+    /// Takes O(nodes² / 64 + links) time and memory for the bitsets. Links to nodes which are not part of the graph are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// let c = graph.add_node(Node::new("C"));
+    /// let d = graph.add_node(Node::new("D"));
+    /// graph.add_link(Link::new((a, b), 1));
+    /// graph.add_link(Link::new((b, c), 1));
+    ///
+    /// let reachability = graph.reachability();
+    /// assert!(reachability.reachable(a, c));
+    /// assert!(!reachability.reachable(a, d));
+    /// assert_eq!(reachability.reachable_from(c), vec![a, b, c]);
+    /// ```
+    pub fn reachability(&self) -> Reachability {
+        let count = self.node_list.len();
+        let mut components = DisjointSet::new(count);
+        for link in &self.link_list {
+            if let (Some(a), Some(b)) = (node_index(link.members.0, count), node_index(link.members.1, count)) {
+                components.union(a, b);
+            }
+        }
+
+        let mut row_of_root: HashMap<usize, usize> = HashMap::new();
+        let mut rows: Vec<Vec<u64>> = Vec::new();
+        let mut rows_of_nodes = Vec::with_capacity(count);
+        for node in 0..count {
+            let root = components.find(node);
+            let row = *row_of_root.entry(root).or_insert_with(|| {
+                rows.push(vec![0; count.div_ceil(64)]);
+                rows.len() - 1
+            });
+            rows[row][node / 64] |= 1 << (node % 64);
+            rows_of_nodes.push(row);
+        }
+        Reachability { rows_of_nodes, rows }
+    }
+
+    /// Computes the [transitive closure](https://en.wikipedia.org/wiki/Transitive_closure#In_graph_theory) of a directed
+    /// acyclic graph: a graph with the same nodes, and a link from every node to every other node it reaches over a path.
+    ///
+    /// Like in transitive_reduction, every link is read as directed from its first member to its second, so the closure of
+    /// the reduction is the closure of the graph. The links only tell that a path exists, so their cost is 0, and they are
+    /// ordered by their source, then by their target. Links to nodes which are not part of the graph are left out.
+    /// Takes O(nodes * links / 64) time, but the closure can have up to nodes² / 2 links.
+    /// For undirected queries, reachability() answers them without building any links.
+    ///
+    /// # Cycles
+    /// Returns None if the links form a cycle, including a link from a node to itself, so the closure is only available
+    /// for directed acyclic graphs. A Graph holds at most one link between two nodes, in either direction, so it cannot
+    /// tell that a reaches b and b reaches a as well, which is what the closure of every cycle would have to say.
+    /// Graphs with cycles have to answer directed queries by searching instead, undirected ones through reachability().
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// let c = graph.add_node(Node::new("C"));
+    /// graph.add_link(Link::new((a, b), 3));
+    /// graph.add_link(Link::new((c, b), 4));
+    ///
+    /// // a and c both depend on b, but not on each other
+    /// let closure = graph.transitive_closure().unwrap();
+    /// assert_eq!(closure.links(), &[Link::new((a, b), 0), Link::new((c, b), 0)]);
+    ///
+    /// // a cycle from a over b and d back to a
+    /// let d = graph.add_node(Node::new("D"));
+    /// graph.add_link(Link::new((b, d), 1));
+    /// graph.add_link(Link::new((d, a), 1));
+    /// assert!(graph.transitive_closure().is_none());
+    /// ```
+    pub fn transitive_closure(&self) -> Option<Graph> {
+        let (order, successors) = self.topological_order()?;
+        let reachable = Graph::descendants(&order, &successors);
+        let mut closure = self.topology_clone();
+        closure.link_list.clear();
+        closure.link_set.clear();
+        for (node, row) in self.node_list.iter().zip(&reachable) {
+            for (target, other) in self.node_list.iter().enumerate() {
+                if row[target / 64] & (1 << (target % 64)) != 0 {
+                    closure.link_list.push(Link::new((node.id, other.id), 0));
+                }
+            }
+        }
+        closure.link_set = closure.link_list.iter().map(|link| link_key(link.members.0, link.members.1)).collect();
+        Some(closure)
+    }
+
+    /// Computes the [transitive reduction](https://en.wikipedia.org/wiki/Transitive_reduction) of a directed acyclic graph:
+    /// the graph without every link whose target can also be reached from its source over other links.
+    ///
+    /// For this, every link is read as directed from its first member to its second, like a dependency.
+    /// The remaining links keep their cost and order, links to nodes which are not part of the graph are left out.
+    /// Returns None if the links form a cycle, including a link from a node to itself, since the reduction is not unique then.
+    /// Takes O(nodes * links / 64) time.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// // a depends on b and c, b depends on c, so the dependency of a on c is implied
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// let c = graph.add_node(Node::new("C"));
+    /// graph.add_link(Link::new((a, b), 1));
+    /// graph.add_link(Link::new((a, c), 1));
+    /// graph.add_link(Link::new((b, c), 1));
+    ///
+    /// let reduction = graph.transitive_reduction().unwrap();
+    /// assert_eq!(reduction.links(), &[Link::new((a, b), 1), Link::new((b, c), 1)]);
+    ///
+    /// // links are undirected in the other algorithms, so a link from c back to a would be the same as the one from a to c
+    /// let d = graph.add_node(Node::new("D"));
+    /// graph.add_link(Link::new((c, d), 1));
+    /// graph.add_link(Link::new((d, a), 1));
+    /// assert!(graph.transitive_reduction().is_none());
+    /// ```
+    pub fn transitive_reduction(&self) -> Option<Graph> {
+        let (order, successors) = self.topological_order()?;
+        let reachable = Graph::descendants(&order, &successors);
+
+        // a link is kept if its target is not reachable from another successor of its source
+        let mut kept = vec![false; self.link_list.len()];
+        for next in &successors {
+            for &(target, id) in next {
+                kept[id.0] = !next.iter().any(|&(other, _)| other != target && reachable[other][target / 64] & (1 << (target % 64)) != 0);
+            }
+        }
+
+        let mut reduction = self.topology_clone();
+        reduction.link_list = self.link_list.iter().zip(&kept).filter(|(_, kept)| **kept).map(|(link, _)| *link).collect();
+        reduction.link_set = reduction.link_list.iter().map(|link| link_key(link.members.0, link.members.1)).collect();
+        Some(reduction)
+    }
+
+    /// Returns a bitset of the nodes reachable from every node, excluding itself, for the result of topological_order.
+    /// The rows are filled from the last node of the order on, so the rows of all successors are known already.
+    fn descendants(order: &[usize], successors: &Successors) -> Vec<Vec<u64>> {
+        let count = order.len();
+        let mut reachable: Vec<Vec<u64>> = vec![vec![0; count.div_ceil(64)]; count];
+        for &node in order.iter().rev() {
+            let mut row = vec![0; count.div_ceil(64)];
+            for &(next, _) in &successors[node] {
+                row[next / 64] |= 1 << (next % 64);
+                for (block, bits) in row.iter_mut().zip(&reachable[next]) {
+                    *block |= bits;
+                }
+            }
+            reachable[node] = row;
+        }
+        reachable
+    }
+
+    /// Orders the nodes so that every link, read as directed from its first member to its second, points to a later node,
+    /// using Kahn's algorithm. Returns the indices of the nodes in that order together with the successors of every node
+    /// and the links leading to them, or None if the links form a cycle. Links to nodes outside of the graph are ignored.
+    pub(crate) fn topological_order(&self) -> Option<(Vec<usize>, Successors)> {
+        let count = self.node_list.len();
+        let mut successors: Vec<Vec<(usize, LinkId)>> = vec![Vec::new(); count];
+        let mut incoming = vec![0; count];
+        for (index, link) in self.link_list.iter().enumerate() {
+            if let (Some(a), Some(b)) = (node_index(link.members.0, count), node_index(link.members.1, count)) {
+                successors[a].push((b, LinkId(index)));
+                incoming[b] += 1;
+            }
+        }
+        let mut order: Vec<usize> = (0..count).filter(|node| incoming[*node] == 0).collect();
+        let mut next = 0;
+        while next < order.len() {
+            let node = order[next];
+            next += 1;
+            for &(successor, _) in &successors[node] {
+                incoming[successor] -= 1;
+                if incoming[successor] == 0 {
+                    order.push(successor);
+                }
+            }
+        }
+        if order.len() < count {
+            return None;
+        }
+        Some((order, successors))
+    }
+
+    /// Checks the structural invariants of the graph and panics with a descriptive message if one is violated.
+    ///
+    /// Every node has to be stored at the index matching its id, node names have to be unique
+    /// and no two links may connect the same pair of nodes.
+    /// Links have to end at nodes of the graph, unless dangling links have been allowed by allow_dangling_links.
+    #[cfg(feature = "debug-validate")]
+    fn validate(&self) {
+        for (index, node) in self.node_list.iter().enumerate() {
+            let id: isize = index.try_into().unwrap();
+            if node.id != id {
+                panic!(
+                    "graph invariant violated: node {:?} is stored at index {} but has id {}",
+                    node.name, index, node.id
+                );
+            }
+            if let Some(other) = self.node_list[..index].iter().find(|other| other.name == node.name) {
+                panic!(
+                    "graph invariant violated: nodes {} and {} share the name {:?}",
+                    other.id, node.id, node.name
+                );
+            }
+        }
+        for (index, link) in self.link_list.iter().enumerate() {
+            let (a, b) = link.members;
+            if self.link_list[..index].iter().any(|other| {
+                other.members == (a, b) || other.members == (b, a)
+            }) {
+                panic!(
+                    "graph invariant violated: nodes {} and {} are connected by more than one link",
+                    a, b
+                );
+            }
+            if !self.contains_link(a, b) {
+                panic!("graph invariant violated: link between {} and {} is missing from the link set", a, b);
+            }
+            let dangling = [a, b].iter().copied().find(|id| node_index(*id, self.node_list.len()).is_none());
+            if let Some(dangling) = dangling.filter(|_| !self.dangling_links_allowed) {
+                panic!(
+                    "graph invariant violated: link between {} and {} ends at {}, which is no node of the graph",
+                    a, b, dangling
+                );
+            }
+        }
+        if self.link_set.len() != self.link_list.len() {
+            panic!(
+                "graph invariant violated: the link set holds {} links, but the graph {}",
+                self.link_set.len(),
+                self.link_list.len()
+            );
+        }
+    }
+}
+
+impl SearchResult {
+    /// Renders the whole graph as DOT, like Graph::to_dot, with the path of this result highlighted in red.
+    ///
+    /// The nodes of the path are labeled with the number of hops from the start node,
+    /// the links with the hop they are taken at, starting at 1.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    /// use rust_algorithms::dijkstra::dijkstra_search_node;
+    ///
+    /// let mut graph = Graph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// let c = graph.add_node(Node::new("C"));
+    /// graph.add_link(Link::new((a, b), 5));
+    /// graph.add_link(Link::new((c, b), 2));
+    /// graph.add_link(Link::new((a, c), 9));
+    ///
+    /// let dot = dijkstra_search_node(&graph, a, c).unwrap().to_dot(&graph);
+    /// assert!(dot.contains("\"2\" [label=\"C\\nhop 2\", color=red, fontcolor=red];"));
+    /// assert!(dot.contains("\"2\" -- \"1\" [label=\"2 (hop 2)\", color=red, fontcolor=red, penwidth=2];"));
+    /// assert!(dot.contains("\"0\" -- \"2\" [label=\"9\"];"));
+    /// ```
+    pub fn to_dot(&self, graph: &Graph) -> String {
+        let mut node_hops = HashMap::new();
+        let mut link_hops = HashMap::new();
+        let mut links = self.links.iter();
+        if let Some(first) = links.next() {
+            let mut current = first.members.0;
+            node_hops.insert(current, 0);
+            for (hop, link) in links.enumerate() {
+                // the links of a path may be oriented either way
+                current = if link.members.0 == current { link.members.1 } else { link.members.0 };
+                node_hops.insert(current, hop + 1);
+                link_hops.insert(link_key(link.members.0, link.members.1), hop + 1);
+            }
+        }
+        graph.dot(&node_hops, &link_hops)
+    }
+
+    /// Returns the ids of the nodes along the path, from the start node on.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let result = SearchResult::new().links(vec![Link::new((1, 1), 0), Link::new((1, 2), 5), Link::new((3, 2), 1)]);
+    /// assert_eq!(result.node_ids(), vec![1, 2, 3]);
+    /// assert!(SearchResult::new().node_ids().is_empty());
+    /// ```
+    pub fn node_ids(&self) -> Vec<isize> {
+        let mut links = self.links.iter();
+        let mut ids = Vec::with_capacity(self.links.len());
+        if let Some(first) = links.next() {
+            let mut current = first.members.0;
+            ids.push(current);
+            for link in links {
+                // the links of a path may be oriented either way
+                current = if link.members.0 == current { link.members.1 } else { link.members.0 };
+                ids.push(current);
+            }
+        }
+        ids
+    }
+
+    /// Creates a new search result to return from a function.
+    ///
+    /// The result contains a list of links, which summarize the path from start to finish.
+    /// Those links are ordered, but their individual orientation might be scrambled.
+    /// An Example would be a link from node 1 to node 3, consisting of (1, 2) , (3, 2) instead of (1, 2) , (2 , 3)
+    ///
+    /// 
+    /// This struct has a builder pattern, so it may be initialized as follows:
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::SearchResult;
+    /// use rust_algorithms::graph::Link;
+    /// 
+    /// let result = SearchResult::new();
+    ///
+    /// let testlink = Link::new((1, 1), 2);
+    /// let buildresult = SearchResult::new().cost(2).links(vec![testlink]);
+    ///
+    /// assert_eq!(buildresult.cost, 2);
+    /// assert_eq!(buildresult.links, vec!(testlink));
+    /// ```
+    pub fn new() -> Self {
+        SearchResult {
+            links: Vec::<Link>::new(),
+            cost: 0,
+        }
+    }
+
+    /// Part of the builder pattern for a SearchResult.
+    /// 
+    /// For more information see the SearchResult::new() documentation.
+    pub fn links(mut self, links: Vec<Link>) -> Self {
+        self.links = links;
+        self
+    }
+    
+    /// Part of the builder pattern for a SearchResult.
+    /// 
+    /// For more information see the SearchResult::new() documentation.
+    pub fn cost(mut self, cost: usize) -> Self {
+        self.cost = cost;
+        self
+    }
+}
+
+impl Predecessors {
+    /// Creates predecessors of a search which has not reached any node but the start node yet.
+    pub fn new(start: isize) -> Self {
+        Predecessors { start, map: HashMap::new() }
+    }
+
+    /// Checks whether the search has reached the node.
+    pub fn contains(&self, node_id: isize) -> bool {
+        node_id == self.start || self.map.contains_key(&node_id)
+    }
+
+    /// Follows the links back from the target to the start node and returns the path between them,
+    /// in the same form as the searches do: the first link is from the start node to itself.
+    /// The cost is the total cost of the links.
+    /// Returns None if the search has not reached the target.
+    pub fn path_to(&self, target: isize) -> Option<SearchResult> {
+        let mut links = Vec::new();
+        let mut current = target;
+        while current != self.start {
+            let (previous, link) = self.map.get(&current)?;
+            links.push(*link);
+            current = *previous;
+            // a map which does not lead back to the start would be followed forever
+            if links.len() > self.map.len() {
+                return None;
+            }
+        }
+        links.push(Link::new((self.start, self.start), 0));
+        links.reverse();
+        let cost = links.iter().map(|link| link.cost).sum();
+        Some(SearchResult::new().links(links).cost(cost))
+    }
+}
+
+impl LinkPath {
+    /// Iterates over the links of the path, as stored in the graph.
+    /// Ids which are not part of the graph are skipped.
+    pub fn links<'a>(&'a self, graph: &'a Graph) -> impl Iterator<Item = &'a Link> + 'a {
+        self.link_ids.iter().filter_map(move |&id| graph.link(id))
+    }
+
+    /// Copies the links of the path out of the graph into a SearchResult,
+    /// which starts with the link from the start node to itself like the results of the searches do.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.allow_dangling_links(true);
+    /// graph.add_link(Link::new((1, 2), 5));
+    /// graph.add_link(Link::new((3, 2), 1));
+    ///
+    /// let path = LinkPath { start: 1, link_ids: vec![LinkId(0), LinkId(1)], cost: 6 };
+    /// let result = path.resolve(&graph);
+    /// assert_eq!(result.links, vec![Link::new((1, 1), 0), Link::new((1, 2), 5), Link::new((3, 2), 1)]);
+    /// assert_eq!(result.cost, 6);
+    /// ```
+    pub fn resolve(&self, graph: &Graph) -> SearchResult {
+        let mut links = Vec::with_capacity(self.link_ids.len() + 1);
+        links.push(Link::new((self.start, self.start), 0));
+        links.extend(self.links(graph).copied());
+        SearchResult::new().links(links).cost(self.cost)
+    }
+}
+
+impl SearchStats {
+    /// Records the current size of the frontier.
+    pub(crate) fn frontier(&mut self, size: usize) {
+        self.max_frontier = self.max_frontier.max(size);
+    }
+}
+
+impl SearchReport {
+    /// Runs the search with fresh statistics and measures the time it takes.
+    /// Instant::now panics on targets without a clock, like wasm32-unknown-unknown, so only the *_report and
+    /// *_with_options functions measure, the plain searches run without it.
+    pub(crate) fn measure<F>(search: F) -> Self
+    where
+        F: FnOnce(&mut SearchStats) -> Option<SearchResult>,
+    {
+        SearchReport::measure_traced(|stats, _| search(stats))
+    }
+
+    /// Works like measure, but also hands the search the trace to record the expanded nodes in.
+    pub(crate) fn measure_traced<F>(search: F) -> Self
+    where
+        F: FnOnce(&mut SearchStats, &mut Vec<isize>) -> Option<SearchResult>,
+    {
+        let mut stats = SearchStats::default();
+        let mut trace = Vec::new();
+        let start = Instant::now();
+        let result = search(&mut stats, &mut trace);
+        stats.elapsed = start.elapsed();
+        SearchReport { result, stats, trace }
+    }
+}
+
+impl<'a> SearchOptions<'a> {
+    /// Creates the default options, which put no limits on the search and record no trace.
+    pub fn new() -> Self {
+        SearchOptions::default()
+    }
+
+    /// Part of the builder pattern for SearchOptions, limits the number of links of a path.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Part of the builder pattern for SearchOptions, limits the number of nodes expanded.
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Part of the builder pattern for SearchOptions, restricts the search to the links the filter returns true for.
+    pub fn edge_filter<E>(mut self, edge_filter: E) -> Self
+    where
+        E: Fn(&Link) -> bool + 'a,
+    {
+        self.edge_filter = Some(Box::new(edge_filter));
+        self
+    }
+
+    /// Part of the builder pattern for SearchOptions, sets whether the expanded nodes are recorded.
+    pub fn record_trace(mut self, record_trace: bool) -> Self {
+        self.record_trace = record_trace;
+        self
+    }
+
+    /// Returns true if the search may follow the link.
+    pub(crate) fn follows(&self, link: &Link) -> bool {
+        self.edge_filter.as_ref().is_none_or(|filter| filter(link))
+    }
+
+    /// Returns true if a path with the given number of links may be extended by another one.
+    pub(crate) fn extends(&self, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max_depth| depth < max_depth)
+    }
+
+    /// Counts the expansion of the node and records it in the trace.
+    /// Returns false instead if the search has expanded as many nodes as it may.
+    pub(crate) fn expand(&self, node_id: isize, stats: &mut SearchStats, trace: &mut Vec<isize>) -> bool {
+        if self.max_nodes.is_some_and(|max_nodes| stats.nodes_expanded >= max_nodes) {
+            return false;
+        }
+        stats.nodes_expanded += 1;
+        if self.record_trace {
+            trace.push(node_id);
+        }
+        true
+    }
+}
+
+impl Graph {
+    /// Returns the ids and names of the nodes, sorted.
+    fn sorted_nodes(&self) -> Vec<(isize, &'static str)> {
+        let mut nodes: Vec<(isize, &'static str)> = self.node_list.iter().map(|node| (node.id, node.name)).collect();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    /// Returns the links with their members in ascending order, sorted.
+    fn sorted_links(&self) -> Vec<(isize, isize, usize)> {
+        let mut links: Vec<(isize, isize, usize)> = self
+            .link_list
+            .iter()
+            .map(|link| {
+                let (a, b) = link.members;
+                (a.min(b), a.max(b), link.cost)
+            })
+            .collect();
+        links.sort_unstable();
+        links
+    }
+}
+
+impl PartialEq for Graph {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_list.len() == other.node_list.len()
+            && self.link_list.len() == other.link_list.len()
+            && self.sorted_nodes() == other.sorted_nodes()
+            && self.sorted_links() == other.sorted_links()
+    }
+}
+
+impl Eq for Graph {}
+
+/// Every call of neighbors looks through all links, so the searches of the bfs, dfs and dijkstra modules made for Graph are faster.
+/// Like those, it ignores links from a node to itself and links to nodes which are not part of the graph.
+impl GraphLike for Graph {
+    fn neighbors(&self, node_id: isize) -> Vec<(isize, usize)> {
+        let contains = |id: isize| usize::try_from(id).is_ok_and(|index| index < self.node_list.len());
+        if !contains(node_id) {
+            return Vec::new();
+        }
+        self.link_list
+            .iter()
+            .filter_map(|link| match link.members {
+                (a, b) if a == node_id => Some((b, link.cost)),
+                (a, b) if b == node_id => Some((a, link.cost)),
+                _ => None,
+            })
+            .filter(|&(other, _)| other != node_id && contains(other))
+            .collect()
+    }
+
+    fn link_cost(&self, a: isize, b: isize) -> Option<usize> {
+        if !self.contains_link(a, b) {
+            return None;
+        }
+        self.neighbors(a).into_iter().find(|(other, _)| *other == b).map(|(_, cost)| cost)
+    }
+}
+
+impl<'a, N, L> GraphView<'a, N, L>
+where
+    N: Fn(&Node) -> bool,
+    L: Fn(&Link) -> bool,
+{
+    /// Returns the graph the view is on.
+    pub fn graph(&self) -> &'a Graph {
+        self.graph
+    }
+
+    /// Returns true if the node is part of the graph and accepted by the node filter.
+    pub fn contains_node(&self, node_id: isize) -> bool {
+        usize::try_from(node_id).ok().and_then(|index| self.graph.node_list.get(index)).is_some_and(|node| (self.node_filter)(node))
+    }
+}
+
+/// Looks through all links of the graph on every call of neighbors, like the implementation for Graph.
+impl<'a, N, L> GraphLike for GraphView<'a, N, L>
+where
+    N: Fn(&Node) -> bool,
+    L: Fn(&Link) -> bool,
+{
+    fn neighbors(&self, node_id: isize) -> Vec<(isize, usize)> {
+        if !self.contains_node(node_id) {
+            return Vec::new();
+        }
+        self.graph
+            .link_list
+            .iter()
+            .filter_map(|link| match link.members {
+                (a, b) if a == node_id => Some((b, link)),
+                (a, b) if b == node_id => Some((a, link)),
+                _ => None,
+            })
+            .filter(|&(other, link)| other != node_id && self.contains_node(other) && (self.link_filter)(link))
+            .map(|(other, link)| (other, link.cost))
+            .collect()
+    }
+
+    fn link_cost(&self, a: isize, b: isize) -> Option<usize> {
+        if !self.graph.contains_link(a, b) {
+            return None;
+        }
+        self.neighbors(a).into_iter().find(|(other, _)| *other == b).map(|(_, cost)| cost)
+    }
+}
+
+impl<'a> DirectedView<'a> {
+    /// Returns the graph the view is on.
+    pub fn graph(&self) -> &'a Graph {
+        self.graph
+    }
+
+    /// Returns true if the links are read from their second member to their first.
+    pub fn is_transposed(&self) -> bool {
+        self.transposed
+    }
+}
+
+/// Looks through all links of the graph on every call of neighbors, like the implementation for Graph.
+/// Links from a node to itself and links to nodes which are not part of the graph are ignored.
+impl<'a> GraphLike for DirectedView<'a> {
+    fn neighbors(&self, node_id: isize) -> Vec<(isize, usize)> {
+        let contains = |id: isize| usize::try_from(id).is_ok_and(|index| index < self.graph.node_list.len());
+        if !contains(node_id) {
+            return Vec::new();
+        }
+        self.graph
+            .link_list
+            .iter()
+            .filter_map(|link| {
+                let (from, to) = if self.transposed { (link.members.1, link.members.0) } else { link.members };
+                Some((to, link.cost)).filter(|_| from == node_id)
+            })
+            .filter(|&(other, _)| other != node_id && contains(other))
+            .collect()
+    }
+}
+
+/// Hashes the fingerprint of the graph, which ignores the same details as the equality.
+impl Hash for Graph {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.fingerprint());
+    }
+}
+
+/// Shows the name of the node followed by its id, e.g. `Node 1 (0)`.
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)
+    }
+}
+
+/// Shows the ids of both members with the cost in between, e.g. `1 -(5)- 2`.
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -({})- {}", self.members.0, self.cost, self.members.1)
+    }
+}
+
+/// Shows the path from the start to the target node with the cost of every link and the total cost,
+/// e.g. `1 -(5)-> 2 -(2)-> 4, total 7`.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+///
+/// let result = SearchResult::new()
+///     .links(vec![Link::new((1, 1), 0), Link::new((2, 1), 5), Link::new((2, 4), 2)])
+///     .cost(7);
+/// assert_eq!(result.to_string(), "1 -(5)-> 2 -(2)-> 4, total 7");
+/// ```
+impl fmt::Display for SearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut links = self.links.iter();
+        let mut current = match links.next() {
+            Some(first) => first.members.0,
+            None => return write!(f, "no path, total {}", self.cost),
+        };
+        write!(f, "{}", current)?;
+        for link in links {
+            // the links of a path may be oriented either way
+            current = if link.members.0 == current { link.members.1 } else { link.members.0 };
+            write!(f, " -({})-> {}", link.cost, current)?;
+        }
+        write!(f, ", total {}", self.cost)
+    }
+}
+
+/// Shows the summary on a single line, e.g. `4 nodes, 3 links, degree 1/1.50/3, 1 component, cost 1..5`.
+impl fmt::Display for GraphSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} node{}, {} link{}, degree {}/{:.2}/{}, {} component{}",
+            self.nodes,
+            if self.nodes == 1 { "" } else { "s" },
+            self.links,
+            if self.links == 1 { "" } else { "s" },
+            self.min_degree,
+            self.avg_degree,
+            self.max_degree,
+            self.components,
+            if self.components == 1 { "" } else { "s" }
+        )?;
+        match self.cost_range {
+            Some((min, max)) => write!(f, ", cost {}..{}", min, max),
+            None => write!(f, ", no costs"),
+        }
+    }
+}
+
+/// The version of the JSON schema written by `Graph::to_json`.
+pub const JSON_VERSION: u64 = 1;
+
+/// An error which occurred while reading a graph with `Graph::from_json`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonError {
+    /// The input is not valid JSON.
+    Syntax(String),
+    /// The input is valid JSON, but does not describe a graph.
+    Schema(String),
+    /// The input was written with a newer version of the schema, which cannot be read.
+    UnsupportedVersion(u64),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonError::Syntax(message) => write!(f, "invalid JSON: {}", message),
+            JsonError::Schema(message) => write!(f, "invalid graph: {}", message),
+            JsonError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported schema version {}, the highest supported version is {}",
+                version, JSON_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+const BINARY_MAGIC: &[u8] = b"RAGB";
+const BINARY_VERSION: u8 = 1;
+
+/// The maximum number of nodes for which `Graph::to_ascii` draws an adjacency matrix.
+pub const ASCII_MATRIX_LIMIT: usize = 10;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The successors of every node by index, with the links leading to them, see Graph::topological_order.
+pub(crate) type Successors = Vec<Vec<(usize, LinkId)>>;
+
+/// Orders the members of a link by id, so both orientations of a link map to the same key.
+fn link_key(a: isize, b: isize) -> (isize, isize) {
+    (a.min(b), a.max(b))
+}
+
+/// Feeds the bytes into the [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function) hash.
+/// Unlike the hasher of the standard library, it is guaranteed to give the same result on every run.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod graph_tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let graph = Graph::new();
+        assert_eq!(graph.link_list.len(), 0);
+        assert_eq!(graph.node_list.len(), 0);
+    }
+
+    #[test]
+    fn add_link() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_link(Link::new((1, 2), 5));
+        graph.add_link(Link::new((2, 5), 8));
+        assert_eq!(graph.link_list.len(), 2);
+        assert_eq!(graph.link_list[0].members.1, 2);
+    }
+
+    #[test]
+    fn find_link() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_link(Link::new((1, 2), 5));
+        graph.add_link(Link::new((2, 5), 8));
+        let link = graph.find_link(2, 1);
+        assert!(link.is_some());
+        let unwrapped_link = link.unwrap();
+        assert_eq!(unwrapped_link.cost, 5);
+        assert!(graph.find_link(7, 9).is_none());
+    }
+
+    #[test]
+    fn reset_state() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new("A"));
+        graph.add_node(Node::new("B"));
+        graph.add_link(Link::new((0, 1), 2));
+        graph.get_node(0).unwrap().is_discovered = true;
+        let copy = graph.topology_clone();
+        assert!(copy.nodes().iter().all(|node| !node.is_discovered));
+        assert_eq!(copy.links(), graph.links());
+        assert!(graph.nodes()[0].is_discovered);
+        graph.reset_state();
+        assert!(!graph.nodes()[0].is_discovered);
+    }
+
+    #[test]
+    fn with_capacity() {
+        let mut graph = Graph::with_capacity(4, 8);
+        graph.allow_dangling_links(true);
+        assert!(graph.node_list.capacity() >= 4);
+        assert!(graph.link_list.capacity() >= 8);
+        graph.add_link(Link::new((0, 1), 1));
+        graph.reserve(0, 20);
+        assert!(graph.link_list.capacity() >= 21);
+        assert!(graph.contains_link(1, 0));
+    }
+
+    #[test]
+    fn contains_link() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_link(Link::new((1, 2), 5));
+        graph.add_link(Link::new((2, 1), 3));
+        graph.add_link(Link::new((4, 4), 1));
+        assert_eq!(graph.link_list.len(), 2);
+        assert!(graph.contains_link(1, 2));
+        assert!(graph.contains_link(2, 1));
+        assert!(graph.contains_link(4, 4));
+        assert!(!graph.contains_link(1, 4));
+        let copy = Graph::from_parts(graph.nodes().to_vec(), graph.links().to_vec());
+        assert!(copy.contains_link(2, 1));
+    }
+
+    #[test]
+    fn find_links_from_node() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        let node1 = Node::new("Node 1");
+        let node2 = Node::new("Node 2");
+        graph.add_node(node1);
+        graph.add_node(node2);
+        let link1 = Link::new((1, 1), 1);
+        let link2 = Link::new((1, 2), 1);
+        let link3 = Link::new((1, 3), 1);
+        let link4 = Link::new((2, 2), 1);
+        graph.add_link(link1);
+        graph.add_link(link2);
+        graph.add_link(link3);
+        graph.add_link(link4);
+        let links = graph.find_links_from_node(1);
+        assert_eq!(links.len(), 3);
+        assert!(links.contains(&&link1));
+        assert!(links.contains(&&link2));
+        assert!(links.contains(&&link3));
+    }
+
+    #[test]
+    fn add_node() {
+        let mut graph = Graph::new();
+        let node = Node::new("Node1");
+        graph.add_node(node);
+        assert_eq!(graph.node_list[0].id, 0);
+        assert_eq!(graph.node_list[0].name, node.name);
+    }
+
+    #[test]
+    fn add_already_existing_node() {
+        let mut graph = Graph::new();
+        let node = Node::new("Node1");
+        let id1 = graph.add_node(node);
+        let id2 = graph.add_node(node);
+        assert_eq!(id1, id2);
+    }
+
+    #[test]
+    fn get_node() {
+        let mut graph = Graph::new();
+        let node = Node::new("Node1");
+        graph.add_node(node);
+        let node_retrieved = graph.get_node(0).unwrap();
+        assert_eq!(node_retrieved.name, node.name);
+        let node_retrieved2 = graph.get_node(2);
+        assert!(node_retrieved2.is_none());
+    }
+
+    #[test]
+    fn fingerprint() {
+        let mut graph = Graph::new();
+        let empty = graph.fingerprint();
+        assert_eq!(empty, Graph::new().fingerprint());
+        graph.add_node(Node::new("Node1"));
+        graph.add_node(Node::new("Node2"));
+        let nodes_only = graph.fingerprint();
+        assert_ne!(nodes_only, empty);
+        graph.add_link(Link::new((0, 1), 5));
+        let linked = graph.fingerprint();
+        assert_ne!(linked, nodes_only);
+        let mut other = Graph::new();
+        other.add_node(Node::new("Node1"));
+        other.add_node(Node::new("Node2"));
+        other.add_link(Link::new((0, 1), 6));
+        assert_ne!(other.fingerprint(), linked);
+        let mut renamed = Graph::new();
+        renamed.add_node(Node::new("Node1"));
+        renamed.add_node(Node::new("Node3"));
+        renamed.add_link(Link::new((0, 1), 5));
+        assert_ne!(renamed.fingerprint(), linked);
+    }
+
+    #[test]
+    fn equality() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_node(Node::new("Node1"));
+        graph.add_node(Node::new("Node2"));
+        graph.add_link(Link::new((0, 1), 5));
+        graph.add_link(Link::new((1, 1), 2));
+        let mut other = Graph::new();
+        other.allow_dangling_links(true);
+        other.add_node(Node::new("Node1"));
+        other.add_node(Node::new("Node2"));
+        other.add_link(Link::new((1, 1), 2));
+        other.add_link(Link::new((1, 0), 5));
+        other.get_node(1).unwrap().is_discovered = true;
+        assert_eq!(graph, other);
+        other.add_link(Link::new((0, 2), 5));
+        assert_ne!(graph, other);
+        let mut renamed = Graph::new();
+        renamed.allow_dangling_links(true);
+        renamed.add_node(Node::new("Node1"));
+        renamed.add_node(Node::new("Node3"));
+        renamed.add_link(Link::new((0, 1), 5));
+        renamed.add_link(Link::new((1, 1), 2));
+        assert_ne!(graph, renamed);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_node(Node::new("A \"quoted\" name"));
+        graph.add_node(Node::new("B"));
+        graph.add_link(Link::new((1, 0), 5));
+        graph.add_link(Link::new((0, 9), 1));
+        let read = Graph::from_json(&graph.to_json()).unwrap();
+        assert_eq!(read, graph);
+        assert_eq!(read.links(), graph.links());
+        assert!(read.allows_dangling_links());
+        assert!(!Graph::from_json(&Graph::new().to_json()).unwrap().allows_dangling_links());
+        assert_eq!(Graph::from_json(&Graph::new().to_json()).unwrap(), Graph::new());
+    }
+
+    #[test]
+    fn json_errors() {
+        assert!(matches!(Graph::from_json("{"), Err(JsonError::Syntax(_))));
+        assert!(matches!(Graph::from_json(&"[".repeat(1_000_000)), Err(JsonError::Syntax(_))));
+        assert!(matches!(Graph::from_json("{}"), Err(JsonError::Schema(_))));
+        assert!(matches!(Graph::from_json(r#"{"version":1,"nodes":[]}"#), Err(JsonError::Schema(_))));
+        let gap = r#"{"version":1,"nodes":[{"id":0,"name":"A"},{"id":2,"name":"B"}],"links":[]}"#;
+        assert!(matches!(Graph::from_json(gap), Err(JsonError::Schema(_))));
+        let duplicate = r#"{"version":1,"nodes":[{"id":0,"name":"A"},{"id":1,"name":"A"}],"links":[]}"#;
+        assert!(matches!(Graph::from_json(duplicate), Err(JsonError::Schema(_))));
+        let negative = r#"{"version":1,"nodes":[],"links":[{"source":0,"target":1,"cost":-1}]}"#;
+        assert!(matches!(Graph::from_json(negative), Err(JsonError::Schema(_))));
+    }
+
+    #[test]
+    fn binary_roundtrip() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_node(Node::new("Ä"));
+        graph.add_node(Node::new(""));
+        graph.add_link(Link::new((1, 0), 5));
+        graph.add_link(Link::new((-3, 9), usize::MAX));
+        let mut bytes = Vec::new();
+        graph.write_binary(&mut bytes).unwrap();
+        let read = Graph::read_binary(&bytes[..]).unwrap();
+        assert_eq!(read, graph);
+        assert_eq!(read.links(), graph.links());
+        bytes.pop();
+        assert_eq!(Graph::read_binary(&bytes[..]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn binary_errors() {
+        let mut bytes = Vec::new();
+        Graph::new().write_binary(&mut bytes).unwrap();
+        bytes[4] = 2;
+        assert_eq!(Graph::read_binary(&bytes[..]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        let mut duplicate = b"RAGB\x01".to_vec();
+        duplicate.extend_from_slice(&2u64.to_le_bytes());
+        for _ in 0..2 {
+            duplicate.extend_from_slice(&1u32.to_le_bytes());
+            duplicate.push(b'A');
+        }
+        duplicate.extend_from_slice(&0u64.to_le_bytes());
+        assert_eq!(Graph::read_binary(&duplicate[..]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn to_ascii() {
+        assert_eq!(Graph::new().to_ascii(), "");
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_node(Node::new("A"));
+        graph.add_link(Link::new((0, 0), 1));
+        graph.add_link(Link::new((0, 7), 3));
+        assert_eq!(graph.to_ascii(), "0 A: 0 A [1], 7 ? [3]\n\n   0\n 0 1\n");
+        for name in ["B", "C", "D", "E", "F", "G", "H", "I", "J", "K"] {
+            graph.add_node(Node::new(name));
+        }
+        assert_eq!(graph.to_ascii().lines().count(), 11);
+    }
+
+    #[test]
+    fn to_dot() {
+        assert_eq!(Graph::new().to_dot(), "graph {\n}\n");
+        let mut graph = Graph::new();
+        graph.add_node(Node::new("A \"quoted\""));
+        graph.add_node(Node::new("B"));
+        graph.add_node(Node::new("C"));
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        assert_eq!(
+            graph.to_dot(),
+            "graph {\n    \"0\" [label=\"A \\\"quoted\\\"\"];\n    \"1\" [label=\"B\"];\n    \"2\" [label=\"C\"];\n    \"0\" -- \"1\" [label=\"1\"];\n    \"1\" -- \"2\" [label=\"1\"];\n}\n"
+        );
+
+        let result = SearchResult::new().links(vec![Link::new((1, 1), 0), Link::new((2, 1), 1)]).cost(1);
+        let dot = result.to_dot(&graph);
+        assert!(dot.contains("\"1\" [label=\"B\\nhop 0\", color=red, fontcolor=red];"));
+        assert!(dot.contains("\"2\" [label=\"C\\nhop 1\", color=red, fontcolor=red];"));
+        assert!(dot.contains("\"0\" [label=\"A \\\"quoted\\\"\"];"));
+        assert!(dot.contains("\"1\" -- \"2\" [label=\"1 (hop 1)\", color=red, fontcolor=red, penwidth=2];"));
+        assert_eq!(SearchResult::new().to_dot(&graph), graph.to_dot());
+    }
+
+    #[test]
+    fn summary() {
+        assert_eq!(Graph::new().summary().to_string(), "0 nodes, 0 links, degree 0/0.00/0, 0 components, no costs");
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_node(Node::new("A"));
+        graph.add_node(Node::new("B"));
+        graph.add_link(Link::new((0, 0), 4));
+        graph.add_link(Link::new((0, 9), 1));
+        let summary = graph.summary();
+        assert_eq!(summary.links, 2);
+        assert_eq!((summary.min_degree, summary.max_degree), (0, 2));
+        assert_eq!(summary.components, 2);
+        assert_eq!(summary.cost_range, Some((1, 4)));
+        graph.add_link(Link::new((1, 0), 2));
+        assert_eq!(graph.summary().to_string(), "2 nodes, 3 links, degree 1/2.00/3, 1 component, cost 1..4");
+    }
+
+    #[test]
+    fn degrees() {
+        let mut graph = crate::generate::topology(&crate::generate::TopologySpec::Grid { rows: 3, columns: 3 }, 1);
+        assert_eq!(graph.max_degree(), 4);
+        assert!(!graph.is_regular());
+        assert!(crate::generate::fat_tree(4).max_degree() == 4 && !crate::generate::fat_tree(4).is_regular());
+        assert!(crate::generate::erdos_renyi(7, 1.0, 1..2, &mut crate::util::SplitMix64::new(1)).is_regular());
+        graph.allow_dangling_links(true);
+        graph.add_link(Link::new((4, 4), 1));
+        graph.add_link(Link::new((4, 99), 1));
+        assert_eq!(graph.max_degree(), 6);
+        assert_eq!(graph.summary().max_degree, graph.max_degree());
+        assert!(Graph::new().is_regular() && Graph::new().max_degree() == 0);
+    }
+
+    #[test]
+    fn reachability() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for index in 0..70 {
+            graph.add_node(Node::new(Box::leak(index.to_string().into_boxed_str())));
+        }
+        // the even nodes form a path, the odd ones stay on their own, except for 1 and 69
+        for id in (2..70).step_by(2) {
+            graph.add_link(Link::new((id - 2, id), 1));
+        }
+        graph.add_link(Link::new((1, 69), 1));
+        graph.add_link(Link::new((3, 500), 1));
+        let reachability = graph.reachability();
+        assert!(reachability.reachable(0, 68));
+        assert!(reachability.reachable(69, 1));
+        assert!(reachability.reachable(5, 5));
+        assert!(!reachability.reachable(0, 1));
+        assert!(!reachability.reachable(3, 500));
+        assert!(!reachability.reachable(-1, -1));
+        assert_eq!(reachability.reachable_from(64).len(), 35);
+        assert_eq!(reachability.reachable_from(1), vec![1, 69]);
+        assert!(reachability.reachable_from(70).is_empty());
+
+        // the links only point to higher ids, so the closure links every even node to all even nodes after it
+        let closure = graph.transitive_closure().unwrap();
+        assert_eq!(closure.nodes(), graph.nodes());
+        assert_eq!(closure.links().len(), 35 * 34 / 2 + 1);
+        assert_eq!(closure.reachability(), reachability);
+        assert!(closure.links().iter().all(|link| link.members.0 < link.members.1 && link.cost == 0));
+        assert!(closure.contains_link(0, 68) && !closure.contains_link(3, 500));
+        assert_eq!(Graph::new().transitive_closure(), Some(Graph::new()));
+    }
+
+    #[test]
+    fn transitive_reduction() {
+        /// The pairs of nodes connected by a directed path, found by a depth-first search from every node.
+        fn directed_paths(graph: &Graph) -> Vec<(isize, isize)> {
+            let mut pairs = Vec::new();
+            for start in 0..graph.nodes().len() as isize {
+                let mut stack = vec![start];
+                let mut seen = HashSet::new();
+                while let Some(node) = stack.pop() {
+                    for link in graph.links().iter().filter(|link| link.members.0 == node) {
+                        if seen.insert(link.members.1) {
+                            pairs.push((start, link.members.1));
+                            stack.push(link.members.1);
+                        }
+                    }
+                }
+            }
+            pairs.sort_unstable();
+            pairs
+        }
+
+        let mut rng = crate::util::SplitMix64::new(23);
+        for _ in 0..20 {
+            // links only point to higher ids, so there are no cycles
+            let mut graph = crate::generate::erdos_renyi(15, 0.3, 1..4, &mut rng);
+            graph.allow_dangling_links(true);
+            graph.add_link(Link::new((3, 42), 1));
+            let reduction = graph.transitive_reduction().unwrap();
+            assert_eq!(reduction.nodes(), graph.nodes());
+            assert!(!reduction.contains_link(3, 42));
+            let paths = directed_paths(&reduction);
+            assert_eq!(paths, directed_paths(&graph).into_iter().filter(|pair| pair.1 != 42).collect::<Vec<_>>());
+            // every remaining link is needed
+            for index in 0..reduction.links().len() {
+                let mut smaller = reduction.clone();
+                smaller.link_list.remove(index);
+                assert_ne!(directed_paths(&smaller), paths);
+            }
+            assert!(reduction.links().iter().all(|link| graph.links().contains(link)));
+            // the closure holds exactly the directed paths, and it is the same for the reduction
+            let closure = graph.transitive_closure().unwrap();
+            let closure_pairs: Vec<(isize, isize)> = closure.links().iter().map(|link| link.members).collect();
+            assert_eq!(closure_pairs, paths);
+            assert_eq!(reduction.transitive_closure(), Some(closure));
+        }
+
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_node(Node::new("A"));
+        graph.add_link(Link::new((0, 0), 1));
+        assert!(graph.transitive_reduction().is_none());
+        assert!(graph.transitive_closure().is_none());
+        assert_eq!(Graph::new().transitive_reduction(), Some(Graph::new()));
+    }
+
+    #[test]
+    fn normalize_costs() {
+        let mut rng = crate::util::SplitMix64::new(27);
+        let mut graph = crate::generate::erdos_renyi(30, 0.2, 50..5000, &mut rng);
+        graph.tag_link(graph.links()[0].members.0, graph.links()[0].members.1, "first");
+        let costs = |graph: &Graph| graph.links().iter().map(|link| link.cost).collect::<Vec<usize>>();
+        let original = costs(&graph);
+
+        let scaled = graph.normalize_costs(Normalization::MinMax { min: 1, max: 100 });
+        assert_eq!(scaled.nodes(), graph.nodes());
+        assert_eq!(scaled.links_with_tag("first").len(), 1);
+        let scaled = costs(&scaled);
+        assert_eq!(scaled.iter().min(), Some(&1));
+        assert_eq!(scaled.iter().max(), Some(&100));
+        // the order of the costs is kept
+        for (a, b) in original.iter().zip(&scaled) {
+            for (c, d) in original.iter().zip(&scaled) {
+                assert!(a <= c || b >= d);
+            }
+        }
+
+        let standard = costs(&graph.normalize_costs(Normalization::ZScore { mean: 1000.0, deviation: 100.0 }));
+        let mean = standard.iter().sum::<usize>() as f64 / standard.len() as f64;
+        let deviation = (standard.iter().map(|cost| (*cost as f64 - mean).powi(2)).sum::<f64>() / standard.len() as f64).sqrt();
+        assert!((mean - 1000.0).abs() < 1.0);
+        assert!((deviation - 100.0).abs() < 1.0);
+
+        let clamped = costs(&graph.normalize_costs(Normalization::Clamp { min: 1000, max: 2000 }));
+        assert!(clamped.iter().zip(&original).all(|(cost, original)| *cost == (*original).clamp(1000, 2000)));
+
+        // equal costs and costs which would become negative
+        let mut equal = Graph::new();
+        equal.allow_dangling_links(true);
+        equal.add_link(Link::new((0, 1), 7));
+        equal.add_link(Link::new((1, 2), 7));
+        assert_eq!(costs(&equal.normalize_costs(Normalization::MinMax { min: 3, max: 9 })), vec![3, 3]);
+        assert_eq!(costs(&equal.normalize_costs(Normalization::ZScore { mean: 5.0, deviation: 2.0 })), vec![5, 5]);
+        equal.add_link(Link::new((2, 3), 1));
+        assert_eq!(costs(&equal.normalize_costs(Normalization::ZScore { mean: 0.0, deviation: 1.0 })), vec![1, 1, 0]);
+        assert!(Graph::new().normalize_costs(Normalization::MinMax { min: 0, max: 1 }).links().is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let mut rng = crate::util::SplitMix64::new(26);
+        let mut first = crate::generate::erdos_renyi(10, 0.3, 1..5, &mut rng);
+        let second = crate::generate::erdos_renyi(15, 0.3, 1..5, &mut rng);
+        first.tag_node(3, "first");
+        let mut tagged = second.clone();
+        tagged.tag_node(12, "second");
+        tagged.allow_dangling_links(true);
+        tagged.add_link(Link::new((10, 99), 1));
+
+        // the names of both generated graphs are their ids, so the first ten nodes are shared
+        let mut merged = first.clone();
+        let ids = merged.merge(&tagged);
+        assert_eq!(ids, (0..15).collect::<Vec<isize>>());
+        assert_eq!(merged.nodes().len(), 15);
+        assert!(merged.links().iter().all(|link| first.contains_link(link.members.0, link.members.1)
+            || second.contains_link(link.members.0, link.members.1)));
+        assert!(first.links().iter().chain(second.links()).all(|link| merged.contains_link(link.members.0, link.members.1)));
+        assert!(!merged.contains_link(10, 99));
+        assert_eq!(merged.nodes_with_tag("first"), vec![3]);
+        assert_eq!(merged.nodes_with_tag("second"), vec![12]);
+
+        // with a prefix, nothing is shared
+        let mut prefixed = first.clone();
+        let ids = prefixed.merge_prefixed(&tagged, "b-");
+        assert_eq!(ids, (10..25).collect::<Vec<isize>>());
+        assert_eq!(prefixed.links().len(), first.links().len() + second.links().len());
+        assert_eq!(prefixed.summary().components, first.summary().components + second.summary().components);
+        assert_eq!(prefixed.nodes()[22].name, "b-12");
+        assert_eq!(prefixed.nodes_with_tag("second"), vec![22]);
+
+        assert!(prefixed.merge(&Graph::new()).is_empty());
+        let mut empty = Graph::new();
+        empty.allow_dangling_links(true);
+        empty.merge(&first);
+        assert_eq!(empty, first);
+    }
+
+    #[test]
+    fn subgraphs() {
+        // a campus of two buildings, one of them with floors of their own
+        let mut floor = crate::generate::topology(&crate::generate::TopologySpec::Line { nodes: 2 }, 1);
+        floor.tag_node(1, "printer");
+        let mut building = crate::generate::topology(&crate::generate::TopologySpec::Star { leaves: 2 }, 2);
+        assert!(building.set_subgraph(1, floor.clone()));
+        assert!(building.set_subgraph(2, floor));
+        let mut campus = Graph::new();
+        let north = campus.add_node(Node::new("North"));
+        let south = campus.add_node(Node::new("South"));
+        let empty = campus.add_node(Node::new("Empty"));
+        campus.add_link(Link::new((north, south), 10));
+        campus.add_link(Link::new((south, empty), 10));
+        campus.tag_node(south, "site");
+        assert!(campus.set_subgraph(south, building));
+        assert!(campus.set_subgraph(empty, Graph::new()));
+        assert!(!campus.set_subgraph(9, Graph::new()));
+        assert_eq!(campus.nodes_with_subgraph(), vec![south, empty]);
+        assert_eq!(campus.subgraph(south).unwrap().nodes_with_subgraph(), vec![1, 2]);
+
+        let flat = campus.flatten();
+        let names: Vec<&str> = flat.nodes().iter().map(|node| node.name).collect();
+        assert_eq!(names, vec!["North", "South/hub", "South/leaf-0/0", "South/leaf-0/1", "South/leaf-1/0", "South/leaf-1/1", "Empty"]);
+        assert!(flat.contains_link(0, 1) && flat.contains_link(1, 6));
+        assert!(flat.contains_link(1, 2) && flat.contains_link(2, 3) && flat.contains_link(1, 4) && flat.contains_link(4, 5));
+        assert_eq!(flat.links().len(), 6);
+        assert_eq!(flat.nodes_with_tag("site"), vec![1]);
+        assert_eq!(flat.nodes_with_tag("printer"), vec![3, 5]);
+        assert!(flat.nodes_with_subgraph().is_empty());
+        assert_eq!(flat.summary().components, 1);
+
+        let dot = campus.to_dot_hierarchical();
+        assert!(dot.starts_with("graph {\n    \"0\" [label=\"North\"];\n    subgraph \"cluster_1\" {\n        label=\"South\";\n"));
+        assert!(dot.contains("        subgraph \"cluster_1/1\" {\n            label=\"leaf-0\";\n"));
+        assert!(dot.contains("            \"1/1/1\" [label=\"1\"];\n"));
+        assert!(dot.contains("        \"1/0\" -- \"1/1/0\" [label=\"2\"];\n"));
+        assert!(dot.contains("    \"0\" -- \"1/0\" [label=\"10\"];\n    \"1/0\" -- \"2\" [label=\"10\"];\n}\n"));
+        assert_eq!(Graph::new().to_dot_hierarchical(), Graph::new().to_dot());
+        assert!(campus.remove_subgraph(south).is_some());
+        assert_eq!(campus.flatten(), campus);
+    }
+
+    #[test]
+    fn views() {
+        let mut rng = crate::util::SplitMix64::new(28);
+        let graph = crate::generate::connected(40, 4, 1..20, &mut rng);
+        let view = graph.view(|node| node.id % 7 != 3, |link| link.cost % 5 != 0);
+        // the same graph, pruned by copying
+        let mut pruned = Graph::new();
+        for node in graph.nodes() {
+            pruned.add_node(Node::new(node.name));
+        }
+        for link in graph.links() {
+            if link.members.0 % 7 != 3 && link.members.1 % 7 != 3 && link.cost % 5 != 0 {
+                pruned.add_link(*link);
+            }
+        }
+        // the filtered nodes have no links left in the copy
+        let cost = |result: Option<SearchResult>| result.map(|result| result.cost);
+        for id in 0..40 {
+            assert_eq!(view.neighbors(id), pruned.neighbors(id));
+            assert_eq!(
+                cost(crate::dijkstra::dijkstra_search_generic(&view, 0, id)),
+                cost(crate::dijkstra::dijkstra_search_generic(&pruned, 0, id))
+            );
+        }
+        assert!(!view.contains_node(3) && !view.contains_node(40) && !view.contains_node(-1));
+        assert!(view.neighbors(40).is_empty());
+        assert_eq!(view.graph().nodes().len(), 40);
+    }
+
+    #[test]
+    fn tags() {
+        let mut graph = crate::generate::fat_tree(4);
+        assert_eq!(graph.nodes_with_tag("core"), vec![0, 1, 2, 3]);
+        assert_eq!(graph.nodes_with_tag("host").len(), 16);
+        assert_eq!(graph.node_tags(4), vec!["aggregation"]);
+        // the tags do not change the topology
+        assert_eq!(graph, Graph::from_parts(graph.nodes().to_vec(), graph.links().to_vec()));
+
+        assert!(graph.tag_link(4, 0, "uplink"));
+        assert!(graph.tag_link(1, 4, "uplink"));
+        assert!(!graph.tag_link(0, 5, "uplink"));
+        assert_eq!(graph.links_with_tag("uplink").len(), 2);
+        assert_eq!(graph.link_tags(0, 4), vec!["uplink"]);
+        assert!(graph.untag_link(0, 4, "uplink"));
+        assert!(!graph.untag_link(0, 4, "uplink"));
+        assert!(graph.link_tags(0, 4).is_empty());
+        assert!(graph.untag_node(4, "aggregation"));
+        assert!(graph.node_tags(4).is_empty());
+        assert!(!graph.tag_node(-1, "core"));
+
+        // restricted to one layer, the hosts cannot be reached from the core
+        let filter = graph.tag_filter("core");
+        assert!(crate::bfs::bfs_search_node_filtered(graph.clone(), 0, 20, filter).is_none());
+        let filter = graph.link_tag_filter("uplink");
+        assert!(crate::bfs::bfs_search_node_filtered(graph.clone(), 1, 4, filter).is_some());
+        let filter = graph.link_tag_filter("uplink");
+        assert!(crate::bfs::bfs_search_node_filtered(graph, 0, 4, filter).is_none());
+    }
+
+    #[test]
+    fn history() {
+        let mut rng = crate::util::SplitMix64::new(41);
+        let mut graph = crate::generate::erdos_renyi(6, 0.4, 1..5, &mut rng);
+        graph.tag_node(0, "before");
+        assert!(graph.replay_to(0).is_none());
+        graph.record_history(true);
+        // every state along the way, to compare the replayed ones with
+        let mut states = vec![graph.clone()];
+        let same = |a: &Graph, b: &Graph| {
+            let subgraphs = a.nodes_with_subgraph() == b.nodes_with_subgraph();
+            a == b && a.node_tags == b.node_tags && a.link_tags == b.link_tags && subgraphs
+        };
+        let changes: [fn(&mut Graph); 7] = [
+            |graph| {
+                graph.add_node(Node::new("new"));
+            },
+            |graph| graph.add_link(Link::new((6, 0), 3)),
+            |graph| {
+                graph.tag_node(6, "edge");
+                graph.tag_link(0, 6, "uplink");
+            },
+            |graph| {
+                graph.untag_node(0, "before");
+                graph.untag_link(6, 0, "uplink");
+            },
+            |graph| {
+                graph.set_subgraph(6, crate::generate::topology(&crate::generate::TopologySpec::Line { nodes: 2 }, 1));
+            },
+            |graph| {
+                graph.merge_prefixed(&crate::generate::erdos_renyi(4, 0.5, 1..5, &mut crate::util::SplitMix64::new(3)), "m-");
+            },
+            |graph| {
+                graph.remove_subgraph(6);
+            },
+        ];
+        for change in &changes {
+            change(&mut graph);
+            states.push(graph.clone());
+        }
+        // changes which change nothing are not recorded
+        graph.add_link(Link::new((0, 6), 9));
+        graph.add_node(Node::new("new"));
+        assert!(!graph.tag_node(6, "edge") && !graph.untag_node(0, "before") && graph.remove_subgraph(6).is_none());
+
+        let history = graph.history();
+        assert_eq!(history.len(), 9);
+        assert!(history.windows(2).all(|pair| pair[0].at <= pair[1].at));
+        assert_eq!(history[0].mutation, Mutation::AddNode(Node { id: 6, ..Node::new("new") }));
+        assert!(matches!(&history[7].mutation, Mutation::Merge(other) if other.nodes()[0].name == "m-0"));
+        let steps = [0, 1, 2, 4, 6, 7, 8, 9];
+        for (state, step) in states.iter().zip(steps) {
+            let replayed = graph.replay_to(step).unwrap();
+            assert!(same(&replayed, state));
+            assert!(!replayed.is_recording_history());
+        }
+        assert!(graph.replay_to(10).is_none());
+
+        // starting again keeps the history, stopping drops it
+        graph.record_history(true);
+        assert_eq!(graph.history().len(), 9);
+        graph.record_history(false);
+        assert!(!graph.is_recording_history() && graph.history().is_empty());
+        graph.add_node(Node::new("after"));
+        assert!(graph.history().is_empty());
+    }
+}
+
+#[cfg(all(test, feature = "debug-validate"))]
+mod validate_tests {
+    use super::*;
+
+    #[test]
+    fn valid_graph() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_node(Node::new("Node 1"));
+        graph.add_node(Node::new("Node 2"));
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 0), 1));
+        graph.add_link(Link::new((1, 7), 1));
+        graph.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "is stored at index")]
+    fn wrong_node_id() {
+        let mut graph = Graph::new();
+        let mut node = Node::new("Node 1");
+        node.id = 3;
+        graph.node_list.push(node);
+        graph.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "share the name")]
+    fn duplicate_node_name() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new("Node 1"));
+        let mut node = Node::new("Node 1");
+        node.id = 1;
+        graph.node_list.push(node);
+        graph.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "missing from the link set")]
+    fn unindexed_link() {
+        let mut graph = Graph::new();
+        graph.link_list.push(Link::new((1, 2), 1));
+        graph.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "which is no node of the graph")]
+    fn dangling_link() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new("Node 1"));
+        graph.add_link(Link::new((0, 1), 1));
+    }
+
+    #[test]
+    fn allowed_dangling_link() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_node(Node::new("Node 1"));
+        graph.add_node(Node::new("Node 2"));
+        graph.allow_dangling_links(false);
+        graph.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "ends at 2")]
+    fn disallowed_dangling_link() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_link(Link::new((0, 2), 1));
+        graph.add_node(Node::new("Node 1"));
+        graph.allow_dangling_links(false);
+    }
+
+    #[test]
+    #[should_panic(expected = "more than one link")]
+    fn duplicate_link() {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        graph.add_link(Link::new((1, 2), 1));
+        graph.link_list.push(Link::new((2, 1), 3));
+        graph.validate();
+    }
+}
+
+#[cfg(test)]
+mod link_tests {
+    use super::Link;
+    #[test]
+    pub fn new() {
+        let link = Link::new((1, 2), 3);
+        assert_eq!(link.members, (1, 2));
+        assert_eq!(link.cost, 3);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Link::new((1, 2), 3).to_string(), "1 -(3)- 2");
+    }
+}
+
+#[cfg(test)]
 mod node_tests {
     use super::Node;
 
@@ -375,6 +3400,13 @@ mod node_tests {
         assert_eq!(node.id, -1);
         assert_eq!(node.name, "A");
     }
+
+    #[test]
+    fn display() {
+        let mut node = Node::new("A");
+        node.id = 4;
+        assert_eq!(node.to_string(), "A (4)");
+    }
 }
 
 #[cfg(test)]
@@ -394,4 +3426,11 @@ mod search_result_tests {
         assert_eq!(buildresult.cost, 2);
         assert_eq!(buildresult.links, vec!(testlink));
     }
+
+    #[test]
+    fn display() {
+        assert_eq!(SearchResult::new().to_string(), "no path, total 0");
+        let to_self = SearchResult::new().links(vec![Link::new((3, 3), 0)]);
+        assert_eq!(to_self.to_string(), "3, total 0");
+    }
 }
@@ -0,0 +1,219 @@
+//! [Greedy algorithms](https://en.wikipedia.org/wiki/Greedy_algorithm) on intervals, like the time slots of maintenance windows.
+//!
+//! Like Kruskal's algorithm in mst, which always adds the cheapest link not closing a cycle, they take the locally best choice
+//! at every step and never revise it, which is provably optimal for these problems.
+//!
+//! Intervals are half-open ranges, so an interval ending at 3 does not overlap one starting at 3.
+//! The results refer to the intervals by their index in the given slice.
+
+use crate::datastructures::BinaryHeap;
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Decides which of several equally good intervals the greedy algorithms take first.
+///
+/// The number of chosen intervals or resources is the same for all of them, only which intervals are picked differs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum TieBreak {
+    /// The interval given first.
+    #[default]
+    LowestIndex,
+    /// The interval given last.
+    HighestIndex,
+    /// The shortest interval.
+    Shortest,
+    /// The longest interval.
+    Longest,
+}
+
+impl TieBreak {
+    /// Orders the intervals at the two indices, the preferred one first.
+    fn compare(self, intervals: &[Range<usize>], a: usize, b: usize) -> Ordering {
+        let len = |index: usize| intervals[index].end.saturating_sub(intervals[index].start);
+        match self {
+            TieBreak::LowestIndex => a.cmp(&b),
+            TieBreak::HighestIndex => b.cmp(&a),
+            TieBreak::Shortest => len(a).cmp(&len(b)).then(a.cmp(&b)),
+            TieBreak::Longest => len(b).cmp(&len(a)).then(a.cmp(&b)),
+        }
+    }
+}
+
+/// Chooses the most intervals which do not overlap each other, by [interval scheduling](https://en.wikipedia.org/wiki/Interval_scheduling)
+/// in O(n log n) time: the interval ending first is taken, then the next one ending first that starts after it, and so on.
+///
+/// Of several intervals ending at the same time, the tie break decides which one is taken.
+/// Empty intervals are taken after the others ending at the same time, as they do not overlap those.
+/// Returns the indices of the chosen intervals, ordered by time.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::greedy::*;
+///
+/// let windows = [0..6, 1..4, 3..5, 4..7, 5..9, 8..9, 6..10];
+/// assert_eq!(interval_scheduling(&windows, TieBreak::LowestIndex), vec![1, 3, 5]);
+/// ```
+pub fn interval_scheduling(intervals: &[Range<usize>], tie_break: TieBreak) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    let empty = |index: usize| intervals[index].is_empty();
+    order.sort_by(|&a, &b| {
+        intervals[a].end.cmp(&intervals[b].end).then(empty(a).cmp(&empty(b))).then_with(|| tie_break.compare(intervals, a, b))
+    });
+    let mut chosen = Vec::new();
+    let mut free_from = 0;
+    for index in order {
+        if intervals[index].start >= free_from {
+            chosen.push(index);
+            free_from = intervals[index].end.max(intervals[index].start);
+        }
+    }
+    chosen
+}
+
+/// Assigns every interval to a resource, like a technician or a link, so that the intervals of a resource never overlap,
+/// using the fewest resources, by interval partitioning in O(n log n) time.
+///
+/// The intervals are assigned by their start, each to the resource which became free the earliest.
+/// The fewest resources needed is the greatest number of intervals overlapping at any time.
+/// Of several intervals starting at the same time, the tie break decides which one is assigned first.
+/// Returns the indices of the intervals of every resource, ordered by time.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::greedy::*;
+///
+/// let lectures = [9..11, 9..13, 9..11, 11..13, 11..14, 13..14];
+/// let rooms = interval_partitioning(&lectures, TieBreak::LowestIndex);
+/// assert_eq!(rooms, vec![vec![0, 3, 5], vec![1], vec![2, 4]]);
+/// ```
+pub fn interval_partitioning(intervals: &[Range<usize>], tie_break: TieBreak) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_by(|&a, &b| intervals[a].start.cmp(&intervals[b].start).then_with(|| tie_break.compare(intervals, a, b)));
+    let mut resources: Vec<Vec<usize>> = Vec::new();
+    // the time every resource becomes free, the earliest first
+    let mut free: BinaryHeap<(usize, usize)> = BinaryHeap::new();
+    for index in order {
+        let interval = &intervals[index];
+        let end = interval.end.max(interval.start);
+        match free.peek() {
+            Some(&(free_from, resource)) if free_from <= interval.start => {
+                free.pop();
+                resources[resource].push(index);
+                free.push((end, resource));
+            }
+            _ => {
+                free.push((end, resources.len()));
+                resources.push(vec![index]);
+            }
+        }
+    }
+    resources
+}
+
+#[cfg(test)]
+mod greedy_test {
+    use super::*;
+    use crate::util::{RandomSource, SplitMix64};
+
+    const TIE_BREAKS: [TieBreak; 4] = [TieBreak::LowestIndex, TieBreak::HighestIndex, TieBreak::Shortest, TieBreak::Longest];
+
+    fn overlap(a: &Range<usize>, b: &Range<usize>) -> bool {
+        a.start < b.end && b.start < a.end
+    }
+
+    fn random_intervals(rng: &mut SplitMix64, count: usize) -> Vec<Range<usize>> {
+        (0..count)
+            .map(|_| {
+                let start = rng.random_index(20);
+                start..start + 1 + rng.random_index(6)
+            })
+            .collect()
+    }
+
+    /// The most intervals not overlapping, by brute force over all subsets.
+    fn most_compatible(intervals: &[Range<usize>]) -> usize {
+        (0..1usize << intervals.len())
+            .filter(|subset| {
+                let picked: Vec<&Range<usize>> =
+                    intervals.iter().enumerate().filter(|(index, _)| subset & (1 << index) != 0).map(|(_, interval)| interval).collect();
+                picked.iter().enumerate().all(|(index, a)| picked[index + 1..].iter().all(|b| !overlap(a, b)))
+            })
+            .map(|subset| subset.count_ones() as usize)
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn scheduling() {
+        let mut rng = SplitMix64::new(15);
+        for _ in 0..100 {
+            let count = rng.random_index(11);
+            let intervals = random_intervals(&mut rng, count);
+            let most = most_compatible(&intervals);
+            for tie_break in TIE_BREAKS {
+                let chosen = interval_scheduling(&intervals, tie_break);
+                assert_eq!(chosen.len(), most);
+                assert!(chosen.windows(2).all(|pair| intervals[pair[0]].end <= intervals[pair[1]].start));
+            }
+        }
+        assert!(interval_scheduling(&[], TieBreak::default()).is_empty());
+    }
+
+    #[test]
+    fn scheduling_empty_intervals() {
+        // the empty interval ends together with 2..4, but fits right after it
+        let intervals = [7..11, 4..4, 0..2, 2..4];
+        for tie_break in TIE_BREAKS {
+            assert_eq!(interval_scheduling(&intervals, tie_break), vec![2, 3, 1, 0]);
+        }
+        assert_eq!(interval_scheduling(&[3..3, 3..3, 1..5], TieBreak::LowestIndex), vec![0, 1]);
+        let mut rng = SplitMix64::new(53);
+        for _ in 0..100 {
+            let count = rng.random_index(11);
+            let intervals: Vec<Range<usize>> = (0..count)
+                .map(|_| {
+                    let start = rng.random_index(12);
+                    start..start + rng.random_index(4)
+                })
+                .collect();
+            let most = most_compatible(&intervals);
+            for tie_break in TIE_BREAKS {
+                let chosen = interval_scheduling(&intervals, tie_break);
+                assert_eq!(chosen.len(), most);
+                assert!(chosen.windows(2).all(|pair| intervals[pair[0]].end <= intervals[pair[1]].start));
+            }
+        }
+    }
+
+    #[test]
+    fn tie_breaks() {
+        let intervals = [2..5, 0..5, 4..5, 1..5];
+        assert_eq!(interval_scheduling(&intervals, TieBreak::LowestIndex), vec![0]);
+        assert_eq!(interval_scheduling(&intervals, TieBreak::HighestIndex), vec![3]);
+        assert_eq!(interval_scheduling(&intervals, TieBreak::Shortest), vec![2]);
+        assert_eq!(interval_scheduling(&intervals, TieBreak::Longest), vec![1]);
+        let intervals = [0..4, 0..1, 0..2];
+        assert_eq!(interval_partitioning(&intervals, TieBreak::Shortest), vec![vec![1], vec![2], vec![0]]);
+        assert_eq!(interval_partitioning(&intervals, TieBreak::HighestIndex), vec![vec![2], vec![1], vec![0]]);
+    }
+
+    #[test]
+    fn partitioning() {
+        let mut rng = SplitMix64::new(16);
+        for _ in 0..100 {
+            let count = rng.random_index(30);
+            let intervals = random_intervals(&mut rng, count);
+            let depth = (0..30).map(|time| intervals.iter().filter(|interval| interval.contains(&time)).count()).max().unwrap();
+            for tie_break in TIE_BREAKS {
+                let resources = interval_partitioning(&intervals, tie_break);
+                assert_eq!(resources.len(), depth);
+                let mut assigned: Vec<usize> = resources.concat();
+                assigned.sort_unstable();
+                assert_eq!(assigned, (0..intervals.len()).collect::<Vec<_>>());
+                for resource in &resources {
+                    assert!(resource.windows(2).all(|pair| intervals[pair[0]].end <= intervals[pair[1]].start));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! Searches on implicit graphs, which are given by a successor function instead of a Graph.
+//!
+//! The successor function returns the neighbours of a node together with the cost of the link leading to each of them.
+//! Nodes are only created when the search reaches them, so these searches work on graphs which are far too large
+//! to be built up front, like the states of a puzzle. They stop at the first node satisfying the goal,
+//! so on infinite graphs they only terminate if such a node is reachable.
+//!
+//! The links of the results point from every node to the next one, the first link is from the start node to itself.
+
+use crate::datastructures::{BinaryHeap, Handle};
+use crate::graph::{GraphLike, Link, SearchResult};
+use std::collections::{HashMap, VecDeque};
+
+/// An implicit graph given by its successor function, which can be passed to all searches taking a GraphLike.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::dijkstra;
+/// use rust_algorithms::implicit::Successors;
+///
+/// // every number is linked to its double and its successor
+/// let numbers = Successors(|id: isize| vec![(id * 2, 1), (id + 1, 1)]);
+/// assert_eq!(dijkstra::dijkstra_search_generic(&numbers, 1, 10).unwrap().cost, 4);
+/// ```
+pub struct Successors<S>(pub S);
+
+impl<S> GraphLike for Successors<S>
+where
+    S: Fn(isize) -> Vec<(isize, usize)>,
+{
+    fn neighbors(&self, node_id: isize) -> Vec<(isize, usize)> {
+        (self.0)(node_id)
+    }
+}
+
+/// Searches for the path with the fewest links to a node satisfying the goal, using the [Breadth-first search](https://en.wikipedia.org/wiki/Breadth-first_search) method.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::implicit;
+///
+/// // the fewest steps to reach 10 from 1, doubling or adding one
+/// let result = implicit::bfs_search(1, |id| id == 10, |id| vec![(id * 2, 1), (id + 1, 1)]).unwrap();
+/// let ids: Vec<isize> = result.links.iter().map(|link| link.members.1).collect();
+/// assert_eq!(ids, vec![1, 2, 4, 5, 10]);
+/// ```
+pub fn bfs_search<G, S>(start_node_id: isize, goal: G, successors: S) -> Option<SearchResult>
+where
+    G: Fn(isize) -> bool,
+    S: Fn(isize) -> Vec<(isize, usize)>,
+{
+    let mut previous: HashMap<isize, (isize, usize)> = HashMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(start_node_id);
+    while let Some(current) = queue.pop_front() {
+        if goal(current) {
+            return Some(path(&previous, start_node_id, current));
+        }
+        for (next, cost) in successors(current) {
+            if next != start_node_id && !previous.contains_key(&next) {
+                previous.insert(next, (current, cost));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Searches for any path to a node satisfying the goal, using the [Depth-first search](https://en.wikipedia.org/wiki/Depth-first_search) method.
+///
+/// The search does not recurse, so it also works on graphs with very long paths.
+/// The last successor of every node is visited first.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::implicit;
+///
+/// let result = implicit::dfs_search(0, |id| id == 3, |id| if id < 5 { vec![(id + 1, 2)] } else { vec![] }).unwrap();
+/// assert_eq!(result.cost, 6);
+/// assert!(implicit::dfs_search(0, |id| id == 9, |id| if id < 5 { vec![(id + 1, 2)] } else { vec![] }).is_none());
+/// ```
+pub fn dfs_search<G, S>(start_node_id: isize, goal: G, successors: S) -> Option<SearchResult>
+where
+    G: Fn(isize) -> bool,
+    S: Fn(isize) -> Vec<(isize, usize)>,
+{
+    let mut previous: HashMap<isize, (isize, usize)> = HashMap::new();
+    let mut stack = vec![start_node_id];
+    while let Some(current) = stack.pop() {
+        if goal(current) {
+            return Some(path(&previous, start_node_id, current));
+        }
+        for (next, cost) in successors(current) {
+            if next != start_node_id && !previous.contains_key(&next) {
+                previous.insert(next, (current, cost));
+                stack.push(next);
+            }
+        }
+    }
+    None
+}
+
+/// Searches for the cheapest path to a node satisfying the goal, using [Dijkstra's algorithm](https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm).
+///
+/// # Example
+/// ```
+/// use rust_algorithms::implicit;
+///
+/// // doubling costs 3, adding one costs 1
+/// let result = implicit::dijkstra_search(1, |id| id == 10, |id| vec![(id * 2, 3), (id + 1, 1)]).unwrap();
+/// assert_eq!(result.cost, 7);
+/// ```
+pub fn dijkstra_search<G, S>(start_node_id: isize, goal: G, successors: S) -> Option<SearchResult>
+where
+    G: Fn(isize) -> bool,
+    S: Fn(isize) -> Vec<(isize, usize)>,
+{
+    astar_search(start_node_id, goal, successors, |_| 0)
+}
+
+/// Searches for the cheapest path to a node satisfying the goal, using the [A* search algorithm](https://en.wikipedia.org/wiki/A*_search_algorithm).
+///
+/// The heuristic estimates the cost from a node to the nearest node satisfying the goal.
+/// If it never overestimates the cost, the path found is the cheapest one, like with dijkstra_search,
+/// but usually far fewer nodes are visited.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::implicit;
+///
+/// // the points of an endless grid, with the id being 1000 * x + y
+/// let successors = |id: isize| vec![(id + 1000, 1), (id - 1000, 1), (id + 1, 1), (id - 1, 1)];
+/// let distance = |id: isize| ((id / 1000 - 7).abs() + (id % 1000 - 3).abs()) as usize;
+/// let result = implicit::astar_search(0, |id| id == 7003, successors, distance).unwrap();
+/// assert_eq!(result.cost, 10);
+/// ```
+pub fn astar_search<G, S, H>(start_node_id: isize, goal: G, successors: S, heuristic: H) -> Option<SearchResult>
+where
+    G: Fn(isize) -> bool,
+    S: Fn(isize) -> Vec<(isize, usize)>,
+    H: Fn(isize) -> usize,
+{
+    // the lowest known cost to every node and the node and link cost it has been reached through.
+    // the queue is ordered by the estimated total cost of a path through the node and holds every discovered node once.
+    let mut costs: HashMap<isize, usize> = HashMap::new();
+    let mut previous: HashMap<isize, (isize, usize)> = HashMap::new();
+    let mut handles: HashMap<isize, Handle> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    costs.insert(start_node_id, 0);
+    handles.insert(start_node_id, queue.push((heuristic(start_node_id), 0, start_node_id)));
+    while let Some((_, cost, current)) = queue.pop() {
+        if goal(current) {
+            return Some(path(&previous, start_node_id, current));
+        }
+        for (next, link_cost) in successors(current) {
+            let next_cost = cost + link_cost;
+            if costs.get(&next).is_none_or(|known| next_cost < *known) {
+                costs.insert(next, next_cost);
+                previous.insert(next, (current, link_cost));
+                let estimate = (next_cost + heuristic(next), next_cost, next);
+                match handles.get(&next) {
+                    Some(handle) if queue.contains(*handle) => {
+                        queue.decrease_key(*handle, estimate);
+                    }
+                    _ => {
+                        handles.insert(next, queue.push(estimate));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Builds the result of a search from the previous node of every node on the path and the cost of the link leading there.
+fn path(previous: &HashMap<isize, (isize, usize)>, start_node_id: isize, target: isize) -> SearchResult {
+    let mut links = Vec::new();
+    let mut node = target;
+    while node != start_node_id {
+        // every node reached except for the start has a previous node
+        let (from, cost) = previous[&node];
+        links.push(Link::new((from, node), cost));
+        node = from;
+    }
+    links.push(Link::new((start_node_id, start_node_id), 0));
+    links.reverse();
+    let cost = links.iter().map(|link| link.cost).sum();
+    SearchResult::new().links(links).cost(cost)
+}
+
+#[cfg(test)]
+mod implicit_test {
+    use super::*;
+
+    /// The knight moves on a chess board, with the id being 8 * row + column.
+    fn knight(id: isize) -> Vec<(isize, usize)> {
+        let (row, column) = (id / 8, id % 8);
+        [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)]
+            .iter()
+            .map(|(rows, columns)| (row + rows, column + columns))
+            .filter(|(row, column)| (0..8).contains(row) && (0..8).contains(column))
+            .map(|(row, column)| (8 * row + column, 1))
+            .collect()
+    }
+
+    #[test]
+    fn searches_agree() {
+        // a knight needs 6 moves from one corner to the opposite one
+        assert_eq!(bfs_search(0, |id| id == 63, knight).unwrap().cost, 6);
+        assert_eq!(dijkstra_search(0, |id| id == 63, knight).unwrap().cost, 6);
+        let distance = |id: isize| ((7 - id / 8) + (7 - id % 8)) as usize / 3;
+        assert_eq!(astar_search(0, |id| id == 63, knight, distance).unwrap().cost, 6);
+        let result = dfs_search(0, |id| id == 63, knight).unwrap();
+        assert!(result.links.windows(2).all(|pair| knight(pair[0].members.1).contains(&(pair[1].members.1, 1))));
+        assert_eq!(result.cost, result.links.len() - 1);
+    }
+
+    #[test]
+    fn goals() {
+        let result = bfs_search(5, |_| true, knight).unwrap();
+        assert_eq!(result.links, vec![Link::new((5, 5), 0)]);
+        assert!(bfs_search(0, |id| id == 64, knight).is_none());
+        assert!(dijkstra_search(0, |id| id < 0, knight).is_none());
+        // any of the squares in the last row
+        assert_eq!(bfs_search(0, |id| id >= 56, knight).unwrap().cost, 4);
+    }
+
+    #[test]
+    fn successors_graph() {
+        let board = Successors(knight);
+        assert_eq!(board.neighbors(0).len(), 2);
+        assert_eq!(board.link_cost(0, 10), Some(1));
+        assert_eq!(board.link_cost(0, 1), None);
+        assert_eq!(crate::bfs::bfs_search_generic(&board, 0, 63).unwrap().cost, 6);
+    }
+}
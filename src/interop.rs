@@ -0,0 +1,118 @@
+//! Conversions between this crate's Graph and the graphs of [petgraph](https://docs.rs/petgraph),
+//! available with the `petgraph` feature.
+//!
+//! Nodes are converted into node weights holding their name, links into edge weights holding their cost.
+//! As links are undirected, the conversions use petgraph's undirected graph.
+
+use crate::graph::{Graph, Link, Node};
+use crate::util::node_index;
+use petgraph::graph::{NodeIndex, UnGraph};
+
+/// Converts the graph into a petgraph graph.
+///
+/// The index of every node in the petgraph graph is its id in this graph.
+/// Links to nodes which are not part of the graph are left out.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use petgraph::graph::{NodeIndex, UnGraph};
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node(Node::new("A"));
+/// let b = graph.add_node(Node::new("B"));
+/// graph.add_link(Link::new((a, b), 5));
+///
+/// let converted = UnGraph::<&'static str, usize>::from(&graph);
+/// assert_eq!(converted[NodeIndex::new(1)], "B");
+/// assert_eq!(converted.edge_weights().collect::<Vec<_>>(), vec![&5]);
+/// ```
+impl From<&Graph> for UnGraph<&'static str, usize> {
+    fn from(graph: &Graph) -> Self {
+        let nodes = graph.nodes();
+        let mut converted = UnGraph::with_capacity(nodes.len(), graph.links().len());
+        for node in nodes {
+            converted.add_node(node.name);
+        }
+        let index_of = |id: isize| node_index(id, nodes.len()).map(NodeIndex::new);
+        for link in graph.links() {
+            if let (Some(a), Some(b)) = (index_of(link.members.0), index_of(link.members.1)) {
+                converted.add_edge(a, b, link.cost);
+            }
+        }
+        converted
+    }
+}
+
+/// Converts a petgraph graph into a graph.
+///
+/// Nodes with the same name become a single node, like with Graph::add_node,
+/// and of several edges between the same nodes only the first one is kept, like with Graph::add_link.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use petgraph::graph::UnGraph;
+///
+/// let mut converted = UnGraph::<&'static str, usize>::new_undirected();
+/// let a = converted.add_node("A");
+/// let b = converted.add_node("B");
+/// converted.add_edge(a, b, 5);
+///
+/// let mut graph = Graph::from(&converted);
+/// assert_eq!(graph.nodes().len(), 2);
+/// assert_eq!(graph.find_link(0, 1).unwrap().cost, 5);
+/// ```
+impl From<&UnGraph<&'static str, usize>> for Graph {
+    fn from(converted: &UnGraph<&'static str, usize>) -> Self {
+        let mut graph = Graph::new();
+        let ids: Vec<isize> = converted
+            .node_indices()
+            .map(|index| graph.add_node(Node::new(converted[index])))
+            .collect();
+        for edge in converted.edge_indices() {
+            // the index is taken from the graph, so the edge exists
+            let (a, b) = converted.edge_endpoints(edge).unwrap();
+            graph.add_link(Link::new((ids[a.index()], ids[b.index()]), converted[edge]));
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod interop_test {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let mut graph = Graph::new();
+        for name in ["A", "B", "C"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((2, 1), 4));
+        graph.add_link(Link::new((2, 2), 0));
+        graph.allow_dangling_links(true);
+        graph.add_link(Link::new((2, 7), 3));
+
+        let converted = UnGraph::from(&graph);
+        assert_eq!(converted.node_count(), 3);
+        assert_eq!(converted.edge_count(), 3);
+        let back = Graph::from(&converted);
+        assert_eq!(back.nodes(), graph.nodes());
+        assert_eq!(back.links(), &graph.links()[..3]);
+    }
+
+    #[test]
+    fn merges_duplicates() {
+        let mut converted = UnGraph::<&'static str, usize>::new_undirected();
+        let a = converted.add_node("A");
+        let b = converted.add_node("B");
+        let other_a = converted.add_node("A");
+        converted.add_edge(a, b, 1);
+        converted.add_edge(b, other_a, 2);
+        let graph = Graph::from(&converted);
+        assert_eq!(graph.nodes().len(), 2);
+        assert_eq!(graph.links(), &[Link::new((0, 1), 1)]);
+    }
+}
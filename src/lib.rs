@@ -1,7 +1,57 @@
+#[macro_use]
+mod logging;
+
 pub mod graph;
+pub mod astar;
+pub mod auto;
 pub mod bfs;
+pub mod community;
+pub mod cover;
+pub mod cycles;
+pub mod dag;
+pub mod datastructures;
 pub mod dfs;
+pub mod dijkstra;
+pub mod distvec;
+mod dot;
+pub mod dp;
+pub mod election;
+pub mod experiment;
+pub mod flow;
+pub mod frozen;
+pub mod generate;
+pub mod geometry;
+pub mod gossip;
+pub mod greedy;
+pub mod implicit;
+#[cfg(feature = "petgraph")]
+mod interop;
+mod json;
+pub mod loader;
+pub mod metrics;
+pub mod mst;
+pub mod partition;
+pub mod pathfinder;
+pub mod paths;
+pub mod placement;
+pub mod planarity;
+pub mod puzzle;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod report;
+pub mod resilience;
+pub mod search;
+pub mod simulation;
+pub mod sorting;
 pub mod spanningtree;
+pub mod strings;
+pub mod switching;
+pub mod temporal;
+pub mod traffic;
+pub mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod wordladder;
 
 #[cfg(test)]
 mod tests {
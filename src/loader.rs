@@ -0,0 +1,187 @@
+use crate::graph::{leak_name, Graph, Link, Node};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead};
+
+/// The progress of a GraphLoader, as passed to its progress callback.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// The number of lines read so far, including comments and empty lines.
+    pub lines: usize,
+    /// The number of distinct nodes found so far.
+    pub nodes: usize,
+    /// The number of distinct links found so far.
+    pub links: usize,
+    /// The number of links which have been skipped, because they connect already linked nodes.
+    pub duplicates: usize,
+}
+
+/// Builds a graph from an edge list, as used by the [SNAP datasets](https://snap.stanford.edu/data/).
+///
+/// Every line contains the labels of two nodes separated by whitespace, optionally followed by the cost of the link.
+/// Empty lines and lines starting with `#` or `%` are skipped.
+/// The file is read line by line, so besides the graph itself only an index of the node labels
+/// and linked node pairs is kept in memory. Links between already linked nodes are skipped.
+///
+/// Every distinct label becomes a node named after it, in the order they first appear.
+/// The labels are leaked for this, see Node::name.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::loader::GraphLoader;
+///
+/// let edges = "# FromNodeId ToNodeId\n10 20\n20 30 5\n30 20\n";
+/// let mut reports = Vec::new();
+/// let graph = GraphLoader::new()
+///     .default_cost(2)
+///     .on_progress(2, |progress| reports.push(progress.lines))
+///     .load(edges.as_bytes())
+///     .unwrap();
+///
+/// assert_eq!(graph.nodes().len(), 3);
+/// assert_eq!(graph.nodes()[0].name, "10");
+/// assert_eq!(graph.links().len(), 2);
+/// assert_eq!(graph.links()[0].cost, 2);
+/// assert_eq!(graph.links()[1].cost, 5);
+/// assert_eq!(reports, vec![2, 4]);
+/// ```
+pub struct GraphLoader<'a> {
+    default_cost: usize,
+    progress_interval: usize,
+    progress: Option<Box<dyn FnMut(LoadProgress) + 'a>>,
+}
+
+impl<'a> GraphLoader<'a> {
+    /// Creates a loader which gives links without a cost the cost 1.
+    pub fn new() -> Self {
+        GraphLoader {
+            default_cost: 1,
+            progress_interval: 0,
+            progress: None,
+        }
+    }
+
+    /// Sets the cost of links for which the edge list contains no cost.
+    pub fn default_cost(mut self, cost: usize) -> Self {
+        self.default_cost = cost;
+        self
+    }
+
+    /// Sets a callback which is called every `interval` lines and once more after the last line, unless it has just been
+    /// called for it. An interval of 0 only reports the final progress.
+    pub fn on_progress<F>(mut self, interval: usize, callback: F) -> Self
+    where
+        F: FnMut(LoadProgress) + 'a,
+    {
+        self.progress_interval = interval;
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Reads the edge list and builds a graph from it.
+    /// Lines which do not contain two labels or contain an invalid cost fail with `ErrorKind::InvalidData`.
+    pub fn load<R: BufRead>(mut self, mut reader: R) -> io::Result<Graph> {
+        let mut nodes = Vec::new();
+        let mut links = Vec::new();
+        let mut ids: HashMap<&'static str, isize> = HashMap::new();
+        let mut pairs = HashSet::new();
+        let mut progress = LoadProgress::default();
+        let mut line = String::new();
+
+        let mut id_of = |label: &str, nodes: &mut Vec<Node>| -> isize {
+            if let Some(&id) = ids.get(label) {
+                return id;
+            }
+            let name = leak_name(label.to_string());
+            let mut node = Node::new(name);
+            node.id = nodes.len() as isize;
+            nodes.push(node);
+            ids.insert(name, node.id);
+            node.id
+        };
+
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            progress.lines += 1;
+            let content = line.trim();
+            if !(content.is_empty() || content.starts_with('#') || content.starts_with('%')) {
+                let invalid = |message: &str| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", progress.lines, message))
+                };
+                let mut fields = content.split_whitespace();
+                let (a, b) = match (fields.next(), fields.next()) {
+                    (Some(a), Some(b)) => (a, b),
+                    _ => return Err(invalid("expected two node labels")),
+                };
+                let cost = match fields.next() {
+                    Some(cost) => cost.parse().map_err(|_| invalid("invalid link cost"))?,
+                    None => self.default_cost,
+                };
+                let a = id_of(a, &mut nodes);
+                let b = id_of(b, &mut nodes);
+                if pairs.insert((a.min(b), a.max(b))) {
+                    links.push(Link::new((a, b), cost));
+                } else {
+                    progress.duplicates += 1;
+                }
+                progress.nodes = nodes.len();
+                progress.links = links.len();
+            }
+            if self.progress_interval > 0 && progress.lines.is_multiple_of(self.progress_interval) {
+                if let Some(callback) = &mut self.progress {
+                    callback(progress);
+                }
+            }
+        }
+        let reported = self.progress_interval > 0 && progress.lines > 0 && progress.lines.is_multiple_of(self.progress_interval);
+        if let Some(callback) = self.progress.as_mut().filter(|_| !reported) {
+            callback(progress);
+        }
+        Ok(Graph::from_parts(nodes, links))
+    }
+}
+
+impl Default for GraphLoader<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod loader_test {
+    use super::*;
+
+    #[test]
+    fn load_edge_list() {
+        let edges = "% comment\n\n  a\tb  \nb a\nc c 4\r\nb c\n";
+        let mut last = LoadProgress::default();
+        let graph = GraphLoader::new().on_progress(0, |progress| last = progress).load(edges.as_bytes()).unwrap();
+        assert_eq!(last, LoadProgress { lines: 6, nodes: 3, links: 3, duplicates: 1 });
+        assert_eq!(graph.links(), &[Link::new((0, 1), 1), Link::new((2, 2), 4), Link::new((1, 2), 1)]);
+        assert_eq!(graph.nodes()[2].name, "c");
+    }
+
+    #[test]
+    fn load_progress() {
+        let reports_of = |edges: &str| {
+            let mut reports = Vec::new();
+            GraphLoader::new().on_progress(2, |progress| reports.push(progress.lines)).load(edges.as_bytes()).unwrap();
+            reports
+        };
+        // the last line is reported once, whether it falls on the interval or not
+        assert_eq!(reports_of("1 2\n2 3\n3 4\n4 5\n"), vec![2, 4]);
+        assert_eq!(reports_of("1 2\n2 3\n3 4\n4 5\n5 6\n"), vec![2, 4, 5]);
+        assert_eq!(reports_of(""), vec![0]);
+    }
+
+    #[test]
+    fn load_errors() {
+        let error = GraphLoader::new().load("1 2\n3\n".as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "line 2: expected two node labels");
+        let error = GraphLoader::new().load("1 2 -1\n".as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "line 1: invalid link cost");
+    }
+}
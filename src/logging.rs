@@ -0,0 +1,25 @@
+//! Instrumentation through the [log](https://docs.rs/log) crate.
+//!
+//! With the `log` feature, the searches and simulations log what they do, results at debug level and single steps at
+//! trace level, so the logger chosen by the application decides how much of it is shown.
+//! Without the feature, the macros compile to nothing, besides checking their arguments.
+
+/// Logs a single step at trace level with the `log` feature.
+macro_rules! log_trace {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        ::log::trace!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)+);
+    };
+}
+
+/// Logs a result at debug level with the `log` feature.
+macro_rules! log_debug {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        ::log::debug!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)+);
+    };
+}
@@ -0,0 +1,138 @@
+//! Metrics of every node of a graph, written as a CSV table with one line per node, to be read by spreadsheets or
+//! plotting tools.
+
+use crate::graph::Graph;
+use crate::resilience::betweenness_centrality;
+use crate::spanningtree::{self, Tree};
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// Writes the metrics of every node as CSV, with a header line and one line per node in the order of graph.nodes().
+///
+/// The columns are the id and the name of the node, its degree, counted like in Graph::summary, and its betweenness
+/// centrality, as computed by resilience::betweenness_centrality. Names are quoted if they contain commas, quotes or
+/// line breaks.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::metrics;
+///
+/// let mut graph = Graph::new();
+/// for name in ["A", "B", "C"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// graph.add_link(Link::new((0, 1), 1));
+/// graph.add_link(Link::new((1, 2), 1));
+///
+/// let mut csv = Vec::new();
+/// metrics::export_csv(&graph, &mut csv).unwrap();
+/// assert_eq!(String::from_utf8(csv).unwrap(), "id,name,degree,betweenness\n0,A,1,0\n1,B,2,1\n2,C,1,0\n");
+/// ```
+pub fn export_csv<W: Write>(graph: &Graph, writer: &mut W) -> io::Result<()> {
+    write_csv(graph, None, writer)
+}
+
+/// Like export_csv, with an additional column root_cost: the cost of the path of every node to the root of the tree,
+/// as the node knows it.
+///
+/// The tree is matched to the graph by the ids of the nodes, like a tree created by Tree::from_graph.
+/// The cost is left empty for nodes which are not part of the tree and for those which do not know the root yet.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+/// use rust_algorithms::metrics;
+/// use rust_algorithms::spanningtree::Tree;
+///
+/// let line = generate::topology(&TopologySpec::Line { nodes: 3 }, 2);
+/// let mut tree = Tree::from_graph(&line);
+/// tree.run_calc(0, true);
+///
+/// let mut csv = Vec::new();
+/// metrics::export_csv_with_tree(&line, &tree, &mut csv).unwrap();
+/// let csv = String::from_utf8(csv).unwrap();
+/// assert_eq!(csv.lines().next(), Some("id,name,degree,betweenness,root_cost"));
+/// assert!(csv.lines().last().unwrap().ends_with(",1,0,4"));
+/// ```
+pub fn export_csv_with_tree<W: Write>(graph: &Graph, tree: &Tree, writer: &mut W) -> io::Result<()> {
+    write_csv(graph, Some(tree), writer)
+}
+
+fn write_csv<W: Write>(graph: &Graph, tree: Option<&Tree>, writer: &mut W) -> io::Result<()> {
+    let degrees = graph.degrees();
+    let betweenness = betweenness_centrality(graph);
+    let tree_nodes: Option<HashMap<isize, &spanningtree::Node>> =
+        tree.map(|tree| tree.nodes().iter().map(|node| (node.id, node)).collect());
+    let root_id = tree.and_then(Tree::root_id);
+    write!(writer, "id,name,degree,betweenness")?;
+    if tree.is_some() {
+        write!(writer, ",root_cost")?;
+    }
+    writeln!(writer)?;
+    for (index, node) in graph.nodes().iter().enumerate() {
+        let name = if node.name.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", node.name.replace('"', "\"\""))
+        } else {
+            node.name.to_string()
+        };
+        write!(writer, "{},{},{},{}", node.id, name, degrees[index], betweenness[index])?;
+        if let Some(tree_nodes) = &tree_nodes {
+            let root_cost = tree_nodes
+                .get(&node.id)
+                .filter(|tree_node| Some(tree_node.root_id) == root_id)
+                .map(|tree_node| tree_node.root_cost.to_string())
+                .unwrap_or_default();
+            write!(writer, ",{}", root_cost)?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod metrics_test {
+    use super::*;
+    use crate::generate;
+    use crate::graph::{Link, Node};
+
+    fn export(graph: &Graph, tree: Option<&Tree>) -> Vec<String> {
+        let mut csv = Vec::new();
+        match tree {
+            Some(tree) => export_csv_with_tree(graph, tree, &mut csv).unwrap(),
+            None => export_csv(graph, &mut csv).unwrap(),
+        }
+        String::from_utf8(csv).unwrap().lines().map(String::from).collect()
+    }
+
+    fn root_costs_of(graph: &Graph, tree: &Tree) -> Vec<String> {
+        export(graph, Some(tree))[1..].iter().map(|line| line.rsplit(',').next().unwrap().to_string()).collect()
+    }
+
+    #[test]
+    fn node_metrics() {
+        let mut graph = generate::topology(&generate::TopologySpec::Star { leaves: 3 }, 1);
+        graph.add_node(Node::new("Lonely, \"isolated\""));
+        graph.add_link(Link::new((1, 1), 1));
+        let lines = export(&graph, None);
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[1], format!("0,{},3,3", graph.nodes()[0].name));
+        assert_eq!(lines[2], format!("1,{},3,0", graph.nodes()[1].name));
+        assert_eq!(lines[5], "4,\"Lonely, \"\"isolated\"\"\",0,0");
+        assert_eq!(export(&Graph::new(), None), vec!["id,name,degree,betweenness"]);
+    }
+
+    #[test]
+    fn root_costs() {
+        let graph = generate::topology(&generate::TopologySpec::Line { nodes: 4 }, 3);
+        let mut tree = Tree::from_graph(&graph);
+        // nobody has heard of the root yet, except the root itself
+        assert_eq!(root_costs_of(&graph, &tree), vec!["0", "", "", ""]);
+        tree.run_calc(0, true);
+        assert_eq!(root_costs_of(&graph, &tree), vec!["0", "3", "6", "9"]);
+        // nodes are matched by id, those missing from the tree get no cost
+        let mut tree = Tree::from_graph(&generate::topology(&generate::TopologySpec::Line { nodes: 2 }, 3));
+        tree.run_calc(0, true);
+        assert_eq!(root_costs_of(&graph, &tree), vec!["0", "3", "", ""]);
+    }
+}
@@ -0,0 +1,306 @@
+use crate::datastructures::DisjointSet;
+use crate::graph::*;
+use crate::util::{node_index, report_progress, Progress};
+
+/// How much the cost of a link can change while the minimum spanning tree stays a minimum spanning tree,
+/// as computed by edge_sensitivity.
+/// None stands for a change without any limit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Sensitivity {
+    /// Whether the link is part of the minimum spanning tree.
+    pub in_tree: bool,
+    /// How much the cost may increase.
+    pub max_increase: Option<usize>,
+    /// How much the cost may decrease. Costs cannot drop below 0, so this is at most the cost of the link.
+    pub max_decrease: Option<usize>,
+}
+
+/// Computes a minimum spanning tree of the graph using [Kruskal's algorithm](https://en.wikipedia.org/wiki/Kruskal%27s_algorithm).
+///
+/// If the graph is not connected, the result is a minimum spanning forest, containing a tree for every part of the graph.
+/// Links to nodes which are not part of the graph and links from a node to itself are ignored.
+/// Of several links with the same cost, the one which was added to the graph first is preferred.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::mst;
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node(Node::new("A"));
+/// let b = graph.add_node(Node::new("B"));
+/// let c = graph.add_node(Node::new("C"));
+/// graph.add_link(Link::new((a, b), 1));
+/// graph.add_link(Link::new((b, c), 2));
+/// graph.add_link(Link::new((a, c), 3));
+///
+/// assert_eq!(mst::minimum_spanning_tree(&graph), vec![Link::new((a, b), 1), Link::new((b, c), 2)]);
+/// ```
+pub fn minimum_spanning_tree(graph: &Graph) -> Vec<Link> {
+    let (tree, _) = split(graph, |_| ());
+    tree
+}
+
+/// Computes a minimum spanning tree like minimum_spanning_tree, passing the number of links considered so far,
+/// out of all usable links, to the progress callback about every percent of them and once at the end.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::{generate, mst};
+/// use rust_algorithms::util::SplitMix64;
+///
+/// let graph = generate::connected(1000, 4, 1..100, &mut SplitMix64::new(1));
+/// let mut last = None;
+/// let tree = mst::minimum_spanning_tree_with_progress(&graph, |progress| last = progress.fraction());
+/// assert_eq!(tree, mst::minimum_spanning_tree(&graph));
+/// assert_eq!(last, Some(1.0));
+/// ```
+pub fn minimum_spanning_tree_with_progress<F>(graph: &Graph, progress: F) -> Vec<Link>
+where
+    F: FnMut(Progress),
+{
+    let (tree, _) = split(graph, progress);
+    tree
+}
+
+/// Computes the cheapest spanning tree which differs from the minimum spanning tree.
+///
+/// It is found by adding a link which is not part of the minimum spanning tree and removing the most
+/// expensive link of the cycle this closes, choosing the link for which this is cheapest.
+/// Its cost may be equal to the one of the minimum spanning tree, if there are several of them.
+/// Returns None if the minimum spanning tree is the only spanning tree.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::mst;
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node(Node::new("A"));
+/// let b = graph.add_node(Node::new("B"));
+/// let c = graph.add_node(Node::new("C"));
+/// graph.add_link(Link::new((a, b), 1));
+/// graph.add_link(Link::new((b, c), 2));
+/// graph.add_link(Link::new((a, c), 3));
+///
+/// let second = mst::second_best(&graph).unwrap();
+/// assert_eq!(second, vec![Link::new((a, b), 1), Link::new((a, c), 3)]);
+/// assert_eq!(second.iter().map(|link| link.cost).sum::<usize>(), 4);
+///
+/// graph.add_node(Node::new("D"));
+/// graph.add_link(Link::new((c, 3), 1));
+/// assert_eq!(mst::second_best(&graph).unwrap().len(), 3);
+/// ```
+pub fn second_best(graph: &Graph) -> Option<Vec<Link>> {
+    let (tree, others) = split(graph, |_| ());
+    let count = graph.nodes().len();
+    // the cheapest swap of a link outside of the tree with the most expensive link on its cycle
+    let (added, removed) = others
+        .iter()
+        .filter_map(|link| {
+            let path = tree_path(&tree, count, link.members.0, link.members.1)?;
+            let removed = path.into_iter().max_by_key(|&index| (tree[index].cost, index))?;
+            Some((link, removed))
+        })
+        .min_by_key(|(link, removed)| link.cost - tree[*removed].cost)?;
+
+    let mut second = tree;
+    second[removed] = *added;
+    Some(second)
+}
+
+/// Computes how much the cost of the link can change before the minimum spanning tree has to change.
+///
+/// A link of the tree may become cheaper without limit, and more expensive up to the cost of the cheapest
+/// link which could replace it. A link outside of the tree may become more expensive without limit, and cheaper
+/// down to the cost of the most expensive link on the path between its members through the tree.
+/// Returns None if the link is not part of the graph.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::mst::{self, Sensitivity};
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node(Node::new("A"));
+/// let b = graph.add_node(Node::new("B"));
+/// let c = graph.add_node(Node::new("C"));
+/// graph.add_link(Link::new((a, b), 1));
+/// graph.add_link(Link::new((b, c), 2));
+/// graph.add_link(Link::new((a, c), 5));
+///
+/// let sensitivity = mst::edge_sensitivity(&graph, &Link::new((b, c), 2)).unwrap();
+/// assert_eq!(sensitivity, Sensitivity { in_tree: true, max_increase: Some(3), max_decrease: Some(2) });
+///
+/// let sensitivity = mst::edge_sensitivity(&graph, &Link::new((a, c), 5)).unwrap();
+/// assert_eq!(sensitivity, Sensitivity { in_tree: false, max_increase: None, max_decrease: Some(3) });
+/// ```
+pub fn edge_sensitivity(graph: &Graph, link: &Link) -> Option<Sensitivity> {
+    let (a, b) = link.members;
+    let (tree, others) = split(graph, |_| ());
+    let count = graph.nodes().len();
+    let same = |other: &Link| other.members == (a, b) || other.members == (b, a);
+
+    if let Some(position) = tree.iter().position(same) {
+        let cost = tree[position].cost;
+        // the cheapest link outside of the tree whose cycle contains the link
+        let replacement = others
+            .iter()
+            .filter(|other| {
+                tree_path(&tree, count, other.members.0, other.members.1)
+                    .is_some_and(|path| path.contains(&position))
+            })
+            .map(|other| other.cost)
+            .min();
+        return Some(Sensitivity {
+            in_tree: true,
+            max_increase: replacement.map(|replacement| replacement - cost),
+            max_decrease: Some(cost),
+        });
+    }
+
+    let other = others.iter().find(|other| same(other))?;
+    let path = tree_path(&tree, count, a, b)?;
+    let most_expensive = path.iter().map(|&index| tree[index].cost).max().unwrap_or(0);
+    Some(Sensitivity {
+        in_tree: false,
+        max_increase: None,
+        max_decrease: Some(other.cost - most_expensive),
+    })
+}
+
+/// Splits the usable links of the graph into the links of the minimum spanning tree and the remaining ones.
+fn split<F>(graph: &Graph, mut progress: F) -> (Vec<Link>, Vec<Link>)
+where
+    F: FnMut(Progress),
+{
+    let count = graph.nodes().len();
+    let mut links: Vec<Link> = graph
+        .links()
+        .iter()
+        .filter(|link| match (node_index(link.members.0, count), node_index(link.members.1, count)) {
+            (Some(a), Some(b)) => a != b,
+            _ => false,
+        })
+        .copied()
+        .collect();
+    // the sort is stable, so links with the same cost keep their order
+    links.sort_by_key(|link| link.cost);
+
+    // the trees of the forest built so far
+    let mut trees = DisjointSet::new(count);
+    let mut tree = Vec::new();
+    let mut others = Vec::new();
+    let total = links.len();
+    if total == 0 {
+        report_progress(&mut progress, 0, 0);
+    }
+    for (done, link) in links.into_iter().enumerate() {
+        report_progress(&mut progress, done + 1, total);
+        let a = node_index(link.members.0, count).unwrap();
+        let b = node_index(link.members.1, count).unwrap();
+        if trees.union(a, b) {
+            tree.push(link);
+        } else {
+            others.push(link);
+        }
+    }
+    (tree, others)
+}
+
+/// Finds the path between two nodes through the tree and returns the indices of the tree links it consists of.
+/// Returns None if the nodes are in different trees of a forest.
+fn tree_path(tree: &[Link], count: usize, from: isize, to: isize) -> Option<Vec<usize>> {
+    let from = node_index(from, count)?;
+    let to = node_index(to, count)?;
+    let mut neighbours: Vec<Vec<(usize, usize)>> = vec![Vec::new(); count];
+    for (index, link) in tree.iter().enumerate() {
+        let a = node_index(link.members.0, count)?;
+        let b = node_index(link.members.1, count)?;
+        neighbours[a].push((b, index));
+        neighbours[b].push((a, index));
+    }
+
+    // a depth-first search, remembering the tree link every node has been reached through
+    let mut reached_by: Vec<Option<(usize, usize)>> = vec![None; count];
+    let mut visited = vec![false; count];
+    let mut stack = vec![from];
+    visited[from] = true;
+    while let Some(node) = stack.pop() {
+        if node == to {
+            let mut path = Vec::new();
+            let mut current = to;
+            while let Some((previous, index)) = reached_by[current] {
+                path.push(index);
+                current = previous;
+            }
+            return Some(path);
+        }
+        for &(next, index) in &neighbours[node] {
+            if !visited[next] {
+                visited[next] = true;
+                reached_by[next] = Some((node, index));
+                stack.push(next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod mst_test {
+    use super::*;
+
+    fn graph() -> Graph {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for name in ["A", "B", "C", "D", "E"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 4));
+        graph.add_link(Link::new((0, 2), 1));
+        graph.add_link(Link::new((1, 2), 2));
+        graph.add_link(Link::new((1, 3), 5));
+        graph.add_link(Link::new((2, 3), 8));
+        graph.add_link(Link::new((3, 4), 3));
+        graph.add_link(Link::new((4, 4), 0));
+        graph.add_link(Link::new((4, 9), 0));
+        graph
+    }
+
+    #[test]
+    fn minimum_spanning_tree() {
+        let tree = super::minimum_spanning_tree(&graph());
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.iter().map(|link| link.cost).sum::<usize>(), 11);
+        assert!(super::minimum_spanning_tree(&Graph::new()).is_empty());
+    }
+
+    #[test]
+    fn second_best() {
+        let second = super::second_best(&graph()).unwrap();
+        assert_eq!(second.len(), 4);
+        // replacing 2 with 4 is the cheapest swap
+        assert_eq!(second.iter().map(|link| link.cost).sum::<usize>(), 13);
+        assert!(second.contains(&Link::new((0, 1), 4)));
+
+        let mut path = Graph::new();
+        path.add_node(Node::new("A"));
+        path.add_node(Node::new("B"));
+        path.add_link(Link::new((0, 1), 1));
+        assert!(super::second_best(&path).is_none());
+    }
+
+    #[test]
+    fn edge_sensitivity() {
+        let graph = graph();
+        let bridge = super::edge_sensitivity(&graph, &Link::new((4, 3), 3)).unwrap();
+        assert_eq!(bridge, Sensitivity { in_tree: true, max_increase: None, max_decrease: Some(3) });
+        let tree_link = super::edge_sensitivity(&graph, &Link::new((1, 3), 5)).unwrap();
+        assert_eq!(tree_link.max_increase, Some(3));
+        let other = super::edge_sensitivity(&graph, &Link::new((0, 1), 4)).unwrap();
+        assert_eq!(other, Sensitivity { in_tree: false, max_increase: None, max_decrease: Some(2) });
+        assert!(super::edge_sensitivity(&graph, &Link::new((0, 4), 1)).is_none());
+        assert!(super::edge_sensitivity(&graph, &Link::new((4, 4), 0)).is_none());
+    }
+}
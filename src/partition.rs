@@ -0,0 +1,233 @@
+//! [Graph partitioning](https://en.wikipedia.org/wiki/Graph_partition): splitting the nodes into parts of equal size,
+//! with links between the parts as cheap as possible, like placing the nodes of a simulated network on racks
+//! so that little traffic has to cross between them.
+//!
+//! The cost of a link is the weight of keeping its members together, so a partition is measured by the total cost of the
+//! links between different parts, see cut_cost. Partitions are returned as the part of every node, in the order of graph.nodes().
+
+use crate::dijkstra::{index_of, neighbours};
+use crate::graph::{Graph, Link};
+use std::collections::HashMap;
+
+/// The most improvement passes of every bisection, if swapping keeps reducing the cut.
+const MAX_PASSES: usize = 50;
+
+/// Splits the nodes into the given number of parts of equal size, up to one node, using the
+/// [Kernighan–Lin algorithm](https://en.wikipedia.org/wiki/Kernighan%E2%80%93Lin_algorithm).
+///
+/// The nodes are split in half recursively. Every split starts with the nodes in the order of graph.nodes(),
+/// then swaps pairs of nodes between both halves as long as that lowers the cost of the links between them.
+/// The result is a local optimum, not necessarily the cheapest partition.
+/// Every pass of a split takes O(n² log n) time for its n nodes.
+/// Links from a node to itself and to nodes outside of the graph are ignored.
+///
+/// # Panics
+/// Panics if parts is 0.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::partition;
+///
+/// // two squares, where the nodes of each square are not next to each other in the order of the nodes
+/// let mut graph = Graph::new();
+/// for name in ["A", "B", "C", "D", "E", "F", "G", "H"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// for (a, b) in [(0, 2), (2, 4), (4, 6), (6, 0), (1, 3), (3, 5), (5, 7), (7, 1), (0, 1)] {
+///     graph.add_link(Link::new((a, b), 1));
+/// }
+/// let parts = partition::kernighan_lin(&graph, 2);
+/// assert_eq!(parts, vec![1, 0, 1, 0, 1, 0, 1, 0]);
+/// assert_eq!(partition::cut_cost(&graph, &parts), 1);
+/// ```
+pub fn kernighan_lin(graph: &Graph, parts: usize) -> Vec<usize> {
+    assert!(parts > 0, "at least one part is needed");
+    let neighbours = neighbours(graph);
+    let mut labels = vec![0; neighbours.len()];
+    split(&neighbours, (0..neighbours.len()).collect(), parts, 0, &mut labels);
+    labels
+}
+
+/// Returns the total cost of the links whose members are in different parts.
+/// Links from a node to itself and to nodes outside of the graph are ignored.
+///
+/// # Panics
+/// Panics if there are fewer parts than nodes.
+pub fn cut_cost(graph: &Graph, parts: &[usize]) -> usize {
+    assert!(parts.len() >= graph.nodes().len(), "every node needs a part");
+    graph
+        .links()
+        .iter()
+        .filter(|link| match (index_of(graph, link.members.0), index_of(graph, link.members.1)) {
+            (Some(a), Some(b)) => parts[a] != parts[b],
+            _ => false,
+        })
+        .map(|link| link.cost)
+        .sum()
+}
+
+/// Splits the nodes into the given number of parts, labelled from first on.
+fn split(neighbours: &[Vec<(usize, &Link)>], nodes: Vec<usize>, parts: usize, first: usize, labels: &mut [usize]) {
+    if parts == 1 {
+        for node in nodes {
+            labels[node] = first;
+        }
+        return;
+    }
+    let left_parts = parts / 2;
+    let left_len = nodes.len() * left_parts / parts;
+    let (left, right) = bisect(neighbours, nodes, left_len);
+    split(neighbours, left, left_parts, first, labels);
+    split(neighbours, right, parts - left_parts, first + left_parts, labels);
+}
+
+/// Splits the nodes into two halves, the first one with left_len nodes, using the Kernighan–Lin algorithm.
+fn bisect(neighbours: &[Vec<(usize, &Link)>], nodes: Vec<usize>, left_len: usize) -> (Vec<usize>, Vec<usize>) {
+    // the links between the nodes to split, by their local index
+    let local: HashMap<usize, usize> = nodes.iter().enumerate().map(|(index, node)| (*node, index)).collect();
+    let mut costs: HashMap<(usize, usize), i64> = HashMap::new();
+    let mut adjacent: Vec<Vec<(usize, i64)>> = vec![Vec::new(); nodes.len()];
+    for (index, node) in nodes.iter().enumerate() {
+        for (neighbour, link) in &neighbours[*node] {
+            if let Some(&other) = local.get(neighbour) {
+                adjacent[index].push((other, link.cost as i64));
+                *costs.entry((index, other)).or_insert(0) += link.cost as i64;
+            }
+        }
+    }
+    let cost = |a: usize, b: usize| costs.get(&(a, b)).copied().unwrap_or(0);
+
+    let mut is_left: Vec<bool> = (0..nodes.len()).map(|index| index < left_len).collect();
+    for _ in 0..MAX_PASSES {
+        // how much the cut gets cheaper by moving a node to the other half
+        let mut gains: Vec<i64> = adjacent
+            .iter()
+            .enumerate()
+            .map(|(index, adjacent)| {
+                adjacent.iter().map(|(other, cost)| if is_left[*other] == is_left[index] { -cost } else { *cost }).sum()
+            })
+            .collect();
+        let mut locked = vec![false; nodes.len()];
+        let mut swaps: Vec<(usize, usize, i64)> = Vec::new();
+        loop {
+            let side = |left: bool| {
+                let mut side: Vec<usize> = (0..nodes.len()).filter(|index| is_left[*index] == left && !locked[*index]).collect();
+                side.sort_by_key(|index| -gains[*index]);
+                side
+            };
+            let (left, right) = (side(true), side(false));
+            if left.is_empty() || right.is_empty() {
+                break;
+            }
+            // the pair gaining the most by swapping, the gain of a pair is at most the sum of the gains of its nodes
+            let mut best: Option<(i64, usize, usize)> = None;
+            for &a in &left {
+                if best.is_some_and(|(gain, _, _)| gains[a] + gains[right[0]] <= gain) {
+                    break;
+                }
+                for &b in &right {
+                    if best.is_some_and(|(gain, _, _)| gains[a] + gains[b] <= gain) {
+                        break;
+                    }
+                    let gain = gains[a] + gains[b] - 2 * cost(a, b);
+                    if best.is_none_or(|(best, _, _)| gain > best) {
+                        best = Some((gain, a, b));
+                    }
+                }
+            }
+            // both sides have nodes left, so there is a pair
+            let (gain, a, b) = best.unwrap();
+            locked[a] = true;
+            locked[b] = true;
+            swaps.push((a, b, gain));
+            for &moved in &[a, b] {
+                for &(other, cost) in &adjacent[moved] {
+                    if !locked[other] {
+                        gains[other] += if is_left[other] == is_left[moved] { 2 * cost } else { -2 * cost };
+                    }
+                }
+            }
+        }
+
+        // apply the swaps up to the point where the cut got the cheapest
+        let (mut total, mut best_total, mut best_len) = (0, 0, 0);
+        for (index, (_, _, gain)) in swaps.iter().enumerate() {
+            total += gain;
+            if total > best_total {
+                best_total = total;
+                best_len = index + 1;
+            }
+        }
+        if best_len == 0 {
+            break;
+        }
+        for &(a, b, _) in &swaps[..best_len] {
+            is_left[a] = false;
+            is_left[b] = true;
+        }
+    }
+
+    let left = nodes.iter().enumerate().filter(|(index, _)| is_left[*index]).map(|(_, node)| *node).collect();
+    let right = nodes.iter().enumerate().filter(|(index, _)| !is_left[*index]).map(|(_, node)| *node).collect();
+    (left, right)
+}
+
+#[cfg(test)]
+mod partition_test {
+    use super::*;
+    use crate::generate;
+    use crate::graph::Node;
+    use crate::util::{RandomSource, SplitMix64};
+
+    fn part_sizes(parts: &[usize], count: usize) -> Vec<usize> {
+        (0..count).map(|part| parts.iter().filter(|label| **label == part).count()).collect()
+    }
+
+    #[test]
+    fn interleaved_clusters() {
+        // two dense clusters, with the members of each cluster placed at random positions
+        let mut rng = SplitMix64::new(19);
+        let mut positions: Vec<isize> = (0..40).collect();
+        crate::util::choose_multiple(&mut positions, 40, &mut rng);
+        let mut graph = generate::erdos_renyi(40, 0.0, 1..2, &mut rng);
+        for cluster in [&positions[..20], &positions[20..]] {
+            for (index, a) in cluster.iter().enumerate() {
+                for b in &cluster[index + 1..] {
+                    if rng.random_f64() < 0.5 {
+                        graph.add_link(Link::new((*a, *b), 1 + rng.random_index(3)));
+                    }
+                }
+            }
+        }
+        graph.add_link(Link::new((positions[0], positions[20]), 2));
+        let parts = kernighan_lin(&graph, 2);
+        assert_eq!(cut_cost(&graph, &parts), 2);
+        assert_eq!(part_sizes(&parts, 2), vec![20, 20]);
+    }
+
+    #[test]
+    fn balanced_parts() {
+        let mut rng = SplitMix64::new(20);
+        let graph = generate::connected(50, 6, 1..5, &mut rng);
+        for count in 1..6 {
+            let parts = kernighan_lin(&graph, count);
+            let sizes = part_sizes(&parts, count);
+            assert!(sizes.iter().all(|size| *size == 50 / count || *size == 50 / count + 1), "{:?}", sizes);
+        }
+        // never worse than the halves in the order of the nodes, where the split starts
+        let initial: Vec<usize> = (0..50).map(|index| index / 25).collect();
+        assert!(cut_cost(&graph, &kernighan_lin(&graph, 2)) < cut_cost(&graph, &initial));
+        assert_eq!(kernighan_lin(&graph, 1), vec![0; 50]);
+    }
+
+    #[test]
+    fn small_graphs() {
+        let mut graph = Graph::new();
+        assert!(kernighan_lin(&graph, 2).is_empty());
+        graph.add_node(Node::new("A"));
+        graph.add_link(Link::new((0, 0), 5));
+        assert_eq!(kernighan_lin(&graph, 3), vec![2]);
+        assert_eq!(cut_cost(&graph, &[0]), 0);
+    }
+}
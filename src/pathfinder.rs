@@ -0,0 +1,155 @@
+use crate::astar::{astar_search_node, astar_search_report};
+use crate::bfs::{bfs_search_node, bfs_search_report};
+use crate::dfs::{dfs_search_node, dfs_search_report};
+use crate::dijkstra::{dijkstra_search_node, dijkstra_search_report};
+use crate::graph::*;
+
+/// A strategy to search for a path between two nodes of a graph.
+///
+/// It allows code to be written independently from the search algorithm used, e.g. to compare several of them.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::pathfinder::*;
+///
+/// let mut graph = Graph::new();
+/// let node1 = graph.add_node(Node::new("Node 1"));
+/// let node2 = graph.add_node(Node::new("Node 2"));
+/// let node3 = graph.add_node(Node::new("Node 3"));
+/// graph.add_link(Link::new((node1, node3), 10));
+/// graph.add_link(Link::new((node1, node2), 3));
+/// graph.add_link(Link::new((node2, node3), 4));
+///
+/// let finders: Vec<(&str, Box<dyn PathFinder>)> = vec![
+///     ("bfs", Box::new(BreadthFirst)),
+///     ("dijkstra", Box::new(Dijkstra)),
+/// ];
+/// let costs: Vec<usize> = finders
+///     .iter()
+///     .map(|(_, finder)| finder.find_path(&graph, node1, node3).unwrap().cost)
+///     .collect();
+/// assert_eq!(costs, vec![10, 7]);
+/// ```
+pub trait PathFinder {
+    /// Searches for a path from the start to the target node.
+    /// Returns None if there is no such path or the start node is not part of the graph.
+    fn find_path(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> Option<SearchResult>;
+
+    /// Works like find_path, but additionally reports statistics about the search.
+    /// Unless implemented otherwise, only the time the search took is reported.
+    fn find_path_report(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> SearchReport {
+        SearchReport::measure(|_| self.find_path(graph, start_node_id, search_node_id))
+    }
+}
+
+/// Finds the path with the fewest links using bfs_search_node.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BreadthFirst;
+
+/// Finds any path using dfs_search_node.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DepthFirst;
+
+/// Finds the cheapest path using dijkstra_search_node.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Dijkstra;
+
+/// Finds the cheapest path using astar_search_node with the given heuristic.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AStar<H> {
+    pub heuristic: H,
+}
+
+impl PathFinder for BreadthFirst {
+    fn find_path(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> Option<SearchResult> {
+        // the search marks the nodes it discovers, so it works on a copy without any earlier marks
+        bfs_search_node(graph.topology_clone(), start_node_id, search_node_id)
+    }
+
+    fn find_path_report(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> SearchReport {
+        bfs_search_report(graph.topology_clone(), start_node_id, search_node_id)
+    }
+}
+
+impl PathFinder for DepthFirst {
+    fn find_path(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> Option<SearchResult> {
+        dfs_search_node(graph.topology_clone(), start_node_id, search_node_id)
+    }
+
+    fn find_path_report(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> SearchReport {
+        dfs_search_report(graph.topology_clone(), start_node_id, search_node_id)
+    }
+}
+
+impl PathFinder for Dijkstra {
+    fn find_path(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> Option<SearchResult> {
+        dijkstra_search_node(graph, start_node_id, search_node_id)
+    }
+
+    fn find_path_report(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> SearchReport {
+        dijkstra_search_report(graph, start_node_id, search_node_id)
+    }
+}
+
+impl<H> PathFinder for AStar<H>
+where
+    H: Fn(&Node) -> usize,
+{
+    fn find_path(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> Option<SearchResult> {
+        astar_search_node(graph, start_node_id, search_node_id, &self.heuristic)
+    }
+
+    fn find_path_report(&self, graph: &Graph, start_node_id: isize, search_node_id: isize) -> SearchReport {
+        astar_search_report(graph, start_node_id, search_node_id, &self.heuristic)
+    }
+}
+
+#[cfg(test)]
+mod pathfinder_test {
+    use super::*;
+
+    fn graph() -> Graph {
+        let mut graph = Graph::new();
+        for name in ["Node 1", "Node 2", "Node 3", "Node 4", "Node 5"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1));
+        graph.add_link(Link::new((1, 2), 1));
+        graph.add_link(Link::new((2, 3), 1));
+        graph.add_link(Link::new((0, 3), 9));
+        graph
+    }
+
+    fn cost_with<P: PathFinder>(finder: P) -> Option<usize> {
+        finder.find_path(&graph(), 0, 3).map(|result| result.cost)
+    }
+
+    #[test]
+    fn all_finders() {
+        assert_eq!(cost_with(BreadthFirst), Some(9));
+        assert!(cost_with(DepthFirst).is_some());
+        assert_eq!(cost_with(Dijkstra), Some(3));
+        assert_eq!(cost_with(AStar { heuristic: |_: &Node| 0 }), Some(3));
+    }
+
+    #[test]
+    fn reports() {
+        let graph = graph();
+        let bfs = BreadthFirst.find_path_report(&graph, 0, 3);
+        let dijkstra = Dijkstra.find_path_report(&graph, 0, 3);
+        assert_eq!(bfs.result.unwrap().cost, 9);
+        assert_eq!(dijkstra.result.unwrap().cost, 3);
+        assert_eq!(bfs.stats.nodes_expanded, 2);
+        assert_eq!(dijkstra.stats.nodes_expanded, 3);
+    }
+
+    #[test]
+    fn unreachable_target() {
+        let finders: [&dyn PathFinder; 3] = [&BreadthFirst, &DepthFirst, &Dijkstra];
+        for finder in finders.iter() {
+            assert!(finder.find_path(&graph(), 0, 4).is_none());
+            assert!(finder.find_path(&graph(), 7, 1).is_none());
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Enumeration of paths between two nodes, e.g. to check the complete set of routes a small topology offers.
+//!
+//! Like the results of the searches, every path starts with a link from the start node to itself with a cost of 0.
+
+use crate::dijkstra::{index_of, neighbours};
+use crate::graph::{Graph, Link, SearchResult};
+
+/// The simple paths between two nodes, found one at a time, as returned by all_simple_paths.
+pub struct AllSimplePaths<'a> {
+    neighbours: Vec<Vec<(usize, &'a Link)>>,
+    start: isize,
+    target: Option<usize>,
+    max_hops: usize,
+    /// The nodes of the current path, each with the position of the next of its neighbours to try.
+    path: Vec<(usize, usize)>,
+    /// The links of the current path.
+    links: Vec<&'a Link>,
+    on_path: Vec<bool>,
+}
+
+/// Lists the [simple paths](https://en.wikipedia.org/wiki/Path_(graph_theory)) from start to target, which visit no node twice,
+/// with at most max_hops links each.
+///
+/// The paths are found lazily by a depth-first search, following the links of every node in the order they were added,
+/// so taking only the first few paths only costs the work needed to find them. The number of paths grows exponentially
+/// with the size of the graph, which max_hops keeps in check.
+/// A path from a node to itself consists of that node alone. If start or target are not part of the graph, there are no paths.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+/// use rust_algorithms::paths;
+///
+/// let graph = generate::topology(&TopologySpec::Grid { rows: 2, columns: 3 }, 1);
+/// let routes: Vec<Vec<isize>> = paths::all_simple_paths(&graph, 0, 5, 10).map(|path| path.node_ids()).collect();
+/// assert_eq!(routes, vec![vec![0, 1, 2, 5], vec![0, 1, 4, 5], vec![0, 3, 4, 1, 2, 5], vec![0, 3, 4, 5]]);
+/// assert_eq!(paths::all_simple_paths(&graph, 0, 5, 3).count(), 3);
+/// ```
+pub fn all_simple_paths(graph: &Graph, start: isize, target: isize, max_hops: usize) -> AllSimplePaths<'_> {
+    let neighbours = neighbours(graph);
+    let mut on_path = vec![false; neighbours.len()];
+    let path = match index_of(graph, start) {
+        Some(index) => {
+            on_path[index] = true;
+            vec![(index, 0)]
+        }
+        None => Vec::new(),
+    };
+    AllSimplePaths {
+        neighbours,
+        start,
+        target: index_of(graph, target),
+        max_hops,
+        path,
+        links: Vec::new(),
+        on_path,
+    }
+}
+
+impl<'a> AllSimplePaths<'a> {
+    /// Returns the current path, extended by the link.
+    fn result(&self, last: &Link) -> SearchResult {
+        let mut links = vec![Link::new((self.start, self.start), 0)];
+        links.extend(self.links.iter().map(|link| **link));
+        links.push(*last);
+        let cost = links.iter().map(|link| link.cost).sum();
+        SearchResult::new().cost(cost).links(links)
+    }
+}
+
+impl<'a> Iterator for AllSimplePaths<'a> {
+    type Item = SearchResult;
+
+    fn next(&mut self) -> Option<SearchResult> {
+        if self.path.len() == 1 && Some(self.path[0].0) == self.target {
+            self.path.clear();
+            return Some(SearchResult::new().cost(0).links(vec![Link::new((self.start, self.start), 0)]));
+        }
+        while let Some(&(node, position)) = self.path.last() {
+            let neighbour = self.neighbours[node].get(position).copied().filter(|_| self.links.len() < self.max_hops);
+            match neighbour {
+                Some((next, link)) => {
+                    self.path.last_mut().unwrap().1 += 1;
+                    if self.on_path[next] {
+                        continue;
+                    }
+                    // paths end at the target, so it is never passed through
+                    if Some(next) == self.target {
+                        return Some(self.result(link));
+                    }
+                    self.on_path[next] = true;
+                    self.path.push((next, 0));
+                    self.links.push(link);
+                }
+                None => {
+                    self.on_path[node] = false;
+                    self.path.pop();
+                    self.links.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod paths_test {
+    use super::*;
+    use crate::cycles;
+    use crate::generate;
+    use crate::util::SplitMix64;
+    use std::collections::HashSet;
+
+    #[test]
+    fn complete_graphs() {
+        // in a complete graph of n nodes, there are (n - 2)! / (n - 2 - k)! paths with k nodes between both ends
+        let graph = generate::erdos_renyi(6, 1.0, 1..2, &mut SplitMix64::new(1));
+        assert_eq!(all_simple_paths(&graph, 0, 5, 10).count(), 1 + 4 + 12 + 24 + 24);
+        assert_eq!(all_simple_paths(&graph, 0, 5, 2).count(), 1 + 4);
+        assert_eq!(all_simple_paths(&graph, 0, 5, 0).count(), 0);
+        let paths: Vec<SearchResult> = all_simple_paths(&graph, 2, 3, 10).collect();
+        let routes: HashSet<Vec<isize>> = paths.iter().map(SearchResult::node_ids).collect();
+        assert_eq!(routes.len(), paths.len());
+        for (path, route) in paths.iter().zip(paths.iter().map(SearchResult::node_ids)) {
+            assert_eq!((route[0], route[route.len() - 1]), (2, 3));
+            assert_eq!(route.iter().collect::<HashSet<_>>().len(), route.len());
+            assert_eq!(path.cost, route.len() - 1);
+        }
+    }
+
+    #[test]
+    fn paths_close_cycles() {
+        // every link of a cycle and the other path along the cycle between its ends, and nothing else
+        let mut rng = SplitMix64::new(31);
+        for _ in 0..20 {
+            let graph = generate::erdos_renyi(8, 0.3, 1..5, &mut rng);
+            for link in graph.links() {
+                let (a, b) = link.members;
+                let through = cycles::enumerate(&graph, usize::MAX)
+                    .into_iter()
+                    .filter(|cycle| {
+                        (0..cycle.len()).any(|index| {
+                            let pair = (cycle[index], cycle[(index + 1) % cycle.len()]);
+                            pair == (a, b) || pair == (b, a)
+                        })
+                    })
+                    .count();
+                assert_eq!(all_simple_paths(&graph, a, b, usize::MAX).count(), through + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn edge_cases() {
+        let graph = generate::topology(&generate::TopologySpec::Line { nodes: 3 }, 4);
+        let to_self: Vec<SearchResult> = all_simple_paths(&graph, 1, 1, 5).collect();
+        assert_eq!(to_self.len(), 1);
+        assert_eq!(to_self[0].links, vec![Link::new((1, 1), 0)]);
+        assert_eq!(all_simple_paths(&graph, 0, 9, 5).count(), 0);
+        assert_eq!(all_simple_paths(&graph, 9, 0, 5).count(), 0);
+        let mut paths = all_simple_paths(&graph, 2, 0, 5);
+        assert_eq!(paths.next().unwrap().cost, 8);
+        assert!(paths.next().is_none());
+        assert!(paths.next().is_none());
+    }
+}
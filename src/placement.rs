@@ -0,0 +1,162 @@
+//! Placement of services on a network, like SDN controllers or caches, so every node has one of them close by.
+
+use crate::dijkstra::neighbours;
+use crate::graph::{Graph, Link};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// The centers chosen by k_center, and how far every node is from the closest one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KCenter {
+    /// The ids of the centers, in the order they were chosen.
+    pub centers: Vec<isize>,
+    /// The id of the closest center of every node, in the order of graph.nodes(), None if it cannot reach any.
+    pub assignment: Vec<Option<isize>>,
+    /// The cost of the path from every node to its closest center, in the order of graph.nodes(), None if it cannot reach any.
+    pub distances: Vec<Option<usize>>,
+    /// The highest distance of any node to its closest center, None if a node cannot reach any.
+    pub radius: Option<usize>,
+}
+
+/// Chooses k nodes as centers, so the highest distance of any node to its closest center, the radius, is as small as possible.
+///
+/// Finding the best centers is NP-hard, so they are chosen greedily, as proposed by Gonzalez: the first node of the graph
+/// is the first center, and every further center is the node furthest away from all centers so far. As the costs of
+/// shortest paths obey the triangle inequality, the radius is at most twice the smallest possible one.
+/// Nodes which cannot reach any center are the furthest away, so every part of a graph which is not connected gets a
+/// center before any part gets a second one.
+///
+/// Takes O(k · links · log nodes) time. Fewer than k centers are chosen if the graph has fewer nodes.
+/// Links to nodes outside of the graph and links from a node to itself are ignored.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+/// use rust_algorithms::placement;
+///
+/// // on a line of 9 nodes, three centers leave every node at most 2 links away from one
+/// let line = generate::topology(&TopologySpec::Line { nodes: 9 }, 1);
+/// let placement = placement::k_center(&line, 3);
+/// assert_eq!(placement.centers, vec![0, 8, 4]);
+/// assert_eq!(placement.radius, Some(2));
+/// assert_eq!(placement.assignment[6], Some(8));
+/// assert_eq!(placement.distances[6], Some(2));
+/// ```
+pub fn k_center(graph: &Graph, k: usize) -> KCenter {
+    let nodes = graph.nodes();
+    let neighbours = neighbours(graph);
+    let mut centers = Vec::new();
+    let mut assignment: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut distances: Vec<Option<usize>> = vec![None; nodes.len()];
+    while centers.len() < k.min(nodes.len()) {
+        // the node furthest away from all centers, with unreachable nodes the furthest
+        // centers are left out, as zero-cost links can leave other nodes just as close to them
+        let next = (0..nodes.len())
+            .filter(|index| assignment[*index] != Some(*index))
+            .max_by_key(|index| (distances[*index].unwrap_or(usize::MAX), Reverse(*index)))
+            .unwrap();
+        centers.push(nodes[next].id);
+        add_center(&neighbours, next, &mut distances, &mut assignment);
+    }
+    let radius = distances.iter().try_fold(0, |radius, distance| distance.map(|distance| distance.max(radius)));
+    KCenter {
+        centers,
+        assignment: assignment.iter().map(|center| center.map(|index| nodes[index].id)).collect(),
+        distances,
+        radius,
+    }
+}
+
+/// Runs Dijkstra's algorithm from the new center, only through nodes which are closer to it than to the centers so far,
+/// and assigns those to it.
+fn add_center(
+    neighbours: &[Vec<(usize, &Link)>],
+    center: usize,
+    distances: &mut [Option<usize>],
+    assignment: &mut [Option<usize>],
+) {
+    let mut queue = BinaryHeap::new();
+    distances[center] = Some(0);
+    assignment[center] = Some(center);
+    queue.push(Reverse((0, center)));
+    while let Some(Reverse((cost, current))) = queue.pop() {
+        if distances[current] != Some(cost) || assignment[current] != Some(center) {
+            continue;
+        }
+        for &(next, link) in &neighbours[current] {
+            let next_cost = cost + link.cost;
+            if distances[next].is_none_or(|known| next_cost < known) {
+                distances[next] = Some(next_cost);
+                assignment[next] = Some(center);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod placement_test {
+    use super::*;
+    use crate::dijkstra::dijkstra_search_node;
+    use crate::generate;
+    use crate::graph::Node;
+    use crate::util::SplitMix64;
+
+    #[test]
+    fn closest_centers() {
+        let mut rng = SplitMix64::new(48);
+        for _ in 0..10 {
+            let graph = generate::connected(30, 3, 1..10, &mut rng);
+            let mut radii = Vec::new();
+            for k in 1..8 {
+                let placement = k_center(&graph, k);
+                assert_eq!(placement.centers.len(), k);
+                for node in graph.nodes() {
+                    let closest = placement
+                        .centers
+                        .iter()
+                        .map(|center| dijkstra_search_node(&graph, node.id, *center).unwrap().cost)
+                        .min();
+                    assert_eq!(placement.distances[node.id as usize], closest);
+                    let center = placement.assignment[node.id as usize].unwrap();
+                    assert_eq!(Some(dijkstra_search_node(&graph, node.id, center).unwrap().cost), closest);
+                }
+                assert_eq!(placement.radius, placement.distances.iter().copied().max().unwrap());
+                radii.push(placement.radius.unwrap());
+            }
+            // every center is chosen the same way no matter how many follow, so the radius never grows
+            assert!(radii.windows(2).all(|pair| pair[0] >= pair[1]));
+            assert_eq!(k_center(&graph, 7).centers[..3], k_center(&graph, 3).centers[..]);
+        }
+    }
+
+    #[test]
+    fn edge_cases() {
+        let empty = KCenter { centers: vec![], assignment: vec![], distances: vec![], radius: Some(0) };
+        assert_eq!(k_center(&Graph::new(), 3), empty);
+        // two parts, each of them gets a center first
+        let mut graph = generate::topology(&generate::TopologySpec::Line { nodes: 3 }, 5);
+        graph.merge_prefixed(&generate::topology(&generate::TopologySpec::Line { nodes: 2 }, 1), "b-");
+        let placement = k_center(&graph, 0);
+        assert_eq!((placement.radius, placement.assignment[0]), (None, None));
+        let placement = k_center(&graph, 1);
+        assert_eq!((placement.radius, placement.assignment[3]), (None, None));
+        let placement = k_center(&graph, 2);
+        assert_eq!(placement.centers, vec![0, 3]);
+        assert_eq!(placement.radius, Some(10));
+        let placement = k_center(&graph, 10);
+        assert_eq!(placement.centers.len(), 5);
+        assert_eq!(placement.radius, Some(0));
+        // nodes behind a zero-cost link are as close as the center itself, but still become centers of their own
+        let mut graph = Graph::new();
+        for name in ["A", "B", "C"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 0));
+        graph.add_link(Link::new((1, 2), 5));
+        let placement = k_center(&graph, 3);
+        assert_eq!(placement.centers, vec![0, 2, 1]);
+        assert_eq!(placement.radius, Some(0));
+        assert_eq!(k_center(&graph, 5).centers, vec![0, 2, 1]);
+    }
+}
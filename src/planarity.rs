@@ -0,0 +1,441 @@
+//! Planarity testing: whether a network can be drawn, or laid out on a single layer of a board, without crossing links.
+//!
+//! The test is the [left-right planarity test](https://arxiv.org/abs/1105.5380) of de Fraysseix and Rosenstiehl,
+//! as described by Brandes, which runs in linear time. It only decides planarity, no drawing is computed.
+
+use crate::dijkstra::indexed_neighbours;
+use crate::graph::Graph;
+
+/// A range of return edges on one side of a conflict pair, from the lowest to the highest one.
+#[derive(Copy, Clone, Debug, Default)]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>,
+}
+
+/// Return edges which have to be on the left and on the right respectively, or the other way round.
+#[derive(Copy, Clone, Debug, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+/// The state of the test. Edges are identified by the index of their link, as every link is oriented once.
+struct LeftRight {
+    neighbours: Vec<Vec<(usize, usize)>>,
+    height: Vec<Option<usize>>,
+    parent_edge: Vec<Option<usize>>,
+    /// The oriented edges leaving every node.
+    outgoing: Vec<Vec<usize>>,
+    oriented: Vec<bool>,
+    tail: Vec<usize>,
+    head: Vec<usize>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<usize>,
+    lowpt_edge: Vec<usize>,
+    reference: Vec<Option<usize>>,
+    stack_bottom: Vec<usize>,
+    stack: Vec<ConflictPair>,
+}
+
+/// Checks whether the graph is [planar](https://en.wikipedia.org/wiki/Planar_graph), so it can be drawn without any
+/// links crossing each other.
+///
+/// Takes O(nodes + links) time. Links from a node to itself and to nodes outside of the graph are ignored.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+/// use rust_algorithms::planarity;
+///
+/// assert!(planarity::is_planar(&generate::topology(&TopologySpec::Grid { rows: 5, columns: 5 }, 1)));
+/// assert!(planarity::is_planar(&generate::topology(&TopologySpec::FullMesh { nodes: 4 }, 1)));
+/// assert!(!planarity::is_planar(&generate::topology(&TopologySpec::FullMesh { nodes: 5 }, 1)));
+/// // every leaf is linked to every spine, so three of each cannot be drawn without crossings
+/// assert!(planarity::is_planar(&generate::topology(&TopologySpec::SpineLeaf { spines: 2, leaves: 8 }, 1)));
+/// assert!(!planarity::is_planar(&generate::topology(&TopologySpec::SpineLeaf { spines: 3, leaves: 3 }, 1)));
+/// ```
+pub fn is_planar(graph: &Graph) -> bool {
+    let neighbours: Vec<Vec<(usize, usize)>> = indexed_neighbours(graph)
+        .into_iter()
+        .map(|links| links.into_iter().map(|(next, id)| (next, id.0)).collect())
+        .collect();
+    let count = neighbours.len();
+    let links = graph.links().len();
+    let used = neighbours.iter().map(Vec::len).sum::<usize>() / 2;
+    // by Euler's formula, a planar graph with at least three nodes has at most 3 · nodes - 6 links
+    if count > 2 && used > 3 * count - 6 {
+        return false;
+    }
+    let mut state = LeftRight {
+        neighbours,
+        height: vec![None; count],
+        parent_edge: vec![None; count],
+        outgoing: vec![Vec::new(); count],
+        oriented: vec![false; links],
+        tail: vec![0; links],
+        head: vec![0; links],
+        lowpt: vec![0; links],
+        lowpt2: vec![0; links],
+        nesting_depth: vec![0; links],
+        lowpt_edge: (0..links).collect(),
+        reference: vec![None; links],
+        stack_bottom: vec![0; links],
+        stack: Vec::new(),
+    };
+    let mut roots = Vec::new();
+    for root in 0..count {
+        if state.height[root].is_none() {
+            state.height[root] = Some(0);
+            roots.push(root);
+            state.orient(root);
+        }
+    }
+    for node in 0..count {
+        let mut outgoing = std::mem::take(&mut state.outgoing[node]);
+        outgoing.sort_by_key(|edge| state.nesting_depth[*edge]);
+        state.outgoing[node] = outgoing;
+    }
+    roots.into_iter().all(|root| state.test(root))
+}
+
+impl Interval {
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+impl LeftRight {
+    fn height(&self, node: usize) -> usize {
+        self.height[node].unwrap()
+    }
+
+    /// Orients the links away from the root by a depth-first search, computing the lowpoints and nesting depths of the edges.
+    fn orient(&mut self, root: usize) {
+        let mut stack = vec![(root, 0)];
+        while let Some(&(node, position)) = stack.last() {
+            match self.neighbours[node].get(position).copied() {
+                Some((next, edge)) => {
+                    stack.last_mut().unwrap().1 += 1;
+                    if self.oriented[edge] {
+                        continue;
+                    }
+                    self.oriented[edge] = true;
+                    self.tail[edge] = node;
+                    self.head[edge] = next;
+                    self.outgoing[node].push(edge);
+                    self.lowpt[edge] = self.height(node);
+                    self.lowpt2[edge] = self.height(node);
+                    match self.height[next] {
+                        // a tree edge, which is finished once the search returns from the next node
+                        None => {
+                            self.parent_edge[next] = Some(edge);
+                            self.height[next] = Some(self.height(node) + 1);
+                            stack.push((next, 0));
+                        }
+                        // a back edge
+                        Some(height) => {
+                            self.lowpt[edge] = height;
+                            self.finish_orientation(edge);
+                        }
+                    }
+                }
+                None => {
+                    stack.pop();
+                    if let Some(edge) = self.parent_edge[node] {
+                        self.finish_orientation(edge);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Computes the nesting depth of the edge and passes its lowpoints on to the edge its tail has been reached through.
+    fn finish_orientation(&mut self, edge: usize) {
+        let tail = self.tail[edge];
+        self.nesting_depth[edge] = 2 * self.lowpt[edge];
+        // chordal edges have to be nested inside of the others
+        if self.lowpt2[edge] < self.height(tail) {
+            self.nesting_depth[edge] += 1;
+        }
+        if let Some(parent) = self.parent_edge[tail] {
+            if self.lowpt[edge] < self.lowpt[parent] {
+                self.lowpt2[parent] = self.lowpt[parent].min(self.lowpt2[edge]);
+                self.lowpt[parent] = self.lowpt[edge];
+            } else if self.lowpt[edge] > self.lowpt[parent] {
+                self.lowpt2[parent] = self.lowpt2[parent].min(self.lowpt[edge]);
+            } else {
+                self.lowpt2[parent] = self.lowpt2[parent].min(self.lowpt2[edge]);
+            }
+        }
+    }
+
+    /// Runs the second depth-first search along the oriented edges, sorted by nesting depth, and checks that the return
+    /// edges can be split into a left and a right side. Returns false if they cannot, so the graph is not planar.
+    fn test(&mut self, root: usize) -> bool {
+        let mut stack = vec![(root, 0)];
+        while let Some(&(node, position)) = stack.last() {
+            match self.outgoing[node].get(position).copied() {
+                Some(edge) => {
+                    self.stack_bottom[edge] = self.stack.len();
+                    let next = self.head[edge];
+                    if self.parent_edge[next] == Some(edge) {
+                        // the return edges of the tree edge are integrated once the search returns from the next node
+                        stack.push((next, 0));
+                        continue;
+                    }
+                    self.lowpt_edge[edge] = edge;
+                    self.stack.push(ConflictPair {
+                        right: Interval { low: Some(edge), high: Some(edge) },
+                        ..ConflictPair::default()
+                    });
+                    if !self.integrate(node, edge, position) {
+                        return false;
+                    }
+                    stack.last_mut().unwrap().1 += 1;
+                }
+                None => {
+                    stack.pop();
+                    if let Some(edge) = self.parent_edge[node] {
+                        self.remove_back_edges(edge);
+                        let (parent, position) = *stack.last().unwrap();
+                        if !self.integrate(parent, edge, position) {
+                            return false;
+                        }
+                        stack.last_mut().unwrap().1 += 1;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Adds the constraints of the return edges of the edge at the position of the outgoing edges of the node.
+    fn integrate(&mut self, node: usize, edge: usize, position: usize) -> bool {
+        if self.lowpt[edge] >= self.height(node) {
+            return true;
+        }
+        // only the root has a height of 0, so a node with return edges below it has a parent edge
+        let parent = self.parent_edge[node].unwrap();
+        if position == 0 {
+            self.lowpt_edge[parent] = self.lowpt_edge[edge];
+            true
+        } else {
+            self.add_constraints(edge, parent)
+        }
+    }
+
+    fn conflicting(&self, interval: &Interval, edge: usize) -> bool {
+        interval.high.is_some_and(|high| self.lowpt[high] > self.lowpt[edge])
+    }
+
+    fn lowest(&self, pair: &ConflictPair) -> usize {
+        let lowpt = |interval: &Interval| interval.low.map_or(usize::MAX, |low| self.lowpt[low]);
+        lowpt(&pair.left).min(lowpt(&pair.right))
+    }
+
+    /// Merges the return edges of the edge with the ones of its siblings which come before it.
+    fn add_constraints(&mut self, edge: usize, parent: usize) -> bool {
+        let mut merged = ConflictPair::default();
+        // the return edges of the edge itself go to the right
+        loop {
+            let mut pair = self.stack.pop().unwrap();
+            if !pair.left.is_empty() {
+                pair.swap();
+            }
+            if !pair.left.is_empty() {
+                return false;
+            }
+            let low = pair.right.low.unwrap();
+            if self.lowpt[low] > self.lowpt[parent] {
+                if merged.right.is_empty() {
+                    merged.right = pair.right;
+                } else {
+                    self.reference[merged.right.low.unwrap()] = pair.right.high;
+                }
+                merged.right.low = pair.right.low;
+            } else {
+                self.reference[low] = Some(self.lowpt_edge[parent]);
+            }
+            if self.stack.len() == self.stack_bottom[edge] {
+                break;
+            }
+        }
+        // the return edges of the siblings which conflict with them go to the left
+        while let Some(top) = self.stack.last() {
+            if !self.conflicting(&top.left, edge) && !self.conflicting(&top.right, edge) {
+                break;
+            }
+            let mut pair = self.stack.pop().unwrap();
+            if self.conflicting(&pair.right, edge) {
+                pair.swap();
+            }
+            if self.conflicting(&pair.right, edge) {
+                return false;
+            }
+            if let Some(low) = merged.right.low {
+                self.reference[low] = pair.right.high;
+            }
+            if pair.right.low.is_some() {
+                merged.right.low = pair.right.low;
+            }
+            if merged.left.is_empty() {
+                merged.left = pair.left;
+            } else {
+                self.reference[merged.left.low.unwrap()] = pair.left.high;
+            }
+            merged.left.low = pair.left.low;
+        }
+        if !merged.left.is_empty() || !merged.right.is_empty() {
+            self.stack.push(merged);
+        }
+        true
+    }
+
+    /// Drops the return edges which end at the tail of the edge, as the search returns through it.
+    fn remove_back_edges(&mut self, edge: usize) {
+        let tail = self.tail[edge];
+        let height = self.height(tail);
+        while self.stack.last().is_some_and(|pair| self.lowest(pair) == height) {
+            self.stack.pop();
+        }
+        if let Some(mut pair) = self.stack.pop() {
+            while let Some(high) = pair.left.high.filter(|high| self.head[*high] == tail) {
+                pair.left.high = self.reference[high];
+            }
+            if pair.left.high.is_none() {
+                if let Some(low) = pair.left.low.take() {
+                    self.reference[low] = pair.right.low;
+                }
+            }
+            while let Some(high) = pair.right.high.filter(|high| self.head[*high] == tail) {
+                pair.right.high = self.reference[high];
+            }
+            if pair.right.high.is_none() {
+                if let Some(low) = pair.right.low.take() {
+                    self.reference[low] = pair.left.low;
+                }
+            }
+            self.stack.push(pair);
+        }
+        // the edge is on the side of its highest return edge
+        if self.lowpt[edge] < height {
+            if let Some(top) = self.stack.last() {
+                let (left, right) = (top.left.high, top.right.high);
+                let left_higher = match (left, right) {
+                    (Some(left), Some(right)) => self.lowpt[left] > self.lowpt[right],
+                    (left, _) => left.is_some(),
+                };
+                self.reference[edge] = if left_higher { left } else { right };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod planarity_test {
+    use super::*;
+    use crate::generate::{self, TopologySpec};
+    use crate::graph::{Link, Node};
+    use crate::util::{RandomSource, SplitMix64};
+
+    fn from_links(nodes: usize, links: &[(isize, isize)]) -> Graph {
+        let mut graph = Graph::new();
+        graph.allow_dangling_links(true);
+        for index in 0..nodes {
+            graph.add_node(Node::new(Box::leak(index.to_string().into_boxed_str())));
+        }
+        for &members in links {
+            graph.add_link(Link::new(members, 1));
+        }
+        graph
+    }
+
+    /// A random maximal planar graph, built by putting every new node into a random triangle and linking it to its corners.
+    fn triangulation(nodes: usize, rng: &mut SplitMix64) -> Graph {
+        let mut graph = from_links(nodes, &[(0, 1), (1, 2), (0, 2)]);
+        let mut faces = vec![(0, 1, 2), (0, 1, 2)];
+        for node in 3..nodes as isize {
+            let (a, b, c) = faces.swap_remove(rng.random_index(faces.len()));
+            for corner in [a, b, c] {
+                graph.add_link(Link::new((corner, node), 1));
+            }
+            faces.extend([(a, b, node), (b, c, node), (a, c, node)]);
+        }
+        graph
+    }
+
+    #[test]
+    fn kuratowski() {
+        let petersen = from_links(10, &[
+            (0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (0, 5), (1, 6), (2, 7), (3, 8), (4, 9),
+            (5, 7), (7, 9), (9, 6), (6, 8), (8, 5),
+        ]);
+        assert!(!is_planar(&petersen));
+        // K3,3 with every link subdivided, and hanging off a tree
+        let mut subdivided = Vec::new();
+        let mut next = 6;
+        for a in 0..3 {
+            for b in 3..6 {
+                subdivided.extend([(a, next), (next, b)]);
+                next += 1;
+            }
+        }
+        subdivided.extend((next..next + 10).map(|node| (node - 1, node)));
+        assert!(!is_planar(&from_links(next as usize + 10, &subdivided)));
+        subdivided.retain(|link| *link != (0, 6));
+        assert!(is_planar(&from_links(next as usize + 10, &subdivided)));
+        assert!(!is_planar(&generate::fat_tree(4)));
+    }
+
+    #[test]
+    fn triangulations() {
+        let mut rng = SplitMix64::new(47);
+        for nodes in [3, 4, 10, 50, 200] {
+            let graph = triangulation(nodes, &mut rng);
+            assert_eq!(graph.links().len(), 3 * nodes - 6);
+            assert!(is_planar(&graph));
+            // with links removed, and with the nodes in a random order, still planar
+            let mut links: Vec<(isize, isize)> = graph.links().iter().map(|link| link.members).collect();
+            for _ in 0..nodes / 3 {
+                links.swap_remove(rng.random_index(links.len()));
+            }
+            let mut order: Vec<isize> = (0..nodes as isize).collect();
+            for index in (1..nodes).rev() {
+                order.swap(index, rng.random_index(index + 1));
+            }
+            let shuffled: Vec<(isize, isize)> = links.iter().map(|&(a, b)| (order[a as usize], order[b as usize])).collect();
+            assert!(is_planar(&from_links(nodes, &shuffled)));
+        }
+        // a triangulation next to a K5, which is too dense only on its own
+        let mut links: Vec<(isize, isize)> = triangulation(30, &mut rng).links().iter().map(|link| link.members).collect();
+        links.retain(|_| rng.random_f64() < 0.7);
+        for a in 30..35 {
+            links.extend((a + 1..35).map(|b| (a, b)));
+        }
+        assert!(!is_planar(&from_links(35, &links)));
+        links.retain(|link| *link != (30, 31));
+        assert!(is_planar(&from_links(35, &links)));
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert!(is_planar(&Graph::new()));
+        assert!(is_planar(&from_links(2, &[(0, 1), (1, 1), (0, 7)])));
+        assert!(is_planar(&generate::topology(&TopologySpec::Star { leaves: 100 }, 1)));
+        assert!(is_planar(&generate::topology(&TopologySpec::Ring { nodes: 1000 }, 1)));
+        // a wheel, and a wheel with one chord through the hub too many
+        let mut wheel: Vec<(isize, isize)> = (1..9).map(|node| (0, node)).collect();
+        wheel.extend((1..9).map(|node| (node, node % 8 + 1)));
+        assert!(is_planar(&from_links(9, &wheel)));
+        wheel.extend([(1, 5), (3, 7)]);
+        assert!(!is_planar(&from_links(9, &wheel)));
+    }
+}
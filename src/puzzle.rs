@@ -0,0 +1,255 @@
+//! Sliding puzzles like the [8-puzzle](https://en.wikipedia.org/wiki/15_puzzle), solved by the A* search on implicit graphs.
+//!
+//! Every arrangement of the tiles is a node of an implicit graph, linked to the arrangements reachable by sliding one tile
+//! into the blank. The graph of the 8-puzzle already has 181440 nodes for every solvable start,
+//! so it is never built, only the arrangements the search reaches are.
+
+use crate::implicit;
+use std::fmt;
+
+/// The arrangement of the tiles of a sliding puzzle with at most 16 fields.
+///
+/// The tiles are numbered from 1, the blank is 0. The puzzle is solved when the tiles are in ascending order row by row,
+/// with the blank in the last field.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::puzzle::Puzzle;
+///
+/// let puzzle = Puzzle::new(3, 3, &[1, 2, 3, 4, 0, 6, 7, 5, 8]).unwrap();
+/// let solution = puzzle.solve().unwrap();
+/// assert_eq!(solution.len(), 3);
+/// assert_eq!(solution.last(), Some(&Puzzle::solved(3, 3)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Puzzle {
+    width: usize,
+    height: usize,
+    tiles: Vec<u8>,
+}
+
+impl Puzzle {
+    /// Creates a puzzle with the given tiles, row by row.
+    ///
+    /// Returns None if the tiles are not the numbers from 0 to width * height - 1, each exactly once,
+    /// or if the puzzle has more than 16 fields.
+    pub fn new(width: usize, height: usize, tiles: &[u8]) -> Option<Self> {
+        let fields = width * height;
+        if fields == 0 || fields > 16 || tiles.len() != fields {
+            return None;
+        }
+        let mut seen = [false; 16];
+        for &tile in tiles {
+            if usize::from(tile) >= fields || seen[usize::from(tile)] {
+                return None;
+            }
+            seen[usize::from(tile)] = true;
+        }
+        Some(Puzzle { width, height, tiles: tiles.to_vec() })
+    }
+
+    /// Creates a solved puzzle of the given size.
+    ///
+    /// # Panics
+    /// Panics if the puzzle would have no or more than 16 fields.
+    pub fn solved(width: usize, height: usize) -> Self {
+        let fields = width * height;
+        assert!(fields > 0 && fields <= 16, "a puzzle needs between 1 and 16 fields");
+        let tiles = (1..fields as u8).chain(std::iter::once(0)).collect();
+        Puzzle { width, height, tiles }
+    }
+
+    /// Returns the tiles, row by row, with 0 being the blank.
+    pub fn tiles(&self) -> &[u8] {
+        &self.tiles
+    }
+
+    /// Returns true if the tiles are in their final order.
+    pub fn is_solved(&self) -> bool {
+        self.tiles == Puzzle::solved(self.width, self.height).tiles
+    }
+
+    /// Returns true if the puzzle can be solved. Exactly half of all arrangements can be.
+    ///
+    /// An arrangement can be solved if the number of inversions, pairs of tiles in the wrong order,
+    /// plus the rows the blank is away from the last row, if the width is even, is even.
+    pub fn is_solvable(&self) -> bool {
+        let tiles: Vec<u8> = self.tiles.iter().copied().filter(|tile| *tile != 0).collect();
+        let mut inversions = 0;
+        for (index, a) in tiles.iter().enumerate() {
+            inversions += tiles[index + 1..].iter().filter(|b| a > b).count();
+        }
+        if !self.width.is_multiple_of(2) {
+            inversions.is_multiple_of(2)
+        } else {
+            let blank_row = self.blank() / self.width;
+            (inversions + self.height - 1 - blank_row).is_multiple_of(2)
+        }
+    }
+
+    /// Returns the arrangements reachable by sliding one tile into the blank.
+    pub fn moves(&self) -> Vec<Puzzle> {
+        let blank = self.blank();
+        let (row, column) = (blank / self.width, blank % self.width);
+        let mut fields = Vec::with_capacity(4);
+        if row > 0 {
+            fields.push(blank - self.width);
+        }
+        if row + 1 < self.height {
+            fields.push(blank + self.width);
+        }
+        if column > 0 {
+            fields.push(blank - 1);
+        }
+        if column + 1 < self.width {
+            fields.push(blank + 1);
+        }
+        fields
+            .into_iter()
+            .map(|field| {
+                let mut next = self.clone();
+                next.tiles.swap(blank, field);
+                next
+            })
+            .collect()
+    }
+
+    /// Returns the sum of the distances of all tiles to their final field, counting rows and columns.
+    ///
+    /// Every move brings one tile one field closer at most, so it never overestimates the number of moves needed
+    /// and the A* search of solve finds the shortest solution.
+    pub fn manhattan_distance(&self) -> usize {
+        let fields = self.tiles.len();
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| **tile != 0)
+            .map(|(field, tile)| {
+                let target = (usize::from(*tile) + fields - 1) % fields;
+                let rows = (field / self.width).abs_diff(target / self.width);
+                let columns = (field % self.width).abs_diff(target % self.width);
+                rows + columns
+            })
+            .sum()
+    }
+
+    /// Finds the shortest sequence of moves solving the puzzle, using implicit::astar_search with the manhattan distance.
+    ///
+    /// Returns the arrangements after every move, starting with this one and ending with the solved one,
+    /// or None if the puzzle cannot be solved.
+    pub fn solve(&self) -> Option<Vec<Puzzle>> {
+        if !self.is_solvable() {
+            return None;
+        }
+        let (width, height) = (self.width, self.height);
+        let solved = Puzzle::solved(width, height).id();
+        let result = implicit::astar_search(
+            self.id(),
+            |id| id == solved,
+            |id| Puzzle::from_id(width, height, id).moves().iter().map(|next| (next.id(), 1)).collect(),
+            |id| Puzzle::from_id(width, height, id).manhattan_distance(),
+        )?;
+        Some(
+            result
+                .links
+                .iter()
+                .map(|link| Puzzle::from_id(width, height, link.members.1))
+                .collect(),
+        )
+    }
+
+    /// Returns the id of the arrangement as a node of the implicit graph, with four bits for every field.
+    pub fn id(&self) -> isize {
+        self.tiles.iter().rev().fold(0u64, |id, tile| id << 4 | u64::from(*tile)) as isize
+    }
+
+    /// Creates the arrangement with the given id, as returned by id.
+    pub fn from_id(width: usize, height: usize, id: isize) -> Self {
+        let id = id as u64;
+        let tiles = (0..width * height).map(|field| (id >> (4 * field) & 0xf) as u8).collect();
+        Puzzle { width, height, tiles }
+    }
+
+    fn blank(&self) -> usize {
+        // new and solved make sure there is a blank
+        self.tiles.iter().position(|tile| *tile == 0).unwrap()
+    }
+}
+
+impl fmt::Display for Puzzle {
+    /// Writes the rows of tiles, with the blank as an underscore.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = (self.tiles.len() - 1).to_string().len();
+        for row in self.tiles.chunks(self.width) {
+            let tiles: Vec<String> = row
+                .iter()
+                .map(|tile| match tile {
+                    0 => format!("{:>width$}", "_", width = digits),
+                    tile => format!("{:>width$}", tile, width = digits),
+                })
+                .collect();
+            writeln!(f, "{}", tiles.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod puzzle_test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        assert!(Puzzle::new(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 0]).unwrap().is_solved());
+        assert!(Puzzle::new(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 8]).is_none());
+        assert!(Puzzle::new(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]).is_none());
+        assert!(Puzzle::new(2, 2, &[1, 2, 0]).is_none());
+        assert!(Puzzle::new(0, 0, &[]).is_none());
+        assert!(Puzzle::new(5, 4, &[0; 20]).is_none());
+        assert_eq!(Puzzle::solved(2, 3).tiles(), &[1, 2, 3, 4, 5, 0]);
+    }
+
+    #[test]
+    fn ids() {
+        let puzzle = Puzzle::new(4, 4, &[15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]).unwrap();
+        assert_eq!(Puzzle::from_id(4, 4, puzzle.id()), puzzle);
+        let puzzle = Puzzle::new(3, 2, &[0, 5, 4, 3, 2, 1]).unwrap();
+        assert_eq!(Puzzle::from_id(3, 2, puzzle.id()), puzzle);
+        assert_ne!(puzzle.id(), Puzzle::solved(3, 2).id());
+    }
+
+    #[test]
+    fn moves_and_distance() {
+        let puzzle = Puzzle::new(3, 3, &[1, 2, 3, 4, 0, 5, 6, 7, 8]).unwrap();
+        assert_eq!(puzzle.moves().len(), 4);
+        assert_eq!(Puzzle::solved(3, 3).moves().len(), 2);
+        assert_eq!(Puzzle::solved(3, 3).manhattan_distance(), 0);
+        // 5, 7 and 8 are one field away, 6 three fields
+        assert_eq!(puzzle.manhattan_distance(), 6);
+        assert_eq!(puzzle.to_string(), "1 2 3\n4 _ 5\n6 7 8\n");
+    }
+
+    #[test]
+    fn solvable() {
+        assert!(Puzzle::solved(3, 3).is_solvable());
+        assert!(!Puzzle::new(3, 3, &[2, 1, 3, 4, 5, 6, 7, 8, 0]).unwrap().is_solvable());
+        assert!(Puzzle::new(2, 2, &[0, 3, 2, 1]).unwrap().is_solvable());
+        assert!(!Puzzle::new(2, 2, &[0, 3, 1, 2]).unwrap().is_solvable());
+        assert!(Puzzle::new(2, 2, &[0, 3, 1, 2]).unwrap().solve().is_none());
+        for puzzle in Puzzle::solved(4, 4).moves().iter().flat_map(Puzzle::moves) {
+            assert!(puzzle.is_solvable());
+        }
+    }
+
+    #[test]
+    fn solve() {
+        assert_eq!(Puzzle::solved(3, 3).solve().unwrap(), vec![Puzzle::solved(3, 3)]);
+        // one of the hardest arrangements of the 8-puzzle, needing 31 moves
+        let puzzle = Puzzle::new(3, 3, &[8, 6, 7, 2, 5, 4, 3, 0, 1]).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert_eq!(solution.len(), 32);
+        assert_eq!(solution[0], puzzle);
+        assert!(solution.windows(2).all(|pair| pair[0].moves().contains(&pair[1])));
+        assert!(solution.last().unwrap().is_solved());
+    }
+}
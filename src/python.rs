@@ -0,0 +1,171 @@
+//! A Python module built with [PyO3](https://pyo3.rs), available with the `python` feature.
+//!
+//! The module can be built and installed with `maturin develop`, and is imported as `rust_algorithms`:
+//! ```python
+//! from rust_algorithms import Graph
+//!
+//! graph = Graph()
+//! a = graph.add_node("A")
+//! b = graph.add_node("B")
+//! graph.add_link(a, b, 5)
+//! links, cost = graph.dijkstra(a, b)
+//! ```
+//! Links are represented by tuples of both members and the cost, search results by a tuple of the links and the total cost.
+
+use crate::graph::{leak_name, Graph, Link, Node, SearchResult};
+use crate::{bfs, dfs, dijkstra, mst, spanningtree};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// A link as seen from Python: both members and the cost.
+type PyLink = (isize, isize, usize);
+
+/// A graph which can be built and analysed from Python.
+#[pyclass(name = "Graph")]
+#[derive(Clone, Debug, Default)]
+pub struct PyGraph {
+    graph: Graph,
+}
+
+#[pymethods]
+impl PyGraph {
+    /// Creates an empty graph.
+    #[new]
+    pub fn new() -> Self {
+        PyGraph { graph: Graph::new() }
+    }
+
+    /// Reads a graph from JSON written by to_json. Raises a ValueError if the JSON is invalid.
+    #[staticmethod]
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        Graph::from_json(json)
+            .map(|graph| PyGraph { graph })
+            .map_err(|error| PyValueError::new_err(error.to_string()))
+    }
+
+    /// Writes the graph as JSON.
+    pub fn to_json(&self) -> String {
+        self.graph.to_json()
+    }
+
+    /// Adds a node with the given name and returns its id, like Graph::add_node.
+    /// New names are leaked, see Node::name.
+    pub fn add_node(&mut self, name: &str) -> isize {
+        if let Some(node) = self.graph.nodes().iter().find(|node| node.name == name) {
+            return node.id;
+        }
+        self.graph.add_node(Node::new(leak_name(name.to_string())))
+    }
+
+    /// Adds a link between the nodes with the given ids, like Graph::add_link.
+    pub fn add_link(&mut self, a: isize, b: isize, cost: usize) {
+        self.graph.add_link(Link::new((a, b), cost));
+    }
+
+    /// Returns the ids and names of all nodes.
+    pub fn nodes(&self) -> Vec<(isize, String)> {
+        self.graph.nodes().iter().map(|node| (node.id, node.name.to_string())).collect()
+    }
+
+    /// Returns all links.
+    pub fn links(&self) -> Vec<PyLink> {
+        self.graph.links().iter().map(to_tuple).collect()
+    }
+
+    /// Searches for a path using bfs_search_node. Returns None if there is none.
+    pub fn bfs(&self, start: isize, target: isize) -> Option<(Vec<PyLink>, usize)> {
+        bfs::bfs_search_node(self.graph.topology_clone(), start, target).map(to_result)
+    }
+
+    /// Searches for a path using dfs_search_node. Returns None if there is none.
+    pub fn dfs(&self, start: isize, target: isize) -> Option<(Vec<PyLink>, usize)> {
+        dfs::dfs_search_node(self.graph.topology_clone(), start, target).map(to_result)
+    }
+
+    /// Searches for the cheapest path using dijkstra_search_node. Returns None if there is none.
+    pub fn dijkstra(&self, start: isize, target: isize) -> Option<(Vec<PyLink>, usize)> {
+        dijkstra::dijkstra_search_node(&self.graph, start, target).map(to_result)
+    }
+
+    /// Computes the links of a minimum spanning tree using mst::minimum_spanning_tree.
+    pub fn minimum_spanning_tree(&self) -> Vec<PyLink> {
+        mst::minimum_spanning_tree(&self.graph).iter().map(to_tuple).collect()
+    }
+
+    /// Simulates the spanning tree protocol on the graph for the given number of iterations.
+    /// Returns for every node its id, the id of the root it has chosen, its cost to the root and its next hop towards it.
+    pub fn simulate_spanning_tree(&self, iterations: usize) -> Vec<(isize, isize, usize, Option<isize>)> {
+        let mut tree = spanningtree::Tree::from_graph(&self.graph);
+        tree.simulate(iterations, 0, true);
+        self.graph
+            .nodes()
+            .iter()
+            .filter_map(|node| {
+                let node = tree.get_node(node.id)?;
+                Some((node.id, node.root_id, node.root_cost, node.next_hop))
+            })
+            .collect()
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.graph.nodes().len()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("Graph({} nodes, {} links)", self.graph.nodes().len(), self.graph.links().len())
+    }
+}
+
+fn to_tuple(link: &Link) -> PyLink {
+    (link.members.0, link.members.1, link.cost)
+}
+
+fn to_result(result: SearchResult) -> (Vec<PyLink>, usize) {
+    (result.links.iter().map(to_tuple).collect(), result.cost)
+}
+
+/// The Python module, named like the crate.
+#[pymodule]
+fn rust_algorithms(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGraph>()
+}
+
+#[cfg(test)]
+mod python_test {
+    use super::*;
+
+    #[test]
+    fn graph() {
+        let mut graph = PyGraph::new();
+        let a = graph.add_node("A");
+        let b = graph.add_node("B");
+        let c = graph.add_node("C");
+        assert_eq!(graph.add_node("B"), b);
+        graph.add_link(a, b, 1);
+        graph.add_link(b, c, 1);
+        graph.add_link(a, c, 5);
+        assert_eq!(graph.nodes()[2], (c, String::from("C")));
+        assert_eq!(graph.__repr__(), "Graph(3 nodes, 3 links)");
+
+        let (links, cost) = graph.dijkstra(a, c).unwrap();
+        assert_eq!(links, vec![(a, a, 0), (a, b, 1), (b, c, 1)]);
+        assert_eq!(cost, 2);
+        assert_eq!(graph.bfs(a, c).unwrap().1, 5);
+        assert!(graph.dfs(a, 9).is_none());
+        assert_eq!(graph.minimum_spanning_tree(), vec![(a, b, 1), (b, c, 1)]);
+        assert_eq!(PyGraph::from_json(&graph.to_json()).unwrap().links(), graph.links());
+    }
+
+    #[test]
+    fn simulate_spanning_tree() {
+        let mut graph = PyGraph::new();
+        let a = graph.add_node("A");
+        let b = graph.add_node("B");
+        let c = graph.add_node("C");
+        graph.add_link(a, b, 4);
+        graph.add_link(b, c, 1);
+        graph.add_link(a, c, 1);
+        let nodes = graph.simulate_spanning_tree(50);
+        assert_eq!(nodes, vec![(a, a, 0, None), (b, a, 2, Some(c)), (c, a, 1, Some(a))]);
+    }
+}
@@ -0,0 +1,278 @@
+//! Self-contained HTML reports of a graph, for teaching or sharing results: a drawing of the graph as SVG, followed by a
+//! section for every requested analysis. The report needs no other files and no network access to be viewed.
+
+use crate::dijkstra::dijkstra_search_node;
+use crate::graph::Graph;
+use crate::mst::minimum_spanning_tree;
+use crate::resilience::betweenness_centrality;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::f64::consts::PI;
+
+const SIZE: f64 = 400.0;
+const NODE_RADIUS: f64 = 14.0;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+td, th { border: 1px solid #999; padding: 0.2em 0.6em; text-align: left; }
+line { stroke: #999; stroke-width: 2; }
+line.path { stroke: #d22; stroke-width: 4; }
+line.mst { stroke: #26c; stroke-width: 4; }
+line.path.mst { stroke: #a2a; }
+circle { fill: #fff; stroke: #333; stroke-width: 2; }
+circle.active { fill: #fd4; }
+text { font-size: 11px; text-anchor: middle; dominant-baseline: middle; }
+";
+
+/// Steps through the activations of a trace, embedded as JSON by the index of its analysis, marking the node activated
+/// at the current step. Every trace keeps its own step, none is shown before its first step is chosen.
+const TRACE_SCRIPT: &str = "const steps = {};
+function move(trace, by) {
+    const activations = JSON.parse(document.getElementById('trace-' + trace).textContent);
+    if (activations.length === 0) return;
+    const step = Math.min(Math.max((trace in steps ? steps[trace] : -1) + by, 0), activations.length - 1);
+    steps[trace] = step;
+    document.querySelectorAll('circle.active').forEach(node => node.classList.remove('active'));
+    const node = document.getElementById('node-' + activations[step]);
+    if (node) node.classList.add('active');
+    const text = 'Step ' + (step + 1) + ' of ' + activations.length + ': node ' + activations[step];
+    document.getElementById('step-' + trace).textContent = text;
+}
+";
+
+/// A section of a report, see html.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Analysis {
+    /// A table of the numbers of Graph::summary.
+    Summary,
+    /// A table of the degree and the betweenness centrality of every node.
+    Metrics,
+    /// The cheapest path between both nodes, as found by dijkstra_search_node, highlighted in red in the drawing.
+    ShortestPath { source: isize, target: isize },
+    /// The links of the minimum spanning tree, highlighted in blue in the drawing.
+    MinimumSpanningTree,
+    /// The ids of activated nodes in the order of their activation, like Tree::recorded_activations.
+    /// The report embeds them as JSON and gets buttons to step through them, marking the activated node in the drawing.
+    /// The elements of a trace are identified by the index of the analysis, like trace-2, so a report can hold several.
+    Trace(Vec<isize>),
+}
+
+/// Creates an HTML page with a drawing of the graph and a section for every analysis, in the given order.
+///
+/// The nodes are drawn on a circle, in the order of graph.nodes(), with their names inside and the costs next to the links.
+/// Links from a node to itself and to nodes outside of the graph are not drawn. Subgraphs are not drawn either.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+/// use rust_algorithms::report::{self, Analysis};
+///
+/// let ring = generate::topology(&TopologySpec::Ring { nodes: 5 }, 1);
+/// let html = report::html(&ring, &[Analysis::Summary, Analysis::ShortestPath { source: 0, target: 2 }]);
+/// assert!(html.starts_with("<!DOCTYPE html>"));
+/// assert_eq!(html.matches("<circle").count(), 5);
+/// assert_eq!(html.matches("<line class=\"path\"").count(), 2);
+/// ```
+pub fn html(graph: &Graph, analyses: &[Analysis]) -> String {
+    let mut path_links = HashSet::new();
+    let mut mst_links = HashSet::new();
+    let mut sections = String::new();
+    let mut trace = false;
+    for (index, analysis) in analyses.iter().enumerate() {
+        match analysis {
+            Analysis::Summary => sections.push_str(&summary_section(graph)),
+            Analysis::Metrics => sections.push_str(&metrics_section(graph)),
+            Analysis::ShortestPath { source, target } => {
+                let (source_name, target_name) = (name_of(graph, *source), name_of(graph, *target));
+                sections.push_str(&format!("<h2>Cheapest path from {} to {}</h2>\n", source_name, target_name));
+                match dijkstra_search_node(graph, *source, *target) {
+                    Some(result) => {
+                        path_links.extend(result.links.iter().map(|link| key(link.members)));
+                        let names: Vec<String> = result.node_ids().into_iter().map(|id| name_of(graph, id)).collect();
+                        sections.push_str(&format!("<p>Cost {}: {}</p>\n", result.cost, names.join(" &rarr; ")));
+                    }
+                    None => sections.push_str("<p>There is no path.</p>\n"),
+                }
+            }
+            Analysis::MinimumSpanningTree => {
+                let tree = minimum_spanning_tree(graph);
+                mst_links.extend(tree.iter().map(|link| key(link.members)));
+                sections.push_str("<h2>Minimum spanning tree</h2>\n");
+                let cost: usize = tree.iter().map(|link| link.cost).sum();
+                sections.push_str(&format!("<p>{} links with a total cost of {}.</p>\n", tree.len(), cost));
+            }
+            Analysis::Trace(activations) => {
+                let ids: Vec<String> = activations.iter().map(|id| id.to_string()).collect();
+                sections.push_str("<h2>Trace</h2>\n");
+                let script = format!("<script type=\"application/json\" id=\"trace-{}\">[{}]</script>\n", index, ids.join(","));
+                sections.push_str(&script);
+                sections.push_str(&format!("<p><button onclick=\"move({}, -1)\">Back</button> ", index));
+                sections.push_str(&format!("<button onclick=\"move({}, 1)\">Next</button> ", index));
+                let status = match activations.len() {
+                    0 => String::from("No activations"),
+                    count => format!("{} activations", count),
+                };
+                sections.push_str(&format!("<span id=\"step-{}\">{}</span></p>\n", index, status));
+                trace = true;
+            }
+        }
+    }
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Graph report</title>\n");
+    html.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n<h1>Graph report</h1>\n", STYLE));
+    html.push_str(&svg(graph, &path_links, &mst_links));
+    html.push_str(&sections);
+    if trace {
+        html.push_str(&format!("<script>\n{}</script>\n", TRACE_SCRIPT));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Draws the nodes on a circle, with the links between them.
+fn svg(graph: &Graph, path_links: &HashSet<(isize, isize)>, mst_links: &HashSet<(isize, isize)>) -> String {
+    let nodes = graph.nodes();
+    let center = SIZE / 2.0;
+    let radius = if nodes.len() > 1 { center - 2.0 * NODE_RADIUS } else { 0.0 };
+    let positions: Vec<(f64, f64)> = (0..nodes.len())
+        .map(|index| {
+            let angle = 2.0 * PI * index as f64 / nodes.len() as f64 - PI / 2.0;
+            (center + radius * angle.cos(), center + radius * angle.sin())
+        })
+        .collect();
+    let position = |id: isize| -> Option<(f64, f64)> { usize::try_from(id).ok().and_then(|index| positions.get(index).copied()) };
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">\n",
+        SIZE);
+    for link in graph.links() {
+        let (a, b) = match (position(link.members.0), position(link.members.1)) {
+            (Some(a), Some(b)) if link.members.0 != link.members.1 => (a, b),
+            _ => continue,
+        };
+        let mut classes = Vec::new();
+        if path_links.contains(&key(link.members)) {
+            classes.push("path");
+        }
+        if mst_links.contains(&key(link.members)) {
+            classes.push("mst");
+        }
+        let class = if classes.is_empty() { String::new() } else { format!(" class=\"{}\"", classes.join(" ")) };
+        svg.push_str(&format!("<line{} x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\"/>\n", class, a.0, a.1, b.0, b.1));
+        svg.push_str(&format!("<text x=\"{:.1}\" y=\"{:.1}\">{}</text>\n", (a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0 - 6.0, link.cost));
+    }
+    for (node, (x, y)) in nodes.iter().zip(&positions) {
+        let name = escape(node.name);
+        svg.push_str(&format!("<circle id=\"node-{}\" cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\"><title>{} ({})</title></circle>\n",
+            node.id, x, y, NODE_RADIUS, name, node.id));
+        svg.push_str(&format!("<text x=\"{:.1}\" y=\"{:.1}\">{}</text>\n", x, y, name));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn summary_section(graph: &Graph) -> String {
+    let summary = graph.summary();
+    let cost_range = match summary.cost_range {
+        Some((min, max)) => format!("{} to {}", min, max),
+        None => String::from("none"),
+    };
+    let rows = [
+        ("Nodes", summary.nodes.to_string()),
+        ("Links", summary.links.to_string()),
+        ("Lowest degree", summary.min_degree.to_string()),
+        ("Average degree", format!("{:.2}", summary.avg_degree)),
+        ("Highest degree", summary.max_degree.to_string()),
+        ("Connected parts", summary.components.to_string()),
+        ("Link costs", cost_range),
+    ];
+    let mut section = String::from("<h2>Summary</h2>\n<table>\n");
+    for (label, value) in rows {
+        section.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", label, value));
+    }
+    section.push_str("</table>\n");
+    section
+}
+
+fn metrics_section(graph: &Graph) -> String {
+    let degrees = graph.degrees();
+    let betweenness = betweenness_centrality(graph);
+    let mut section = String::from("<h2>Nodes</h2>\n<table>\n");
+    section.push_str("<tr><th>Id</th><th>Name</th><th>Degree</th><th>Betweenness</th></tr>\n");
+    for (index, node) in graph.nodes().iter().enumerate() {
+        section.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td></tr>\n",
+            node.id, escape(node.name), degrees[index], betweenness[index]));
+    }
+    section.push_str("</table>\n");
+    section
+}
+
+/// Returns the escaped name of the node, or its id if it is not part of the graph.
+fn name_of(graph: &Graph, id: isize) -> String {
+    match usize::try_from(id).ok().and_then(|index| graph.nodes().get(index)) {
+        Some(node) => escape(node.name),
+        None => id.to_string(),
+    }
+}
+
+/// Returns the members of a link in ascending order, the same for both directions.
+fn key(members: (isize, isize)) -> (isize, isize) {
+    (members.0.min(members.1), members.0.max(members.1))
+}
+
+/// Escapes a string so it can be used as text or inside of a quoted attribute of HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod report_test {
+    use super::*;
+    use crate::generate;
+    use crate::graph::{Link, Node};
+
+    #[test]
+    fn sections() {
+        let mut graph = generate::topology(&generate::TopologySpec::Grid { rows: 2, columns: 3 }, 2);
+        graph.add_node(Node::new("<Lonely & \"isolated\">"));
+        graph.allow_dangling_links(true);
+        graph.add_link(Link::new((1, 1), 1));
+        graph.add_link(Link::new((2, 42), 1));
+        let plain = html(&graph, &[]);
+        assert_eq!(plain.matches("<circle").count(), 7);
+        assert_eq!(plain.matches("<line ").count(), 7);
+        assert!(!plain.contains("<h2>") && !plain.contains("<script"));
+        assert!(plain.contains("&lt;Lonely &amp; &quot;isolated&quot;&gt;") && !plain.contains("<Lonely"));
+
+        let analyses = [
+            Analysis::Summary,
+            Analysis::Metrics,
+            Analysis::ShortestPath { source: 0, target: 5 },
+            Analysis::ShortestPath { source: 0, target: 6 },
+            Analysis::MinimumSpanningTree,
+        ];
+        let report = html(&graph, &analyses);
+        assert_eq!(report.matches("<h2>").count(), 5);
+        assert!(report.find("<h2>Summary").unwrap() < report.find("<h2>Nodes").unwrap());
+        assert!(report.contains("<tr><th>Connected parts</th><td>2</td></tr>"));
+        assert_eq!(report.matches("<tr><td>").count(), 7);
+        assert!(report.contains("<p>Cost 6: "));
+        assert!(report.contains("<p>There is no path.</p>"));
+        assert!(report.contains("<p>5 links with a total cost of 10.</p>"));
+        assert_eq!(report.matches("<line class=\"path").count(), 3);
+        assert_eq!(report.matches("<line class=\"path mst\"").count() + report.matches("<line class=\"mst\"").count(), 5);
+    }
+
+    #[test]
+    fn trace() {
+        let graph = generate::topology(&generate::TopologySpec::Line { nodes: 3 }, 1);
+        let report = html(&graph, &[Analysis::Trace(vec![2, 0, 1, 7])]);
+        assert!(report.contains("<script type=\"application/json\" id=\"trace-0\">[2,0,1,7]</script>"));
+        assert!(report.contains("<span id=\"step-0\">4 activations</span>"));
+        assert_eq!(report.matches("<script").count(), 2);
+        // every trace gets ids of its own, the script is only added once
+        let report = html(&graph, &[Analysis::Trace(vec![1]), Analysis::Summary, Analysis::Trace(vec![])]);
+        assert!(report.contains("id=\"trace-0\">[1]</script>") && report.contains("id=\"trace-2\">[]</script>"));
+        assert!(report.contains("onclick=\"move(2, 1)\"") && report.contains("<span id=\"step-2\">No activations</span>"));
+        assert_eq!(report.matches("<script").count(), 3);
+        assert!(report.contains("id=\"node-2\""));
+        assert_eq!(html(&Graph::new(), &[Analysis::Trace(vec![])]).matches("<circle").count(), 0);
+    }
+}
@@ -0,0 +1,422 @@
+//! How well a network survives failures: random link failures simulated many times over, to estimate how often the nodes
+//! stay connected and how much longer their paths get, and attacks on the most important nodes, to see how fast the network
+//! falls apart.
+
+use crate::datastructures::DisjointSet;
+use crate::dijkstra::{indexed_neighbours, neighbours};
+use crate::graph::{Graph, Link, LinkId};
+use crate::util::{report_progress, Progress, RandomSource};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// How a pair of nodes fared over the trials of monte_carlo.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PairAvailability {
+    pub a: isize,
+    pub b: isize,
+    /// The number of trials in which both nodes stayed connected.
+    pub connected: usize,
+    /// The share of the trials in which both nodes stayed connected.
+    pub availability: f64,
+    /// The average cost of the cheapest path between both nodes over the trials in which they stayed connected,
+    /// None if they never did.
+    pub mean_cost: Option<f64>,
+}
+
+/// The order in which targeted_attack removes the nodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AttackStrategy {
+    /// The nodes with the most links first.
+    Degree,
+    /// The nodes with the highest betweenness centrality first, the ones most shortest paths pass through.
+    Betweenness,
+}
+
+/// The results of targeted_attack.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttackReport {
+    /// The ids of the nodes in the order they were removed.
+    pub removed: Vec<isize>,
+    /// The number of nodes in the largest connected part of the graph after removing none, one, two, … of the nodes,
+    /// ending with 0 once all are removed.
+    pub largest_component: Vec<usize>,
+}
+
+/// The results of monte_carlo.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonteCarloReport {
+    pub trials: usize,
+    /// Every pair of different nodes once, in the order of graph.nodes(): (0, 1), (0, 2), …, (1, 2), …
+    pub pairs: Vec<PairAvailability>,
+    /// The number of trials in which all nodes stayed connected.
+    pub fully_connected: usize,
+}
+
+impl MonteCarloReport {
+    /// Returns the results of the pair of nodes, in either order, or None if it is not a pair of different nodes of the graph.
+    pub fn pair(&self, a: isize, b: isize) -> Option<&PairAvailability> {
+        let (a, b) = (a.min(b), a.max(b));
+        self.pairs.iter().find(|pair| (pair.a, pair.b) == (a, b))
+    }
+
+    /// Returns the average availability of all pairs, 1 if there are no pairs.
+    pub fn mean_availability(&self) -> f64 {
+        if self.pairs.is_empty() {
+            return 1.0;
+        }
+        self.pairs.iter().map(|pair| pair.availability).sum::<f64>() / self.pairs.len() as f64
+    }
+}
+
+/// Fails every link with the given probability, independently of the others, and looks at which pairs of nodes stay
+/// connected and what their cheapest paths cost, over the given number of trials.
+///
+/// Every trial runs Dijkstra's algorithm from every node over the links which did not fail, so it takes
+/// O(trials · nodes · links · log nodes) time. Links from a node to itself and to nodes outside of the graph are ignored.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+/// use rust_algorithms::resilience;
+/// use rust_algorithms::util::SplitMix64;
+///
+/// // in a ring, both neighbours of a node are connected as long as at most one link fails
+/// let ring = generate::topology(&TopologySpec::Ring { nodes: 6 }, 1);
+/// let report = resilience::monte_carlo(&ring, 0.1, 1000, &mut SplitMix64::new(1));
+/// let pair = report.pair(0, 1).unwrap();
+/// assert!(pair.availability > 0.95 && pair.availability < 1.0);
+/// assert!(pair.mean_cost.unwrap() > 1.0);
+/// assert!(report.fully_connected < 1000);
+/// ```
+pub fn monte_carlo<R>(graph: &Graph, failure_prob: f64, trials: usize, rng: &mut R) -> MonteCarloReport
+where
+    R: RandomSource + ?Sized,
+{
+    monte_carlo_with_progress(graph, failure_prob, trials, rng, |_| ())
+}
+
+/// Simulates link failures like monte_carlo, passing the number of trials done so far to the progress callback
+/// about every percent of the trials and once at the end.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+/// use rust_algorithms::resilience;
+/// use rust_algorithms::util::SplitMix64;
+///
+/// let ring = generate::topology(&TopologySpec::Ring { nodes: 6 }, 1);
+/// let mut reports = 0;
+/// let report = resilience::monte_carlo_with_progress(&ring, 0.1, 1000, &mut SplitMix64::new(1), |_| reports += 1);
+/// assert_eq!(report, resilience::monte_carlo(&ring, 0.1, 1000, &mut SplitMix64::new(1)));
+/// assert_eq!(reports, 100);
+/// ```
+pub fn monte_carlo_with_progress<R, F>(
+    graph: &Graph,
+    failure_prob: f64,
+    trials: usize,
+    rng: &mut R,
+    mut progress: F,
+) -> MonteCarloReport
+where
+    R: RandomSource + ?Sized,
+    F: FnMut(Progress),
+{
+    let nodes = graph.nodes();
+    let count = nodes.len();
+    let neighbours = indexed_neighbours(graph);
+    let links = graph.links();
+    // the number of trials every pair stayed connected in, and the total cost of its paths, in the order of the pairs
+    let mut connected = vec![0; count * count.saturating_sub(1) / 2];
+    let mut costs = vec![0; connected.len()];
+    let mut fully_connected = 0;
+    if trials == 0 {
+        report_progress(&mut progress, 0, 0);
+    }
+    for trial in 1..=trials {
+        let up: Vec<bool> = links.iter().map(|_| rng.random_f64() >= failure_prob).collect();
+        let mut pair = 0;
+        let mut all = true;
+        for start in 0..count {
+            let distances = distances(&neighbours, links, &up, start);
+            for distance in &distances[start + 1..] {
+                match distance {
+                    Some(cost) => {
+                        connected[pair] += 1;
+                        costs[pair] += cost;
+                    }
+                    None => all = false,
+                }
+                pair += 1;
+            }
+        }
+        if all {
+            fully_connected += 1;
+        }
+        report_progress(&mut progress, trial, trials);
+    }
+
+    let mut pairs = Vec::with_capacity(connected.len());
+    for a in 0..count {
+        for b in a + 1..count {
+            let index = pairs.len();
+            pairs.push(PairAvailability {
+                a: nodes[a].id,
+                b: nodes[b].id,
+                connected: connected[index],
+                availability: if trials == 0 { 0.0 } else { connected[index] as f64 / trials as f64 },
+                mean_cost: Some(costs[index] as f64 / connected[index] as f64).filter(|_| connected[index] > 0),
+            });
+        }
+    }
+    MonteCarloReport { trials, pairs, fully_connected }
+}
+
+impl AttackReport {
+    /// Returns the [robustness](https://doi.org/10.1073/pnas.1009440108) of the graph against the attack:
+    /// the share of the nodes in the largest connected part, averaged over the attack after every removal.
+    ///
+    /// It ranges from 0 for a graph without links to almost 1/2 for a complete graph, 0 for an empty graph.
+    pub fn robustness(&self) -> f64 {
+        let count = self.removed.len();
+        if count == 0 {
+            return 0.0;
+        }
+        self.largest_component[1..].iter().sum::<usize>() as f64 / (count * count) as f64
+    }
+}
+
+/// Removes the nodes one by one, the most important ones first, and records how the largest connected part of the graph
+/// shrinks, the classic robustness curve.
+///
+/// The order is taken from the intact graph, nodes of the same importance are removed in the order of graph.nodes().
+/// Networks with a few hubs, like the ones grown by preferential attachment, fall apart much faster than random ones.
+/// Links from a node to itself and to nodes outside of the graph are ignored.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+/// use rust_algorithms::resilience::{self, AttackStrategy};
+///
+/// // removing the hub disconnects all leaves
+/// let star = generate::topology(&TopologySpec::Star { leaves: 4 }, 1);
+/// let report = resilience::targeted_attack(&star, AttackStrategy::Degree);
+/// assert_eq!(report.removed, vec![0, 1, 2, 3, 4]);
+/// assert_eq!(report.largest_component, vec![5, 1, 1, 1, 1, 0]);
+/// assert_eq!(report.robustness(), 4.0 / 25.0);
+/// ```
+pub fn targeted_attack(graph: &Graph, strategy: AttackStrategy) -> AttackReport {
+    let neighbours = neighbours(graph);
+    let count = neighbours.len();
+    let scores: Vec<f64> = match strategy {
+        AttackStrategy::Degree => neighbours.iter().map(|neighbours| neighbours.len() as f64).collect(),
+        AttackStrategy::Betweenness => betweenness_centrality(graph),
+    };
+    let mut order: Vec<usize> = (0..count).collect();
+    order.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+
+    // adding the nodes back in reverse order, joining the parts of a disjoint set, gives the sizes backwards
+    let mut parts = DisjointSet::new(count);
+    let mut present = vec![false; count];
+    let mut largest = 0;
+    let mut largest_component = vec![0];
+    for &node in order.iter().rev() {
+        present[node] = true;
+        largest = largest.max(1);
+        for &(next, _) in &neighbours[node] {
+            if !present[next] {
+                continue;
+            }
+            if parts.union(node, next) {
+                largest = largest.max(parts.size(node));
+            }
+        }
+        largest_component.push(largest);
+    }
+    largest_component.reverse();
+    let nodes = graph.nodes();
+    AttackReport { removed: order.iter().map(|index| nodes[*index].id).collect(), largest_component }
+}
+
+/// Computes the [betweenness centrality](https://en.wikipedia.org/wiki/Betweenness_centrality) of every node, in the order
+/// of graph.nodes(): the number of shortest paths between pairs of other nodes passing through it, where the pairs are
+/// counted once and the paths of a pair with several shortest paths are shared evenly.
+///
+/// Paths are measured by their number of links, not their cost. Uses Brandes' algorithm, a breadth-first search from every
+/// node, in O(nodes · links) time. Links from a node to itself and to nodes outside of the graph are ignored.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::generate::{self, TopologySpec};
+/// use rust_algorithms::resilience;
+///
+/// // the middle node of a line of five is passed by the paths between the two nodes on its left and the two on its right
+/// let line = generate::topology(&TopologySpec::Line { nodes: 5 }, 1);
+/// assert_eq!(resilience::betweenness_centrality(&line), vec![0.0, 3.0, 4.0, 3.0, 0.0]);
+/// ```
+pub fn betweenness_centrality(graph: &Graph) -> Vec<f64> {
+    let neighbours = neighbours(graph);
+    let count = neighbours.len();
+    let mut centrality = vec![0.0; count];
+    for start in 0..count {
+        // the number of shortest paths to every node, the nodes they come from, and the order the nodes are reached in
+        let mut paths = vec![0.0; count];
+        let mut depths: Vec<Option<usize>> = vec![None; count];
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); count];
+        let mut order = Vec::with_capacity(count);
+        let mut queue = VecDeque::new();
+        paths[start] = 1.0;
+        depths[start] = Some(0);
+        queue.push_back(start);
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            let depth = depths[current].unwrap_or(0);
+            for &(next, _) in &neighbours[current] {
+                if depths[next].is_none() {
+                    depths[next] = Some(depth + 1);
+                    queue.push_back(next);
+                }
+                if depths[next] == Some(depth + 1) {
+                    paths[next] += paths[current];
+                    predecessors[next].push(current);
+                }
+            }
+        }
+        // the share of the paths to all nodes further away each node is part of, collected back from the farthest ones
+        let mut dependencies = vec![0.0; count];
+        for &node in order.iter().rev() {
+            for &previous in &predecessors[node] {
+                dependencies[previous] += paths[previous] / paths[node] * (1.0 + dependencies[node]);
+            }
+            if node != start {
+                centrality[node] += dependencies[node];
+            }
+        }
+    }
+    // every pair has been counted from both ends
+    centrality.iter().map(|value| value / 2.0).collect()
+}
+
+/// The cost of the cheapest path from the start to every node over the links which are up, None for unreachable nodes.
+fn distances(neighbours: &[Vec<(usize, LinkId)>], links: &[Link], up: &[bool], start: usize) -> Vec<Option<usize>> {
+    let mut distances: Vec<Option<usize>> = vec![None; neighbours.len()];
+    let mut queue = BinaryHeap::new();
+    distances[start] = Some(0);
+    queue.push(Reverse((0, start)));
+    while let Some(Reverse((cost, current))) = queue.pop() {
+        if distances[current] != Some(cost) {
+            continue;
+        }
+        for &(next, id) in &neighbours[current] {
+            if !up[id.0] {
+                continue;
+            }
+            let next_cost = cost + links[id.0].cost;
+            if distances[next].is_none_or(|known| next_cost < known) {
+                distances[next] = Some(next_cost);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    distances
+}
+
+#[cfg(test)]
+mod resilience_test {
+    use super::*;
+    use crate::dijkstra::dijkstra_search_node;
+    use crate::generate::{self, TopologySpec};
+    use crate::util::SplitMix64;
+
+    #[test]
+    fn certain_outcomes() {
+        let mut rng = SplitMix64::new(40);
+        let graph = generate::erdos_renyi(8, 0.4, 1..10, &mut rng);
+        let report = monte_carlo(&graph, 0.0, 3, &mut rng);
+        assert_eq!(report.pairs.len(), 28);
+        for pair in &report.pairs {
+            let cost = dijkstra_search_node(&graph, pair.a, pair.b).map(|result| result.cost as f64);
+            assert_eq!(pair.mean_cost, cost);
+            assert_eq!(pair.connected, if cost.is_some() { 3 } else { 0 });
+        }
+        assert_eq!(report.fully_connected > 0, graph.summary().components == 1);
+        let report = monte_carlo(&graph, 1.0, 3, &mut rng);
+        assert!(report.pairs.iter().all(|pair| pair.connected == 0 && pair.mean_cost.is_none()));
+        assert_eq!((report.fully_connected, report.mean_availability()), (0, 0.0));
+        assert_eq!(report.pair(3, 1).map(|pair| (pair.a, pair.b)), Some((1, 3)));
+        assert!(report.pair(2, 2).is_none());
+    }
+
+    #[test]
+    fn betweenness() {
+        // the centrality of a node counts the pairs of other nodes whose shortest paths all pass through it
+        let mut rng = SplitMix64::new(42);
+        for _ in 0..10 {
+            let graph = generate::erdos_renyi(9, 0.3, 1..2, &mut rng);
+            let centrality = betweenness_centrality(&graph);
+            let report = monte_carlo(&graph, 0.0, 1, &mut rng);
+            for (node, value) in centrality.iter().enumerate() {
+                let node = node as isize;
+                assert!(*value >= 0.0);
+                let others = graph.view(|other| other.id != node, |_| true);
+                let mut cut = 0.0;
+                for pair in report.pairs.iter().filter(|pair| pair.a != node && pair.b != node && pair.connected > 0) {
+                    if crate::bfs::bfs_search_generic(&others, pair.a, pair.b).is_none() {
+                        cut += 1.0;
+                    }
+                }
+                assert!(*value >= cut);
+            }
+        }
+        let ring = generate::topology(&TopologySpec::Ring { nodes: 5 }, 1);
+        assert!(betweenness_centrality(&ring).iter().all(|value| (value - 1.0).abs() < 1e-9));
+        let grid = generate::topology(&TopologySpec::Grid { rows: 2, columns: 2 }, 1);
+        assert_eq!(betweenness_centrality(&grid), vec![0.5; 4]);
+    }
+
+    #[test]
+    fn attacks() {
+        let mut rng = SplitMix64::new(43);
+        let graph = generate::connected(30, 3, 1..5, &mut rng);
+        for strategy in [AttackStrategy::Degree, AttackStrategy::Betweenness] {
+            let report = targeted_attack(&graph, strategy);
+            let mut removed = report.removed.clone();
+            removed.sort_unstable();
+            assert_eq!(removed, (0..30).collect::<Vec<isize>>());
+            assert_eq!(report.largest_component.len(), 31);
+            assert_eq!((report.largest_component[0], report.largest_component[30]), (30, 0));
+            // the size of the largest part after every removal, counted from scratch
+            for (removals, size) in report.largest_component.iter().enumerate() {
+                let gone = &report.removed[..removals];
+                let remaining = graph.view(|node| !gone.contains(&node.id), |_| true);
+                let largest = (0..30)
+                    .filter(|id| !gone.contains(id))
+                    .map(|id| (0..30).filter(|other| crate::bfs::bfs_search_generic(&remaining, id, *other).is_some()).count())
+                    .max()
+                    .unwrap_or(0);
+                assert_eq!(*size, largest);
+            }
+            assert!(report.robustness() > 0.0 && report.robustness() < 0.5);
+        }
+        let degrees = targeted_attack(&graph, AttackStrategy::Degree);
+        let degree = |id: isize| graph.links().iter().filter(|link| link.members.0 == id || link.members.1 == id).count();
+        assert!(degrees.removed.windows(2).all(|pair| degree(pair[0]) >= degree(pair[1])));
+        let empty = targeted_attack(&Graph::new(), AttackStrategy::Betweenness);
+        assert_eq!((empty.removed.len(), empty.robustness()), (0, 0.0));
+        assert_eq!(empty.largest_component, vec![0]);
+    }
+
+    #[test]
+    fn line_availability() {
+        // both links of a line have to survive for its ends to stay connected
+        let graph = generate::topology(&TopologySpec::Line { nodes: 3 }, 2);
+        let report = monte_carlo(&graph, 0.3, 20000, &mut SplitMix64::new(41));
+        assert!((report.pair(0, 2).unwrap().availability - 0.49).abs() < 0.02);
+        assert!((report.pair(0, 1).unwrap().availability - 0.7).abs() < 0.02);
+        assert_eq!(report.pair(0, 2).unwrap().mean_cost, Some(4.0));
+        assert_eq!(report.fully_connected, report.pair(0, 2).unwrap().connected);
+        let empty = monte_carlo(&Graph::new(), 0.5, 10, &mut SplitMix64::new(1));
+        assert!(empty.pairs.is_empty());
+        assert_eq!((empty.fully_connected, empty.mean_availability()), (10, 1.0));
+        assert!(monte_carlo(&graph, 0.3, 0, &mut SplitMix64::new(1)).pairs.iter().all(|pair| pair.availability == 0.0));
+    }
+}
@@ -0,0 +1,121 @@
+//! Binary searches, both over sorted slices and over the answers to a question, for parametric searches like
+//! "what is the lowest link cost limit which still connects two nodes".
+
+use std::cmp::Ordering;
+
+/// Searches a sorted slice using [Binary search](https://en.wikipedia.org/wiki/Binary_search_algorithm), in O(log n) time.
+///
+/// The comparator tells for an element whether it is less than, equal to or greater than the element searched for.
+/// Returns Ok with the index of a matching element, or Err with the index where a matching element could be inserted
+/// while keeping the slice sorted, like the binary search of the standard library.
+/// If several elements match, the first one is found.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::search::*;
+///
+/// let links = [(1, "A"), (3, "B"), (3, "C"), (8, "D")];
+/// assert_eq!(binary_search_by(&links, |link| link.0.cmp(&3)), Ok(1));
+/// assert_eq!(binary_search_by(&links, |link| link.0.cmp(&5)), Err(3));
+/// assert_eq!(binary_search_by(&links, |link| link.0.cmp(&9)), Err(4));
+/// ```
+pub fn binary_search_by<T, F>(slice: &[T], mut compare: F) -> Result<usize, usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
+    // the first element which is not less than the one searched for lies within low..high
+    let (mut low, mut high) = (0, slice.len());
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if compare(&slice[middle]) == Ordering::Less {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    match slice.get(low).map(compare) {
+        Some(Ordering::Equal) => Ok(low),
+        _ => Err(low),
+    }
+}
+
+/// Finds the lowest number between low and high, both inclusive, for which the predicate holds, in O(log(high - low)) calls of it.
+///
+/// The predicate needs to be monotone: once it holds for a number, it needs to hold for all higher ones.
+/// Returns None if it does not hold for high, or if low is greater than high.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::dijkstra::*;
+/// use rust_algorithms::search::*;
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node(Node::new("A"));
+/// let b = graph.add_node(Node::new("B"));
+/// let c = graph.add_node(Node::new("C"));
+/// graph.add_link(Link::new((a, b), 4));
+/// graph.add_link(Link::new((b, c), 2));
+/// graph.add_link(Link::new((a, c), 9));
+///
+/// // the lowest cost limit for links which still connects A and C
+/// let connects = |limit: usize| dijkstra_search_node_filtered(&graph, a, c, |link| link.cost <= limit).is_some();
+/// assert_eq!(binary_search_answer(0, 10, connects), Some(4));
+/// assert_eq!(binary_search_answer(0, 3, connects), None);
+/// ```
+pub fn binary_search_answer<P>(low: usize, high: usize, mut predicate: P) -> Option<usize>
+where
+    P: FnMut(usize) -> bool,
+{
+    if low > high || !predicate(high) {
+        return None;
+    }
+    // the predicate holds for high and the answer lies within low..=high
+    let (mut low, mut high) = (low, high);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if predicate(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    Some(low)
+}
+
+#[cfg(test)]
+mod search_test {
+    use super::*;
+
+    #[test]
+    fn sorted_slices() {
+        let numbers = [1, 2, 2, 2, 5, 7, 7, 9];
+        for value in 0..11 {
+            let expected = numbers.iter().position(|number| *number >= value).unwrap_or(numbers.len());
+            let result = binary_search_by(&numbers, |number| number.cmp(&value));
+            if numbers.contains(&value) {
+                assert_eq!(result, Ok(expected));
+            } else {
+                assert_eq!(result, Err(expected));
+            }
+        }
+        let empty: [u8; 0] = [];
+        assert_eq!(binary_search_by(&empty, |number| number.cmp(&1)), Err(0));
+    }
+
+    #[test]
+    fn answers() {
+        // the integer square root of 1000
+        assert_eq!(binary_search_answer(0, 1000, |root| (root + 1) * (root + 1) > 1000), Some(31));
+        assert_eq!(binary_search_answer(5, 5, |_| true), Some(5));
+        assert_eq!(binary_search_answer(6, 5, |_| true), None);
+        assert_eq!(binary_search_answer(0, usize::MAX, |number| number >= usize::MAX - 1), Some(usize::MAX - 1));
+
+        let mut calls = 0;
+        binary_search_answer(0, 1 << 20, |number| {
+            calls += 1;
+            number > 12345
+        });
+        assert!(calls <= 22);
+    }
+}
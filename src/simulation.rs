@@ -0,0 +1,232 @@
+use crate::util::{Progress, RandomSource};
+
+/// A distributed algorithm which can be simulated by activating one node after the other.
+///
+/// Every activation lets a node do one step of the algorithm, e.g. send its current knowledge to its neighbours.
+/// The functions of this module drive an engine until it converges, so different algorithms can share the same driver.
+pub trait Engine {
+    /// Returns the ids of all nodes which can be activated.
+    fn node_ids(&self) -> Vec<isize>;
+
+    /// Lets the node with the given id do one step of the algorithm.
+    fn activate(&mut self, node_id: isize);
+
+    /// Returns true if the algorithm has converged, so further activations would not change its state.
+    fn converged(&self) -> bool;
+}
+
+/// Runs an engine in batches of the given number of activations, until it has converged.
+///
+/// The node to activate next is chosen by select, which receives the engine and returns a node id.
+/// After every batch the engine is asked whether it has converged.
+/// If max_batches is set, the simulation also stops once that many batches have been run.
+///
+/// Returns the number of batches that have been run, which is at least 1.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::simulation::*;
+/// use rust_algorithms::spanningtree::*;
+///
+/// let mut tree: Tree = Tree::new();
+/// tree.add_node(Node::new(3, "Third Node"));
+/// tree.add_node(Node::new(2, "Second Node"));
+/// tree.add_node(Node::new(1, "Root"));
+/// tree.add_link(Link::new((1, 2), 5));
+/// tree.add_link(Link::new((2, 3), 5));
+///
+/// let mut next = 0;
+/// let batches = run_with(&mut tree, 1, Some(10), |_tree| {
+///     next = next % 3 + 1;
+///     next
+/// });
+/// assert_eq!(batches, 2);
+/// assert_eq!(tree.get_node(3).unwrap().root_id, 1);
+/// ```
+pub fn run_with<E, F>(engine: &mut E, iterations: usize, max_batches: Option<usize>, select: F) -> usize
+where
+    E: Engine + ?Sized,
+    F: FnMut(&E) -> isize,
+{
+    run_with_progress(engine, iterations, max_batches, select, |_| ())
+}
+
+/// Runs an engine like run_with, passing the number of batches run so far to the progress callback after every batch.
+/// The total is max_batches, as the batches until the engine converges are not known in advance.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::simulation::*;
+/// use rust_algorithms::spanningtree::*;
+/// use rust_algorithms::util::Progress;
+///
+/// let mut tree: Tree = Tree::new();
+/// tree.add_node(Node::new(3, "Third Node"));
+/// tree.add_node(Node::new(2, "Second Node"));
+/// tree.add_node(Node::new(1, "Root"));
+/// tree.add_link(Link::new((1, 2), 5));
+/// tree.add_link(Link::new((2, 3), 5));
+///
+/// let mut next = 0;
+/// let mut reports = Vec::new();
+/// let select = |_tree: &Tree| {
+///     next = next % 3 + 1;
+///     next
+/// };
+/// let batches = run_with_progress(&mut tree, 1, Some(10), select, |progress| reports.push(progress));
+/// assert_eq!(batches, 2);
+/// assert_eq!(reports, vec![Progress { done: 1, total: Some(10) }, Progress { done: 2, total: Some(10) }]);
+/// ```
+pub fn run_with_progress<E, F, P>(
+    engine: &mut E,
+    iterations: usize,
+    max_batches: Option<usize>,
+    mut select: F,
+    mut progress: P,
+) -> usize
+where
+    E: Engine + ?Sized,
+    F: FnMut(&E) -> isize,
+    P: FnMut(Progress),
+{
+    let mut batches = 0;
+    loop {
+        for _i in 0..iterations {
+            let node_id = select(engine);
+            engine.activate(node_id);
+        }
+        batches += 1;
+        log_trace!("simulation: batch {} of {} activations done", batches, iterations);
+        progress(Progress { done: batches, total: max_batches });
+        if engine.converged() || Some(batches) == max_batches {
+            log_debug!("simulation: stopped after {} batches, converged: {}", batches, engine.converged());
+            return batches;
+        }
+    }
+}
+
+/// Runs an engine like run_with, activating nodes picked uniformly at random by the given random number generator.
+///
+/// # Panics
+/// Panics if the engine has no nodes.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::simulation::*;
+/// use rust_algorithms::spanningtree::*;
+/// use rust_algorithms::util::SplitMix64;
+///
+/// let mut tree: Tree = Tree::new();
+/// tree.add_node(Node::new(2, "Second Node"));
+/// tree.add_node(Node::new(1, "Root"));
+/// tree.add_link(Link::new((1, 2), 5));
+///
+/// run_random(&mut tree, 10, None, &mut SplitMix64::new(1));
+/// assert_eq!(tree.get_node(2).unwrap().root_id, 1);
+/// ```
+pub fn run_random<E, R>(engine: &mut E, iterations: usize, max_batches: Option<usize>, rng: &mut R) -> usize
+where
+    E: Engine + ?Sized,
+    R: RandomSource + ?Sized,
+{
+    run_with(engine, iterations, max_batches, |engine| {
+        let node_ids = engine.node_ids();
+        node_ids[rng.random_index(node_ids.len())]
+    })
+}
+
+/// Runs an engine in rounds, activating every node once per round in the order of Engine::node_ids().
+/// Stops as soon as the engine has converged after a round, or after max_rounds rounds.
+///
+/// Returns the number of rounds that have been run.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::simulation::*;
+/// use rust_algorithms::spanningtree::*;
+///
+/// let mut tree: Tree = Tree::new();
+/// tree.add_node(Node::new(3, "Third Node"));
+/// tree.add_node(Node::new(2, "Second Node"));
+/// tree.add_node(Node::new(1, "Root"));
+/// tree.add_link(Link::new((1, 2), 5));
+/// tree.add_link(Link::new((2, 3), 5));
+///
+/// assert_eq!(run_rounds(&mut tree, 10), 2);
+/// assert_eq!(tree.get_node(3).unwrap().root_cost, 10);
+/// ```
+pub fn run_rounds<E>(engine: &mut E, max_rounds: usize) -> usize
+where
+    E: Engine + ?Sized,
+{
+    let mut rounds = 0;
+    while rounds < max_rounds {
+        for node_id in engine.node_ids() {
+            engine.activate(node_id);
+        }
+        rounds += 1;
+        log_trace!("simulation: round {} done", rounds);
+        if engine.converged() {
+            break;
+        }
+    }
+    log_debug!("simulation: stopped after {} rounds", rounds);
+    rounds
+}
+
+#[cfg(test)]
+mod simulation_test {
+    use super::*;
+
+    /// Counts down every node until it reaches 0.
+    struct Countdown {
+        counters: Vec<usize>,
+        activations: usize,
+    }
+
+    impl Engine for Countdown {
+        fn node_ids(&self) -> Vec<isize> {
+            (0..self.counters.len() as isize).collect()
+        }
+
+        fn activate(&mut self, node_id: isize) {
+            self.activations += 1;
+            let counter = &mut self.counters[node_id as usize];
+            *counter = counter.saturating_sub(1);
+        }
+
+        fn converged(&self) -> bool {
+            self.counters.iter().all(|counter| *counter == 0)
+        }
+    }
+
+    #[test]
+    fn run_with_batches() {
+        let mut engine = Countdown { counters: vec![3, 0], activations: 0 };
+        assert_eq!(run_with(&mut engine, 2, None, |_engine| 0), 2);
+        assert_eq!(engine.counters, vec![0, 0]);
+        assert_eq!(engine.activations, 4);
+
+        let mut engine = Countdown { counters: vec![9, 1], activations: 0 };
+        assert_eq!(run_with(&mut engine, 2, Some(3), |_engine| 0), 3);
+        assert_eq!(engine.counters, vec![3, 1]);
+        assert_eq!(engine.activations, 6);
+    }
+
+    #[test]
+    fn run_random_converges() {
+        let mut engine = Countdown { counters: vec![2, 2, 2], activations: 0 };
+        run_random(&mut engine, 1, None, &mut crate::util::SplitMix64::new(3));
+        assert!(engine.converged());
+        assert!(engine.activations >= 6);
+    }
+
+    #[test]
+    fn run_rounds_limit() {
+        let mut engine = Countdown { counters: vec![5, 1], activations: 0 };
+        assert_eq!(run_rounds(&mut engine, 2), 2);
+        assert_eq!(engine.counters, vec![3, 0]);
+        assert_eq!(run_rounds(&mut engine, 10), 3);
+        assert_eq!(run_rounds(&mut engine, 0), 0);
+    }
+}
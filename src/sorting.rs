@@ -0,0 +1,252 @@
+//! Classic comparison based sorting algorithms, sorting slices of any ordered type in ascending order.
+//!
+//! All of them sort in place. Merge sort and insertion sort are stable, they keep equal elements in their order,
+//! quicksort and heapsort are not.
+
+/// Sorts the slice using [Merge sort](https://en.wikipedia.org/wiki/Merge_sort), in O(n log n) time.
+///
+/// Elements are never cloned: the positions of the elements are merge sorted, then the elements are moved there.
+/// This needs O(n) additional memory for the positions.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::sorting::*;
+///
+/// let mut numbers = [5, 1, 4, 2, 3];
+/// merge_sort(&mut numbers);
+/// assert_eq!(numbers, [1, 2, 3, 4, 5]);
+/// ```
+pub fn merge_sort<T: Ord>(slice: &mut [T]) {
+    let mut order: Vec<usize> = (0..slice.len()).collect();
+    let mut buffer = order.clone();
+    merge_sort_positions(slice, &mut order, &mut buffer);
+    apply_order(slice, order);
+}
+
+/// Sorts the slice using [Quicksort](https://en.wikipedia.org/wiki/Quicksort), in O(n log n) time on average.
+///
+/// The pivot is the median of the first, middle and last element, so sorted input does not lead to a quadratic runtime.
+/// The smaller part is sorted first, keeping the recursion depth at O(log n), and short parts are sorted by insertion sort.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::sorting::*;
+///
+/// let mut words = ["pear", "apple", "fig"];
+/// quick_sort(&mut words);
+/// assert_eq!(words, ["apple", "fig", "pear"]);
+/// ```
+pub fn quick_sort<T: Ord>(mut slice: &mut [T]) {
+    while slice.len() > 16 {
+        let pivot = partition(slice);
+        let (left, right) = slice.split_at_mut(pivot);
+        let right = &mut right[1..];
+        if left.len() < right.len() {
+            quick_sort(left);
+            slice = right;
+        } else {
+            quick_sort(right);
+            slice = left;
+        }
+    }
+    insertion_sort(slice);
+}
+
+/// Sorts the slice using [Heapsort](https://en.wikipedia.org/wiki/Heapsort), in O(n log n) time without additional memory.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::sorting::*;
+///
+/// let mut numbers = vec![3, -1, 2, -1];
+/// heap_sort(&mut numbers);
+/// assert_eq!(numbers, vec![-1, -1, 2, 3]);
+/// ```
+pub fn heap_sort<T: Ord>(slice: &mut [T]) {
+    for index in (0..slice.len() / 2).rev() {
+        sift_down(slice, index);
+    }
+    for end in (1..slice.len()).rev() {
+        slice.swap(0, end);
+        sift_down(&mut slice[..end], 0);
+    }
+}
+
+/// Sorts the slice using [Insertion sort](https://en.wikipedia.org/wiki/Insertion_sort), in O(n²) time,
+/// but fast for short or almost sorted slices.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::sorting::*;
+///
+/// let mut letters = ['c', 'a', 'b'];
+/// insertion_sort(&mut letters);
+/// assert_eq!(letters, ['a', 'b', 'c']);
+/// ```
+pub fn insertion_sort<T: Ord>(slice: &mut [T]) {
+    for index in 1..slice.len() {
+        let mut position = index;
+        while position > 0 && slice[position - 1] > slice[position] {
+            slice.swap(position - 1, position);
+            position -= 1;
+        }
+    }
+}
+
+/// Sorts the positions of the elements, using the buffer for merging. The buffer needs to hold the same positions as order.
+fn merge_sort_positions<T: Ord>(slice: &[T], order: &mut [usize], buffer: &mut [usize]) {
+    if order.len() < 2 {
+        return;
+    }
+    let middle = order.len() / 2;
+    // sort both halves of the buffer, then merge them into order
+    merge_sort_positions(slice, &mut buffer[..middle], &mut order[..middle]);
+    merge_sort_positions(slice, &mut buffer[middle..], &mut order[middle..]);
+    let (left, right) = buffer.split_at(middle);
+    let (mut a, mut b) = (0, 0);
+    for target in order.iter_mut() {
+        // taking from the left on equal elements keeps the sort stable
+        if b == right.len() || (a < left.len() && slice[left[a]] <= slice[right[b]]) {
+            *target = left[a];
+            a += 1;
+        } else {
+            *target = right[b];
+            b += 1;
+        }
+    }
+}
+
+/// Moves the elements so the element at order[i] ends up at index i, following the cycles of the permutation.
+fn apply_order<T>(slice: &mut [T], mut order: Vec<usize>) {
+    for start in 0..slice.len() {
+        let mut current = start;
+        while order[current] != start {
+            let next = order[current];
+            slice.swap(current, next);
+            order[current] = current;
+            current = next;
+        }
+        order[current] = current;
+    }
+}
+
+/// Partitions the slice around the median of three and returns the final index of the pivot.
+/// Smaller elements end up before it, the others after it.
+fn partition<T: Ord>(slice: &mut [T]) -> usize {
+    let last = slice.len() - 1;
+    let middle = last / 2;
+    if slice[middle] < slice[0] {
+        slice.swap(middle, 0);
+    }
+    if slice[last] < slice[0] {
+        slice.swap(last, 0);
+    }
+    if slice[last] < slice[middle] {
+        slice.swap(last, middle);
+    }
+    // the median is at middle now, move it to the end while partitioning
+    slice.swap(middle, last);
+    let mut store = 0;
+    for index in 0..last {
+        if slice[index] < slice[last] {
+            slice.swap(index, store);
+            store += 1;
+        }
+    }
+    slice.swap(store, last);
+    store
+}
+
+/// Moves the element at index down the max-heap until both its children are smaller.
+fn sift_down<T: Ord>(heap: &mut [T], mut index: usize) {
+    loop {
+        let mut largest = index;
+        for child in [2 * index + 1, 2 * index + 2] {
+            if child < heap.len() && heap[child] > heap[largest] {
+                largest = child;
+            }
+        }
+        if largest == index {
+            return;
+        }
+        heap.swap(index, largest);
+        index = largest;
+    }
+}
+
+#[cfg(test)]
+mod sorting_test {
+    use super::*;
+    use crate::util::{RandomSource, SplitMix64};
+    use std::cmp::Ordering;
+
+    /// Ordered by the key only, to check which sorts are stable.
+    #[derive(Debug, Clone, Copy)]
+    struct Keyed(u8, usize);
+
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Keyed {}
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    fn inputs() -> Vec<Vec<u32>> {
+        let mut rng = SplitMix64::new(17);
+        let mut inputs = vec![vec![], vec![1], vec![2, 1], (0..100).collect(), (0..100).rev().collect(), vec![7; 50]];
+        for len in [3, 17, 64, 1000] {
+            inputs.push((0..len).map(|_| rng.random_index(len / 2 + 1) as u32).collect());
+        }
+        inputs
+    }
+
+    #[test]
+    fn sorts() {
+        type Sort = fn(&mut [u32]);
+        let sorts: [(&str, Sort); 4] =
+            [("merge", merge_sort), ("quick", quick_sort), ("heap", heap_sort), ("insertion", insertion_sort)];
+        for input in inputs() {
+            let mut expected = input.clone();
+            expected.sort();
+            for (name, sort) in sorts.iter() {
+                let mut sorted = input.clone();
+                sort(&mut sorted);
+                assert_eq!(sorted, expected, "{} sort of {:?}", name, input);
+            }
+        }
+    }
+
+    #[test]
+    fn stable() {
+        let mut rng = SplitMix64::new(5);
+        let input: Vec<Keyed> = (0..300).map(|index| Keyed(rng.random_index(10) as u8, index)).collect();
+        let mut expected = input.clone();
+        expected.sort();
+        let order = |slice: &[Keyed]| slice.iter().map(|keyed| keyed.1).collect::<Vec<_>>();
+        for sort in [merge_sort, insertion_sort] {
+            let mut sorted = input.clone();
+            sort(&mut sorted);
+            assert_eq!(order(&sorted), order(&expected));
+        }
+    }
+
+    #[test]
+    fn strings() {
+        let mut words: Vec<String> = "the quick brown fox jumps over the lazy dog".split(' ').map(String::from).collect();
+        merge_sort(&mut words);
+        assert_eq!(words, ["brown", "dog", "fox", "jumps", "lazy", "over", "quick", "the", "the"]);
+    }
+}
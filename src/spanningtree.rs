@@ -1,25 +1,127 @@
-use rand::Rng;
+use crate::dot;
+use crate::json;
+use crate::simulation::{self, Engine};
+use crate::util::{RandomSource, WeightedSampler};
+use std::fmt;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
+/// The priority nodes get when they are created, matching the default bridge priority of IEEE 802.1D.
+pub const DEFAULT_PRIORITY: usize = 32768;
+
+/// The number of ticks a link stays in the listening and in the learning state, matching the default forward delay of IEEE 802.1D in seconds.
+pub const DEFAULT_FORWARD_DELAY: usize = 15;
+
+#[derive(Clone, Debug)]
 pub struct Node {
     pub id: isize,
     #[allow(dead_code)]
     pub name: &'static str,
     pub msg_count: usize,
+    pub accept_count: usize,
     pub next_hop: Option<isize>,
     pub root_cost: usize,
     pub root_id: isize,
+    pub root_priority: usize,
+    pub priority: usize,
+    pub hello_time: usize,
 }
 
+#[derive(Clone, Debug)]
 pub struct Link {
     pub members: (isize, isize),
-    pub cost: usize
+    pub cost: usize,
+    pub state: PortState,
+    pub state_ticks: usize
+}
+
+/// The state of the ports on both ends of a link.
+///
+/// Links which become part of the spanning tree pass through the listening and learning states
+/// before they start forwarding frames, links which are not part of it are blocking.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PortState {
+    Blocking,
+    Listening,
+    Learning,
+    Forwarding,
+}
+
+/// The speed of a physical link between two switches.
+/// It can be turned into the path cost a switch would assign to a port with that speed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LinkSpeed {
+    TenMbit,
+    HundredMbit,
+    Gigabit,
+    TenGigabit,
+}
+
+/// Statistics about a single node of a tree, as collected by Tree::stats().
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeStats {
+    pub id: isize,
+    pub name: &'static str,
+    pub msg_count: usize,
+    pub accept_count: usize,
+    pub acceptance_ratio: f64,
+    pub depth: Option<usize>,
+}
+
+/// Statistics about a simulated tree, as returned by Tree::stats().
+/// They can be exported as CSV or JSON for further analysis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeStats {
+    pub nodes: Vec<NodeStats>,
+    pub height: Option<usize>,
+}
+
+/// What a call of Tree::run_calc did, including the runs on other nodes it caused with the recursive option.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CalcOutcome {
+    /// The number of suggestions sent to neighbouring nodes.
+    pub suggestions_sent: usize,
+    /// The number of suggestions the neighbouring nodes accepted.
+    pub suggestions_accepted: usize,
+    /// The ids of the nodes which accepted any suggestion, in the order they first did.
+    pub updated_nodes: Vec<isize>,
+}
+
+/// The result of a broadcast simulated by Tree::flood_from().
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FloodReport {
+    /// The node which sent the broadcast.
+    pub source: isize,
+    /// The nodes which received the frame, in the order they received it, together with their hop count.
+    /// The source is part of the list with a hop count of 0.
+    pub received: Vec<(isize, usize)>,
+    /// The nodes which received the frame again after they had already received it, once per duplicate.
+    pub duplicates: Vec<isize>,
+}
+
+/// The distribution tree of multicast traffic, as computed by Tree::multicast_tree().
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MulticastTree {
+    /// The node which sends the traffic.
+    pub source: isize,
+    /// The receivers which are reached by the traffic, in the order they were given.
+    pub receivers: Vec<isize>,
+    /// The receivers which cannot be reached from the source over forwarding links.
+    pub unreachable: Vec<isize>,
+    /// The links the traffic is forwarded over, as pairs of the sending and the receiving node, in the order they are reached.
+    pub links: Vec<(isize, isize)>,
+    /// The forwarding links the traffic is not forwarded over, as there is no receiver behind them.
+    pub pruned: Vec<(isize, isize)>,
 }
 
-#[derive(Default)]
+#[derive(Clone, Debug)]
 pub struct Tree {
     node_list: Vec<Node>,
     root_id: Option<isize>,
-    link_list: Vec<Link>
+    link_list: Vec<Link>,
+    time: usize,
+    forward_delay: usize,
+    activations: Option<Vec<isize>>
 }
 
 impl Node {
@@ -27,7 +129,8 @@ impl Node {
     /// The Node is to be used within a Tree.
     /// In a Spanningtree it represents one participant, therefore a node.
     /// For identification purposes a payload can be supplied in the form of a String.
-    /// The ID of the Node also represents the weight of it.
+    /// The priority of the Node, followed by its ID, represents the weight of it.
+    /// As all nodes start with the same priority, the ID decides unless a priority is set.
     /// A Spanningtree is balanced so that the node with the lowest weight is the root node.
     /// ```text
     /// *-*  * <- high weight (high id)
@@ -47,12 +150,52 @@ impl Node {
             id,
             name,
             msg_count: 0,
+            accept_count: 0,
             next_hop: None,
             root_cost: 0,
-            root_id: id
+            root_id: id,
+            root_priority: DEFAULT_PRIORITY,
+            priority: DEFAULT_PRIORITY,
+            hello_time: 1
         }
     }
 
+    /// Sets the priority of the node, which is compared before the id when the root of a tree is chosen.
+    /// A lower priority makes it more likely for the node to become the root.
+    /// Nodes start with the DEFAULT_PRIORITY.
+    ///
+    /// To change the priority of a node which is already part of a tree, use Tree::set_priority().
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::Node;
+    /// let node: Node = Node::new(7, "Core Switch").priority(4096);
+    /// assert_eq!(node.priority, 4096);
+    /// assert_eq!(node.root_priority, 4096);
+    /// ```
+    pub fn priority(mut self, priority: usize) -> Self {
+        self.priority = priority;
+        self.root_priority = priority;
+        self
+    }
+
+    /// Sets the hello time of the node, which is the number of ticks between two suggestions originated by the node.
+    /// Nodes start with a hello time of 1, meaning they send a suggestion on every tick.
+    /// A node with a hello time of 0 never sends suggestions on its own.
+    ///
+    /// See Tree::tick() for more information.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::Node;
+    /// let node: Node = Node::new(1, "Node Numero Uno").hello_time(2);
+    /// assert_eq!(node.hello_time, 2);
+    /// ```
+    pub fn hello_time(mut self, hello_time: usize) -> Self {
+        self.hello_time = hello_time;
+        self
+    }
+
     /// Receives a suggestion for a path to a node. 
     /// If the path seems to go to the root node or 
     /// is smaller than the already known path it gets accepted by the node.
@@ -64,6 +207,12 @@ impl Node {
     /// 
     /// Returns true if the node accepted the suggestion and either changed it's path to root
     /// or the cost it to the already known root is smaller than the current cost.
+    ///
+    /// Since nodes have priorities, the suggested root is assumed to have the DEFAULT_PRIORITY.
+    /// As long as all nodes keep that priority, only the ids are compared, like before priorities were added.
+    /// A node which knows a root with a lower priority than the default however rejects every suggestion made this way,
+    /// whatever the id, and one which knows a root with a higher priority accepts every suggestion.
+    /// Use receive_suggestion_with_priority to suggest roots with other priorities.
     /// 
     /// # Example
     /// ```
@@ -71,17 +220,40 @@ impl Node {
     /// let mut node: Node = Node::new(3, "Node Three");
     /// let accepted: bool = node.receive_suggestion(2, 8, 10);
     /// assert_eq!(accepted, true);
+    ///
+    /// // the known root has a lower priority, so a lower id does not help
+    /// let mut node: Node = Node::new(3, "Node Three").priority(0);
+    /// assert_eq!(node.receive_suggestion(1, 1, 10), false);
     /// ```
     pub fn receive_suggestion(&mut self, suggested_id: isize, source_id: isize, root_cost: usize) -> bool {
+        self.receive_suggestion_with_priority(DEFAULT_PRIORITY, suggested_id, source_id, root_cost)
+    }
+
+    /// Receives a suggestion for a path to a node with the given priority.
+    ///
+    /// Works like receive_suggestion, but compares the priority of the assumed root before its id.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::Node;
+    /// let mut node: Node = Node::new(3, "Node Three");
+    /// assert_eq!(node.receive_suggestion_with_priority(4096, 8, 8, 10), true);
+    /// assert_eq!(node.receive_suggestion(2, 2, 5), false);
+    /// assert_eq!(node.root_id, 8);
+    /// ```
+    pub fn receive_suggestion_with_priority(&mut self, suggested_priority: usize, suggested_id: isize, source_id: isize, root_cost: usize) -> bool {
         self.msg_count += 1;
-        if suggested_id < self.root_id {
+        if (suggested_priority, suggested_id) < (self.root_priority, self.root_id) {
             self.root_cost = root_cost;
             self.next_hop = Some(source_id);
             self.root_id = suggested_id;
+            self.root_priority = suggested_priority;
+            self.accept_count += 1;
             return true;
-        } else if suggested_id == self.root_id && root_cost < self.root_cost {
+        } else if (suggested_priority, suggested_id) == (self.root_priority, self.root_id) && root_cost < self.root_cost {
             self.root_cost = root_cost;
             self.next_hop = Some(source_id);
+            self.accept_count += 1;
             return true;
         }
         false
@@ -92,7 +264,58 @@ impl Link {
     pub fn new(members: (isize, isize), cost: usize) -> Self {
         Link {
             members,
-            cost
+            cost,
+            state: PortState::Blocking,
+            state_ticks: 0
+        }
+    }
+
+    /// Creates a link between two nodes, with the path cost STP assigns to a link of that speed.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let link: Link = Link::with_speed((1, 2), LinkSpeed::Gigabit);
+    /// assert_eq!(link.cost, 4);
+    /// ```
+    pub fn with_speed(members: (isize, isize), speed: LinkSpeed) -> Self {
+        Link::new(members, speed.to_stp_cost())
+    }
+}
+
+impl LinkSpeed {
+    /// Returns the recommended path cost for a port of this speed, as defined by IEEE 802.1D-1998.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::LinkSpeed;
+    /// assert_eq!(LinkSpeed::TenMbit.to_stp_cost(), 100);
+    /// assert_eq!(LinkSpeed::TenGigabit.to_stp_cost(), 2);
+    /// ```
+    pub fn to_stp_cost(self) -> usize {
+        match self {
+            LinkSpeed::TenMbit => 100,
+            LinkSpeed::HundredMbit => 19,
+            LinkSpeed::Gigabit => 4,
+            LinkSpeed::TenGigabit => 2,
+        }
+    }
+
+    /// Returns the recommended path cost for a port of this speed, as defined by IEEE 802.1D-2004 for RSTP.
+    /// Those costs are inversely proportional to the speed of the link.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::LinkSpeed;
+    /// assert_eq!(LinkSpeed::TenMbit.to_rstp_cost(), 2_000_000);
+    /// assert_eq!(LinkSpeed::TenGigabit.to_rstp_cost(), 2_000);
+    /// ```
+    pub fn to_rstp_cost(self) -> usize {
+        match self {
+            LinkSpeed::TenMbit => 2_000_000,
+            LinkSpeed::HundredMbit => 200_000,
+            LinkSpeed::Gigabit => 20_000,
+            LinkSpeed::TenGigabit => 2_000,
         }
     }
 }
@@ -102,8 +325,100 @@ impl Tree {
         Tree {
             node_list: Vec::new(),
             root_id: None,
-            link_list : Vec::new()
+            link_list : Vec::new(),
+            time: 0,
+            forward_delay: DEFAULT_FORWARD_DELAY,
+            activations: None
+        }
+    }
+
+    /// Creates a tree with room for the given number of nodes and links,
+    /// so building a large tree does not repeatedly reallocate its lists.
+    pub fn with_capacity(nodes: usize, links: usize) -> Self {
+        let mut tree = Tree::new();
+        tree.reserve(nodes, links);
+        tree
+    }
+
+    /// Reserves room for at least the given number of additional nodes and links.
+    pub fn reserve(&mut self, nodes: usize, links: usize) {
+        self.node_list.reserve(nodes);
+        self.link_list.reserve(links);
+    }
+
+    /// Creates a tree with a switch for every node of the graph and a link for every link of the graph,
+    /// keeping their ids and costs.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph;
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut graph = graph::Graph::new();
+    /// let a = graph.add_node(graph::Node::new("A"));
+    /// let b = graph.add_node(graph::Node::new("B"));
+    /// graph.add_link(graph::Link::new((a, b), 4));
+    ///
+    /// let mut tree = Tree::from_graph(&graph);
+    /// assert_eq!(tree.get_node(b).unwrap().name, "B");
+    /// assert_eq!(tree.find_link(a, b).unwrap().cost, 4);
+    /// ```
+    pub fn from_graph(graph: &crate::graph::Graph) -> Self {
+        let mut tree = Tree::with_capacity(graph.nodes().len(), graph.links().len());
+        for node in graph.nodes() {
+            tree.add_node(Node::new(node.id, node.name));
         }
+        for link in graph.links() {
+            tree.add_link(Link::new(link.members, link.cost));
+        }
+        tree
+    }
+
+    /// Sets the number of ticks a link stays in the listening and in the learning state, before it starts forwarding.
+    /// Trees start with the DEFAULT_FORWARD_DELAY.
+    ///
+    /// See Tree::tick() for more information.
+    pub fn set_forward_delay(&mut self, forward_delay: usize) {
+        self.forward_delay = forward_delay;
+    }
+
+    /// Returns the number of ticks that have passed on the tree.
+    pub fn time(&self) -> usize {
+        self.time
+    }
+
+    /// Returns the nodes of the tree, in the order they were added.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Root Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.simulate_rounds(10);
+    /// assert!(tree.nodes().iter().all(|node| node.root_id == 2));
+    /// assert_eq!(tree.node_count(), 2);
+    /// assert_eq!(tree.links()[0].cost, 5);
+    /// assert_eq!(tree.root_id(), Some(2));
+    /// ```
+    pub fn nodes(&self) -> &[Node] {
+        &self.node_list
+    }
+
+    /// Returns the links of the tree, in the order they were added.
+    pub fn links(&self) -> &[Link] {
+        &self.link_list
+    }
+
+    /// Returns the number of nodes of the tree.
+    pub fn node_count(&self) -> usize {
+        self.node_list.len()
+    }
+
+    /// Returns the id of the node which should become the root of the tree, the one with the lowest priority and id,
+    /// or None if the tree has no nodes. Whether the other nodes know about it yet is told by is_converged.
+    pub fn root_id(&self) -> Option<isize> {
+        self.root_id
     }
 
     /// Returns a link, if there is one, between node a and node b, identified by their ids.
@@ -156,6 +471,8 @@ impl Tree {
         if self.find_link(link.members.0, link.members.1).is_none() {
             self.link_list.push(link);
         }
+        #[cfg(feature = "debug-validate")]
+        self.validate();
     }
 
     /// Adds a node to the tree if this doesnt exist already. 
@@ -175,10 +492,55 @@ impl Tree {
                 return;
             }
         }
-        if self.root_id.is_none() || (self.root_id.is_some() && node.id < self.root_id.unwrap()) {
-            self.root_id = Some(node.id);
-        }
         self.node_list.push(node);
+        self.update_root_id();
+        #[cfg(feature = "debug-validate")]
+        self.validate();
+    }
+
+    /// Changes the priority of a node in the tree, e.g. to make it the root of the tree.
+    ///
+    /// As the knowledge of the nodes about the root is outdated afterwards,
+    /// all nodes forget the root they learned about and assume to be the root themselves again,
+    /// so the next simulation run reconverges the tree with the new priority.
+    /// The message counters of the nodes are kept.
+    ///
+    /// Returns false if no node with that id has been found.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(2, "Old Root"));
+    /// tree.add_node(Node::new(4, "New Root"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.simulate_rounds(10);
+    /// assert_eq!(tree.get_node(4).unwrap().root_id, 2);
+    /// assert_eq!(tree.set_priority(4, 4096), true);
+    /// tree.simulate_rounds(10);
+    /// assert_eq!(tree.get_node(2).unwrap().root_id, 4);
+    /// assert_eq!(tree.get_node(2).unwrap().next_hop, Some(4));
+    /// ```
+    pub fn set_priority(&mut self, node_id: isize, priority: usize) -> bool {
+        match self.node_list.iter_mut().find(|node| node.id == node_id) {
+            Some(node) => node.priority = priority,
+            None => return false
+        }
+        for node in &mut self.node_list {
+            node.root_id = node.id;
+            node.root_priority = node.priority;
+            node.root_cost = 0;
+            node.next_hop = None;
+        }
+        self.update_root_id();
+        #[cfg(feature = "debug-validate")]
+        self.validate();
+        true
+    }
+
+    /// Sets the root id of the tree to the node with the lowest priority and id.
+    fn update_root_id(&mut self) {
+        self.root_id = self.node_list.iter().min_by_key(|node| (node.priority, node.id)).map(|node| node.id);
     }
 
     /// Gets a specific node from the tree, specified by their id, wrapped in an Option.
@@ -209,7 +571,8 @@ impl Tree {
     /// if a node accepts a new root/a lower cost to the root run_calc will run on this node again
     /// and will spread the information to their neighbouring nodes and so on.
     /// 
-    /// Returns false if no node with that id has been found.
+    /// Returns how many suggestions were sent and accepted and which nodes accepted them,
+    /// or None if no node with that id has been found.
     /// 
     /// # Recursive Run Example
     /// ```text
@@ -229,38 +592,60 @@ impl Tree {
     /// tree.add_node(Node::new(3, "Second Node"));
     /// tree.add_link(Link::new((2,4), 5));
     /// tree.add_link(Link::new((3,2), 8));
-    /// let run: bool = tree.run_calc(2, false);
-    /// assert_eq!(run, true);
+    /// let outcome: CalcOutcome = tree.run_calc(2, false).unwrap();
+    /// assert_eq!(outcome, CalcOutcome { suggestions_sent: 2, suggestions_accepted: 2, updated_nodes: vec![4, 3] });
     /// assert_eq!(tree.get_node(4).unwrap().root_id, 2);
     /// assert_eq!(tree.get_node(3).unwrap().root_cost, 8);
+    /// assert!(tree.run_calc(5, false).is_none());
     /// ```
-    pub fn run_calc(&mut self, node_id: isize, recursive: bool) -> bool {
+    pub fn run_calc(&mut self, node_id: isize, recursive: bool) -> Option<CalcOutcome> {
         let root_cost: usize;
         let root_id: isize;
+        let root_priority: usize;
             {   // Scoped, so the borrow of self is released after this scope ends.
                 let node: Option<&Node> = self.node_list.iter().find(|n| n.id == node_id);
                 match node {
                     Some(node_result) => {
                         root_cost = node_result.root_cost;
                         root_id = node_result.root_id;
+                        root_priority = node_result.root_priority;
                     },
-                    None => return false
+                    None => return None
                 }
             }
+        let mut outcome = CalcOutcome::default();
         let mut recursive_vec: Vec<isize> = Vec::new();
         for link in &self.link_list {
             if let Some(index) = self.node_list.iter().position(|node_item| node_item.id == (if node_id == link.members.0 {link.members.1} else if node_id == link.members.1 {link.members.0} else {-1})) {
                 let other_node = self.node_list.get_mut(index).unwrap(); // Safe to unwrap due to the if let Some in the line before
-                let accept = other_node.receive_suggestion(root_id, node_id, root_cost + link.cost);
-                if accept && recursive {
-                    recursive_vec.push(other_node.id);
+                let accept = other_node.receive_suggestion_with_priority(root_priority, root_id, node_id, root_cost + link.cost);
+                log_trace!("spanningtree: {} suggests root {} at cost {} to {}, accepted: {}", node_id, root_id, root_cost + link.cost, other_node.id, accept);
+                outcome.suggestions_sent += 1;
+                if accept {
+                    outcome.suggestions_accepted += 1;
+                    if !outcome.updated_nodes.contains(&other_node.id) {
+                        outcome.updated_nodes.push(other_node.id);
+                    }
+                    if recursive {
+                        recursive_vec.push(other_node.id);
+                    }
                 }
             }
         }
         for id in recursive_vec {
-            self.run_calc(id, recursive);
+            if let Some(caused) = self.run_calc(id, recursive) {
+                outcome.suggestions_sent += caused.suggestions_sent;
+                outcome.suggestions_accepted += caused.suggestions_accepted;
+                for updated in caused.updated_nodes {
+                    if !outcome.updated_nodes.contains(&updated) {
+                        outcome.updated_nodes.push(updated);
+                    }
+                }
+            }
         }
-        true
+        #[cfg(feature = "debug-validate")]
+        self.validate();
+        Some(outcome)
     }
 
     /// Simulates a tree until for x iterations or even longer if there was a min_hops specified and if there are nodes in the tree that haven't been touched by the simulation enough.
@@ -270,6 +655,8 @@ impl Tree {
     /// In a "real" implementation a node would keep on sending until it hasn't found out about a new root node for a while.
     /// Here we can utilize a counter on each node to count how often it has been visited. (min_hop)
     /// We can use that solely or combine it with a iteration count. min_iterations defines how many nodes we should let the simulation run on at least.
+    ///
+    /// The nodes are selected by `rand::thread_rng()`. To reproduce a simulation, use simulate_with_rng with a seeded generator.
     /// 
     /// # Example
     /// ```
@@ -284,104 +671,1567 @@ impl Tree {
     /// assert_eq!(tree.get_node(4).unwrap().msg_count > 100, true);
     /// assert_eq!(tree.get_node(4).unwrap().root_id, 2);
     /// ```
+    #[cfg(feature = "rand")]
     pub fn simulate(&mut self, min_iterations: usize, min_hops: usize, recursive: bool) {
-        while {
-            for _i in 0..min_iterations {
-                let randi = rand::thread_rng().gen_range(0, self.node_list.len());
-                let nodeid: isize = self.node_list[randi].id;
-                self.run_calc(nodeid, recursive);
-            }
-            self.node_list.iter().any(|node| node.msg_count <= min_hops) && min_hops != 0
-        } {}
+        self.simulate_with_rng(min_iterations, min_hops, recursive, &mut rand::thread_rng());
     }
-}
 
-#[cfg(test)]
-mod tree_tests {
-    use super::*;
+    /// Simulates a tree like simulate, but the random nodes are selected by the given random number generator.
+    /// Any generator of rand can be passed, so e.g. `StdRng::seed_from_u64` makes the simulation reproducible.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// use rust_algorithms::util::SplitMix64;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.simulate_with_rng(10, 0, true, &mut SplitMix64::new(42));
+    /// assert_eq!(tree.get_node(4).unwrap().root_id, 2);
+    /// ```
+    pub fn simulate_with_rng<R>(&mut self, min_iterations: usize, min_hops: usize, recursive: bool, rng: &mut R)
+    where
+        R: RandomSource + ?Sized,
+    {
+        self.simulate_with_sampler(min_iterations, min_hops, recursive, |nodes| rng.random_index(nodes.len()));
+    }
 
-    #[test]
-    fn add_link() {
-        let mut tree = Tree::new();
-        tree.add_link(Link::new((1,2), 5));
-        tree.add_link(Link::new((2,5), 8));
-        assert_eq!(tree.link_list.len(), 2);
-        assert_eq!(tree.link_list[0].members.1, 2);
+    /// Simulates a tree like simulate, but nodes are selected with a probability proportional to their weight.
+    ///
+    /// The weight of every node is computed once by the given function. This allows modeling chatty and quiet switches.
+    /// Nodes with a weight of 0 are never selected, but still receive suggestions from their neighbours.
+    ///
+    /// # Panics
+    /// Panics if the weights of all nodes are 0.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_node(Node::new(3, "Second Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.add_link(Link::new((3,2), 8));
+    /// tree.simulate_weighted(10, 0, false, |node| if node.id == 2 { 1 } else { 0 });
+    /// assert_eq!(tree.get_node(4).unwrap().msg_count, 10);
+    /// assert_eq!(tree.get_node(2).unwrap().msg_count, 0);
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn simulate_weighted<F>(&mut self, min_iterations: usize, min_hops: usize, recursive: bool, weight: F)
+    where
+        F: Fn(&Node) -> usize,
+    {
+        self.simulate_weighted_with_rng(min_iterations, min_hops, recursive, weight, &mut rand::thread_rng());
     }
 
-    #[test]
-    fn find_link() {
-        let mut tree = Tree::new();
-        tree.add_link(Link::new((1,2), 5));
-        tree.add_link(Link::new((2,5), 8));
-        let link = tree.find_link(2, 1);
-        assert_eq!(link.is_some(), true);
-        let unwrapped_link = link.unwrap();
-        assert_eq!(unwrapped_link.cost, 5);
-        assert_eq!(tree.find_link(7, 9).is_none(), true);
+    /// Simulates a tree like simulate_weighted, but the nodes are selected by the given random number generator.
+    ///
+    /// # Panics
+    /// Panics if the weights of all nodes are 0.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// use rust_algorithms::util::SplitMix64;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.simulate_weighted_with_rng(10, 0, false, |node| if node.id == 2 { 1 } else { 0 }, &mut SplitMix64::new(3));
+    /// assert_eq!(tree.get_node(4).unwrap().msg_count, 10);
+    /// ```
+    pub fn simulate_weighted_with_rng<F, R>(&mut self, min_iterations: usize, min_hops: usize, recursive: bool, weight: F, rng: &mut R)
+    where
+        F: Fn(&Node) -> usize,
+        R: RandomSource + ?Sized,
+    {
+        let weights: Vec<f64> = self.node_list.iter().map(|node| weight(node) as f64).collect();
+        let sampler = WeightedSampler::new(&weights).expect("at least one node needs a weight above 0");
+        self.simulate_with_sampler(min_iterations, min_hops, recursive, |_nodes| sampler.sample(rng));
     }
 
-    #[test]
-    fn find_links() {
-        let mut tree = Tree::new();
-        tree.add_link(Link::new((1,2), 5));
-        tree.add_link(Link::new((2,5), 8));
-        tree.add_link(Link::new((7,9), 2));
-        let links = tree.find_links(2);
-        assert_eq!(links.len(), 2);
+    /// Simulates a tree like simulate, but the nodes to run run_calc on are selected by the given sampler.
+    ///
+    /// The sampler receives the nodes of the tree in the order they were added and returns the index of the next node to run.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_node(Node::new(3, "Second Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.add_link(Link::new((3,2), 8));
+    /// let mut next = 0;
+    /// tree.simulate_with_sampler(6, 0, false, |nodes| {
+    ///     next = (next + 1) % nodes.len();
+    ///     next
+    /// });
+    /// assert_eq!(tree.get_node(2).unwrap().msg_count, 4);
+    /// assert_eq!(tree.get_node(3).unwrap().root_id, 2);
+    /// ```
+    pub fn simulate_with_sampler<F>(&mut self, min_iterations: usize, min_hops: usize, recursive: bool, mut sampler: F)
+    where
+        F: FnMut(&[Node]) -> usize,
+    {
+        let mut simulation = Simulation { tree: self, recursive, min_hops };
+        simulation::run_with(&mut simulation, min_iterations, None, |simulation| {
+            let index = sampler(&simulation.tree.node_list);
+            simulation.tree.node_list[index].id
+        });
     }
 
-    #[test]
-    fn multiple_nodes_with_same_id() {
-        let mut tree = Tree::new();
-        tree.add_node(Node::new(4, "E"));
-        tree.add_node(Node::new(4, "E"));
-        assert_eq!(tree.node_list.len(), 1);
+    /// Starts or stops recording the nodes selected by the simulation.
+    ///
+    /// While recording, the ids of the nodes selected by simulate, simulate_replay and the other simulate_ methods which select one node at a time are remembered
+    /// in the order they have been run, so exactly the same simulation can be run again with simulate_replay.
+    /// Starting the recording discards everything recorded before.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.set_recording(true);
+    /// tree.simulate_with_rng(10, 0, false, &mut rust_algorithms::util::SplitMix64::new(1));
+    /// assert_eq!(tree.recorded_activations().len(), 10);
+    /// ```
+    pub fn set_recording(&mut self, record: bool) {
+        self.activations = if record { Some(Vec::new()) } else { None };
     }
 
-    #[test]
-    fn test_run_calc() {
-        let mut tree = Tree::new();
-        tree.add_node(Node::new(5, "A"));
-        tree.add_node(Node::new(1, "B"));
-        tree.add_node(Node::new(1, "B"));
-        tree.add_node(Node::new(3, "C"));
-        tree.add_node(Node::new(7, "D"));
-        let node2 = Node::new(6, "E");
-        tree.add_node(node2);
-        tree.add_node(Node::new(4, "F"));
-        tree.add_link(Link::new((5, 1), 10));
-        tree.add_link(Link::new((5, 3), 10));
-        tree.add_link(Link::new((1, 7), 15));
-        tree.add_link(Link::new((1, 6), 10));
-        tree.add_link(Link::new((3, 7), 3));
-        tree.add_link(Link::new((3, 6), 10));
-        tree.add_link(Link::new((7, 6), 2));
-        tree.add_link(Link::new((7, 4), 10));
-        tree.add_link(Link::new((6, 4), 2));
-        assert_eq!(tree.run_calc(999, false), false);
-        assert_eq!(tree.run_calc(3, false), true);
-        tree.simulate(10, 10, true);
-        assert_eq!(tree.node_list.iter().all(|node| node.msg_count > 10), true);
-        assert_eq!(tree.node_list.iter().all(|node| node.root_id == 1), true);
-        assert_eq!(tree.get_node(3).unwrap().next_hop.unwrap(), 7);
-        assert_eq!(tree.node_list[1].root_id, 1);
-        for node in tree.node_list {
-            println!("ID: {}, Name: {}, Messages: {}, Next Hop: {}, Root Cost: {}, Root ID: {}", node.id, node.name, node.msg_count, node.next_hop.unwrap_or(0), node.root_cost, node.root_id);
+    /// Returns the ids of the nodes that have been run since the recording was started, in the order they have been run.
+    /// Returns an empty list if nothing is recorded.
+    pub fn recorded_activations(&self) -> &[isize] {
+        match &self.activations {
+            Some(activations) => activations,
+            None => &[]
         }
     }
-    
-}
 
-#[cfg(test)]
-mod link_test {
-    use super::*;
+    /// Runs run_calc on the given nodes in the given order, e.g. to replay a recorded simulation.
+    ///
+    /// Returns false if any of the ids did not belong to a node of the tree.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let build = || {
+    ///     let mut tree: Tree = Tree::new();
+    ///     tree.add_node(Node::new(4, "Second Node"));
+    ///     tree.add_node(Node::new(2, "Second Node"));
+    ///     tree.add_node(Node::new(3, "Second Node"));
+    ///     tree.add_link(Link::new((2,4), 5));
+    ///     tree.add_link(Link::new((3,4), 8));
+    ///     tree
+    /// };
+    /// let mut tree = build();
+    /// tree.set_recording(true);
+    /// tree.simulate_with_rng(3, 0, false, &mut rust_algorithms::util::SplitMix64::new(1));
+    /// let recording: Vec<isize> = tree.recorded_activations().to_vec();
+    ///
+    /// let mut replayed = build();
+    /// assert_eq!(replayed.simulate_replay(&recording, false), true);
+    /// assert_eq!(replayed.get_node(3).unwrap().root_id, tree.get_node(3).unwrap().root_id);
+    /// assert_eq!(replayed.get_node(3).unwrap().msg_count, tree.get_node(3).unwrap().msg_count);
+    /// ```
+    pub fn simulate_replay(&mut self, activations: &[isize], recursive: bool) -> bool {
+        let mut all_found = true;
+        for &nodeid in activations {
+            if let Some(recorded) = &mut self.activations {
+                recorded.push(nodeid);
+            }
+            all_found &= self.run_calc(nodeid, recursive).is_some();
+        }
+        all_found
+    }
 
-    #[test]
-    fn test_link() {
-        let link = Link::new((3,9), 4);
-        assert_eq!(link.cost, 4);
-        assert_eq!(link.members.0, 3);
+    /// Advances the time of the tree by one tick.
+    ///
+    /// Every node whose hello time has passed since its last suggestion sends its knowledge about the root
+    /// to its neighbouring nodes, like run_calc does when called without the recursive option.
+    /// Like the hello timer of a switch, a node with a hello time of n does this on every n-th tick.
+    /// The nodes are processed in the order they were added to the tree.
+    ///
+    /// Afterwards the port states of the links are updated:
+    /// A link which connects a node to its next hop is part of the spanning tree.
+    /// Such a link moves from blocking to listening, and after the forward delay has passed from listening to learning
+    /// and from learning to forwarding. Links which are not part of the spanning tree (anymore) are blocking immediately.
+    ///
+    /// Returns the number of nodes that sent a suggestion during this tick.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(2, "Root Node").hello_time(2));
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// assert_eq!(tree.tick(), 1);
+    /// assert_eq!(tree.get_node(4).unwrap().root_id, 4);
+    /// assert_eq!(tree.tick(), 2);
+    /// assert_eq!(tree.get_node(4).unwrap().root_id, 2);
+    /// assert_eq!(tree.time(), 2);
+    /// ```
+    pub fn tick(&mut self) -> usize {
+        self.time += 1;
+        let time = self.time;
+        let senders: Vec<isize> = self.node_list.iter()
+            .filter(|node| node.hello_time != 0 && time.is_multiple_of(node.hello_time))
+            .map(|node| node.id)
+            .collect();
+        for node_id in &senders {
+            self.run_calc(*node_id, false);
+        }
+        self.update_port_states();
+        senders.len()
+    }
+
+    /// Moves the port state of every link one step towards the state matching the current next hops.
+    fn update_port_states(&mut self) {
+        let node_list = &self.node_list;
+        let is_next_hop = |from: isize, to: isize| node_list.iter().any(|node| node.id == from && node.next_hop == Some(to));
+        for link in &mut self.link_list {
+            let (a, b) = link.members;
+            let on_tree = a != b && (is_next_hop(a, b) || is_next_hop(b, a));
+            link.state_ticks += 1;
+            let next_state = match (on_tree, link.state) {
+                (false, _) => PortState::Blocking,
+                (true, PortState::Blocking) => PortState::Listening,
+                (true, PortState::Listening) if link.state_ticks >= self.forward_delay => PortState::Learning,
+                (true, PortState::Learning) if link.state_ticks >= self.forward_delay => PortState::Forwarding,
+                (true, state) => state,
+            };
+            if next_state != link.state {
+                link.state = next_state;
+                link.state_ticks = 0;
+            }
+        }
+    }
+
+    /// Returns the ids of all nodes which can be reached from the given node over forwarding links,
+    /// in the order a frame sent by the node would reach them, starting with the node itself.
+    ///
+    /// Returns an empty list if no node with that id has been found.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.set_forward_delay(1);
+    /// tree.add_node(Node::new(1, "Root"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_link(Link::new((1, 2), 5));
+    /// tree.tick();
+    /// assert_eq!(tree.reachable_from(1), vec![1]);
+    /// tree.tick();
+    /// tree.tick();
+    /// assert_eq!(tree.find_link(1, 2).unwrap().state, PortState::Forwarding);
+    /// assert_eq!(tree.reachable_from(1), vec![1, 2]);
+    /// ```
+    pub fn reachable_from(&self, node_id: isize) -> Vec<isize> {
+        use std::collections::VecDeque;
+
+        if !self.node_list.iter().any(|node| node.id == node_id) {
+            return Vec::new();
+        }
+        let mut reached = vec![node_id];
+        let mut queue = VecDeque::new();
+        queue.push_back(node_id);
+        while let Some(current) = queue.pop_front() {
+            for link in self.find_links(current) {
+                if link.state != PortState::Forwarding {
+                    continue;
+                }
+                let other = if link.members.0 == current { link.members.1 } else { link.members.0 };
+                if !reached.contains(&other) && self.node_list.iter().any(|node| node.id == other) {
+                    reached.push(other);
+                    queue.push_back(other);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Simulates a broadcast frame sent by the given node.
+    ///
+    /// Like a switch floods a broadcast, every node forwards the frame over all of its forwarding links,
+    /// except the one it received the frame on.
+    /// On a converged spanning tree every reachable node receives the frame exactly once.
+    /// If the forwarding links contain a loop, nodes receive duplicates of the frame.
+    /// A duplicate is reported, but not forwarded again, as it would circle through the loop forever otherwise.
+    ///
+    /// Returns an empty report if no node with that id has been found.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.set_forward_delay(1);
+    /// tree.add_node(Node::new(1, "Root"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_node(Node::new(3, "Third Node"));
+    /// tree.add_link(Link::new((1, 2), 5));
+    /// tree.add_link(Link::new((2, 3), 5));
+    /// for _i in 0..3 {
+    ///     tree.tick();
+    /// }
+    /// let report: FloodReport = tree.flood_from(3);
+    /// assert_eq!(report.received, vec![(3, 0), (2, 1), (1, 2)]);
+    /// assert_eq!(report.hop_count(1), Some(2));
+    /// assert_eq!(report.has_loop(), false);
+    /// ```
+    pub fn flood_from(&self, node_id: isize) -> FloodReport {
+        use std::collections::VecDeque;
+
+        let mut report = FloodReport { source: node_id, ..FloodReport::default() };
+        if !self.node_list.iter().any(|node| node.id == node_id) {
+            return report;
+        }
+        report.received.push((node_id, 0));
+        // every queue element is a copy of the frame: the node it arrived at, the index of the link it arrived over and its hop count
+        let mut queue: VecDeque<(isize, Option<usize>, usize)> = VecDeque::new();
+        queue.push_back((node_id, None, 0));
+        while let Some((current, arrival, hops)) = queue.pop_front() {
+            for (index, link) in self.link_list.iter().enumerate() {
+                if Some(index) == arrival || link.state != PortState::Forwarding {
+                    continue;
+                }
+                let other = if link.members.0 == current {
+                    link.members.1
+                } else if link.members.1 == current {
+                    link.members.0
+                } else {
+                    continue;
+                };
+                if !self.node_list.iter().any(|node| node.id == other) {
+                    continue;
+                }
+                if report.received.iter().any(|&(id, _)| id == other) {
+                    report.duplicates.push(other);
+                } else {
+                    report.received.push((other, hops + 1));
+                    queue.push_back((other, Some(index), hops + 1));
+                }
+            }
+        }
+        report
+    }
+
+    /// Computes the multicast distribution tree for traffic sent by the source to the given receivers.
+    ///
+    /// Like a switch snooping on IGMP memberships, a node only forwards the traffic over a forwarding link
+    /// if a receiver is behind it, all other forwarding links are pruned.
+    /// On a converged spanning tree, the traffic therefore reaches every receiver exactly once over the
+    /// links on the path to it, without reaching parts of the tree without receivers.
+    ///
+    /// Returns a tree without links if no node with the source id has been found.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.set_forward_delay(1);
+    /// tree.add_node(Node::new(1, "Root"));
+    /// tree.add_node(Node::new(2, "Receiver"));
+    /// tree.add_node(Node::new(3, "Other Node"));
+    /// tree.add_link(Link::new((1, 2), 5));
+    /// tree.add_link(Link::new((1, 3), 5));
+    /// for _i in 0..3 {
+    ///     tree.tick();
+    /// }
+    /// let multicast: MulticastTree = tree.multicast_tree(1, &[2]);
+    /// assert_eq!(multicast.links, vec![(1, 2)]);
+    /// assert_eq!(multicast.pruned, vec![(1, 3)]);
+    /// assert_eq!(multicast.receivers, vec![2]);
+    /// ```
+    pub fn multicast_tree(&self, source: isize, receivers: &[isize]) -> MulticastTree {
+        use std::collections::VecDeque;
+
+        let mut multicast = MulticastTree { source, ..MulticastTree::default() };
+        if !self.node_list.iter().any(|node| node.id == source) {
+            multicast.unreachable = receivers.to_vec();
+            return multicast;
+        }
+        // every reached node together with the node the traffic reaches it from
+        let mut upstream: Vec<(isize, Option<isize>)> = vec![(source, None)];
+        let mut queue = VecDeque::new();
+        queue.push_back(source);
+        while let Some(current) = queue.pop_front() {
+            for link in self.find_links(current) {
+                if link.state != PortState::Forwarding {
+                    continue;
+                }
+                let other = if link.members.0 == current { link.members.1 } else { link.members.0 };
+                if !upstream.iter().any(|&(id, _)| id == other) && self.node_list.iter().any(|node| node.id == other) {
+                    upstream.push((other, Some(current)));
+                    queue.push_back(other);
+                }
+            }
+        }
+
+        // walk up from every receiver towards the source, keeping every link on the way
+        let mut kept: Vec<isize> = Vec::new();
+        for &receiver in receivers {
+            if !upstream.iter().any(|&(id, _)| id == receiver) {
+                multicast.unreachable.push(receiver);
+                continue;
+            }
+            multicast.receivers.push(receiver);
+            let mut current = receiver;
+            while !kept.contains(&current) {
+                kept.push(current);
+                match upstream.iter().find(|&&(id, _)| id == current).and_then(|&(_, from)| from) {
+                    Some(from) => current = from,
+                    None => break,
+                }
+            }
+        }
+        for &(id, from) in &upstream {
+            if let Some(from) = from {
+                if kept.contains(&id) {
+                    multicast.links.push((from, id));
+                } else {
+                    multicast.pruned.push((from, id));
+                }
+            }
+        }
+        multicast
+    }
+
+    /// Checks whether a frame can travel from node a to node b over forwarding links.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(1, "Root"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_link(Link::new((1, 2), 5));
+    /// assert_eq!(tree.is_reachable(1, 1), true);
+    /// assert_eq!(tree.is_reachable(1, 2), false);
+    /// ```
+    pub fn is_reachable(&self, a: isize, b: isize) -> bool {
+        self.reachable_from(a).contains(&b)
+    }
+
+    /// Simulates the tree in synchronized rounds, until no node changes its path to the root anymore or max_rounds is reached.
+    ///
+    /// Other than simulate, which lets one random node after the other send its suggestion,
+    /// every node sends its suggestion to all neighbouring nodes "simultaneously" in each round.
+    /// All suggestions of a round are computed from the state of the tree at the beginning of the round and are applied afterwards,
+    /// so the result does not depend on the order in which the nodes are processed.
+    /// With the `rayon` feature enabled the nodes are processed in parallel.
+    ///
+    /// Returns the number of rounds that have been run, including the final round in which nothing changed.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_node(Node::new(3, "Second Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.add_link(Link::new((3,4), 8));
+    /// let rounds: usize = tree.simulate_rounds(10);
+    /// assert_eq!(rounds, 3);
+    /// assert_eq!(tree.get_node(3).unwrap().root_id, 2);
+    /// assert_eq!(tree.get_node(3).unwrap().root_cost, 13);
+    /// ```
+    pub fn simulate_rounds(&mut self, max_rounds: usize) -> usize {
+        // resolve the neighbours of every node once as (index of the neighbour, link cost)
+        let mut neighbours: Vec<Vec<(usize, usize)>> = vec![Vec::new(); self.node_list.len()];
+        for link in &self.link_list {
+            let index_a = self.node_list.iter().position(|node| node.id == link.members.0);
+            let index_b = self.node_list.iter().position(|node| node.id == link.members.1);
+            if let (Some(a), Some(b)) = (index_a, index_b) {
+                if a != b {
+                    neighbours[a].push((b, link.cost));
+                    neighbours[b].push((a, link.cost));
+                }
+            }
+        }
+
+        let mut rounds = 0;
+        while rounds < max_rounds {
+            rounds += 1;
+            // the state every node sends out in this round: (id, root priority, root id, root cost)
+            let snapshot: Vec<(isize, usize, isize, usize)> = self.node_list.iter().map(|node| (node.id, node.root_priority, node.root_id, node.root_cost)).collect();
+            let receive = |(node, node_neighbours): (&mut Node, &Vec<(usize, usize)>)| {
+                let mut accepted = false;
+                for &(index, cost) in node_neighbours {
+                    let (source_id, root_priority, root_id, root_cost) = snapshot[index];
+                    accepted |= node.receive_suggestion_with_priority(root_priority, root_id, source_id, root_cost + cost);
+                }
+                accepted
+            };
+            #[cfg(feature = "rayon")]
+            let changed = self.node_list.par_iter_mut().zip(neighbours.par_iter()).map(receive).filter(|accepted| *accepted).count() > 0;
+            #[cfg(not(feature = "rayon"))]
+            let changed = self.node_list.iter_mut().zip(neighbours.iter()).map(receive).filter(|accepted| *accepted).count() > 0;
+            if !changed {
+                break;
+            }
+        }
+        log_debug!("spanningtree: {} rounds simulated", rounds);
+        #[cfg(feature = "debug-validate")]
+        self.validate();
+        rounds
+    }
+
+    /// Collects statistics about the current state of the tree.
+    ///
+    /// For every node the number of received and accepted suggestions is reported,
+    /// as well as its depth, which is the number of next hops between the node and the root of the tree.
+    /// The depth of a node is None if following its next hops does not lead to the root.
+    /// The height of the tree is the highest depth of all nodes, or None if the tree has no nodes.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_node(Node::new(3, "Second Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.add_link(Link::new((3,4), 8));
+    /// tree.simulate_rounds(10);
+    /// let stats: TreeStats = tree.stats();
+    /// assert_eq!(stats.height, Some(2));
+    /// assert_eq!(stats.nodes[0].depth, Some(1));
+    /// assert_eq!(stats.nodes[2].depth, Some(2));
+    /// ```
+    pub fn stats(&self) -> TreeStats {
+        let nodes: Vec<NodeStats> = self.node_list.iter().map(|node| {
+            NodeStats {
+                id: node.id,
+                name: node.name,
+                msg_count: node.msg_count,
+                accept_count: node.accept_count,
+                acceptance_ratio: if node.msg_count == 0 { 0.0 } else { node.accept_count as f64 / node.msg_count as f64 },
+                depth: self.depth(node.id),
+            }
+        }).collect();
+        let height = if nodes.is_empty() {
+            None
+        } else {
+            Some(nodes.iter().filter_map(|node| node.depth).max().unwrap_or(0))
+        };
+        TreeStats { nodes, height }
+    }
+
+    /// Renders the tree in the [DOT language](https://graphviz.org/doc/info/lang.html) of Graphviz.
+    ///
+    /// All links are drawn as grey lines labeled with their cost.
+    /// The next hops chosen by the nodes are drawn as bold arrows pointing towards the root,
+    /// so the spanning tree the simulation converged to can be seen at a glance.
+    /// Every node is labeled with its name, id and message count, the root node is drawn with a double border.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Root Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// tree.run_calc(2, false);
+    /// let dot: String = tree.to_dot();
+    /// assert!(dot.contains("\"2\" -> \"4\" [dir=none, color=grey, label=\"5\"];"));
+    /// assert!(dot.contains("\"4\" -> \"2\" [style=bold];"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph tree {\n");
+        for node in &self.node_list {
+            let root = if Some(node.id) == self.root_id { ", peripheries=2" } else { "" };
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\nid: {}, msgs: {}\"{}];\n",
+                node.id, dot::escape(node.name), node.id, node.msg_count, root
+            ));
+        }
+        for link in &self.link_list {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [dir=none, color=grey, label=\"{}\"];\n",
+                link.members.0, link.members.1, link.cost
+            ));
+        }
+        for node in &self.node_list {
+            if let Some(next_hop) = node.next_hop {
+                dot.push_str(&format!("    \"{}\" -> \"{}\" [style=bold];\n", node.id, next_hop));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Detects forwarding loops in the current state of the tree.
+    ///
+    /// While a simulation has not converged yet, nodes may point their next hops at each other,
+    /// so a frame forwarded towards the root would circle between them forever.
+    /// This can happen at any point of the simulation, e.g. after set_priority or when only some nodes have been run.
+    ///
+    /// Returns every loop of next hops once, as the list of node ids in forwarding order.
+    /// The next hops are followed from every node in the order the nodes were added to the tree,
+    /// and every loop starts at the first of its nodes reached that way, which is not always the first one added.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(1, "Root"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_node(Node::new(3, "Third Node"));
+    /// tree.get_node(2).unwrap().next_hop = Some(3);
+    /// tree.get_node(3).unwrap().next_hop = Some(2);
+    /// assert_eq!(tree.find_loops(), vec![vec![2, 3]]);
+    /// tree.get_node(2).unwrap().next_hop = Some(1);
+    /// assert!(tree.find_loops().is_empty());
+    ///
+    /// // following the next hops from the root reaches the loop at 3, before 2
+    /// tree.get_node(1).unwrap().next_hop = Some(3);
+    /// tree.get_node(2).unwrap().next_hop = Some(3);
+    /// assert_eq!(tree.find_loops(), vec![vec![3, 2]]);
+    /// ```
+    pub fn find_loops(&self) -> Vec<Vec<isize>> {
+        use std::collections::HashSet;
+
+        let mut loops = Vec::new();
+        // nodes whose chain of next hops has already been followed completely
+        let mut finished: HashSet<isize> = HashSet::new();
+        for node in &self.node_list {
+            let mut chain: Vec<isize> = Vec::new();
+            let mut current = Some(node.id);
+            while let Some(id) = current {
+                if finished.contains(&id) {
+                    break;
+                }
+                // reaching a node of the current chain again closes a loop
+                if let Some(position) = chain.iter().position(|&chain_id| chain_id == id) {
+                    loops.push(chain[position..].to_vec());
+                    break;
+                }
+                chain.push(id);
+                current = self.node_list.iter().find(|node| node.id == id).and_then(|node| node.next_hop);
+            }
+            finished.extend(chain);
+        }
+        loops
+    }
+
+    /// Checks whether no node would accept the suggestion of any of its neighbouring nodes,
+    /// so further simulation runs would not change what the nodes know about the root.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(4, "Second Node"));
+    /// tree.add_node(Node::new(2, "Root Node"));
+    /// tree.add_link(Link::new((2,4), 5));
+    /// assert!(!tree.is_converged());
+    /// tree.run_calc(2, false);
+    /// assert!(tree.is_converged());
+    /// ```
+    pub fn is_converged(&self) -> bool {
+        let find = |id: isize| self.node_list.iter().find(|node| node.id == id);
+        self.link_list.iter().all(|link| {
+            match (find(link.members.0), find(link.members.1)) {
+                (Some(a), Some(b)) => {
+                    let accepts = |from: &Node, to: &Node| {
+                        let suggestion = (from.root_priority, from.root_id);
+                        let current = (to.root_priority, to.root_id);
+                        suggestion < current || (suggestion == current && from.root_cost + link.cost < to.root_cost)
+                    };
+                    !accepts(a, b) && !accepts(b, a)
+                },
+                _ => true
+            }
+        })
+    }
+
+    /// Follows the next hops from the node to the root of the tree and counts them.
+    /// Returns None if the chain of next hops breaks off or contains a loop.
+    fn depth(&self, node_id: isize) -> Option<usize> {
+        let mut current = node_id;
+        for depth in 0..=self.node_list.len() {
+            if Some(current) == self.root_id {
+                return Some(depth);
+            }
+            current = self.node_list.iter().find(|node| node.id == current)?.next_hop?;
+        }
+        None
+    }
+
+    /// Checks the structural invariants of the tree and panics with a descriptive message if one is violated.
+    ///
+    /// Node ids have to be unique, the root id of the tree has to be the id of the node with the lowest priority and id,
+    /// no two links may connect the same pair of nodes
+    /// and the root id and next hop known to a node have to refer to nodes of the tree.
+    /// A next hop additionally has to be reachable over a link.
+    #[cfg(feature = "debug-validate")]
+    fn validate(&self) {
+        let contains = |id: isize| self.node_list.iter().any(|node| node.id == id);
+        for (index, node) in self.node_list.iter().enumerate() {
+            if self.node_list[..index].iter().any(|other| other.id == node.id) {
+                panic!("tree invariant violated: more than one node has the id {}", node.id);
+            }
+            match self.node_list.iter().find(|root| root.id == node.root_id) {
+                None => panic!(
+                    "tree invariant violated: node {} assumes the root {}, which is not part of the tree",
+                    node.id, node.root_id
+                ),
+                Some(root) if root.priority != node.root_priority => panic!(
+                    "tree invariant violated: node {} assumes the priority {} for the root {}, which has the priority {}",
+                    node.id, node.root_priority, root.id, root.priority
+                ),
+                Some(_) => {}
+            }
+            if let Some(next_hop) = node.next_hop {
+                if !contains(next_hop) {
+                    panic!(
+                        "tree invariant violated: next hop {} of node {} is not part of the tree",
+                        next_hop, node.id
+                    );
+                }
+                if !self.link_list.iter().any(|link| {
+                    link.members == (node.id, next_hop) || link.members == (next_hop, node.id)
+                }) {
+                    panic!(
+                        "tree invariant violated: node {} has no link to its next hop {}",
+                        node.id, next_hop
+                    );
+                }
+            }
+        }
+        let lowest_id = self.node_list.iter().min_by_key(|node| (node.priority, node.id)).map(|node| node.id);
+        if self.root_id != lowest_id {
+            panic!(
+                "tree invariant violated: root id is {:?}, but the node with the lowest priority and id is {:?}",
+                self.root_id, lowest_id
+            );
+        }
+        for (index, link) in self.link_list.iter().enumerate() {
+            let (a, b) = link.members;
+            if self.link_list[..index].iter().any(|other| {
+                other.members == (a, b) || other.members == (b, a)
+            }) {
+                panic!(
+                    "tree invariant violated: nodes {} and {} are connected by more than one link",
+                    a, b
+                );
+            }
+        }
+    }
+}
+
+impl FloodReport {
+    /// Returns the number of hops the frame needed to reach the node, or None if the node did not receive it.
+    pub fn hop_count(&self, node_id: isize) -> Option<usize> {
+        self.received.iter().find(|&&(id, _)| id == node_id).map(|&(_, hops)| hops)
+    }
+
+    /// Returns true if any node received the frame more than once, which means the forwarding links contain a loop.
+    pub fn has_loop(&self) -> bool {
+        !self.duplicates.is_empty()
+    }
+}
+
+/// The simulation of a tree, as run by Tree::simulate_with_sampler.
+/// It is done once every node has received more than min_hops messages.
+struct Simulation<'a> {
+    tree: &'a mut Tree,
+    recursive: bool,
+    min_hops: usize,
+}
+
+/// Shows the node together with the root it has learned and how it reaches it,
+/// e.g. `3 C: root 1 via 7, cost 5`, or `1 A: root` for a node which considers itself the root.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::spanningtree::Node;
+///
+/// let mut node = Node::new(3, "C");
+/// assert_eq!(node.to_string(), "3 C: root");
+/// node.receive_suggestion(1, 7, 5);
+/// assert_eq!(node.to_string(), "3 C: root 1 via 7, cost 5");
+/// ```
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.next_hop {
+            Some(next_hop) => write!(
+                f,
+                "{} {}: root {} via {}, cost {}",
+                self.id, self.name, self.root_id, next_hop, self.root_cost
+            ),
+            None => write!(f, "{} {}: root", self.id, self.name),
+        }
+    }
+}
+
+impl Engine for Simulation<'_> {
+    fn node_ids(&self) -> Vec<isize> {
+        self.tree.node_ids()
+    }
+
+    fn activate(&mut self, node_id: isize) {
+        if let Some(activations) = &mut self.tree.activations {
+            activations.push(node_id);
+        }
+        self.tree.run_calc(node_id, self.recursive);
+    }
+
+    fn converged(&self) -> bool {
+        self.min_hops == 0 || self.tree.node_list.iter().all(|node| node.msg_count > self.min_hops)
+    }
+}
+
+/// A tree can be driven by the functions of the simulation module.
+/// Activating a node runs run_calc on it without the recursive option,
+/// and the tree has converged once no node would accept the suggestion of any neighbouring node anymore.
+impl Engine for Tree {
+    fn node_ids(&self) -> Vec<isize> {
+        self.node_list.iter().map(|node| node.id).collect()
+    }
+
+    fn activate(&mut self, node_id: isize) {
+        self.run_calc(node_id, false);
+    }
+
+    fn converged(&self) -> bool {
+        self.is_converged()
+    }
+}
+
+impl Default for Tree {
+    fn default() -> Self {
+        Tree::new()
+    }
+}
+
+impl TreeStats {
+    /// Exports the statistics as CSV, with one line per node and a header line.
+    /// Unknown depths are left empty.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(2, "Root"));
+    /// assert_eq!(tree.stats().to_csv(), "id,name,msg_count,accept_count,acceptance_ratio,depth\n2,Root,0,0,0,0\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("id,name,msg_count,accept_count,acceptance_ratio,depth\n");
+        for node in &self.nodes {
+            let name = if node.name.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", node.name.replace('"', "\"\""))
+            } else {
+                node.name.to_string()
+            };
+            let depth = node.depth.map(|depth| depth.to_string()).unwrap_or_default();
+            csv.push_str(&format!("{},{},{},{},{},{}\n", node.id, name, node.msg_count, node.accept_count, node.acceptance_ratio, depth));
+        }
+        csv
+    }
+
+    /// Exports the statistics as a JSON object, containing the height of the tree and a list of node statistics.
+    /// Unknown depths and heights are written as null.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// let mut tree: Tree = Tree::new();
+    /// tree.add_node(Node::new(2, "Root"));
+    /// assert_eq!(tree.stats().to_json(), r#"{"height":0,"nodes":[{"id":2,"name":"Root","msg_count":0,"accept_count":0,"acceptance_ratio":0,"depth":0}]}"#);
+    /// ```
+    pub fn to_json(&self) -> String {
+        let optional = |value: Option<usize>| value.map(|value| value.to_string()).unwrap_or_else(|| String::from("null"));
+        let nodes: Vec<String> = self.nodes.iter().map(|node| {
+            let name = json::escape(node.name);
+            format!(
+                "{{\"id\":{},\"name\":\"{}\",\"msg_count\":{},\"accept_count\":{},\"acceptance_ratio\":{},\"depth\":{}}}",
+                node.id, name, node.msg_count, node.accept_count, node.acceptance_ratio, optional(node.depth)
+            )
+        }).collect();
+        format!("{{\"height\":{},\"nodes\":[{}]}}", optional(self.height), nodes.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tree_tests {
+    use super::*;
+
+    #[test]
+    fn add_link() {
+        let mut tree = Tree::new();
+        tree.add_link(Link::new((1,2), 5));
+        tree.add_link(Link::new((2,5), 8));
+        assert_eq!(tree.links().len(), 2);
+        assert_eq!(tree.links()[0].members.1, 2);
+    }
+
+    #[test]
+    fn with_capacity() {
+        let mut tree = Tree::with_capacity(3, 5);
+        assert!(tree.node_list.capacity() >= 3);
+        assert!(tree.link_list.capacity() >= 5);
+        tree.reserve(10, 0);
+        assert!(tree.node_list.capacity() >= 10);
+        assert!(tree.node_list.is_empty());
+    }
+
+    #[test]
+    fn find_link() {
+        let mut tree = Tree::new();
+        tree.add_link(Link::new((1,2), 5));
+        tree.add_link(Link::new((2,5), 8));
+        let link = tree.find_link(2, 1);
+        assert!(link.is_some());
+        let unwrapped_link = link.unwrap();
+        assert_eq!(unwrapped_link.cost, 5);
+        assert!(tree.find_link(7, 9).is_none());
+    }
+
+    #[test]
+    fn find_links() {
+        let mut tree = Tree::new();
+        tree.add_link(Link::new((1,2), 5));
+        tree.add_link(Link::new((2,5), 8));
+        tree.add_link(Link::new((7,9), 2));
+        let links = tree.find_links(2);
+        assert_eq!(links.len(), 2);
+    }
+
+    #[test]
+    fn multiple_nodes_with_same_id() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(4, "E"));
+        tree.add_node(Node::new(4, "E"));
+        assert_eq!(tree.node_count(), 1);
+    }
+
+    #[test]
+    fn test_run_calc() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(5, "A"));
+        tree.add_node(Node::new(1, "B"));
+        tree.add_node(Node::new(1, "B"));
+        tree.add_node(Node::new(3, "C"));
+        tree.add_node(Node::new(7, "D"));
+        let node2 = Node::new(6, "E");
+        tree.add_node(node2);
+        tree.add_node(Node::new(4, "F"));
+        tree.add_link(Link::new((5, 1), 10));
+        tree.add_link(Link::new((5, 3), 10));
+        tree.add_link(Link::new((1, 7), 15));
+        tree.add_link(Link::new((1, 6), 10));
+        tree.add_link(Link::new((3, 7), 3));
+        tree.add_link(Link::new((3, 6), 10));
+        tree.add_link(Link::new((7, 6), 2));
+        tree.add_link(Link::new((7, 4), 10));
+        tree.add_link(Link::new((6, 4), 2));
+        assert!(tree.run_calc(999, false).is_none());
+        // 3 tells its neighbours 5, 7 and 6 about itself as root, which all only know higher ids
+        let outcome = tree.run_calc(3, false).unwrap();
+        assert_eq!(outcome, CalcOutcome { suggestions_sent: 3, suggestions_accepted: 3, updated_nodes: vec![5, 7, 6] });
+        // 5 then tells 1 and 3, but both know a lower root already
+        assert_eq!(tree.run_calc(5, false).unwrap(), CalcOutcome { suggestions_sent: 2, ..CalcOutcome::default() });
+        // recursively, the root 1 reaches every node, some of them more than once over cheaper paths
+        let outcome = tree.run_calc(1, true).unwrap();
+        assert_eq!(outcome.updated_nodes.len(), 5);
+        assert!(outcome.suggestions_accepted > 5 && outcome.suggestions_sent > outcome.suggestions_accepted);
+        tree.simulate_with_rng(10, 10, true, &mut crate::util::SplitMix64::new(2));
+        assert!(tree.nodes().iter().all(|node| node.msg_count > 10));
+        assert!(tree.nodes().iter().all(|node| node.root_id == 1));
+        assert_eq!(tree.get_node(3).unwrap().next_hop.unwrap(), 7);
+        assert_eq!(tree.nodes()[1].root_id, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn simulate_weighted() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(1, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(3, "C"));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 1));
+        tree.simulate_weighted(50, 0, false, |node| if node.id == 3 { 0 } else { 1 });
+        // node 1 and 2 have been selected 50 times in total, node 3 never
+        assert_eq!(tree.nodes()[0].msg_count + tree.nodes()[1].msg_count, 50);
+        assert_eq!(tree.nodes()[2].msg_count, tree.nodes()[0].msg_count);
+        assert_eq!(tree.get_node(3).unwrap().root_id, 1);
+        assert_eq!(tree.get_node(2).unwrap().accept_count, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    #[should_panic(expected = "weight above 0")]
+    fn simulate_weighted_without_weights() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(1, "A"));
+        tree.simulate_weighted(1, 0, false, |_node| 0);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn simulate_seeded() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let simulate = |seed| {
+            let mut tree = Tree::new();
+            for id in 1..=5 {
+                tree.add_node(Node::new(id, "N"));
+            }
+            tree.add_link(Link::new((1, 2), 3));
+            tree.add_link(Link::new((2, 3), 1));
+            tree.add_link(Link::new((3, 4), 1));
+            tree.add_link(Link::new((4, 5), 2));
+            tree.add_link(Link::new((5, 1), 1));
+            tree.set_recording(true);
+            tree.simulate_with_rng(30, 0, false, &mut StdRng::seed_from_u64(seed));
+            tree.recorded_activations().to_vec()
+        };
+        assert_eq!(simulate(11), simulate(11));
+        assert_ne!(simulate(11), simulate(12));
+    }
+
+    #[test]
+    fn simulate_with_sampler() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(3, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(1, "C"));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 1));
+        let mut selected = Vec::new();
+        tree.simulate_with_sampler(2, 0, false, |nodes| {
+            let index = nodes.len() - 1 - selected.len();
+            selected.push(nodes[index].id);
+            index
+        });
+        assert_eq!(selected, vec![1, 2]);
+        assert_eq!(tree.get_node(3).unwrap().root_id, 1);
+        assert_eq!(tree.get_node(3).unwrap().root_cost, 2);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn record_and_replay() {
+        let build = || {
+            let mut tree = Tree::new();
+            for id in 1..=6 {
+                tree.add_node(Node::new(id, "N"));
+            }
+            tree.add_link(Link::new((1, 2), 4));
+            tree.add_link(Link::new((2, 3), 1));
+            tree.add_link(Link::new((3, 4), 1));
+            tree.add_link(Link::new((4, 1), 1));
+            tree.add_link(Link::new((4, 5), 2));
+            tree.add_link(Link::new((5, 6), 1));
+            tree.add_link(Link::new((6, 2), 1));
+            tree
+        };
+        let mut tree = build();
+        assert!(tree.recorded_activations().is_empty());
+        tree.set_recording(true);
+        tree.simulate(20, 0, true);
+        tree.simulate_weighted(5, 0, false, |node| node.id as usize);
+        let recording = tree.recorded_activations().to_vec();
+        assert_eq!(recording.len(), 25);
+
+        let mut replayed = build();
+        replayed.set_recording(true);
+        assert!(replayed.simulate_replay(&recording[..20], true));
+        assert!(replayed.simulate_replay(&recording[20..], false));
+        assert_eq!(replayed.recorded_activations(), &recording[..]);
+        for (original, replayed) in tree.nodes().iter().zip(replayed.nodes().iter()) {
+            assert_eq!(original.msg_count, replayed.msg_count);
+            assert_eq!(original.root_id, replayed.root_id);
+            assert_eq!(original.root_cost, replayed.root_cost);
+            assert_eq!(original.next_hop, replayed.next_hop);
+        }
+
+        tree.set_recording(false);
+        assert!(tree.recorded_activations().is_empty());
+        assert!(!tree.simulate_replay(&[1, 99], false));
+    }
+
+    #[test]
+    fn engine() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(5, "A"));
+        tree.add_node(Node::new(1, "B"));
+        tree.add_node(Node::new(3, "C"));
+        tree.add_node(Node::new(7, "D"));
+        tree.add_link(Link::new((5, 1), 10));
+        tree.add_link(Link::new((5, 3), 10));
+        tree.add_link(Link::new((1, 7), 15));
+        tree.add_link(Link::new((3, 7), 3));
+        assert_eq!(tree.node_ids(), vec![5, 1, 3, 7]);
+        assert!(!tree.converged());
+        simulation::run_random(&mut tree, 5, None, &mut crate::util::SplitMix64::new(4));
+        assert!(tree.converged());
+        assert!(tree.nodes().iter().all(|node| node.root_id == 1));
+        assert_eq!(tree.get_node(3).unwrap().root_cost, 18);
+        assert_eq!(tree.get_node(7).unwrap().next_hop, Some(1));
+    }
+
+    #[test]
+    fn simulate_rounds() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(5, "A"));
+        tree.add_node(Node::new(1, "B"));
+        tree.add_node(Node::new(3, "C"));
+        tree.add_node(Node::new(7, "D"));
+        tree.add_node(Node::new(6, "E"));
+        tree.add_node(Node::new(4, "F"));
+        tree.add_link(Link::new((5, 1), 10));
+        tree.add_link(Link::new((5, 3), 10));
+        tree.add_link(Link::new((1, 7), 15));
+        tree.add_link(Link::new((1, 6), 10));
+        tree.add_link(Link::new((3, 7), 3));
+        tree.add_link(Link::new((3, 6), 10));
+        tree.add_link(Link::new((7, 6), 2));
+        tree.add_link(Link::new((7, 4), 10));
+        tree.add_link(Link::new((6, 4), 2));
+        let rounds = tree.simulate_rounds(100);
+        assert!(rounds < 100);
+        assert!(tree.nodes().iter().all(|node| node.root_id == 1));
+        assert_eq!(tree.get_node(3).unwrap().next_hop.unwrap(), 7);
+        assert_eq!(tree.get_node(3).unwrap().root_cost, 15);
+        assert_eq!(tree.get_node(4).unwrap().root_cost, 12);
+        assert_eq!(tree.simulate_rounds(100), 1);
+    }
+
+    #[test]
+    fn stats() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(3, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(1, "C"));
+        tree.add_node(Node::new(9, "Lonely, \"isolated\""));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 1));
+        tree.simulate_rounds(10);
+        let stats = tree.stats();
+        assert_eq!(stats.height, Some(2));
+        assert_eq!(stats.nodes[0].depth, Some(2));
+        assert_eq!(stats.nodes[1].depth, Some(1));
+        assert_eq!(stats.nodes[2].depth, Some(0));
+        assert_eq!(stats.nodes[3].depth, None);
+        assert_eq!(stats.nodes[0].msg_count, 3);
+        assert_eq!(stats.nodes[0].accept_count, 2);
+        assert!((stats.nodes[0].acceptance_ratio - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats.nodes[3].acceptance_ratio, 0.0);
+
+        let csv = stats.to_csv();
+        assert_eq!(csv.lines().count(), 5);
+        assert_eq!(csv.lines().nth(2).unwrap(), "2,B,6,1,0.16666666666666666,1");
+        assert_eq!(csv.lines().nth(4).unwrap(), "9,\"Lonely, \"\"isolated\"\"\",0,0,0,");
+
+        let json = stats.to_json();
+        assert!(json.starts_with("{\"height\":2,\"nodes\":[{\"id\":3,\"name\":\"A\","));
+        assert!(json.contains("\"name\":\"Lonely, \\\"isolated\\\"\",\"msg_count\":0,\"accept_count\":0,\"acceptance_ratio\":0,\"depth\":null}"));
+    }
+
+    #[test]
+    fn stats_with_loop() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(1, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(3, "C"));
+        tree.get_node(2).unwrap().next_hop = Some(3);
+        tree.get_node(3).unwrap().next_hop = Some(2);
+        let stats = tree.stats();
+        assert_eq!(stats.nodes[1].depth, None);
+        assert_eq!(stats.nodes[2].depth, None);
+        assert_eq!(stats.height, Some(0));
+        assert_eq!(Tree::new().stats().height, None);
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(3, "A \"quoted\""));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(1, "C"));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 4));
+        tree.simulate_rounds(10);
+        let dot = tree.to_dot();
+        assert!(dot.starts_with("digraph tree {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("    \"3\" [label=\"A \\\"quoted\\\"\\nid: 3, msgs: 3\"];\n"));
+        assert!(dot.contains("    \"1\" [label=\"C\\nid: 1, msgs: 3\", peripheries=2];\n"));
+        assert!(dot.contains("    \"2\" -> \"3\" [dir=none, color=grey, label=\"4\"];\n"));
+        assert!(dot.contains("    \"3\" -> \"2\" [style=bold];\n"));
+        assert!(dot.contains("    \"2\" -> \"1\" [style=bold];\n"));
+        assert_eq!(dot.matches("style=bold").count(), 2);
+    }
+
+    #[test]
+    fn priority() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(1, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(3, "C").priority(4096));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 1));
+        assert_eq!(tree.root_id(), Some(3));
+        tree.simulate_with_rng(10, 10, true, &mut crate::util::SplitMix64::new(2));
+        assert!(tree.nodes().iter().all(|node| node.root_id == 3));
+        assert_eq!(tree.get_node(1).unwrap().next_hop, Some(2));
+        assert_eq!(tree.get_node(1).unwrap().root_cost, 2);
+
+        assert!(!tree.set_priority(9, 0));
+        assert!(tree.set_priority(1, 0));
+        assert_eq!(tree.root_id(), Some(1));
+        assert!(tree.nodes().iter().all(|node| node.root_id == node.id && node.next_hop.is_none()));
+        tree.simulate_rounds(10);
+        assert!(tree.nodes().iter().all(|node| node.root_id == 1 && node.root_priority == 0));
+        assert_eq!(tree.get_node(3).unwrap().next_hop, Some(2));
+        assert_eq!(tree.get_node(1).unwrap().next_hop, None);
+    }
+
+    #[test]
+    fn find_loops() {
+        let mut tree = Tree::new();
+        for id in 1..=7 {
+            tree.add_node(Node::new(id, "N"));
+        }
+        tree.get_node(2).unwrap().next_hop = Some(3);
+        tree.get_node(3).unwrap().next_hop = Some(4);
+        tree.get_node(4).unwrap().next_hop = Some(2);
+        tree.get_node(5).unwrap().next_hop = Some(3);
+        tree.get_node(6).unwrap().next_hop = Some(7);
+        tree.get_node(7).unwrap().next_hop = Some(6);
+        assert_eq!(tree.find_loops(), vec![vec![2, 3, 4], vec![6, 7]]);
+        tree.get_node(1).unwrap().next_hop = Some(99);
+        assert_eq!(tree.find_loops().len(), 2);
+    }
+
+    #[test]
+    fn converged_tree_has_no_loops() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(3, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(1, "C"));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 1));
+        tree.add_link(Link::new((1, 3), 1));
+        tree.simulate_with_rng(10, 10, true, &mut crate::util::SplitMix64::new(2));
+        assert!(tree.find_loops().is_empty());
+    }
+
+    #[test]
+    fn simulate_rounds_limit() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(3, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(1, "C"));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 1));
+        assert_eq!(tree.simulate_rounds(1), 1);
+        assert_eq!(tree.get_node(2).unwrap().root_id, 1);
+        assert_eq!(tree.get_node(3).unwrap().root_id, 2);
+        assert_eq!(tree.simulate_rounds(0), 0);
+    }
+}
+
+#[cfg(test)]
+mod tick_test {
+    use super::*;
+
+    #[test]
+    fn hello_time() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(1, "A").hello_time(3));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(3, "C").hello_time(0));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 1));
+        assert_eq!(tree.tick(), 1);
+        assert_eq!(tree.tick(), 1);
+        assert_eq!(tree.get_node(2).unwrap().root_id, 2);
+        assert_eq!(tree.get_node(3).unwrap().root_id, 2);
+        assert_eq!(tree.tick(), 2);
+        assert_eq!(tree.get_node(2).unwrap().root_id, 1);
+        assert_eq!(tree.get_node(3).unwrap().root_id, 1);
+        assert_eq!(tree.get_node(3).unwrap().root_cost, 2);
+        assert_eq!(tree.time(), 3);
+    }
+
+    #[test]
+    fn slower_hello_time_sends_fewer_messages() {
+        let build = |hello_time: usize| {
+            let mut tree = Tree::new();
+            for id in 1..=4 {
+                tree.add_node(Node::new(id, "N").hello_time(hello_time));
+            }
+            tree.add_link(Link::new((1, 2), 1));
+            tree.add_link(Link::new((2, 3), 1));
+            tree.add_link(Link::new((3, 4), 1));
+            for _i in 0..12 {
+                tree.tick();
+            }
+            tree
+        };
+        let mut fast = build(1);
+        let mut slow = build(4);
+        assert_eq!(fast.get_node(4).unwrap().root_id, 1);
+        assert_eq!(slow.get_node(4).unwrap().root_id, 1);
+        assert!(slow.get_node(2).unwrap().msg_count < fast.get_node(2).unwrap().msg_count);
+    }
+}
+
+#[cfg(test)]
+mod port_state_test {
+    use super::*;
+
+    fn line() -> Tree {
+        let mut tree = Tree::new();
+        tree.set_forward_delay(2);
+        tree.add_node(Node::new(1, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(3, "C"));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 1));
+        tree.add_link(Link::new((1, 3), 5));
+        tree
+    }
+
+    #[test]
+    fn port_state_transitions() {
+        let mut tree = line();
+        assert_eq!(tree.find_link(1, 2).unwrap().state, PortState::Blocking);
+        tree.tick();
+        assert_eq!(tree.find_link(1, 2).unwrap().state, PortState::Listening);
+        tree.tick();
+        assert_eq!(tree.find_link(1, 2).unwrap().state, PortState::Listening);
+        tree.tick();
+        assert_eq!(tree.find_link(1, 2).unwrap().state, PortState::Learning);
+        tree.tick();
+        tree.tick();
+        assert_eq!(tree.find_link(1, 2).unwrap().state, PortState::Forwarding);
+        assert_eq!(tree.find_link(2, 3).unwrap().state, PortState::Forwarding);
+        assert_eq!(tree.find_link(1, 3).unwrap().state, PortState::Blocking);
+        assert_eq!(tree.reachable_from(3), vec![3, 2, 1]);
+        assert!(tree.is_reachable(1, 3));
+        assert!(tree.reachable_from(9).is_empty());
+    }
+
+    #[test]
+    fn default_forward_delay() {
+        let mut tree = line();
+        tree.set_forward_delay(DEFAULT_FORWARD_DELAY);
+        for _i in 0..(2 * DEFAULT_FORWARD_DELAY) {
+            tree.tick();
+        }
+        assert_eq!(tree.find_link(1, 2).unwrap().state, PortState::Learning);
+        tree.tick();
+        assert_eq!(tree.find_link(1, 2).unwrap().state, PortState::Forwarding);
+        assert_eq!(Tree::default().forward_delay, DEFAULT_FORWARD_DELAY);
+    }
+
+    #[test]
+    fn flood_on_converged_tree() {
+        let mut tree = line();
+        for _i in 0..6 {
+            tree.tick();
+        }
+        let report = tree.flood_from(1);
+        assert_eq!(report.source, 1);
+        assert_eq!(report.received, vec![(1, 0), (2, 1), (3, 2)]);
+        assert!(!report.has_loop());
+        assert_eq!(report.hop_count(3), Some(2));
+        assert_eq!(report.hop_count(9), None);
+        assert!(tree.flood_from(9).received.is_empty());
+    }
+
+    #[test]
+    fn flood_with_loop() {
+        let mut tree = line();
+        for link in &mut tree.link_list {
+            link.state = PortState::Forwarding;
+        }
+        let report = tree.flood_from(1);
+        assert_eq!(report.received, vec![(1, 0), (2, 1), (3, 1)]);
+        assert!(report.has_loop());
+        assert_eq!(report.duplicates, vec![3, 2]);
+    }
+
+    #[test]
+    fn flood_before_forwarding() {
+        let tree = line();
+        let report = tree.flood_from(2);
+        assert_eq!(report.received, vec![(2, 0)]);
+        assert!(!report.has_loop());
+    }
+
+    #[test]
+    fn links_leaving_the_tree_block() {
+        let mut tree = line();
+        for _i in 0..6 {
+            tree.tick();
+        }
+        assert!(tree.is_reachable(1, 3));
+        tree.get_node(3).unwrap().next_hop = None;
+        tree.get_node(3).unwrap().hello_time = 0;
+        tree.get_node(2).unwrap().hello_time = 0;
+        tree.tick();
+        assert_eq!(tree.find_link(2, 3).unwrap().state, PortState::Blocking);
+        assert!(!tree.is_reachable(1, 3));
+    }
+}
+
+#[cfg(test)]
+mod multicast_test {
+    use super::*;
+
+    fn star() -> Tree {
+        let mut tree = Tree::new();
+        tree.set_forward_delay(1);
+        for id in 1..=6 {
+            tree.add_node(Node::new(id, "N"));
+        }
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((1, 3), 1));
+        tree.add_link(Link::new((2, 4), 1));
+        tree.add_link(Link::new((2, 5), 1));
+        tree.add_link(Link::new((3, 5), 5));
+        tree.add_link(Link::new((3, 6), 1));
+        for _i in 0..6 {
+            tree.tick();
+        }
+        tree
+    }
+
+    #[test]
+    fn prunes_branches_without_receivers() {
+        let tree = star();
+        let multicast = tree.multicast_tree(4, &[5, 9, 4]);
+        assert_eq!(multicast.receivers, vec![5, 4]);
+        assert_eq!(multicast.unreachable, vec![9]);
+        assert_eq!(multicast.links, vec![(4, 2), (2, 5)]);
+        assert_eq!(multicast.pruned, vec![(2, 1), (1, 3), (3, 6)]);
+    }
+
+    #[test]
+    fn receivers_on_all_branches() {
+        let tree = star();
+        let multicast = tree.multicast_tree(1, &[4, 5, 6]);
+        assert_eq!(multicast.links.len(), 5);
+        assert!(multicast.pruned.is_empty());
+        let empty = tree.multicast_tree(1, &[]);
+        assert!(empty.links.is_empty());
+        assert_eq!(empty.pruned.len(), 5);
+        let unknown = tree.multicast_tree(9, &[1]);
+        assert_eq!(unknown.unreachable, vec![1]);
+        assert!(unknown.links.is_empty());
+    }
+}
+
+#[cfg(all(test, feature = "debug-validate"))]
+mod validate_test {
+    use super::*;
+
+    #[test]
+    fn valid_tree() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(4, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_link(Link::new((2, 4), 5));
+        tree.run_calc(2, true);
+        tree.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "more than one node has the id")]
+    fn duplicate_node_id() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(4, "A"));
+        tree.node_list.push(Node::new(4, "B"));
+        tree.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "lowest priority and id")]
+    fn wrong_root_id() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(4, "A"));
+        tree.node_list.push(Node::new(2, "B"));
+        tree.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "is not part of the tree")]
+    fn dangling_next_hop() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(4, "A"));
+        tree.node_list[0].next_hop = Some(9);
+        tree.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "has no link to its next hop")]
+    fn unlinked_next_hop() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(4, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.get_node(4).unwrap().receive_suggestion(2, 2, 1);
+        tree.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "assumes the priority")]
+    fn outdated_root_priority() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(4, "A"));
+        tree.node_list[0].priority = 0;
+        tree.validate();
+    }
+}
+
+#[cfg(test)]
+mod link_test {
+    use super::*;
+
+    #[test]
+    fn test_link() {
+        let link = Link::new((3,9), 4);
+        assert_eq!(link.cost, 4);
+        assert_eq!(link.members.0, 3);
+    }
+
+    #[test]
+    fn test_link_with_speed() {
+        assert_eq!(Link::with_speed((1, 2), LinkSpeed::TenMbit).cost, 100);
+        assert_eq!(Link::with_speed((1, 2), LinkSpeed::HundredMbit).cost, 19);
+        assert_eq!(Link::with_speed((1, 2), LinkSpeed::Gigabit).cost, 4);
+        assert_eq!(Link::with_speed((1, 2), LinkSpeed::TenGigabit).cost, 2);
+        assert_eq!(LinkSpeed::HundredMbit.to_rstp_cost(), 200_000);
+        assert_eq!(LinkSpeed::Gigabit.to_rstp_cost(), 20_000);
+    }
+
+    #[test]
+    fn faster_links_are_preferred() {
+        let mut tree = Tree::new();
+        tree.add_node(Node::new(1, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(3, "C"));
+        tree.add_link(Link::with_speed((1, 3), LinkSpeed::TenMbit));
+        tree.add_link(Link::with_speed((1, 2), LinkSpeed::Gigabit));
+        tree.add_link(Link::with_speed((2, 3), LinkSpeed::Gigabit));
+        tree.simulate_rounds(10);
+        assert_eq!(tree.get_node(3).unwrap().next_hop, Some(2));
+        assert_eq!(tree.get_node(3).unwrap().root_cost, 8);
     }
 }
 
@@ -394,5 +2244,6 @@ mod node_test {
         let node = Node::new(1, "A");
         assert_eq!(node.root_id, 1);
         assert_eq!(node.name, "A");
+        assert_eq!(node.priority, DEFAULT_PRIORITY);
     }
 }
\ No newline at end of file
@@ -0,0 +1,360 @@
+//! Algorithms on strings: a trie for prefix lookups, and substring searches for one pattern ([Knuth–Morris–Pratt](https://en.wikipedia.org/wiki/Knuth%E2%80%93Morris%E2%80%93Pratt_algorithm))
+//! and for many patterns at once ([Aho–Corasick](https://en.wikipedia.org/wiki/Aho%E2%80%93Corasick_algorithm)).
+//!
+//! The searches compare bytes and report byte offsets, like the search methods of str.
+//! Since no character of UTF-8 is part of another one, matches of a str always start and end at character boundaries.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// A node of a Trie, the children ordered by their character.
+#[derive(Clone, Debug, Default)]
+struct TrieNode {
+    children: BTreeMap<char, usize>,
+    terminal: bool,
+}
+
+/// A [trie](https://en.wikipedia.org/wiki/Trie) of words, which finds words and prefixes in time proportional to their length,
+/// no matter how many words it holds.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::strings::Trie;
+///
+/// let mut trie = Trie::new();
+/// trie.insert("switch");
+/// trie.insert("swap");
+/// trie.insert("router");
+/// assert!(trie.contains("swap"));
+/// assert!(!trie.contains("sw"));
+/// assert!(trie.starts_with("sw"));
+/// assert_eq!(trie.words_with_prefix("sw"), vec!["swap", "switch"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Trie {
+    nodes: Vec<TrieNode>,
+    len: usize,
+}
+
+impl Trie {
+    /// Creates an empty trie.
+    pub fn new() -> Self {
+        Trie { nodes: vec![TrieNode::default()], len: 0 }
+    }
+
+    /// Returns the number of words in the trie.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the trie holds no words.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds a word. Returns false if the trie already held it.
+    pub fn insert(&mut self, word: &str) -> bool {
+        let mut node = 0;
+        for letter in word.chars() {
+            node = match self.nodes[node].children.get(&letter) {
+                Some(&child) => child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(letter, child);
+                    child
+                }
+            };
+        }
+        if self.nodes[node].terminal {
+            return false;
+        }
+        self.nodes[node].terminal = true;
+        self.len += 1;
+        true
+    }
+
+    /// Returns true if the trie holds the word.
+    pub fn contains(&self, word: &str) -> bool {
+        self.find(word).is_some_and(|node| self.nodes[node].terminal)
+    }
+
+    /// Returns true if any word of the trie starts with the prefix. Every trie holding a word starts with the empty prefix.
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.find(prefix).is_some_and(|node| node != 0 || !self.is_empty())
+    }
+
+    /// Returns all words starting with the prefix, in lexicographic order of their characters.
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        if let Some(node) = self.find(prefix) {
+            self.collect(node, &mut prefix.to_string(), &mut words);
+        }
+        words
+    }
+
+    /// Returns the node reached by following the characters of the prefix from the root.
+    fn find(&self, prefix: &str) -> Option<usize> {
+        prefix.chars().try_fold(0, |node, letter| self.nodes[node].children.get(&letter).copied())
+    }
+
+    /// Adds the words below the node to words, with word holding the characters leading to the node.
+    fn collect(&self, node: usize, word: &mut String, words: &mut Vec<String>) {
+        if self.nodes[node].terminal {
+            words.push(word.clone());
+        }
+        for (&letter, &child) in &self.nodes[node].children {
+            word.push(letter);
+            self.collect(child, word, words);
+            word.pop();
+        }
+    }
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Trie::new()
+    }
+}
+
+/// Finds the start indices of all occurrences of the pattern in the text using the Knuth–Morris–Pratt algorithm,
+/// in O(text + pattern) time. Overlapping occurrences are all found.
+///
+/// It works on slices of anything comparable, use as_bytes to search strings. The empty pattern occurs at every index.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::strings::*;
+///
+/// assert_eq!(kmp_search("abababa".as_bytes(), "aba".as_bytes()), vec![0, 2, 4]);
+/// assert_eq!(kmp_search(&[1, 2, 3], &[2, 3]), vec![1]);
+/// assert!(kmp_search("abc".as_bytes(), "abcd".as_bytes()).is_empty());
+/// ```
+pub fn kmp_search<T: PartialEq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    if pattern.is_empty() {
+        return (0..=text.len()).collect();
+    }
+    let borders = borders(pattern);
+    let mut matches = Vec::new();
+    // the number of characters of the pattern matching the end of the text read so far
+    let mut matched = 0;
+    for (index, item) in text.iter().enumerate() {
+        while matched > 0 && pattern[matched] != *item {
+            matched = borders[matched - 1];
+        }
+        if pattern[matched] == *item {
+            matched += 1;
+        }
+        if matched == pattern.len() {
+            matches.push(index + 1 - matched);
+            matched = borders[matched - 1];
+        }
+    }
+    matches
+}
+
+/// Returns for every prefix of the pattern the length of its longest proper prefix which is also a suffix of it.
+fn borders<T: PartialEq>(pattern: &[T]) -> Vec<usize> {
+    let mut borders = vec![0; pattern.len()];
+    let mut length = 0;
+    for index in 1..pattern.len() {
+        while length > 0 && pattern[index] != pattern[length] {
+            length = borders[length - 1];
+        }
+        if pattern[index] == pattern[length] {
+            length += 1;
+        }
+        borders[index] = length;
+    }
+    borders
+}
+
+/// An occurrence of a pattern found by AhoCorasick, with the byte range it spans in the text.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// The index of the pattern in the patterns the automaton was built from.
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A state of the AhoCorasick automaton, the nodes of the trie of all patterns.
+#[derive(Clone, Debug, Default)]
+struct State {
+    next: HashMap<u8, usize>,
+    /// The state of the longest proper suffix of this state which is also a prefix of a pattern.
+    fail: usize,
+    /// The patterns ending in this state, including the ones ending in the states reached by failing.
+    outputs: Vec<usize>,
+}
+
+/// An automaton finding all occurrences of many patterns in a text at once using the Aho–Corasick algorithm,
+/// in O(text + patterns + matches) time, instead of searching for one pattern after the other.
+///
+/// # Example:
+/// ```rust
+/// use rust_algorithms::strings::*;
+///
+/// let automaton = AhoCorasick::new(&["he", "she", "his", "hers"]);
+/// let found: Vec<(usize, usize)> = automaton.find_all("ushers").iter().map(|found| (found.pattern, found.start)).collect();
+/// assert_eq!(found, vec![(1, 1), (0, 2), (3, 2)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AhoCorasick {
+    states: Vec<State>,
+    lengths: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Builds the automaton for the patterns. Empty patterns are never matched.
+    pub fn new<P: AsRef<str>>(patterns: &[P]) -> Self {
+        let mut states = vec![State::default()];
+        for (index, pattern) in patterns.iter().enumerate() {
+            let pattern = pattern.as_ref();
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut state = 0;
+            for &byte in pattern.as_bytes() {
+                state = match states[state].next.get(&byte) {
+                    Some(&next) => next,
+                    None => {
+                        states.push(State::default());
+                        let next = states.len() - 1;
+                        states[state].next.insert(byte, next);
+                        next
+                    }
+                };
+            }
+            states[state].outputs.push(index);
+        }
+
+        // the fail links of a state only lead to shallower states, so they are set in breadth-first order
+        let mut queue: VecDeque<usize> = states[0].next.values().copied().collect();
+        while let Some(state) = queue.pop_front() {
+            let next: Vec<(u8, usize)> = states[state].next.iter().map(|(&byte, &next)| (byte, next)).collect();
+            for (byte, child) in next {
+                let mut fail = states[state].fail;
+                while fail != 0 && !states[fail].next.contains_key(&byte) {
+                    fail = states[fail].fail;
+                }
+                let fail = states[fail].next.get(&byte).copied().filter(|&target| target != child).unwrap_or(0);
+                states[child].fail = fail;
+                let inherited = states[fail].outputs.clone();
+                states[child].outputs.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+        let lengths = patterns.iter().map(|pattern| pattern.as_ref().len()).collect();
+        AhoCorasick { states, lengths }
+    }
+
+    /// Returns all occurrences of the patterns in the text, ordered by their end, then from the longest to the shortest.
+    /// Overlapping occurrences are all found.
+    pub fn find_all(&self, text: &str) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut state = 0;
+        for (index, byte) in text.bytes().enumerate() {
+            while state != 0 && !self.states[state].next.contains_key(&byte) {
+                state = self.states[state].fail;
+            }
+            state = self.states[state].next.get(&byte).copied().unwrap_or(0);
+            let end = index + 1;
+            for &pattern in &self.states[state].outputs {
+                matches.push(Match { pattern, start: end - self.lengths[pattern], end });
+            }
+        }
+        matches
+    }
+
+    /// Returns true if any of the patterns occurs in the text.
+    pub fn is_match(&self, text: &str) -> bool {
+        let mut state = 0;
+        for byte in text.bytes() {
+            while state != 0 && !self.states[state].next.contains_key(&byte) {
+                state = self.states[state].fail;
+            }
+            state = self.states[state].next.get(&byte).copied().unwrap_or(0);
+            if !self.states[state].outputs.is_empty() {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod strings_test {
+    use super::*;
+    use crate::util::{RandomSource, SplitMix64};
+
+    /// Generates a text of the letters a and b, which makes for many overlapping matches.
+    fn random_text(rng: &mut SplitMix64, len: usize) -> String {
+        (0..len).map(|_| if rng.random_index(2) == 0 { 'a' } else { 'b' }).collect()
+    }
+
+    fn naive_search(text: &str, pattern: &str) -> Vec<usize> {
+        (0..=text.len().saturating_sub(pattern.len()))
+            .filter(|&start| text.len() >= pattern.len() && text.as_bytes()[start..].starts_with(pattern.as_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn trie() {
+        let mut trie = Trie::default();
+        assert!(!trie.starts_with(""));
+        for word in ["core", "cord", "cor", "car", "über", ""] {
+            assert!(trie.insert(word));
+        }
+        assert!(!trie.insert("cord"));
+        assert_eq!(trie.len(), 6);
+        assert!(trie.contains("") && trie.contains("cor") && trie.contains("über"));
+        assert!(!trie.contains("co") && !trie.contains("cords") && !trie.contains("ü"));
+        assert!(trie.starts_with("ü") && trie.starts_with(""));
+        assert!(!trie.starts_with("d"));
+        assert_eq!(trie.words_with_prefix("cor"), vec!["cor", "cord", "core"]);
+        assert_eq!(trie.words_with_prefix(""), vec!["", "car", "cor", "cord", "core", "über"]);
+        assert!(trie.words_with_prefix("x").is_empty());
+    }
+
+    #[test]
+    fn kmp() {
+        let mut rng = SplitMix64::new(3);
+        for _ in 0..200 {
+            let text = random_text(&mut rng, 30);
+            let len = 1 + rng.random_index(5);
+            let pattern = random_text(&mut rng, len);
+            assert_eq!(kmp_search(text.as_bytes(), pattern.as_bytes()), naive_search(&text, &pattern), "{} in {}", pattern, text);
+        }
+        assert_eq!(kmp_search("aaaa".as_bytes(), "aa".as_bytes()), vec![0, 1, 2]);
+        assert_eq!(kmp_search("ab".as_bytes(), "".as_bytes()), vec![0, 1, 2]);
+        assert_eq!(kmp_search("größer".as_bytes(), "ß".as_bytes()), vec![4]);
+        assert!(kmp_search::<u8>(&[], &[1]).is_empty());
+    }
+
+    #[test]
+    fn aho_corasick() {
+        let mut rng = SplitMix64::new(9);
+        for _ in 0..100 {
+            let patterns: Vec<String> = (0..4).map(|_| {
+                let len = 1 + rng.random_index(4);
+                random_text(&mut rng, len)
+            }).collect();
+            let automaton = AhoCorasick::new(&patterns);
+            let text = random_text(&mut rng, 40);
+            let mut found: Vec<(usize, usize)> = automaton.find_all(&text).iter().map(|found| (found.pattern, found.start)).collect();
+            found.sort_unstable();
+            let mut expected = Vec::new();
+            for (index, pattern) in patterns.iter().enumerate() {
+                expected.extend(naive_search(&text, pattern).into_iter().map(|start| (index, start)));
+            }
+            expected.sort_unstable();
+            assert_eq!(found, expected, "{:?} in {}", patterns, text);
+            assert_eq!(automaton.is_match(&text), !expected.is_empty());
+        }
+
+        let automaton = AhoCorasick::new(&["", "ab", "ab"]);
+        assert_eq!(automaton.find_all("xab"), vec![Match { pattern: 1, start: 1, end: 3 }, Match { pattern: 2, start: 1, end: 3 }]);
+        assert!(!automaton.is_match("ba"));
+        assert!(AhoCorasick::new::<&str>(&[]).find_all("abc").is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+use crate::spanningtree::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The address of a host attached to a node of a tree, like the MAC address of a network card.
+pub type MacAddress = u64;
+
+/// The port of a node a frame was received on or has to be sent out of.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Port {
+    /// The port the hosts attached to the node are connected to.
+    Local,
+    /// The port of the link to the neighbouring node with that id.
+    Neighbour(isize),
+}
+
+/// The result of a frame sent by Switching::send().
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameReport {
+    /// True if the frame reached the node the destination host is attached to.
+    pub delivered: bool,
+    /// True if any node did not know the destination and had to flood the frame.
+    pub flooded: bool,
+    /// The ids of all nodes which handled the frame, in the order they received it.
+    pub nodes: Vec<isize>,
+}
+
+/// The data plane of a network, whose control plane is modeled by a spanning tree.
+///
+/// Hosts are attached to the nodes of a tree, which act as learning switches:
+/// Every node remembers the port it received a frame from a host on in its address table,
+/// and forwards frames to that host only out of this port from then on.
+/// Frames to hosts a node does not know yet are flooded over all forwarding links.
+#[derive(Clone, Debug, Default)]
+pub struct Switching {
+    hosts: HashMap<MacAddress, isize>,
+    tables: HashMap<isize, HashMap<MacAddress, Port>>,
+}
+
+impl Switching {
+    /// Creates a network without any hosts and with empty address tables.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::switching::Switching;
+    ///
+    /// let switching = Switching::new();
+    /// ```
+    pub fn new() -> Self {
+        Switching {
+            hosts: HashMap::new(),
+            tables: HashMap::new(),
+        }
+    }
+
+    /// Attaches a host to the local port of a node.
+    /// If the host was attached to another node before, it is moved.
+    pub fn attach_host(&mut self, address: MacAddress, node_id: isize) {
+        self.hosts.insert(address, node_id);
+    }
+
+    /// Returns the port a node has learned for an address, if it learned one yet.
+    pub fn lookup(&self, node_id: isize, address: MacAddress) -> Option<Port> {
+        self.tables.get(&node_id).and_then(|table| table.get(&address)).copied()
+    }
+
+    /// Forgets all learned addresses, like switches flush their address tables after a topology change.
+    pub fn clear_tables(&mut self) {
+        self.tables.clear();
+    }
+
+    /// Sends a frame from one host to another over the forwarding links of the tree.
+    ///
+    /// Every node the frame reaches learns the port it came from for the source address.
+    /// If the node has learned a port for the destination, the frame is only sent out of that port,
+    /// or dropped if that is the link it came from. Otherwise the frame is flooded out of all forwarding links
+    /// except the one it came from. Learned ports whose link is not forwarding anymore are ignored.
+    /// Like in Tree::flood_from, a node which receives the same frame again drops it.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::spanningtree::*;
+    /// use rust_algorithms::switching::*;
+    ///
+    /// let mut tree: Tree = Tree::new();
+    /// tree.set_forward_delay(1);
+    /// tree.add_node(Node::new(1, "Root"));
+    /// tree.add_node(Node::new(2, "Second Node"));
+    /// tree.add_node(Node::new(3, "Third Node"));
+    /// tree.add_link(Link::new((1, 2), 5));
+    /// tree.add_link(Link::new((1, 3), 5));
+    /// for _i in 0..3 {
+    ///     tree.tick();
+    /// }
+    ///
+    /// let mut switching = Switching::new();
+    /// switching.attach_host(0xA, 2);
+    /// switching.attach_host(0xB, 3);
+    ///
+    /// let first = switching.send(&tree, 0xA, 0xB);
+    /// assert_eq!(first.delivered, true);
+    /// assert_eq!(first.flooded, true);
+    ///
+    /// let answer = switching.send(&tree, 0xB, 0xA);
+    /// assert_eq!(answer.delivered, true);
+    /// assert_eq!(answer.flooded, false);
+    /// assert_eq!(answer.nodes, vec![3, 1, 2]);
+    /// ```
+    pub fn send(&mut self, tree: &Tree, source: MacAddress, destination: MacAddress) -> FrameReport {
+        let mut report = FrameReport::default();
+        let start = match self.hosts.get(&source) {
+            Some(node_id) => *node_id,
+            None => return report,
+        };
+        let destination_node = self.hosts.get(&destination).copied();
+
+        let mut handled: HashSet<isize> = HashSet::new();
+        let mut queue: VecDeque<(isize, Port)> = VecDeque::new();
+        queue.push_back((start, Port::Local));
+        while let Some((current, incoming)) = queue.pop_front() {
+            if !handled.insert(current) {
+                continue;
+            }
+            report.nodes.push(current);
+
+            // learn where the source host can be reached from this node
+            self.tables.entry(current).or_default().insert(source, incoming);
+
+            // all ports of links which are currently forwarding
+            let forwarding: Vec<isize> = tree
+                .find_links(current)
+                .iter()
+                .filter(|link| link.state == PortState::Forwarding && link.members.0 != link.members.1)
+                .map(|link| if link.members.0 == current { link.members.1 } else { link.members.0 })
+                .collect();
+
+            let outgoing = match self.lookup(current, destination) {
+                Some(Port::Local) => vec![Port::Local],
+                Some(Port::Neighbour(neighbour)) if forwarding.contains(&neighbour) => {
+                    vec![Port::Neighbour(neighbour)]
+                }
+                _ => {
+                    report.flooded = true;
+                    let mut ports: Vec<Port> = forwarding.into_iter().map(Port::Neighbour).collect();
+                    ports.push(Port::Local);
+                    ports
+                }
+            };
+            for port in outgoing {
+                // the local port connects all hosts of the node, so frames between them are still delivered
+                if port == incoming && port != Port::Local {
+                    continue;
+                }
+                match port {
+                    Port::Local => {
+                        if destination_node == Some(current) {
+                            report.delivered = true;
+                        }
+                    }
+                    Port::Neighbour(neighbour) => {
+                        queue.push_back((neighbour, Port::Neighbour(current)));
+                    }
+                }
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod switching_test {
+    use super::*;
+
+    fn converged_line() -> Tree {
+        let mut tree = Tree::new();
+        tree.set_forward_delay(1);
+        tree.add_node(Node::new(1, "A"));
+        tree.add_node(Node::new(2, "B"));
+        tree.add_node(Node::new(3, "C"));
+        tree.add_node(Node::new(4, "D"));
+        tree.add_link(Link::new((1, 2), 1));
+        tree.add_link(Link::new((2, 3), 1));
+        tree.add_link(Link::new((2, 4), 1));
+        tree.add_link(Link::new((3, 4), 5));
+        for _i in 0..4 {
+            tree.tick();
+        }
+        tree
+    }
+
+    #[test]
+    fn learning() {
+        let tree = converged_line();
+        let mut switching = Switching::new();
+        switching.attach_host(1, 1);
+        switching.attach_host(3, 3);
+        switching.attach_host(4, 4);
+
+        let report = switching.send(&tree, 1, 3);
+        assert!(report.delivered);
+        assert!(report.flooded);
+        assert_eq!(report.nodes, vec![1, 2, 3, 4]);
+        assert_eq!(switching.lookup(1, 1), Some(Port::Local));
+        assert_eq!(switching.lookup(3, 1), Some(Port::Neighbour(2)));
+        assert_eq!(switching.lookup(4, 1), Some(Port::Neighbour(2)));
+
+        let report = switching.send(&tree, 3, 1);
+        assert!(report.delivered);
+        assert!(!report.flooded);
+        assert_eq!(report.nodes, vec![3, 2, 1]);
+
+        let report = switching.send(&tree, 1, 3);
+        assert!(!report.flooded);
+        assert_eq!(report.nodes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn unknown_hosts() {
+        let tree = converged_line();
+        let mut switching = Switching::new();
+        switching.attach_host(1, 1);
+        assert_eq!(switching.send(&tree, 9, 1), FrameReport::default());
+        let report = switching.send(&tree, 1, 9);
+        assert!(!report.delivered);
+        assert!(report.flooded);
+        assert_eq!(report.nodes.len(), 4);
+    }
+
+    #[test]
+    fn same_node() {
+        let tree = converged_line();
+        let mut switching = Switching::new();
+        switching.attach_host(1, 2);
+        switching.attach_host(2, 2);
+        switching.send(&tree, 2, 1);
+        let report = switching.send(&tree, 1, 2);
+        assert!(report.delivered);
+        assert!(!report.flooded);
+        assert_eq!(report.nodes, vec![2]);
+    }
+
+    #[test]
+    fn moved_host_after_clear() {
+        let tree = converged_line();
+        let mut switching = Switching::new();
+        switching.attach_host(1, 1);
+        switching.attach_host(3, 3);
+        switching.send(&tree, 3, 1);
+        switching.attach_host(3, 4);
+        switching.clear_tables();
+        assert_eq!(switching.lookup(1, 3), None);
+        let report = switching.send(&tree, 1, 3);
+        assert!(report.delivered);
+        assert!(report.flooded);
+    }
+
+    #[test]
+    fn blocked_links_are_not_used() {
+        let tree = converged_line();
+        let mut switching = Switching::new();
+        switching.attach_host(3, 3);
+        switching.attach_host(4, 4);
+        let report = switching.send(&tree, 3, 4);
+        assert!(report.delivered);
+        assert_eq!(report.nodes, vec![3, 2, 1, 4]);
+    }
+}
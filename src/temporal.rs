@@ -0,0 +1,195 @@
+use crate::graph::{Graph, Link, Node};
+use crate::util::node_index;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// A link which only exists during an interval of time, starting at `start` and ending right before `end`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TemporalLink {
+    pub link: Link,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl TemporalLink {
+    /// Checks whether the link exists at the given time.
+    pub fn is_valid_at(&self, time: usize) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
+/// A graph whose links appear and disappear over time, like the contacts in a contact network.
+///
+/// The nodes always exist, while every link is only valid during an interval.
+/// The same pair of nodes can be connected during several intervals.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::temporal::TemporalGraph;
+///
+/// let mut graph = TemporalGraph::new();
+/// let a = graph.add_node(Node::new("A"));
+/// let b = graph.add_node(Node::new("B"));
+/// let c = graph.add_node(Node::new("C"));
+/// graph.add_link(Link::new((a, b), 1), 5, 10);
+/// graph.add_link(Link::new((b, c), 1), 0, 3);
+///
+/// assert_eq!(graph.snapshot(6).links(), &[Link::new((a, b), 1)]);
+///
+/// // the link to C is gone before B can be reached, so C can only reach A
+/// assert!(!graph.is_reachable(a, c, 0));
+/// assert!(graph.is_reachable(c, a, 0));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TemporalGraph {
+    graph: Graph,
+    links: Vec<TemporalLink>,
+}
+
+impl TemporalGraph {
+    /// Creates an empty temporal graph.
+    pub fn new() -> Self {
+        TemporalGraph {
+            graph: Graph::new(),
+            links: Vec::new(),
+        }
+    }
+
+    /// Adds a node, which exists at all times, and returns its id like Graph::add_node.
+    pub fn add_node(&mut self, node: Node) -> isize {
+        self.graph.add_node(node)
+    }
+
+    /// Adds a link which exists from the time `start` up to right before the time `end`.
+    /// Empty intervals, where `end` is not after `start`, are ignored.
+    pub fn add_link(&mut self, link: Link, start: usize, end: usize) {
+        if start < end {
+            self.links.push(TemporalLink { link, start, end });
+        }
+    }
+
+    /// Returns all nodes of the graph.
+    pub fn nodes(&self) -> &[Node] {
+        self.graph.nodes()
+    }
+
+    /// Returns all links of the graph with the intervals during which they exist, in the order they were added.
+    pub fn links(&self) -> &[TemporalLink] {
+        &self.links
+    }
+
+    /// Creates a graph containing all nodes and the links which exist at the given time.
+    pub fn snapshot(&self, time: usize) -> Graph {
+        let mut snapshot = self.graph.clone();
+        for temporal in self.links.iter().filter(|temporal| temporal.is_valid_at(time)) {
+            snapshot.add_link(temporal.link);
+        }
+        snapshot
+    }
+
+    /// Computes the earliest time every node can be reached at from the given node, when starting at the given time.
+    ///
+    /// A path through a temporal graph has to respect the order of time: after arriving at a node,
+    /// it can only continue over links which still exist. Crossing a link takes no time,
+    /// but the path may wait at a node for a link to appear.
+    /// The result contains the ids of the reachable nodes, including the start node, and their arrival times, sorted by id.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::graph::*;
+    /// use rust_algorithms::temporal::TemporalGraph;
+    ///
+    /// let mut graph = TemporalGraph::new();
+    /// let a = graph.add_node(Node::new("A"));
+    /// let b = graph.add_node(Node::new("B"));
+    /// let c = graph.add_node(Node::new("C"));
+    /// graph.add_link(Link::new((a, b), 1), 2, 4);
+    /// graph.add_link(Link::new((b, c), 1), 3, 8);
+    ///
+    /// assert_eq!(graph.reachable_from(a, 0), vec![(a, 0), (b, 2), (c, 3)]);
+    /// assert_eq!(graph.reachable_from(a, 4), vec![(a, 4)]);
+    /// ```
+    pub fn reachable_from(&self, node_id: isize, time: usize) -> Vec<(isize, usize)> {
+        let count = self.graph.nodes().len();
+        let start = match node_index(node_id, count) {
+            Some(start) => start,
+            None => return Vec::new(),
+        };
+
+        let mut arrivals: Vec<Option<usize>> = vec![None; count];
+        let mut queue = BinaryHeap::new();
+        arrivals[start] = Some(time);
+        queue.push(Reverse((time, start)));
+        while let Some(Reverse((arrival, current))) = queue.pop() {
+            if arrivals[current] != Some(arrival) {
+                continue;
+            }
+            for temporal in &self.links {
+                let (a, b) = temporal.link.members;
+                let other = match (node_index(a, count), node_index(b, count)) {
+                    (Some(a), Some(b)) if a == current => b,
+                    (Some(a), Some(b)) if b == current => a,
+                    _ => continue,
+                };
+                // wait for the link to appear, if it has not yet
+                let crossing = arrival.max(temporal.start);
+                if crossing < temporal.end && arrivals[other].is_none_or(|known| crossing < known) {
+                    arrivals[other] = Some(crossing);
+                    queue.push(Reverse((crossing, other)));
+                }
+            }
+        }
+
+        self.graph
+            .nodes()
+            .iter()
+            .zip(arrivals)
+            .filter_map(|(node, arrival)| arrival.map(|arrival| (node.id, arrival)))
+            .collect()
+    }
+
+    /// Checks whether a path respecting the order of time leads from one node to another, when starting at the given time.
+    pub fn is_reachable(&self, from: isize, to: isize, time: usize) -> bool {
+        self.reachable_from(from, time).iter().any(|&(id, _)| id == to)
+    }
+}
+
+#[cfg(test)]
+mod temporal_test {
+    use super::*;
+
+    fn line() -> TemporalGraph {
+        let mut graph = TemporalGraph::new();
+        for name in ["A", "B", "C", "D"] {
+            graph.add_node(Node::new(name));
+        }
+        graph.add_link(Link::new((0, 1), 1), 0, 2);
+        graph.add_link(Link::new((1, 2), 1), 1, 2);
+        graph.add_link(Link::new((1, 2), 1), 6, 9);
+        graph.add_link(Link::new((3, 2), 4), 4, 8);
+        graph.add_link(Link::new((0, 3), 1), 3, 3);
+        graph
+    }
+
+    #[test]
+    fn snapshot() {
+        let graph = line();
+        assert_eq!(graph.links().len(), 4);
+        assert_eq!(graph.snapshot(1).links(), &[Link::new((0, 1), 1), Link::new((1, 2), 1)]);
+        assert_eq!(graph.snapshot(2).links(), &[] as &[Link]);
+        assert_eq!(graph.snapshot(7).links(), &[Link::new((1, 2), 1), Link::new((3, 2), 4)]);
+        assert_eq!(graph.snapshot(7).nodes().len(), 4);
+    }
+
+    #[test]
+    fn reachable_from() {
+        let graph = line();
+        assert_eq!(graph.reachable_from(0, 0), vec![(0, 0), (1, 0), (2, 1), (3, 4)]);
+        assert_eq!(graph.reachable_from(0, 2), vec![(0, 2)]);
+        assert_eq!(graph.reachable_from(3, 0), vec![(1, 6), (2, 4), (3, 0)]);
+        assert_eq!(graph.reachable_from(9, 0), vec![]);
+        assert!(graph.is_reachable(3, 1, 0));
+        assert!(!graph.is_reachable(3, 0, 0));
+    }
+}
@@ -0,0 +1,206 @@
+//! Traffic load simulation: demands between pairs of nodes are sent along their routes, and the amounts are added up
+//! to the load of every link, to find the hot spots of a network before it is built.
+//!
+//! Like a router with [ECMP](https://en.wikipedia.org/wiki/Equal-cost_multi-path_routing), a demand with several routes
+//! is split evenly across them. The routes can come from any search, route_demands uses dijkstra::all_shortest_paths.
+
+use crate::dijkstra::all_shortest_paths;
+use crate::graph::{Graph, Link, SearchResult};
+use std::collections::HashMap;
+
+/// An amount of traffic to be sent from one node to another.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Demand {
+    pub source: isize,
+    pub target: isize,
+    pub amount: f64,
+}
+
+/// A link carrying a high share of its capacity, as returned by LinkLoads::hot_spots.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HotSpot {
+    pub link: Link,
+    pub load: f64,
+    /// The load divided by the capacity of the link.
+    pub utilization: f64,
+}
+
+/// The traffic every link carries, in both directions together.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkLoads {
+    /// The load of every link, by its members in ascending order.
+    loads: HashMap<(isize, isize), f64>,
+    unrouted: f64,
+}
+
+impl Demand {
+    /// Creates a demand of the given amount from source to target.
+    pub fn new(source: isize, target: isize, amount: f64) -> Self {
+        Demand { source, target, amount }
+    }
+}
+
+/// Sends every demand along all of its cheapest paths, split evenly, and returns the resulting loads.
+/// Demands without a path are counted as unrouted.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::graph::*;
+/// use rust_algorithms::traffic::{self, Demand};
+///
+/// // two routes of the same cost between A and D
+/// let mut graph = Graph::new();
+/// for name in ["A", "B", "C", "D"] {
+///     graph.add_node(Node::new(name));
+/// }
+/// for (a, b) in [(0, 1), (1, 3), (0, 2), (2, 3)] {
+///     graph.add_link(Link::new((a, b), 1));
+/// }
+///
+/// let loads = traffic::route_demands(&graph, &[Demand::new(0, 3, 10.0), Demand::new(0, 1, 4.0)]);
+/// assert_eq!(loads.load(0, 1), 9.0);
+/// assert_eq!(loads.load(3, 2), 5.0);
+///
+/// let hot_spots = loads.hot_spots(&graph, |_| 10, 0.8);
+/// assert_eq!(hot_spots.len(), 1);
+/// assert_eq!(hot_spots[0].link, Link::new((0, 1), 1));
+/// assert_eq!(hot_spots[0].utilization, 0.9);
+/// ```
+pub fn route_demands(graph: &Graph, demands: &[Demand]) -> LinkLoads {
+    let mut loads = LinkLoads::new();
+    for demand in demands {
+        loads.add_paths(&all_shortest_paths(graph, demand.source, demand.target), demand.amount);
+    }
+    loads
+}
+
+impl LinkLoads {
+    /// Creates loads without any traffic.
+    pub fn new() -> Self {
+        LinkLoads::default()
+    }
+
+    /// Splits the amount evenly across the paths and adds it to the load of their links.
+    /// Returns false and counts the amount as unrouted if there are no paths.
+    ///
+    /// Links from a node to itself, like the first link of every SearchResult, carry no load.
+    pub fn add_paths(&mut self, paths: &[SearchResult], amount: f64) -> bool {
+        if paths.is_empty() {
+            self.unrouted += amount;
+            return false;
+        }
+        let share = amount / paths.len() as f64;
+        for link in paths.iter().flat_map(|path| &path.links) {
+            let (a, b) = link.members;
+            if a != b {
+                *self.loads.entry((a.min(b), a.max(b))).or_insert(0.0) += share;
+            }
+        }
+        true
+    }
+
+    /// Returns the load of the link between both nodes, 0 if it carries no traffic.
+    pub fn load(&self, a: isize, b: isize) -> f64 {
+        self.loads.get(&(a.min(b), a.max(b))).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the total amount of the demands which could not be routed.
+    pub fn unrouted(&self) -> f64 {
+        self.unrouted
+    }
+
+    /// Returns the load of every link of the graph divided by its capacity, in the order of graph.links().
+    ///
+    /// A link without capacity has a utilization of infinity if it carries anything, otherwise 0.
+    pub fn utilization<F>(&self, graph: &Graph, capacity: F) -> Vec<f64>
+    where
+        F: Fn(&Link) -> usize,
+    {
+        graph
+            .links()
+            .iter()
+            .map(|link| {
+                let load = self.load(link.members.0, link.members.1);
+                match capacity(link) {
+                    0 if load > 0.0 => f64::INFINITY,
+                    0 => 0.0,
+                    capacity => load / capacity as f64,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the links with a utilization of at least the threshold, the most utilized ones first.
+    /// Links with the same utilization are in the order of graph.links().
+    pub fn hot_spots<F>(&self, graph: &Graph, capacity: F, threshold: f64) -> Vec<HotSpot>
+    where
+        F: Fn(&Link) -> usize,
+    {
+        let mut hot_spots: Vec<HotSpot> = graph
+            .links()
+            .iter()
+            .zip(self.utilization(graph, capacity))
+            .filter(|(_, utilization)| *utilization >= threshold)
+            .map(|(link, utilization)| HotSpot { link: *link, load: self.load(link.members.0, link.members.1), utilization })
+            .collect();
+        hot_spots.sort_by(|a, b| b.utilization.total_cmp(&a.utilization));
+        hot_spots
+    }
+}
+
+#[cfg(test)]
+mod traffic_test {
+    use super::*;
+    use crate::generate;
+    use crate::util::{RandomSource, SplitMix64};
+
+    #[test]
+    fn conserved_traffic() {
+        // every unit of a demand loads as many links as its path has
+        let mut rng = SplitMix64::new(39);
+        for _ in 0..10 {
+            let graph = generate::erdos_renyi(12, 0.25, 1..3, &mut rng);
+            let demands: Vec<Demand> = (0..20)
+                .map(|_| {
+                    let (source, target) = (rng.random_index(12) as isize, rng.random_index(12) as isize);
+                    Demand::new(source, target, 1.0 + rng.random_index(9) as f64)
+                })
+                .collect();
+            let loads = route_demands(&graph, &demands);
+            let mut carried = 0.0;
+            let mut unrouted = 0.0;
+            for demand in &demands {
+                let paths = all_shortest_paths(&graph, demand.source, demand.target);
+                if paths.is_empty() {
+                    unrouted += demand.amount;
+                }
+                let hops: usize = paths.iter().map(|path| path.links.len() - 1).sum();
+                carried += demand.amount * hops as f64 / paths.len().max(1) as f64;
+            }
+            let total: f64 = graph.links().iter().map(|link| loads.load(link.members.0, link.members.1)).sum();
+            assert!((total - carried).abs() < 1e-9);
+            assert_eq!(loads.unrouted(), unrouted);
+            let utilization = loads.utilization(&graph, |_| 100);
+            for (share, link) in utilization.iter().zip(graph.links()) {
+                assert!((share * 100.0 - loads.load(link.members.0, link.members.1)).abs() < 1e-9);
+            }
+            let hot_spots = loads.hot_spots(&graph, |_| 100, 0.0);
+            assert_eq!(hot_spots.len(), graph.links().len());
+            assert!(hot_spots.windows(2).all(|pair| pair[0].utilization >= pair[1].utilization));
+        }
+    }
+
+    #[test]
+    fn capacities() {
+        let graph = generate::topology(&generate::TopologySpec::Line { nodes: 3 }, 1);
+        let loads = route_demands(&graph, &[Demand::new(0, 1, 3.0), Demand::new(2, 2, 5.0), Demand::new(0, 7, 2.0)]);
+        assert_eq!(loads.utilization(&graph, |_| 0), vec![f64::INFINITY, 0.0]);
+        assert_eq!(loads.utilization(&graph, |link| link.members.0 as usize + 2), vec![1.5, 0.0]);
+        assert_eq!(loads.unrouted(), 2.0);
+        assert!(loads.hot_spots(&graph, |_| 4, 0.8).is_empty());
+        let mut loads = LinkLoads::new();
+        assert!(!loads.add_paths(&[], 1.0));
+        assert!(loads.add_paths(&all_shortest_paths(&graph, 2, 0), 1.0));
+        assert_eq!((loads.load(1, 0), loads.load(2, 1), loads.unrouted()), (1.0, 1.0, 1.0));
+    }
+}
@@ -0,0 +1,317 @@
+//! Helpers shared by the simulations, most importantly the source of random numbers they use.
+//!
+//! All randomized simulations take the random number generator as an argument. With the `rand` feature,
+//! which is enabled by default, every generator of rand can be passed, e.g. `rand::thread_rng()`.
+//! Without it, the crate has no dependencies and SplitMix64 can be used instead.
+//!
+//! To make a simulation reproducible, pass a seeded generator, e.g. `rand::rngs::StdRng::seed_from_u64(42)` or `SplitMix64::new(42)`.
+
+use std::convert::TryInto;
+use std::ops::Range;
+
+/// A source of random numbers.
+///
+/// With the `rand` feature, it is implemented for every `rand::RngCore`.
+pub trait RandomSource {
+    /// Returns the next random number, with all 64 bits being random.
+    fn random_u64(&mut self) -> u64;
+
+    /// Returns a random index below len, with all indices being equally likely except for a negligible bias.
+    ///
+    /// # Panics
+    /// Panics if len is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::util::{RandomSource, SplitMix64};
+    ///
+    /// let mut rng = SplitMix64::new(7);
+    /// assert!(rng.random_index(3) < 3);
+    /// ```
+    fn random_index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "cannot pick an index of an empty range");
+        ((u128::from(self.random_u64()) * len as u128) >> 64) as usize
+    }
+
+    /// Returns a random number of the given range, like random_index.
+    ///
+    /// # Panics
+    /// Panics if the range is empty.
+    fn random_range(&mut self, range: Range<usize>) -> usize {
+        assert!(range.start < range.end, "cannot pick a number of an empty range");
+        range.start + self.random_index(range.end - range.start)
+    }
+
+    /// Returns a random number between 0 (inclusive) and 1 (exclusive).
+    fn random_f64(&mut self) -> f64 {
+        (self.random_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<R: rand::RngCore + ?Sized> RandomSource for R {
+    fn random_u64(&mut self) -> u64 {
+        self.next_u64()
+    }
+}
+
+/// A small, seeded random number generator which needs no dependencies, see [SplitMix64](https://prng.di.unimi.it/splitmix64.c).
+///
+/// It is fast and good enough for simulations, but not suitable for cryptography.
+/// The same seed always produces the same numbers, so simulations using it can be reproduced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator starting from the given seed.
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn random_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// How far a long operation has got, as passed to the progress callbacks of the *_with_progress functions,
+/// e.g. to show a progress bar. GraphLoader reports LoadProgress instead, as the length of its input is not known.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of steps done so far.
+    pub done: usize,
+    /// The number of steps of the whole operation, None if it is not known in advance.
+    pub total: Option<usize>,
+}
+
+impl Progress {
+    /// Returns the share of the steps done so far, 1 for an operation without any steps, or None if the total is not known.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_algorithms::util::Progress;
+    ///
+    /// assert_eq!(Progress { done: 3, total: Some(4) }.fraction(), Some(0.75));
+    /// assert_eq!(Progress { done: 0, total: Some(0) }.fraction(), Some(1.0));
+    /// assert_eq!(Progress { done: 3, total: None }.fraction(), None);
+    /// ```
+    pub fn fraction(&self) -> Option<f64> {
+        self.total.map(|total| if total == 0 { 1.0 } else { self.done as f64 / total as f64 })
+    }
+}
+
+/// Passes the progress of an operation of many small steps to the callback, but only about every percent of them and
+/// after the last one, so even operations of millions of steps stay cheap to follow.
+pub(crate) fn report_progress<F>(progress: &mut F, done: usize, total: usize)
+where
+    F: FnMut(Progress),
+{
+    if done == total || done.is_multiple_of((total / 100).max(1)) {
+        progress(Progress { done, total: Some(total) });
+    }
+}
+
+/// Returns the index of the node with the given id among count nodes, if there is one, as the id of a node is its index.
+pub(crate) fn node_index(id: isize, count: usize) -> Option<usize> {
+    id.try_into().ok().filter(|index| *index < count)
+}
+
+/// Samples indices with probabilities proportional to given weights, in constant time per sample,
+/// using the [alias method](https://en.wikipedia.org/wiki/Alias_method) of Vose.
+///
+/// Building the sampler takes time linear in the number of weights, so it pays off as soon as many samples are taken from the same weights.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::util::{SplitMix64, WeightedSampler};
+///
+/// let sampler = WeightedSampler::new(&[1.0, 0.0, 3.0]).unwrap();
+/// let mut rng = SplitMix64::new(1);
+/// let mut counts = [0; 3];
+/// for _ in 0..1000 {
+///     counts[sampler.sample(&mut rng)] += 1;
+/// }
+/// assert_eq!(counts[1], 0);
+/// assert!(counts[2] > 2 * counts[0]);
+/// assert!(WeightedSampler::new(&[0.0, 0.0]).is_none());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedSampler {
+    /// The probability to keep the index picked uniformly, instead of using its alias.
+    probabilities: Vec<f64>,
+    aliases: Vec<usize>,
+}
+
+impl WeightedSampler {
+    /// Creates a sampler for the given weights. The index of every weight is sampled with a probability of its share of the sum.
+    ///
+    /// Returns None if there are no weights, if any weight is negative or not finite, or if all weights are 0.
+    pub fn new(weights: &[f64]) -> Option<Self> {
+        if weights.iter().any(|weight| !weight.is_finite() || *weight < 0.0) {
+            return None;
+        }
+        let sum: f64 = weights.iter().sum();
+        if !(sum > 0.0 && sum.is_finite()) {
+            return None;
+        }
+
+        let len = weights.len();
+        let mut scaled: Vec<f64> = weights.iter().map(|weight| weight * len as f64 / sum).collect();
+        let mut probabilities = vec![1.0; len];
+        let mut aliases: Vec<usize> = (0..len).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..len).partition(|index| scaled[*index] < 1.0);
+        while let (Some(&less), Some(&more)) = (small.last(), large.last()) {
+            small.pop();
+            probabilities[less] = scaled[less];
+            aliases[less] = more;
+            scaled[more] -= 1.0 - scaled[less];
+            if scaled[more] < 1.0 {
+                large.pop();
+                small.push(more);
+            }
+        }
+        // Only rounding errors leave indices in small, which would be kept with a probability of 1,
+        // unless their weight is 0, so they must never be sampled.
+        if let Some(positive) = weights.iter().position(|weight| *weight > 0.0) {
+            for index in small {
+                if weights[index] == 0.0 {
+                    probabilities[index] = 0.0;
+                    aliases[index] = positive;
+                }
+            }
+        }
+        Some(WeightedSampler { probabilities, aliases })
+    }
+
+    /// Returns the number of weights.
+    pub fn len(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    /// Returns true if there are no weights, which is never the case since new refuses to create such a sampler.
+    pub fn is_empty(&self) -> bool {
+        self.probabilities.is_empty()
+    }
+
+    /// Returns a random index, with every index being picked with a probability proportional to its weight.
+    pub fn sample<R>(&self, rng: &mut R) -> usize
+    where
+        R: RandomSource + ?Sized,
+    {
+        let index = rng.random_index(self.len());
+        if rng.random_f64() < self.probabilities[index] {
+            index
+        } else {
+            self.aliases[index]
+        }
+    }
+}
+
+/// Moves amount randomly chosen items to the front of the slice and returns them, in random order.
+/// Returns all items if there are not more than amount.
+pub(crate) fn choose_multiple<'a, T, R>(items: &'a mut [T], amount: usize, rng: &mut R) -> &'a [T]
+where
+    R: RandomSource + ?Sized,
+{
+    let amount = amount.min(items.len());
+    for index in 0..amount {
+        let other = index + rng.random_index(items.len() - index);
+        items.swap(index, other);
+    }
+    &items[..amount]
+}
+
+#[cfg(test)]
+mod util_test {
+    use super::*;
+
+    #[test]
+    fn progress_reports() {
+        let mut reports = Vec::new();
+        for done in 1..=1000 {
+            report_progress(&mut |progress: Progress| reports.push(progress.done), done, 1000);
+        }
+        assert_eq!(reports, (1..=100).map(|step| step * 10).collect::<Vec<usize>>());
+        reports.clear();
+        for done in 0..=3 {
+            report_progress(&mut |progress: Progress| reports.push(progress.done), done, 3);
+        }
+        assert_eq!(reports, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn split_mix() {
+        // the first outputs of the reference implementation for the seed 1234567
+        let mut rng = SplitMix64::new(1234567);
+        assert_eq!(rng.random_u64(), 6457827717110365317);
+        assert_eq!(rng.random_u64(), 3203168211198807973);
+        assert_eq!(SplitMix64::new(3).random_u64(), SplitMix64::new(3).random_u64());
+
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            counts[rng.random_index(4)] += 1;
+        }
+        assert!(counts.iter().all(|count| *count > 800));
+        assert!((0..100).map(|_| rng.random_range(5..7)).all(|number| number == 5 || number == 6));
+        assert!((0..100).map(|_| rng.random_f64()).all(|number| (0.0..1.0).contains(&number)));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn seeded_rand_generators() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let numbers = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..5).map(|_| rng.random_index(1000)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(8), numbers(8));
+        assert_ne!(numbers(8), numbers(9));
+    }
+
+    #[test]
+    fn weighted_sampler() {
+        assert!(WeightedSampler::new(&[]).is_none());
+        assert!(WeightedSampler::new(&[1.0, -1.0]).is_none());
+        assert!(WeightedSampler::new(&[1.0, f64::NAN]).is_none());
+        assert!(WeightedSampler::new(&[f64::MAX, f64::MAX]).is_none());
+
+        let weights = [0.0, 1.0, 2.0, 0.0, 7.0];
+        let sampler = WeightedSampler::new(&weights).unwrap();
+        assert_eq!(sampler.len(), 5);
+        assert!(!sampler.is_empty());
+        let mut rng = SplitMix64::new(6);
+        let mut counts = [0usize; 5];
+        for _ in 0..10000 {
+            counts[sampler.sample(&mut rng)] += 1;
+        }
+        assert_eq!(counts[0] + counts[3], 0);
+        for (count, weight) in counts.iter().zip(weights.iter()) {
+            let expected = 1000.0 * weight;
+            assert!((*count as f64 - expected).abs() < 150.0, "{} instead of {}", count, expected);
+        }
+
+        let single = WeightedSampler::new(&[0.0, 0.5]).unwrap();
+        assert!((0..100).all(|_| single.sample(&mut rng) == 1));
+    }
+
+    #[test]
+    fn choose() {
+        let mut rng = SplitMix64::new(1);
+        let mut items = [1, 2, 3, 4, 5];
+        let mut chosen = choose_multiple(&mut items, 3, &mut rng).to_vec();
+        chosen.sort_unstable();
+        chosen.dedup();
+        assert_eq!(chosen.len(), 3);
+        assert_eq!(choose_multiple(&mut items, 9, &mut rng).len(), 5);
+        assert!(choose_multiple(&mut items, 0, &mut rng).is_empty());
+    }
+}
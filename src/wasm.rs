@@ -0,0 +1,156 @@
+//! Bindings for JavaScript through [wasm-bindgen](https://rustwasm.github.io/wasm-bindgen/), available with the `wasm` feature.
+//!
+//! Graphs are exchanged in the JSON format of Graph::to_json, results of searches and simulations are returned as JSON as well,
+//! so they can be used with `JSON.parse` right away.
+
+use crate::graph::{leak_name, Graph, Node, SearchResult};
+use crate::{bfs, dfs, dijkstra, spanningtree};
+use wasm_bindgen::prelude::*;
+
+/// A graph which can be built and searched from JavaScript.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct WasmGraph {
+    graph: Graph,
+}
+
+#[wasm_bindgen]
+impl WasmGraph {
+    /// Creates an empty graph.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGraph {
+        WasmGraph { graph: Graph::new() }
+    }
+
+    /// Reads a graph from JSON written by `toJson`. Throws the description of the problem if the JSON is invalid.
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<WasmGraph, JsValue> {
+        Graph::from_json(json)
+            .map(|graph| WasmGraph { graph })
+            .map_err(|error| JsValue::from_str(&error.to_string()))
+    }
+
+    /// Writes the graph as JSON.
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        self.graph.to_json()
+    }
+
+    /// Adds a node with the given name and returns its id, like Graph::add_node.
+    /// New names are leaked, see Node::name.
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self, name: &str) -> isize {
+        if let Some(node) = self.graph.nodes().iter().find(|node| node.name == name) {
+            return node.id;
+        }
+        self.graph.add_node(Node::new(leak_name(name.to_string())))
+    }
+
+    /// Adds a link between the nodes with the given ids, like Graph::add_link.
+    #[wasm_bindgen(js_name = addLink)]
+    pub fn add_link(&mut self, a: isize, b: isize, cost: usize) {
+        self.graph.add_link(crate::graph::Link::new((a, b), cost));
+    }
+
+    /// Searches for a path using bfs_search_node and returns it as JSON, or `null` if there is none.
+    pub fn bfs(&self, start: isize, target: isize) -> String {
+        result_to_json(bfs::bfs_search_node(self.graph.topology_clone(), start, target))
+    }
+
+    /// Searches for a path using dfs_search_node and returns it as JSON, or `null` if there is none.
+    pub fn dfs(&self, start: isize, target: isize) -> String {
+        result_to_json(dfs::dfs_search_node(self.graph.topology_clone(), start, target))
+    }
+
+    /// Searches for the cheapest path using dijkstra_search_node and returns it as JSON, or `null` if there is none.
+    pub fn dijkstra(&self, start: isize, target: isize) -> String {
+        result_to_json(dijkstra::dijkstra_search_node(&self.graph, start, target))
+    }
+}
+
+/// Simulates the spanning tree protocol on the graph given as JSON, in the format of Graph::to_json.
+///
+/// The result lists for every node the root it has chosen, its cost to the root and its next hop towards it.
+/// Throws the description of the problem if the JSON is invalid.
+#[wasm_bindgen(js_name = simulateSpanningTree)]
+pub fn simulate_spanning_tree(json: &str, iterations: usize) -> Result<String, JsValue> {
+    spanning_tree(json, iterations).map_err(|error| JsValue::from_str(&error))
+}
+
+/// Writes a search result as JSON, as `{"links":[{"source":0,"target":0,"cost":0}],"cost":0}`.
+fn result_to_json(result: Option<SearchResult>) -> String {
+    let result = match result {
+        Some(result) => result,
+        None => return String::from("null"),
+    };
+    let links: Vec<String> = result
+        .links
+        .iter()
+        .map(|link| {
+            format!(
+                "{{\"source\":{},\"target\":{},\"cost\":{}}}",
+                link.members.0, link.members.1, link.cost
+            )
+        })
+        .collect();
+    format!("{{\"links\":[{}],\"cost\":{}}}", links.join(","), result.cost)
+}
+
+/// The part of simulate_spanning_tree which does not depend on JavaScript, so it can be tested natively.
+fn spanning_tree(json: &str, iterations: usize) -> Result<String, String> {
+    let graph = Graph::from_json(json).map_err(|error| error.to_string())?;
+    let mut tree = spanningtree::Tree::from_graph(&graph);
+    tree.simulate(iterations, 0, true);
+
+    let nodes: Vec<String> = graph
+        .nodes()
+        .iter()
+        .filter_map(|node| {
+            let node = tree.get_node(node.id)?;
+            let next_hop = node.next_hop.map(|hop| hop.to_string()).unwrap_or_else(|| String::from("null"));
+            Some(format!(
+                "{{\"id\":{},\"name\":\"{}\",\"root_id\":{},\"root_cost\":{},\"next_hop\":{}}}",
+                node.id,
+                crate::json::escape(node.name),
+                node.root_id,
+                node.root_cost,
+                next_hop
+            ))
+        })
+        .collect();
+    Ok(format!("{{\"nodes\":[{}]}}", nodes.join(",")))
+}
+
+#[cfg(test)]
+mod wasm_test {
+    use super::*;
+
+    #[test]
+    fn searches() {
+        let mut graph = WasmGraph::new();
+        let a = graph.add_node("A");
+        let b = graph.add_node("B");
+        let c = graph.add_node("C");
+        assert_eq!(graph.add_node("A"), a);
+        graph.add_link(a, b, 1);
+        graph.add_link(b, c, 2);
+        let expected = r#"{"links":[{"source":0,"target":0,"cost":0},{"source":0,"target":1,"cost":1},{"source":1,"target":2,"cost":2}],"cost":3}"#;
+        assert_eq!(graph.bfs(a, c), expected);
+        assert_eq!(graph.dfs(a, c), expected);
+        assert_eq!(graph.dijkstra(a, c), expected);
+        assert_eq!(graph.dijkstra(a, 7), "null");
+        assert_eq!(WasmGraph::from_json(&graph.to_json()).unwrap().to_json(), graph.to_json());
+    }
+
+    #[test]
+    fn spanning_tree() {
+        let json = r#"{"version":1,"nodes":[{"id":0,"name":"A"},{"id":1,"name":"B"},{"id":2,"name":"C"}],
+            "links":[{"source":0,"target":1,"cost":4},{"source":1,"target":2,"cost":1},{"source":0,"target":2,"cost":1}]}"#;
+        let result = super::spanning_tree(json, 50).unwrap();
+        assert_eq!(
+            result,
+            r#"{"nodes":[{"id":0,"name":"A","root_id":0,"root_cost":0,"next_hop":null},{"id":1,"name":"B","root_id":0,"root_cost":2,"next_hop":2},{"id":2,"name":"C","root_id":0,"root_cost":1,"next_hop":0}]}"#
+        );
+        assert!(super::spanning_tree("{", 1).is_err());
+    }
+}
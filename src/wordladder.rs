@@ -0,0 +1,106 @@
+//! [Word ladders](https://en.wikipedia.org/wiki/Word_ladder): turning one word into another by changing one letter at a time,
+//! with every step being a word of a dictionary.
+//!
+//! The words are the nodes of an implicit graph, linked if they differ in exactly one letter.
+//! The breadth-first search on implicit graphs only looks at the words reachable within the length of the shortest ladder.
+
+use crate::implicit;
+use std::collections::HashMap;
+
+/// Finds one of the shortest word ladders from start to end, using implicit::bfs_search.
+///
+/// Returns all words of the ladder, starting with start and ending with end, or None if there is no ladder.
+/// The start word does not need to be part of the dictionary, but end does. Words of other lengths than start are ignored,
+/// letters are compared exactly, so the dictionary should have the same case as start and end.
+///
+/// # Example
+/// ```
+/// use rust_algorithms::wordladder;
+///
+/// let dictionary = ["hot", "dot", "dog", "lot", "log", "cog"];
+/// let ladder = wordladder::solve(&dictionary, "hit", "cog").unwrap();
+/// assert_eq!(ladder.len(), 5);
+/// assert_eq!(ladder[0], "hit");
+/// assert_eq!(ladder[4], "cog");
+/// assert!(wordladder::solve(&dictionary, "hit", "cat").is_none());
+/// ```
+pub fn solve<'a>(dictionary: &[&'a str], start: &'a str, end: &str) -> Option<Vec<&'a str>> {
+    let letters = |word: &str| word.chars().collect::<Vec<char>>();
+    let start_letters = letters(start);
+    // the start is node -1, the words of the dictionary are the nodes with their index as id
+    let words: Vec<Vec<char>> = dictionary.iter().map(|word| letters(word)).collect();
+    let target = dictionary.iter().position(|word| *word == end)?;
+    if start == end {
+        return Some(vec![start]);
+    }
+
+    // the words matching every pattern of a word with one letter replaced by a wildcard
+    let mut patterns: HashMap<(usize, Vec<char>), Vec<isize>> = HashMap::new();
+    for (index, word) in words.iter().enumerate() {
+        if word.len() == start_letters.len() {
+            for position in 0..word.len() {
+                patterns.entry(pattern(word, position)).or_default().push(index as isize);
+            }
+        }
+    }
+
+    let word_of = |id: isize| if id < 0 { &start_letters } else { &words[id as usize] };
+    let successors = |id: isize| {
+        let word = word_of(id);
+        let mut next: Vec<(isize, usize)> = Vec::new();
+        for position in 0..word.len() {
+            if let Some(matching) = patterns.get(&pattern(word, position)) {
+                next.extend(matching.iter().filter(|other| **other != id && word_of(**other) != word).map(|other| (*other, 1)));
+            }
+        }
+        next
+    };
+    let result = implicit::bfs_search(-1, |id| id == target as isize, successors)?;
+    Some(
+        result
+            .links
+            .iter()
+            .map(|link| if link.members.1 < 0 { start } else { dictionary[link.members.1 as usize] })
+            .collect(),
+    )
+}
+
+/// Returns the position and the word with the letter at that position removed.
+fn pattern(word: &[char], position: usize) -> (usize, Vec<char>) {
+    let mut letters = word.to_vec();
+    letters.remove(position);
+    (position, letters)
+}
+
+#[cfg(test)]
+mod wordladder_test {
+    use super::*;
+
+    fn differs_by_one(a: &str, b: &str) -> bool {
+        a.chars().count() == b.chars().count() && a.chars().zip(b.chars()).filter(|(x, y)| x != y).count() == 1
+    }
+
+    #[test]
+    fn shortest() {
+        let dictionary = ["cold", "cord", "card", "ward", "warm", "word", "worm", "wore", "core", "bold", "corm"];
+        let ladder = solve(&dictionary, "cold", "warm").unwrap();
+        assert_eq!(ladder.len(), 5);
+        assert_eq!(ladder.first(), Some(&"cold"));
+        assert_eq!(ladder.last(), Some(&"warm"));
+        assert!(ladder.windows(2).all(|pair| differs_by_one(pair[0], pair[1])));
+        assert!(ladder[1..].iter().all(|word| dictionary.contains(word)));
+    }
+
+    #[test]
+    fn edge_cases() {
+        let dictionary = ["cat", "cot", "dot", "dog", "cats", "über", "aber"];
+        assert_eq!(solve(&dictionary, "cat", "cat"), Some(vec!["cat"]));
+        assert_eq!(solve(&dictionary, "bat", "dog"), Some(vec!["bat", "cat", "cot", "dot", "dog"]));
+        // the end has to be part of the dictionary, words of different lengths are never linked
+        assert!(solve(&dictionary, "cat", "cog").is_none());
+        assert!(solve(&dictionary, "cat", "cats").is_none());
+        assert_eq!(solve(&dictionary, "abex", "über"), Some(vec!["abex", "aber", "über"]));
+        assert!(solve(&dictionary, "über", "dog").is_none());
+        assert!(solve(&[], "a", "b").is_none());
+    }
+}